@@ -0,0 +1,498 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Splitting of a program into dependency cones. A dependency cone contains a
+//! single method that has a body (the unit that the verifier actually checks)
+//! together with the transitive closure of the domains, functions, predicates,
+//! fields and bodyless methods that it depends on. Two programs that contain
+//! the same cone will produce the same verification result for its method,
+//! which is what the fine-grained verification cache relies on.
+//!
+//! The dependencies are computed by name: a declaration depends on every other
+//! declaration whose name occurs as a string in its `Debug` representation.
+//! This over-approximates the real dependencies, which is safe for caching.
+//! Domains are additionally included whenever their axioms mention a
+//! declaration of the cone, because axioms constrain the symbols they mention.
+
+use crate::vir::program::Program;
+use log::trace;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::fmt::Debug;
+use vir::{legacy, low};
+
+/// A declaration of a program, as seen by the dependency analysis.
+struct Declaration {
+    /// The names under which other declarations can refer to this one.
+    names: Vec<String>,
+    /// All the names mentioned by this declaration.
+    mentioned_names: FxHashSet<String>,
+    /// Whether this declaration is a method with a body.
+    is_unit: bool,
+    /// Whether this declaration has to be included whenever it mentions a
+    /// declaration of the cone (e.g. a domain with axioms).
+    is_global: bool,
+    /// Whether this declaration is checked by the verifier on its own (e.g.
+    /// the postcondition of a function).
+    is_checked: bool,
+}
+
+impl Declaration {
+    fn new<T: Debug>(names: Vec<String>, declaration: &T) -> Self {
+        Self {
+            names,
+            mentioned_names: collect_string_literals(&format!("{declaration:?}")),
+            is_unit: false,
+            is_global: false,
+            is_checked: false,
+        }
+    }
+
+    fn unit(mut self) -> Self {
+        self.is_unit = true;
+        self
+    }
+
+    fn global(mut self) -> Self {
+        self.is_global = true;
+        self
+    }
+
+    fn checked(mut self) -> Self {
+        self.is_checked = true;
+        self
+    }
+}
+
+/// Collect all string literals of a `Debug` representation.
+fn collect_string_literals(text: &str) -> FxHashSet<String> {
+    let mut literals = FxHashSet::default();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '"' {
+            continue;
+        }
+        let mut literal = String::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => break,
+                '\\' => {
+                    if let Some(escaped) = chars.next() {
+                        literal.push(escaped);
+                    }
+                }
+                _ => literal.push(c),
+            }
+        }
+        literals.insert(literal);
+    }
+    literals
+}
+
+/// A dependency cone of a program.
+pub struct DependencyCone {
+    /// The declarations of the original program that belong to the cone.
+    pub program: Program,
+    /// The indices of these declarations in the original program.
+    included: FxHashSet<usize>,
+}
+
+/// Split the program into dependency cones, one for each method that has a
+/// body. If some functions or predicates are not part of any cone, an
+/// additional cone containing them is created, so that they are still checked.
+/// A program without methods is returned unchanged.
+///
+/// The cones keep the name of the original program if there is only one of
+/// them; otherwise, the name of the method is appended.
+#[tracing::instrument(level = "debug", skip_all, fields(program = %program.get_name()))]
+pub fn split_into_dependency_cones(program: &Program) -> Vec<DependencyCone> {
+    let declarations = declarations(program);
+    let cones = compute_cones(&declarations);
+    if cones.len() <= 1 {
+        return vec![DependencyCone {
+            program: program.clone(),
+            included: (0..declarations.len()).collect(),
+        }];
+    }
+    cones
+        .into_iter()
+        .map(|(cone_name, included)| {
+            let mut cone = restrict_program(program, &included);
+            cone.set_name(format!("{}${}", program.get_name(), cone_name));
+            DependencyCone {
+                program: cone,
+                included,
+            }
+        })
+        .collect()
+}
+
+/// Restrict the program to the declarations of the given cones, which must
+/// have been computed by `split_into_dependency_cones` from this program.
+/// Verifying the result verifies all the cones at once.
+pub fn union_of_cones<'a>(
+    program: &Program,
+    cones: impl IntoIterator<Item = &'a DependencyCone>,
+) -> Program {
+    let included = cones
+        .into_iter()
+        .flat_map(|cone| cone.included.iter().copied())
+        .collect();
+    restrict_program(program, &included)
+}
+
+/// Collect the ids of the positions that occur in the program, that is, the
+/// positions at which the verifier can report errors of this program.
+pub fn position_ids(program: &Program) -> FxHashSet<u64> {
+    match program {
+        Program::Legacy(program) => {
+            let mut ids = FxHashSet::default();
+            program.visit_positions(|position| {
+                ids.insert(position.id());
+            });
+            ids
+        }
+        Program::Low(program) => collect_position_ids(&format!("{program:?}")),
+    }
+}
+
+/// Collect the ids of all positions of a `Debug` representation.
+fn collect_position_ids(text: &str) -> FxHashSet<u64> {
+    text.split("Position {")
+        .skip(1)
+        .filter_map(|position| {
+            let position = &position[..position.find('}')?];
+            let id = position.split("id: ").nth(1)?;
+            id.trim().trim_end_matches(',').parse().ok()
+        })
+        .collect()
+}
+
+fn declarations(program: &Program) -> Vec<Declaration> {
+    match program {
+        Program::Legacy(program) => legacy_declarations(program),
+        Program::Low(program) => low_declarations(program),
+    }
+}
+
+fn restrict_program(program: &Program, included: &FxHashSet<usize>) -> Program {
+    match program {
+        Program::Legacy(program) => Program::Legacy(legacy_cone(program, included)),
+        Program::Low(program) => Program::Low(low_cone(program, included)),
+    }
+}
+
+/// Compute the cones of the given declarations. Returns the name of each cone
+/// together with the set of indices of the declarations it includes.
+fn compute_cones(declarations: &[Declaration]) -> Vec<(String, FxHashSet<usize>)> {
+    let mut declarations_by_name: FxHashMap<&str, Vec<usize>> = FxHashMap::default();
+    for (index, declaration) in declarations.iter().enumerate() {
+        for name in &declaration.names {
+            declarations_by_name.entry(name).or_default().push(index);
+        }
+    }
+    let mut cones = Vec::new();
+    let mut covered: FxHashSet<usize> = FxHashSet::default();
+    for (index, declaration) in declarations.iter().enumerate() {
+        if declaration.is_unit {
+            let cone = compute_cone(declarations, &declarations_by_name, vec![index]);
+            trace!("Cone of {:?}: {:?}", declaration.names, cone);
+            covered.extend(cone.iter().copied());
+            cones.push((declaration.names[0].clone(), cone));
+        }
+    }
+    if cones.is_empty() {
+        return cones;
+    }
+    let uncovered: Vec<usize> = declarations
+        .iter()
+        .enumerate()
+        .filter(|(index, declaration)| declaration.is_checked && !covered.contains(index))
+        .map(|(index, _)| index)
+        .collect();
+    if !uncovered.is_empty() {
+        cones.push((
+            "preamble".to_string(),
+            compute_cone(declarations, &declarations_by_name, uncovered),
+        ));
+    }
+    cones
+}
+
+/// Compute the transitive dependencies of the given roots, including the
+/// global declarations that mention any of them.
+fn compute_cone(
+    declarations: &[Declaration],
+    declarations_by_name: &FxHashMap<&str, Vec<usize>>,
+    roots: Vec<usize>,
+) -> FxHashSet<usize> {
+    let mut included: FxHashSet<usize> = FxHashSet::default();
+    let mut worklist = roots;
+    loop {
+        while let Some(index) = worklist.pop() {
+            if !included.insert(index) {
+                continue;
+            }
+            for name in &declarations[index].mentioned_names {
+                if let Some(dependencies) = declarations_by_name.get(name.as_str()) {
+                    worklist.extend(dependencies.iter().copied());
+                }
+            }
+        }
+        // Add global declarations that mention something already included.
+        let included_names: FxHashSet<&str> = included
+            .iter()
+            .flat_map(|&index| declarations[index].names.iter().map(String::as_str))
+            .collect();
+        for (index, declaration) in declarations.iter().enumerate() {
+            if declaration.is_global
+                && !included.contains(&index)
+                && declaration
+                    .mentioned_names
+                    .iter()
+                    .any(|name| included_names.contains(name.as_str()))
+            {
+                worklist.push(index);
+            }
+        }
+        if worklist.is_empty() {
+            return included;
+        }
+    }
+}
+
+/// Indices into the declaration lists of a program, in the order in which
+/// `legacy_declarations` and `low_declarations` enumerate them.
+struct DeclarationIndex<'a> {
+    included: &'a FxHashSet<usize>,
+    next: usize,
+}
+
+impl<'a> DeclarationIndex<'a> {
+    fn new(included: &'a FxHashSet<usize>) -> Self {
+        Self { included, next: 0 }
+    }
+
+    fn filter<T: Clone>(&mut self, declarations: &[T]) -> Vec<T> {
+        let first = self.next;
+        self.next += declarations.len();
+        declarations
+            .iter()
+            .enumerate()
+            .filter(|(offset, _)| self.included.contains(&(first + offset)))
+            .map(|(_, declaration)| declaration.clone())
+            .collect()
+    }
+}
+
+fn legacy_declarations(program: &legacy::Program) -> Vec<Declaration> {
+    let mut declarations = Vec::new();
+    for domain in &program.domains {
+        let mut names = vec![domain.name.clone()];
+        // Snapshot types refer to their domains without the prefix.
+        if let Some(name) = domain.name.strip_prefix("Snap$") {
+            names.push(name.to_string());
+        }
+        names.extend(
+            domain
+                .functions
+                .iter()
+                .map(|function| function.name.clone()),
+        );
+        declarations.push(Declaration::new(names, domain).global());
+    }
+    for backend_type in &program.backend_types {
+        let mut names = vec![backend_type.name.clone()];
+        names.extend(
+            backend_type
+                .functions
+                .iter()
+                .map(|function| function.name.clone()),
+        );
+        declarations.push(Declaration::new(names, backend_type).global());
+    }
+    for field in &program.fields {
+        declarations.push(Declaration::new(vec![field.name.clone()], field));
+    }
+    for method in &program.builtin_methods {
+        declarations.push(Declaration::new(vec![method.name.clone()], method));
+    }
+    for method in &program.methods {
+        declarations.push(Declaration::new(vec![method.name()], method).unit());
+    }
+    for function in &program.functions {
+        declarations.push(Declaration::new(vec![function.name.clone()], function).checked());
+    }
+    for predicate in &program.viper_predicates {
+        declarations
+            .push(Declaration::new(vec![predicate.name().to_string()], predicate).checked());
+    }
+    declarations
+}
+
+fn legacy_cone(program: &legacy::Program, included: &FxHashSet<usize>) -> legacy::Program {
+    let mut index = DeclarationIndex::new(included);
+    legacy::Program {
+        name: program.name.clone(),
+        domains: index.filter(&program.domains),
+        backend_types: index.filter(&program.backend_types),
+        fields: index.filter(&program.fields),
+        builtin_methods: index.filter(&program.builtin_methods),
+        methods: index.filter(&program.methods),
+        functions: index.filter(&program.functions),
+        viper_predicates: index.filter(&program.viper_predicates),
+    }
+}
+
+fn low_declarations(program: &low::Program) -> Vec<Declaration> {
+    let mut declarations = Vec::new();
+    for domain in &program.domains {
+        let mut names = vec![domain.name.clone()];
+        names.extend(
+            domain
+                .functions
+                .iter()
+                .map(|function| function.name.clone()),
+        );
+        declarations.push(Declaration::new(names, domain).global());
+    }
+    for predicate in &program.predicates {
+        declarations.push(Declaration::new(vec![predicate.name.clone()], predicate).checked());
+    }
+    for function in &program.functions {
+        declarations.push(Declaration::new(vec![function.name.clone()], function).checked());
+    }
+    for method in &program.methods {
+        let declaration = Declaration::new(vec![method.name.clone()], method);
+        declarations.push(if method.body.is_some() {
+            declaration.unit()
+        } else {
+            declaration
+        });
+    }
+    for procedure in &program.procedures {
+        declarations.push(Declaration::new(vec![procedure.name.clone()], procedure).unit());
+    }
+    declarations
+}
+
+fn low_cone(program: &low::Program, included: &FxHashSet<usize>) -> low::Program {
+    let mut index = DeclarationIndex::new(included);
+    low::Program {
+        name: program.name.clone(),
+        check_mode: program.check_mode,
+        domains: index.filter(&program.domains),
+        predicates: index.filter(&program.predicates),
+        functions: index.filter(&program.functions),
+        methods: index.filter(&program.methods),
+        procedures: index.filter(&program.procedures),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn declaration(name: &str, mentioned_names: &[&str]) -> Declaration {
+        Declaration {
+            names: vec![name.to_string()],
+            mentioned_names: mentioned_names
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            is_unit: false,
+            is_global: false,
+            is_checked: false,
+        }
+    }
+
+    fn cone_names(declarations: &[Declaration]) -> Vec<(String, Vec<String>)> {
+        compute_cones(declarations)
+            .into_iter()
+            .map(|(name, included)| {
+                let mut names: Vec<_> = included
+                    .into_iter()
+                    .map(|index| declarations[index].names[0].clone())
+                    .collect();
+                names.sort();
+                (name, names)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn collect_position_ids_finds_all_positions() {
+        let ids = collect_position_ids(
+            "Assert { position: Position { line: 1, column: 2, id: 42 } }, \
+            Position { line: 0, column: 0, id: 7 }",
+        );
+        assert_eq!(ids, [42, 7].into_iter().collect());
+    }
+
+    #[test]
+    fn collect_string_literals_handles_escapes() {
+        let literals = collect_string_literals(r#"Foo { name: "a\"b", other: "c" }"#);
+        assert_eq!(literals.len(), 2);
+        assert!(literals.contains("a\"b"));
+        assert!(literals.contains("c"));
+    }
+
+    #[test]
+    fn cones_contain_transitive_dependencies_only() {
+        let declarations = vec![
+            declaration("f", &["g"]),
+            declaration("g", &[]).checked(),
+            declaration("h", &[]).checked(),
+            declaration("m1", &["f"]).unit(),
+            declaration("m2", &["h"]).unit(),
+        ];
+        assert_eq!(
+            cone_names(&declarations),
+            vec![
+                (
+                    "m1".to_string(),
+                    vec!["f".to_string(), "g".to_string(), "m1".to_string()]
+                ),
+                ("m2".to_string(), vec!["h".to_string(), "m2".to_string()]),
+            ]
+        );
+    }
+
+    #[test]
+    fn global_declarations_are_included_when_they_mention_the_cone() {
+        let declarations = vec![
+            declaration("f", &[]),
+            declaration("D", &["f"]).global(),
+            declaration("E", &["unrelated"]).global(),
+            declaration("m", &["f"]).unit(),
+        ];
+        assert_eq!(
+            cone_names(&declarations),
+            vec![(
+                "m".to_string(),
+                vec!["D".to_string(), "f".to_string(), "m".to_string()]
+            )]
+        );
+    }
+
+    #[test]
+    fn uncovered_checked_declarations_get_their_own_cone() {
+        let declarations = vec![
+            declaration("f", &[]).checked(),
+            declaration("g", &["f"]).checked(),
+            declaration("m", &[]).unit(),
+        ];
+        assert_eq!(
+            cone_names(&declarations),
+            vec![
+                ("m".to_string(), vec!["m".to_string()]),
+                (
+                    "preamble".to_string(),
+                    vec!["f".to_string(), "g".to_string()]
+                ),
+            ]
+        );
+    }
+}
@@ -570,6 +570,10 @@ impl<'v> ToViper<'v, viper::Expr<'v>> for expression::ContainerOp {
             expression::ContainerOpKind::SeqIndex => ast.seq_index(arg(0), arg(1)),
             expression::ContainerOpKind::SeqConcat => ast.seq_append(arg(0), arg(1)),
             expression::ContainerOpKind::SeqLen => ast.seq_length(arg(0)),
+            expression::ContainerOpKind::SeqTake => ast.seq_take(arg(0), arg(1)),
+            expression::ContainerOpKind::SeqDrop => ast.seq_drop(arg(0), arg(1)),
+            expression::ContainerOpKind::SeqUpdate => ast.seq_update(arg(0), arg(1), arg(2)),
+            expression::ContainerOpKind::SeqContains => ast.seq_contains(arg(0), arg(1)),
             expression::ContainerOpKind::MapEmpty => {
                 let (key_ty, val_ty) = key_value_types();
                 ast.empty_map(key_ty, val_ty)
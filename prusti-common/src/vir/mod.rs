@@ -7,6 +7,7 @@
 pub use self::{
     low_to_viper::{ToViper, ToViperDecl},
     to_graphviz::ToGraphViz,
+    to_silver::ToSilver,
 };
 pub use low_to_viper::Context as LoweringContext;
 pub use vir::{high as vir_high, legacy::*, polymorphic as polymorphic_vir};
@@ -16,6 +17,8 @@ pub mod optimizations;
 mod to_viper;
 mod low_to_viper;
 mod to_graphviz;
+mod to_silver;
 pub mod program;
 pub mod macros;
 pub mod program_normalization;
+pub mod dependency_cones;
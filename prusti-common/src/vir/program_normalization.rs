@@ -72,6 +72,20 @@ impl NormalizationInfo {
         }
     }
 
+    /// Normalize a position id of the original program, or return `None` if
+    /// the program does not contain the position.
+    pub fn normalize_position_id(&self, pos_id: u64) -> Option<u64> {
+        match self {
+            NormalizationInfo::LowProgram => Some(pos_id),
+            NormalizationInfo::LegacyProgram {
+                original_position_ids,
+            } => original_position_ids
+                .binary_search(&pos_id)
+                .ok()
+                .map(|index| index as u64),
+        }
+    }
+
     /// Denormalize a position.
     pub fn denormalize_position(&self, pos: Position) -> Position {
         Position::new(
@@ -118,4 +132,27 @@ impl NormalizationInfo {
             });
         }
     }
+
+    /// Normalize a verification result of the original program, the inverse
+    /// of `denormalize_result`. Returns `None` if the result mentions a
+    /// position that the program does not contain.
+    pub fn normalize_result(&self, result: &VerificationResult) -> Option<VerificationResult> {
+        let mut result = result.clone();
+        if let VerificationResult::Failure(ref mut ver_errors) = result {
+            for ver_error in ver_errors {
+                for pos in [
+                    &mut ver_error.pos_id,
+                    &mut ver_error.offending_pos_id,
+                    &mut ver_error.reason_pos_id,
+                ]
+                .into_iter()
+                .flatten()
+                {
+                    let pos_id = self.normalize_position_id(pos.parse().ok()?)?;
+                    *pos = pos_id.to_string();
+                }
+            }
+        }
+        Some(result)
+    }
 }
@@ -0,0 +1,690 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use super::{comma_separated, FloatSize, SilverPrinter};
+use crate::{
+    config,
+    vir::{
+        ast::*,
+        borrows::borrow_id,
+        cfg::{CfgBlock, CfgMethod, Successor, RETURN_LABEL},
+        Program,
+    },
+};
+use vir::common::identifier::WithIdentifier;
+
+impl SilverPrinter {
+    pub(super) fn legacy_program(&mut self, program: &Program) {
+        for domain in &program.domains {
+            self.legacy_domain(domain);
+            self.blank_line();
+        }
+        for backend_type in &program.backend_types {
+            self.legacy_backend_type(backend_type);
+            self.blank_line();
+        }
+        for field in &program.fields {
+            let ty = self.legacy_type(&field.typ);
+            self.line(format!("field {}: {ty}", field.name));
+        }
+        if !program.fields.is_empty() {
+            self.blank_line();
+        }
+        for function in &program.functions {
+            self.legacy_function(function);
+            self.blank_line();
+        }
+        // The function that represents the symbolic read permission amount.
+        self.line("function read$(): Perm");
+        self.line("  ensures none < result");
+        self.line("  ensures result < write");
+        self.blank_line();
+        for predicate in &program.viper_predicates {
+            self.legacy_predicate(predicate);
+            self.blank_line();
+        }
+        if !config::verify_only_preamble() {
+            for method in &program.methods {
+                self.legacy_cfg_method(method);
+                self.blank_line();
+            }
+            for method in &program.builtin_methods {
+                self.legacy_bodyless_method(method);
+                self.blank_line();
+            }
+        }
+    }
+
+    fn legacy_domain(&mut self, domain: &Domain) {
+        let mut header = format!("domain {}", domain.name);
+        if !domain.type_vars.is_empty() {
+            let type_vars: Vec<_> = domain
+                .type_vars
+                .iter()
+                .map(|ty| self.legacy_type(ty))
+                .collect();
+            header.push_str(&format!("[{}]", comma_separated(type_vars)));
+        }
+        self.open(header);
+        for function in &domain.functions {
+            let unique = if function.unique { "unique " } else { "" };
+            let parameters = self.legacy_declarations(&function.formal_args);
+            let return_type = self.legacy_type(&function.return_type);
+            self.line(format!(
+                "{unique}function {}({parameters}): {return_type}",
+                function.get_identifier()
+            ));
+        }
+        for axiom in &domain.axioms {
+            if let Some(comment) = &axiom.comment {
+                self.comment(comment);
+            }
+            let body = self.legacy_expr(&axiom.expr);
+            self.line(format!("axiom {} {{ {body} }}", axiom.name));
+        }
+        self.close();
+    }
+
+    fn legacy_backend_type(&mut self, backend_type: &BackendType) {
+        let interpretations = backend_type
+            .interpretations
+            .iter()
+            .map(|(backend, interpretation)| format!("{backend}: \"{interpretation}\""));
+        self.open(format!(
+            "domain {} interpretation ({})",
+            backend_type.name,
+            comma_separated(interpretations)
+        ));
+        for function in &backend_type.functions {
+            let parameters = self.legacy_declarations(&function.formal_args);
+            let return_type = self.legacy_type(&function.return_type);
+            self.line(format!(
+                "function {}({parameters}): {return_type} interpretation \"{}\"",
+                function.get_identifier(),
+                function.interpretation
+            ));
+        }
+        self.close();
+    }
+
+    fn legacy_function(&mut self, function: &Function) {
+        let parameters = self.legacy_declarations(&function.formal_args);
+        let return_type = self.legacy_type(&function.return_type);
+        self.line(format!(
+            "function {}({parameters}): {return_type}",
+            function.get_identifier()
+        ));
+        let pres = self.legacy_exprs(&function.pres);
+        let posts = self.legacy_exprs(&function.posts);
+        self.specifications_and_body(&pres, &posts, function.body.as_ref(), |this, body| {
+            let body = this.legacy_expr(body);
+            this.line(body);
+        });
+    }
+
+    fn legacy_predicate(&mut self, predicate: &Predicate) {
+        let (name, this, body) = match predicate {
+            Predicate::Struct(predicate) => {
+                (&predicate.name, &predicate.this, predicate.body.clone())
+            }
+            Predicate::Enum(predicate) => {
+                (&predicate.name, &predicate.this, Some(predicate.body()))
+            }
+            Predicate::Bodyless(name, this) => (name, this, None),
+        };
+        let parameter = self.legacy_declaration(this);
+        if let Some(body) = body {
+            self.open(format!("predicate {name}({parameter})"));
+            let body = self.legacy_expr(&body);
+            self.line(body);
+            self.close();
+        } else {
+            self.line(format!("predicate {name}({parameter})"));
+        }
+    }
+
+    fn legacy_bodyless_method(&mut self, method: &BodylessMethod) {
+        let header =
+            self.legacy_method_header(&method.name, &method.formal_args, &method.formal_returns);
+        self.line(header);
+        let pres = self.legacy_exprs(&method.pres);
+        let posts = self.legacy_exprs(&method.posts);
+        self.specifications_and_body(&pres, &posts, None::<()>, |_, _| {});
+    }
+
+    fn legacy_method_header(
+        &mut self,
+        name: &str,
+        parameters: &[LocalVar],
+        returns: &[LocalVar],
+    ) -> String {
+        let mut header = format!("method {name}({})", self.legacy_declarations(parameters));
+        if !returns.is_empty() {
+            header.push_str(&format!(" returns ({})", self.legacy_declarations(returns)));
+        }
+        header
+    }
+
+    fn legacy_cfg_method(&mut self, method: &CfgMethod) {
+        let header = self.legacy_method_header(&method.name(), &[], method.get_formal_returns());
+        self.line(header);
+        self.line("{");
+        self.indentation += 1;
+        for local_var in &method.local_vars {
+            let declaration = self.legacy_declaration(local_var);
+            self.line(format!("var {declaration}"));
+        }
+        let labels = method.basic_blocks_labels();
+        // Sort blocks by label, except for the first block.
+        let mut blocks: Vec<_> = method.basic_blocks.iter().enumerate().skip(1).collect();
+        blocks.sort_by_key(|(index, _)| &labels[*index]);
+        blocks.insert(0, (0, &method.basic_blocks[0]));
+        for (index, block) in blocks {
+            self.legacy_block(labels, block, index);
+        }
+        self.line(format!("label {RETURN_LABEL}"));
+        self.close();
+    }
+
+    fn legacy_block(&mut self, labels: &[String], block: &CfgBlock, index: usize) {
+        // To put a bit of white space between blocks.
+        self.comment("");
+        self.line(format!("label {}", labels[index]));
+        for stmt in &block.stmts {
+            self.legacy_stmt(stmt, false);
+        }
+        match &block.successor {
+            Successor::Undefined => panic!("CFG block '{}' has no successor.", labels[index]),
+            Successor::Return => self.line(format!("goto {RETURN_LABEL}")),
+            Successor::Goto(target) => self.line(format!("goto {}", labels[target.index()])),
+            Successor::GotoSwitch(successors, default_target) => {
+                for (test, target) in successors {
+                    let test = self.legacy_expr(test);
+                    self.line(format!("if ({test}) {{ goto {} }}", labels[target.index()]));
+                }
+                self.line(format!("goto {}", labels[default_target.index()]));
+            }
+        }
+    }
+
+    /// Print a statement. Inside a `package` statement, Silicon needs help in
+    /// showing that it has access to the needed paths, hence the footprints
+    /// of some statements are asserted first.
+    fn legacy_stmt(&mut self, stmt: &Stmt, inside_package: bool) {
+        let footprint = match stmt {
+            Stmt::Assign(_, expr, _) | Stmt::Exhale(expr, _) if inside_package => Some(expr),
+            Stmt::Fold(_, args, ..) if inside_package => args.first(),
+            _ => None,
+        };
+        if let Some(expr) = footprint {
+            for access in expr.compute_footprint(PermAmount::Read) {
+                let access = self.legacy_expr(&access);
+                self.line(format!("assert {access}"));
+            }
+        }
+        match stmt {
+            Stmt::Comment(comment) => self.comment(comment),
+            Stmt::Label(label) => self.line(format!("label {label}")),
+            Stmt::Inhale(expr) => {
+                let expr = self.legacy_expr(expr);
+                self.line(format!("inhale {expr}"));
+            }
+            Stmt::Exhale(expr, _) => {
+                let expr = self.legacy_expr(expr);
+                self.line(format!("exhale {expr}"));
+            }
+            Stmt::Assert(expr, _) => {
+                let expr = self.legacy_expr(expr);
+                self.line(format!("assert {expr}"));
+            }
+            Stmt::MethodCall(method_name, args, targets) => {
+                let args = comma_separated(self.legacy_exprs(args));
+                if targets.is_empty() {
+                    self.line(format!("{method_name}({args})"));
+                } else {
+                    let targets = comma_separated(targets.iter().map(legacy_local_var));
+                    self.line(format!("{targets} := {method_name}({args})"));
+                }
+            }
+            Stmt::Assign(lhs, rhs, _) => {
+                let lhs = self.legacy_expr(lhs);
+                let rhs = self.legacy_expr(rhs);
+                self.line(format!("{lhs} := {rhs}"));
+            }
+            Stmt::Fold(predicate_name, args, perm, ..) => {
+                let access = self.legacy_predicate_access(predicate_name, args, *perm);
+                self.line(format!("fold {access}"));
+            }
+            Stmt::Unfold(predicate_name, args, perm, _) => {
+                let access = self.legacy_predicate_access(predicate_name, args, *perm);
+                self.line(format!("unfold {access}"));
+            }
+            Stmt::Obtain(..)
+            | Stmt::BeginFrame
+            | Stmt::EndFrame
+            | Stmt::TransferPerm(..)
+            | Stmt::ExpireBorrows(_)
+            | Stmt::Downcast(..) => {
+                // Skip
+                self.comment(&stmt.to_string());
+            }
+            Stmt::PackageMagicWand(wand, package_stmts, _, vars, _) => {
+                let wand = self.legacy_expr(wand);
+                self.open(format!("package {wand}"));
+                for var in vars {
+                    let declaration = self.legacy_declaration(var);
+                    self.line(format!("var {declaration}"));
+                }
+                for stmt in package_stmts {
+                    self.legacy_stmt(stmt, true);
+                }
+                self.close();
+            }
+            Stmt::ApplyMagicWand(wand, _) => {
+                let Expr::MagicWand(_, _, Some(borrow), _) = wand else { unreachable!() };
+                self.line(format!(
+                    "inhale acc(DeadBorrowToken$({}), write)",
+                    borrow_id(*borrow)
+                ));
+                let wand = self.legacy_expr(wand);
+                self.line(format!("apply {wand}"));
+            }
+            Stmt::If(guard, then_stmts, else_stmts) => {
+                let guard = self.legacy_expr(guard);
+                self.open(format!("if ({guard})"));
+                for stmt in then_stmts {
+                    self.legacy_stmt(stmt, inside_package);
+                }
+                self.indentation -= 1;
+                self.line("} else {");
+                self.indentation += 1;
+                for stmt in else_stmts {
+                    self.legacy_stmt(stmt, inside_package);
+                }
+                self.close();
+            }
+        }
+    }
+
+    fn legacy_predicate_access(
+        &mut self,
+        predicate_name: &str,
+        args: &[Expr],
+        perm: PermAmount,
+    ) -> String {
+        let args = comma_separated(self.legacy_exprs(args));
+        format!("acc({predicate_name}({args}), {})", legacy_perm(perm))
+    }
+
+    fn legacy_type(&mut self, ty: &Type) -> String {
+        match ty {
+            Type::Int => "Int".to_string(),
+            Type::Bool => "Bool".to_string(),
+            Type::Ref | Type::TypedRef(_) => "Ref".to_string(),
+            Type::Domain(name) => name.clone(),
+            Type::Snapshot(name) => format!("Snap${name}"),
+            Type::Seq(elem_ty) => format!("Seq[{}]", self.legacy_type(elem_ty)),
+            Type::Map(key_ty, val_ty) => format!(
+                "Map[{}, {}]",
+                self.legacy_type(key_ty),
+                self.legacy_type(val_ty)
+            ),
+            Type::Float(float) => self.float_type(legacy_float_size(*float)),
+            Type::BitVector(bv) => self.bitvector_type(legacy_bitvector_bits(*bv)),
+        }
+    }
+
+    fn legacy_declaration(&mut self, var: &LocalVar) -> String {
+        format!("{}: {}", var.name, self.legacy_type(&var.typ))
+    }
+
+    fn legacy_declarations(&mut self, vars: &[LocalVar]) -> String {
+        let declarations: Vec<_> = vars
+            .iter()
+            .map(|var| self.legacy_declaration(var))
+            .collect();
+        comma_separated(declarations)
+    }
+
+    fn legacy_exprs(&mut self, exprs: &[Expr]) -> Vec<String> {
+        exprs.iter().map(|expr| self.legacy_expr(expr)).collect()
+    }
+
+    fn legacy_expr(&mut self, expr: &Expr) -> String {
+        match expr {
+            Expr::Local(local_var, _) => legacy_local_var(local_var),
+            Expr::Variant(base, field, _) | Expr::Field(base, field, _) => {
+                format!("{}.{}", self.legacy_expr(base), field.name)
+            }
+            Expr::AddrOf(..) => unreachable!(),
+            Expr::Const(value, _) => self.legacy_const(value),
+            Expr::LabelledOld(label, expr, _) => {
+                format!("old[{label}]({})", self.legacy_expr(expr))
+            }
+            Expr::MagicWand(lhs, rhs, maybe_borrow, _) => {
+                let borrow_id = if let Some(borrow) = maybe_borrow {
+                    borrow_id(*borrow) as isize
+                } else {
+                    -1
+                };
+                format!(
+                    "((acc(DeadBorrowToken$({borrow_id}), write) && {}) --* {})",
+                    self.legacy_expr(lhs),
+                    self.legacy_expr(rhs)
+                )
+            }
+            Expr::PredicateAccessPredicate(predicate_name, arg, perm, _) => {
+                self.legacy_predicate_access(predicate_name, std::slice::from_ref(arg), *perm)
+            }
+            Expr::FieldAccessPredicate(loc, perm, _) => {
+                format!("acc({}, {})", self.legacy_expr(loc), legacy_perm(*perm))
+            }
+            Expr::UnaryOp(op, arg, _) => match arg.get_type() {
+                Type::Float(float) => {
+                    let suffix = match op {
+                        UnaryOpKind::Minus => "neg",
+                        UnaryOpKind::IsNaN => "is_nan",
+                        _ => unreachable!("illegal unary operation for floats: {}", op),
+                    };
+                    let function = self.float_function(legacy_float_size(*float), suffix);
+                    format!("{function}({})", self.legacy_expr(arg))
+                }
+                Type::BitVector(bv) => {
+                    let suffix = match op {
+                        UnaryOpKind::Not => "not",
+                        UnaryOpKind::Minus => "neg",
+                        _ => unreachable!("illegal unary operation for bitvectors: {}", op),
+                    };
+                    let function = self.bitvector_function(legacy_bitvector_bits(*bv), suffix);
+                    format!("{function}({})", self.legacy_expr(arg))
+                }
+                ty => match op {
+                    UnaryOpKind::Not => format!("!({})", self.legacy_expr(arg)),
+                    UnaryOpKind::Minus => format!("-({})", self.legacy_expr(arg)),
+                    _ => unreachable!("illegal unary operation {} for type {}", op, ty),
+                },
+            },
+            Expr::BinOp(op, left, right, _) => match left.get_maybe_type() {
+                Some(Type::Float(float)) => {
+                    let suffix = match op {
+                        BinaryOpKind::Add => "add",
+                        BinaryOpKind::Sub => "sub",
+                        BinaryOpKind::Mul => "mul",
+                        BinaryOpKind::Div => "div",
+                        BinaryOpKind::EqCmp => "eq",
+                        BinaryOpKind::GtCmp => "gt",
+                        BinaryOpKind::GeCmp => "geq",
+                        BinaryOpKind::LtCmp => "lt",
+                        BinaryOpKind::LeCmp => "leq",
+                        BinaryOpKind::Min => "min",
+                        BinaryOpKind::Max => "max",
+                        _ => unreachable!("illegal binary operation for floats: {}", op),
+                    };
+                    let function = self.float_function(legacy_float_size(*float), suffix);
+                    format!(
+                        "{function}({}, {})",
+                        self.legacy_expr(left),
+                        self.legacy_expr(right)
+                    )
+                }
+                Some(Type::BitVector(bv)) => {
+                    let bv = *bv;
+                    match op {
+                        BinaryOpKind::EqCmp | BinaryOpKind::NeCmp => format!(
+                            "({} {} {})",
+                            self.legacy_expr(left),
+                            legacy_binary_op(*op),
+                            self.legacy_expr(right)
+                        ),
+                        BinaryOpKind::GtCmp
+                        | BinaryOpKind::GeCmp
+                        | BinaryOpKind::LtCmp
+                        | BinaryOpKind::LeCmp => format!(
+                            "({} {} {})",
+                            self.legacy_bitvector_to_int(bv, left),
+                            legacy_binary_op(*op),
+                            self.legacy_bitvector_to_int(bv, right)
+                        ),
+                        _ => {
+                            let suffix = match op {
+                                BinaryOpKind::Add => "add",
+                                BinaryOpKind::Sub => "sub",
+                                BinaryOpKind::Mul => "mul",
+                                BinaryOpKind::Div => "udiv",
+                                BinaryOpKind::BitAnd => "and",
+                                BinaryOpKind::BitOr => "or",
+                                BinaryOpKind::BitXor => "xor",
+                                BinaryOpKind::Shl => "shl",
+                                BinaryOpKind::LShr => "lshr",
+                                BinaryOpKind::AShr => "ashr",
+                                _ => {
+                                    unreachable!("illegal binary operation for bitvectors: {}", op)
+                                }
+                            };
+                            let function =
+                                self.bitvector_function(legacy_bitvector_bits(bv), suffix);
+                            format!(
+                                "{function}({}, {})",
+                                self.legacy_expr(left),
+                                self.legacy_expr(right)
+                            )
+                        }
+                    }
+                }
+                ty => match op {
+                    BinaryOpKind::BitAnd
+                    | BinaryOpKind::BitOr
+                    | BinaryOpKind::BitXor
+                    | BinaryOpKind::Shl
+                    | BinaryOpKind::LShr
+                    | BinaryOpKind::AShr
+                    | BinaryOpKind::Min
+                    | BinaryOpKind::Max => {
+                        unreachable!("illegal binary operation {} for type {:?}", op, ty)
+                    }
+                    _ => format!(
+                        "({} {} {})",
+                        self.legacy_expr(left),
+                        legacy_binary_op(*op),
+                        self.legacy_expr(right)
+                    ),
+                },
+            },
+            Expr::ContainerOp(op_kind, left, right, _) => match op_kind {
+                ContainerOpKind::SeqIndex => {
+                    format!("{}[{}]", self.legacy_expr(left), self.legacy_expr(right))
+                }
+                ContainerOpKind::SeqConcat => {
+                    format!(
+                        "({} ++ {})",
+                        self.legacy_expr(left),
+                        self.legacy_expr(right)
+                    )
+                }
+                ContainerOpKind::SeqLen => format!("|{}|", self.legacy_expr(left)),
+            },
+            Expr::Seq(ty, elems, _) => {
+                let Type::Seq(box elem_ty) = ty else { unreachable!() };
+                if elems.is_empty() {
+                    format!("Seq[{}]()", self.legacy_type(elem_ty))
+                } else {
+                    format!("Seq({})", comma_separated(self.legacy_exprs(elems)))
+                }
+            }
+            Expr::Map(ty, elems, _) => {
+                let Type::Map(box key_ty, box val_ty) = ty else { unreachable!() };
+                if elems.is_empty() {
+                    format!(
+                        "Map[{}, {}]()",
+                        self.legacy_type(key_ty),
+                        self.legacy_type(val_ty)
+                    )
+                } else {
+                    format!("Map({})", comma_separated(self.legacy_exprs(elems)))
+                }
+            }
+            Expr::Unfolding(predicate_name, args, expr, perm, ..) => {
+                let access = self.legacy_predicate_access(predicate_name, args, *perm);
+                format!("(unfolding {access} in {})", self.legacy_expr(expr))
+            }
+            Expr::Cond(guard, left, right, _) => format!(
+                "({} ? {} : {})",
+                self.legacy_expr(guard),
+                self.legacy_expr(left),
+                self.legacy_expr(right)
+            ),
+            Expr::ForAll(vars, triggers, body, _) => {
+                self.legacy_quantifier("forall", vars, triggers, body)
+            }
+            Expr::Exists(vars, triggers, body, _) => {
+                self.legacy_quantifier("exists", vars, triggers, body)
+            }
+            Expr::LetExpr(var, expr, body, _) => format!(
+                "(let {} == ({}) in {})",
+                var.name,
+                self.legacy_expr(expr),
+                self.legacy_expr(body)
+            ),
+            Expr::FuncApp(function_name, args, ..) => {
+                format!(
+                    "{function_name}({})",
+                    comma_separated(self.legacy_exprs(args))
+                )
+            }
+            Expr::DomainFuncApp(function, args, _) => format!(
+                "{}({})",
+                function.get_identifier(),
+                comma_separated(self.legacy_exprs(args))
+            ),
+            Expr::InhaleExhale(inhale_expr, exhale_expr, _) => format!(
+                "[{}, {}]",
+                self.legacy_expr(inhale_expr),
+                self.legacy_expr(exhale_expr)
+            ),
+            Expr::Downcast(base, ..) => self.legacy_expr(base),
+            Expr::SnapApp(..) => unreachable!("unpatched snapshot operation"),
+            Expr::Cast(kind, base, _) => match kind {
+                CastKind::BVIntoInt(bv) => self.legacy_bitvector_to_int(*bv, base),
+                CastKind::IntIntoBV(bv) => {
+                    let function = self.bitvector_function(legacy_bitvector_bits(*bv), "from_int");
+                    format!("{function}({})", self.legacy_expr(base))
+                }
+            },
+        }
+    }
+
+    fn legacy_quantifier(
+        &mut self,
+        quantifier: &str,
+        vars: &[LocalVar],
+        triggers: &[Trigger],
+        body: &Expr,
+    ) -> String {
+        let vars = self.legacy_declarations(vars);
+        let mut triggers_text = String::new();
+        for trigger in triggers {
+            let elements = self.legacy_exprs(trigger.elements());
+            triggers_text.push_str(&format!("{{ {} }} ", comma_separated(elements)));
+        }
+        format!(
+            "({quantifier} {vars} :: {triggers_text}{})",
+            self.legacy_expr(body)
+        )
+    }
+
+    /// Convert a bitvector to an integer, interpreting signed bitvectors in
+    /// two's complement.
+    fn legacy_bitvector_to_int(&mut self, bv: BitVector, value: &Expr) -> String {
+        let size = legacy_bitvector_bits(bv);
+        let to_int = self.bitvector_function(size, "to_int");
+        let value = format!("{to_int}({})", self.legacy_expr(value));
+        match bv {
+            BitVector::Signed(_) => {
+                let signed_max = (1u128 << (size - 1)) - 1;
+                let unsigned_max = u128::MAX >> (128 - size);
+                format!("(({signed_max} < {value}) ? (({value} - {unsigned_max}) - 1) : {value})")
+            }
+            BitVector::Unsigned(_) => value,
+        }
+    }
+
+    fn legacy_const(&mut self, value: &Const) -> String {
+        match value {
+            Const::Bool(value) => value.to_string(),
+            Const::Int(value) => value.to_string(),
+            Const::BigInt(value) => value.clone(),
+            Const::Float(FloatConst::F32(bits)) => self.float_literal(FloatSize::F32, bits),
+            Const::Float(FloatConst::F64(bits)) => self.float_literal(FloatSize::F64, bits),
+            Const::BitVector(bv_const) => {
+                let function =
+                    self.bitvector_function(legacy_bitvector_bits(bv_const.typ), "from_int");
+                format!("{function}({})", bv_const.value)
+            }
+            Const::FnPtr => "null".to_string(),
+        }
+    }
+}
+
+fn legacy_local_var(local_var: &LocalVar) -> String {
+    if local_var.name == "__result" {
+        "result".to_string()
+    } else {
+        local_var.name.clone()
+    }
+}
+
+fn legacy_perm(perm: PermAmount) -> &'static str {
+    match perm {
+        PermAmount::Write => "write",
+        PermAmount::Read => "read$()",
+        PermAmount::Remaining => "(write - read$())",
+    }
+}
+
+fn legacy_binary_op(op: BinaryOpKind) -> &'static str {
+    match op {
+        BinaryOpKind::EqCmp => "==",
+        BinaryOpKind::NeCmp => "!=",
+        BinaryOpKind::GtCmp => ">",
+        BinaryOpKind::GeCmp => ">=",
+        BinaryOpKind::LtCmp => "<",
+        BinaryOpKind::LeCmp => "<=",
+        BinaryOpKind::Add => "+",
+        BinaryOpKind::Sub => "-",
+        BinaryOpKind::Mul => "*",
+        BinaryOpKind::Div => "\\",
+        BinaryOpKind::Mod => "%",
+        BinaryOpKind::And => "&&",
+        BinaryOpKind::Or => "||",
+        BinaryOpKind::Implies => "==>",
+        BinaryOpKind::BitAnd
+        | BinaryOpKind::BitOr
+        | BinaryOpKind::BitXor
+        | BinaryOpKind::Shl
+        | BinaryOpKind::LShr
+        | BinaryOpKind::AShr
+        | BinaryOpKind::Min
+        | BinaryOpKind::Max => unreachable!("{} is not a Silver operator", op),
+    }
+}
+
+fn legacy_float_size(float: Float) -> FloatSize {
+    match float {
+        Float::F32 => FloatSize::F32,
+        Float::F64 => FloatSize::F64,
+    }
+}
+
+fn legacy_bitvector_bits(bv: BitVector) -> u32 {
+    let (BitVector::Signed(size) | BitVector::Unsigned(size)) = bv;
+    match size {
+        BitVectorSize::BV8 => 8,
+        BitVectorSize::BV16 => 16,
+        BitVectorSize::BV32 => 32,
+        BitVectorSize::BV64 => 64,
+        BitVectorSize::BV128 => 128,
+    }
+}
@@ -0,0 +1,504 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use super::{comma_separated, FloatSize, SilverPrinter};
+use vir::{
+    legacy::RETURN_LABEL,
+    low::{
+        ast::{
+            expression::{self, ConstantValue, ContainerOpKind, Expression},
+            statement::Statement,
+            ty::{self, BitVector, BitVectorSize, Float, Type},
+            variable::VariableDecl,
+        },
+        cfg::{procedure::Successor, MethodDecl, ProcedureDecl},
+        program::Program,
+        DomainDecl, FunctionDecl, PredicateDecl,
+    },
+};
+
+impl SilverPrinter {
+    pub(super) fn low_program(&mut self, program: &Program) {
+        for domain in &program.domains {
+            self.low_domain(domain);
+            self.blank_line();
+        }
+        for function in &program.functions {
+            self.low_function(function);
+            self.blank_line();
+        }
+        for predicate in &program.predicates {
+            self.low_predicate(predicate);
+            self.blank_line();
+        }
+        for procedure in &program.procedures {
+            self.low_procedure(procedure);
+            self.blank_line();
+        }
+        for method in &program.methods {
+            self.low_method(method);
+            self.blank_line();
+        }
+    }
+
+    fn low_domain(&mut self, domain: &DomainDecl) {
+        self.open(format!("domain {}", domain.name));
+        for function in &domain.functions {
+            let unique = if function.is_unique { "unique " } else { "" };
+            let parameters = self.low_declarations(&function.parameters);
+            let return_type = self.low_type(&function.return_type);
+            self.line(format!(
+                "{unique}function {}({parameters}): {return_type}",
+                function.name
+            ));
+        }
+        for axiom in &domain.axioms {
+            if let Some(comment) = &axiom.comment {
+                self.comment(comment);
+            }
+            let body = self.low_expression(&axiom.body);
+            self.line(format!("axiom {} {{ {body} }}", axiom.name));
+        }
+        self.close();
+    }
+
+    fn low_function(&mut self, function: &FunctionDecl) {
+        let parameters = self.low_declarations(&function.parameters);
+        let return_type = self.low_type(&function.return_type);
+        self.line(format!(
+            "function {}({parameters}): {return_type}",
+            function.name
+        ));
+        let pres = self.low_expressions(&function.pres);
+        let posts = self.low_expressions(&function.posts);
+        self.specifications_and_body(&pres, &posts, function.body.as_ref(), |this, body| {
+            let body = this.low_expression(body);
+            this.line(body);
+        });
+    }
+
+    fn low_predicate(&mut self, predicate: &PredicateDecl) {
+        let header = format!(
+            "predicate {}({})",
+            predicate.name,
+            self.low_declarations(&predicate.parameters)
+        );
+        if let Some(body) = &predicate.body {
+            self.open(header);
+            let body = self.low_expression(body);
+            self.line(body);
+            self.close();
+        } else {
+            self.line(header);
+        }
+    }
+
+    fn low_procedure(&mut self, procedure: &ProcedureDecl) {
+        self.line(format!("method {}()", procedure.name));
+        self.line("{");
+        self.indentation += 1;
+        for local in &procedure.locals {
+            let declaration = self.low_declaration(local);
+            self.line(format!("var {declaration}"));
+        }
+        for block in &procedure.basic_blocks {
+            self.line(format!("label {}", block.label.name));
+            self.low_statements(&block.statements);
+            match &block.successor {
+                Successor::Goto(target) => self.line(format!("goto {}", target.name)),
+                Successor::GotoSwitch(targets) => {
+                    for (test, target) in targets {
+                        let test = self.low_expression(test);
+                        self.line(format!("if ({test}) {{ goto {} }}", target.name));
+                    }
+                    self.line("assert false");
+                }
+                Successor::Return => self.line(format!("goto {RETURN_LABEL}")),
+            }
+        }
+        self.line(format!("label {RETURN_LABEL}"));
+        self.close();
+    }
+
+    fn low_method(&mut self, method: &MethodDecl) {
+        let mut header = format!(
+            "method {}({})",
+            method.name,
+            self.low_declarations(&method.parameters)
+        );
+        if !method.targets.is_empty() {
+            header.push_str(&format!(
+                " returns ({})",
+                self.low_declarations(&method.targets)
+            ));
+        }
+        self.line(header);
+        let pres = self.low_expressions(&method.pres);
+        let posts = self.low_expressions(&method.posts);
+        self.specifications_and_body(&pres, &posts, method.body.as_ref(), |this, body| {
+            this.low_statements(body);
+        });
+    }
+
+    fn low_statements(&mut self, statements: &[Statement]) {
+        for statement in statements {
+            self.low_statement(statement);
+        }
+    }
+
+    fn low_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Comment(statement) => self.comment(&statement.comment),
+            Statement::LogEvent(statement) => {
+                let expression = self.low_expression(&statement.expression);
+                self.line(format!("inhale {expression}"));
+            }
+            Statement::Assume(statement) => {
+                let expression = self.low_expression(&statement.expression);
+                self.line(format!("inhale {expression}"));
+            }
+            Statement::Assert(statement) => {
+                let expression = self.low_expression(&statement.expression);
+                self.line(format!("assert {expression}"));
+            }
+            Statement::Inhale(statement) => {
+                let expression = self.low_expression(&statement.expression);
+                self.line(format!("inhale {expression}"));
+            }
+            Statement::Exhale(statement) => {
+                let expression = self.low_expression(&statement.expression);
+                self.line(format!("exhale {expression}"));
+            }
+            Statement::Fold(statement) => {
+                let expression = self.low_expression(&statement.expression);
+                self.line(format!("fold {expression}"));
+            }
+            Statement::Unfold(statement) => {
+                let expression = self.low_expression(&statement.expression);
+                self.line(format!("unfold {expression}"));
+            }
+            Statement::ApplyMagicWand(statement) => {
+                let expression = self.low_expression(&statement.expression);
+                self.line(format!("apply {expression}"));
+            }
+            Statement::Conditional(statement) => {
+                let guard = self.low_expression(&statement.guard);
+                self.open(format!("if ({guard})"));
+                self.low_statements(&statement.then_branch);
+                self.indentation -= 1;
+                self.line("} else {");
+                self.indentation += 1;
+                self.low_statements(&statement.else_branch);
+                self.close();
+            }
+            Statement::MethodCall(statement) => {
+                let arguments = comma_separated(self.low_expressions(&statement.arguments));
+                if statement.targets.is_empty() {
+                    self.line(format!("{}({arguments})", statement.method_name));
+                } else {
+                    let targets = comma_separated(self.low_expressions(&statement.targets));
+                    self.line(format!(
+                        "{targets} := {}({arguments})",
+                        statement.method_name
+                    ));
+                }
+            }
+            Statement::Assign(statement) => {
+                let value = self.low_expression(&statement.value);
+                self.line(format!(
+                    "{} := {value}",
+                    low_variable_name(&statement.target)
+                ));
+            }
+        }
+    }
+
+    fn low_type(&mut self, ty: &Type) -> String {
+        match ty {
+            Type::Int => "Int".to_string(),
+            Type::Bool => "Bool".to_string(),
+            Type::Ref => "Ref".to_string(),
+            Type::Perm => "Perm".to_string(),
+            Type::Domain(ty) => ty.name.clone(),
+            Type::Seq(ty) => format!("Seq[{}]", self.low_type(&ty.element_type)),
+            Type::Set(ty) => format!("Set[{}]", self.low_type(&ty.element_type)),
+            Type::MultiSet(ty) => format!("Multiset[{}]", self.low_type(&ty.element_type)),
+            Type::Map(ty) => format!(
+                "Map[{}, {}]",
+                self.low_type(&ty.key_type),
+                self.low_type(&ty.val_type)
+            ),
+            Type::Float(Float::F32) => self.float_type(FloatSize::F32),
+            Type::Float(Float::F64) => self.float_type(FloatSize::F64),
+            Type::BitVector(BitVector::Signed(size) | BitVector::Unsigned(size)) => {
+                let size = match size {
+                    BitVectorSize::BV8 => 8,
+                    BitVectorSize::BV16 => 16,
+                    BitVectorSize::BV32 => 32,
+                    BitVectorSize::BV64 => 64,
+                    BitVectorSize::BV128 => 128,
+                };
+                self.bitvector_type(size)
+            }
+        }
+    }
+
+    fn low_declaration(&mut self, variable: &VariableDecl) -> String {
+        format!("{}: {}", variable.name, self.low_type(&variable.ty))
+    }
+
+    fn low_declarations(&mut self, variables: &[VariableDecl]) -> String {
+        let declarations: Vec<_> = variables
+            .iter()
+            .map(|variable| self.low_declaration(variable))
+            .collect();
+        comma_separated(declarations)
+    }
+
+    fn low_expressions(&mut self, expressions: &[Expression]) -> Vec<String> {
+        expressions
+            .iter()
+            .map(|expression| self.low_expression(expression))
+            .collect()
+    }
+
+    fn low_expression(&mut self, expression: &Expression) -> String {
+        match expression {
+            Expression::Local(expression) => low_variable_name(&expression.variable),
+            Expression::Field(expression) => format!(
+                "{}.{}",
+                self.low_expression(&expression.base),
+                expression.field.name
+            ),
+            Expression::LabelledOld(expression) => {
+                let base = self.low_expression(&expression.base);
+                if let Some(label) = &expression.label {
+                    format!("old[{label}]({base})")
+                } else {
+                    format!("old({base})")
+                }
+            }
+            Expression::Constant(expression) => low_constant(expression),
+            Expression::MagicWand(expression) => format!(
+                "({} --* {})",
+                self.low_expression(&expression.left),
+                self.low_expression(&expression.right)
+            ),
+            Expression::PredicateAccessPredicate(expression) => {
+                let arguments = comma_separated(self.low_expressions(&expression.arguments));
+                format!(
+                    "acc({}({arguments}), {})",
+                    expression.name,
+                    self.low_expression(&expression.permission)
+                )
+            }
+            Expression::FieldAccessPredicate(expression) => format!(
+                "acc({}, {})",
+                self.low_expression(&expression.base),
+                self.low_expression(&expression.permission)
+            ),
+            Expression::Unfolding(expression) => {
+                let arguments = comma_separated(self.low_expressions(&expression.arguments));
+                format!(
+                    "(unfolding acc({}({arguments}), {}) in {})",
+                    expression.predicate,
+                    self.low_expression(&expression.permission),
+                    self.low_expression(&expression.base)
+                )
+            }
+            Expression::UnaryOp(expression) => {
+                let operator = match expression.op_kind {
+                    expression::UnaryOpKind::Not => "!",
+                    expression::UnaryOpKind::Minus => "-",
+                };
+                format!("{operator}({})", self.low_expression(&expression.argument))
+            }
+            Expression::BinaryOp(expression) => {
+                let operator = match expression.op_kind {
+                    expression::BinaryOpKind::EqCmp => "==",
+                    expression::BinaryOpKind::NeCmp => "!=",
+                    expression::BinaryOpKind::GtCmp => ">",
+                    expression::BinaryOpKind::GeCmp => ">=",
+                    expression::BinaryOpKind::LtCmp => "<",
+                    expression::BinaryOpKind::LeCmp => "<=",
+                    expression::BinaryOpKind::Add => "+",
+                    expression::BinaryOpKind::Sub => "-",
+                    expression::BinaryOpKind::Mul => "*",
+                    expression::BinaryOpKind::Div => "\\",
+                    expression::BinaryOpKind::Mod => "%",
+                    expression::BinaryOpKind::And => "&&",
+                    expression::BinaryOpKind::Or => "||",
+                    expression::BinaryOpKind::Implies => "==>",
+                };
+                format!(
+                    "({} {operator} {})",
+                    self.low_expression(&expression.left),
+                    self.low_expression(&expression.right)
+                )
+            }
+            Expression::PermBinaryOp(expression) => {
+                let operator = match expression.op_kind {
+                    expression::PermBinaryOpKind::Add => "+",
+                    expression::PermBinaryOpKind::Sub => "-",
+                    expression::PermBinaryOpKind::Mul => "*",
+                    expression::PermBinaryOpKind::Div => "/",
+                };
+                format!(
+                    "({} {operator} {})",
+                    self.low_expression(&expression.left),
+                    self.low_expression(&expression.right)
+                )
+            }
+            Expression::ContainerOp(expression) => self.low_container_op(expression),
+            Expression::Conditional(expression) => format!(
+                "({} ? {} : {})",
+                self.low_expression(&expression.guard),
+                self.low_expression(&expression.then_expr),
+                self.low_expression(&expression.else_expr)
+            ),
+            Expression::Quantifier(expression) => {
+                let quantifier = match expression.kind {
+                    expression::QuantifierKind::ForAll => "forall",
+                    expression::QuantifierKind::Exists => "exists",
+                };
+                let variables = self.low_declarations(&expression.variables);
+                let mut triggers = String::new();
+                for trigger in &expression.triggers {
+                    let terms = self.low_trigger_terms(&trigger.terms);
+                    triggers.push_str(&format!("{{ {} }} ", comma_separated(terms)));
+                }
+                format!(
+                    "({quantifier} {variables} :: {triggers}{})",
+                    self.low_expression(&expression.body)
+                )
+            }
+            Expression::LetExpr(expression) => format!(
+                "(let {} == ({}) in {})",
+                expression.variable.name,
+                self.low_expression(&expression.def),
+                self.low_expression(&expression.body)
+            ),
+            Expression::FuncApp(expression) => format!(
+                "{}({})",
+                expression.function_name,
+                comma_separated(self.low_expressions(&expression.arguments))
+            ),
+            Expression::DomainFuncApp(expression) => format!(
+                "{}({})",
+                expression.function_name,
+                comma_separated(self.low_expressions(&expression.arguments))
+            ),
+            Expression::InhaleExhale(expression) => format!(
+                "[{}, {}]",
+                self.low_expression(&expression.inhale_expression),
+                self.low_expression(&expression.exhale_expression)
+            ),
+        }
+    }
+
+    /// Inside triggers, predicate accesses are used without the permission
+    /// amount.
+    fn low_trigger_terms(&mut self, terms: &[Expression]) -> Vec<String> {
+        terms
+            .iter()
+            .map(|term| match term {
+                Expression::PredicateAccessPredicate(access) => {
+                    let arguments = comma_separated(self.low_expressions(&access.arguments));
+                    format!("{}({arguments})", access.name)
+                }
+                _ => self.low_expression(term),
+            })
+            .collect()
+    }
+
+    fn low_container_op(&mut self, expression: &expression::ContainerOp) -> String {
+        let operands = self.low_expressions(&expression.operands);
+        let element_type = |this: &mut Self| match &expression.container_type {
+            Type::Seq(ty::Seq { element_type })
+            | Type::Set(ty::Set { element_type })
+            | Type::MultiSet(ty::MultiSet { element_type }) => this.low_type(element_type),
+            _ => unreachable!("{}", expression.container_type),
+        };
+        let collection = |this: &mut Self, name: &str| {
+            if operands.is_empty() {
+                format!("{name}[{}]()", element_type(this))
+            } else {
+                format!("{name}({})", comma_separated(&operands))
+            }
+        };
+        match expression.kind {
+            ContainerOpKind::SeqEmpty => format!("Seq[{}]()", element_type(self)),
+            ContainerOpKind::SeqConstructor => collection(self, "Seq"),
+            ContainerOpKind::SeqIndex => format!("{}[{}]", operands[0], operands[1]),
+            ContainerOpKind::SeqConcat => format!("({} ++ {})", operands[0], operands[1]),
+            ContainerOpKind::SeqTake => format!("{}[..{}]", operands[0], operands[1]),
+            ContainerOpKind::SeqDrop => format!("{}[{}..]", operands[0], operands[1]),
+            ContainerOpKind::SeqUpdate => {
+                format!("{}[{} := {}]", operands[0], operands[1], operands[2])
+            }
+            ContainerOpKind::SeqLen
+            | ContainerOpKind::MapLen
+            | ContainerOpKind::SetCardinality
+            | ContainerOpKind::MultiSetCardinality => format!("|{}|", operands[0]),
+            ContainerOpKind::MapEmpty => {
+                let Type::Map(ty::Map { key_type, val_type }) = &expression.container_type else {
+                    unreachable!("{}", expression.container_type)
+                };
+                format!(
+                    "Map[{}, {}]()",
+                    self.low_type(key_type),
+                    self.low_type(val_type)
+                )
+            }
+            ContainerOpKind::MapUpdate => {
+                format!("{}[{} := {}]", operands[0], operands[1], operands[2])
+            }
+            ContainerOpKind::MapContains => format!("({} in {})", operands[1], operands[0]),
+            ContainerOpKind::MapLookup => format!("{}[{}]", operands[0], operands[1]),
+            ContainerOpKind::SetEmpty => format!("Set[{}]()", element_type(self)),
+            ContainerOpKind::SetConstructor => collection(self, "Set"),
+            ContainerOpKind::MultiSetEmpty => format!("Multiset[{}]()", element_type(self)),
+            ContainerOpKind::MultiSetConstructor => collection(self, "Multiset"),
+            ContainerOpKind::SetUnion | ContainerOpKind::MultiSetUnion => {
+                format!("({} union {})", operands[0], operands[1])
+            }
+            ContainerOpKind::SetIntersection | ContainerOpKind::MultiSetIntersection => {
+                format!("({} intersection {})", operands[0], operands[1])
+            }
+            ContainerOpKind::SetSubset | ContainerOpKind::MultiSetSubset => {
+                format!("({} subset {})", operands[0], operands[1])
+            }
+            ContainerOpKind::SetMinus | ContainerOpKind::MultiSetMinus => {
+                format!("({} setminus {})", operands[0], operands[1])
+            }
+            ContainerOpKind::SeqContains
+            | ContainerOpKind::SetContains
+            | ContainerOpKind::MultiSetContains => {
+                format!("({} in {})", operands[0], operands[1])
+            }
+        }
+    }
+}
+
+fn low_variable_name(variable: &VariableDecl) -> String {
+    if variable.name == "__result" {
+        "result".to_string()
+    } else {
+        variable.name.clone()
+    }
+}
+
+fn low_constant(constant: &expression::Constant) -> String {
+    match (&constant.ty, &constant.value) {
+        (Type::Perm, ConstantValue::Int(0)) => "none".to_string(),
+        (Type::Perm, ConstantValue::Int(1)) => "write".to_string(),
+        (Type::Perm, ConstantValue::Int(-1)) => "wildcard".to_string(),
+        (Type::Int, ConstantValue::Int(value)) => value.to_string(),
+        (Type::Int, ConstantValue::BigInt(value)) => value.clone(),
+        (Type::Bool, ConstantValue::Bool(value)) => value.to_string(),
+        _ => unimplemented!("constant: {:?}", constant),
+    }
+}
@@ -0,0 +1,443 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! A pretty printer that produces the textual Silver syntax of a program
+//! without going through the JVM.
+//!
+//! The output mirrors the Viper AST that `ToViper` constructs, so that the
+//! printed program can be parsed and verified by Viper (for example, in the
+//! Viper IDE). Binary operations are always parenthesized, hence the output
+//! does not depend on Silver's operator precedences.
+
+mod legacy;
+mod low;
+
+use super::program::Program;
+use std::{collections::BTreeSet, fmt::Write};
+
+pub trait ToSilver {
+    /// Print `self` in the textual Silver syntax.
+    fn to_silver(&self) -> String;
+}
+
+impl ToSilver for Program {
+    fn to_silver(&self) -> String {
+        let mut printer = SilverPrinter::default();
+        match self {
+            Program::Legacy(program) => printer.legacy_program(program),
+            Program::Low(program) => printer.low_program(program),
+        }
+        printer.finish()
+    }
+}
+
+/// The size of a backend floating-point type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum FloatSize {
+    F32,
+    F64,
+}
+
+impl FloatSize {
+    fn bits(self) -> u32 {
+        match self {
+            FloatSize::F32 => 32,
+            FloatSize::F64 => 64,
+        }
+    }
+
+    /// The exponent and significand sizes; the same as the ones used by
+    /// `AstFactory::backend_f32_type` and `AstFactory::backend_f64_type`.
+    fn exponent_and_significand(self) -> (u32, u32) {
+        match self {
+            FloatSize::F32 => (8, 24),
+            FloatSize::F64 => (12, 52),
+        }
+    }
+}
+
+/// A domain that is interpreted by the SMT solver. Viper creates these domains
+/// implicitly when `ToViper` uses the `FloatFactory` and the `BVFactory`; in
+/// the textual syntax they have to be declared explicitly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum BackendDomain {
+    BitVector(u32),
+    Float(FloatSize),
+}
+
+/// Bitvector functions: the suffix of the function name and its SMT-LIB
+/// interpretation. All functions except `from_int` and `to_int` take
+/// bitvectors and return a bitvector.
+const BITVECTOR_FUNCTIONS: &[(&str, &str, usize)] = &[
+    ("not", "bvnot", 1),
+    ("neg", "bvneg", 1),
+    ("and", "bvand", 2),
+    ("or", "bvor", 2),
+    ("xor", "bvxor", 2),
+    ("add", "bvadd", 2),
+    ("sub", "bvsub", 2),
+    ("mul", "bvmul", 2),
+    ("udiv", "bvudiv", 2),
+    ("shl", "bvshl", 2),
+    ("lshr", "bvlshr", 2),
+    ("ashr", "bvashr", 2),
+];
+
+/// Floating-point functions: the suffix of the function name, its SMT-LIB
+/// interpretation, its arity and whether it returns a `Bool`.
+const FLOAT_FUNCTIONS: &[(&str, &str, usize, bool)] = &[
+    ("neg", "fp.neg", 1, false),
+    ("abs", "fp.abs", 1, false),
+    ("is_nan", "fp.isNaN", 1, true),
+    ("add", "fp.add RNE", 2, false),
+    ("sub", "fp.sub RNE", 2, false),
+    ("mul", "fp.mul RNE", 2, false),
+    ("div", "fp.div RNE", 2, false),
+    ("min", "fp.min", 2, false),
+    ("max", "fp.max", 2, false),
+    ("eq", "fp.eq", 2, true),
+    ("leq", "fp.leq", 2, true),
+    ("geq", "fp.geq", 2, true),
+    ("lt", "fp.lt", 2, true),
+    ("gt", "fp.gt", 2, true),
+];
+
+#[derive(Default)]
+struct SilverPrinter {
+    output: String,
+    indentation: usize,
+    backend_domains: BTreeSet<BackendDomain>,
+}
+
+impl SilverPrinter {
+    fn line(&mut self, text: impl AsRef<str>) {
+        for _ in 0..self.indentation {
+            self.output.push_str("  ");
+        }
+        self.output.push_str(text.as_ref());
+        self.output.push('\n');
+    }
+
+    fn blank_line(&mut self) {
+        self.output.push('\n');
+    }
+
+    /// Print `text` as a sequence of line comments.
+    fn comment(&mut self, text: &str) {
+        if text.is_empty() {
+            self.line("//");
+        }
+        for line in text.lines() {
+            self.line(format!("// {line}"));
+        }
+    }
+
+    fn open(&mut self, text: impl AsRef<str>) {
+        self.line(format!("{} {{", text.as_ref()));
+        self.indentation += 1;
+    }
+
+    fn close(&mut self) {
+        self.indentation -= 1;
+        self.line("}");
+    }
+
+    /// Print the specifications of a function or method followed by its
+    /// optional body, which is printed by `print_body`.
+    fn specifications_and_body<T>(
+        &mut self,
+        pres: &[String],
+        posts: &[String],
+        body: Option<T>,
+        print_body: impl FnOnce(&mut Self, T),
+    ) {
+        self.indentation += 1;
+        for pre in pres {
+            self.line(format!("requires {pre}"));
+        }
+        for post in posts {
+            self.line(format!("ensures {post}"));
+        }
+        self.indentation -= 1;
+        if let Some(body) = body {
+            self.line("{");
+            self.indentation += 1;
+            print_body(self, body);
+            self.close();
+        }
+    }
+
+    fn bitvector_type(&mut self, size: u32) -> String {
+        self.backend_domains.insert(BackendDomain::BitVector(size));
+        bitvector_domain_name(size)
+    }
+
+    fn bitvector_function(&mut self, size: u32, suffix: &str) -> String {
+        self.backend_domains.insert(BackendDomain::BitVector(size));
+        format!("bv{size}_{suffix}")
+    }
+
+    fn float_type(&mut self, size: FloatSize) -> String {
+        self.backend_domains.insert(BackendDomain::Float(size));
+        float_domain_name(size)
+    }
+
+    fn float_function(&mut self, size: FloatSize, suffix: &str) -> String {
+        self.backend_domains.insert(BackendDomain::Float(size));
+        format!("f{}_fp_{}", size.bits(), suffix)
+    }
+
+    /// A floating-point literal given by its IEEE 754 bit pattern.
+    fn float_literal(&mut self, size: FloatSize, bits: impl std::fmt::Display) -> String {
+        self.backend_domains.insert(BackendDomain::Float(size));
+        let from_int = self.bitvector_function(size.bits(), "from_int");
+        format!("f{}_from_bv({from_int}({bits}))", size.bits())
+    }
+
+    /// Return the printed program, preceded by the declarations of the used
+    /// backend domains.
+    fn finish(mut self) -> String {
+        // Floating-point literals are constructed from bitvectors.
+        let float_sizes: Vec<_> = self
+            .backend_domains
+            .iter()
+            .filter_map(|domain| match domain {
+                BackendDomain::Float(size) => Some(size.bits()),
+                BackendDomain::BitVector(_) => None,
+            })
+            .collect();
+        self.backend_domains
+            .extend(float_sizes.into_iter().map(BackendDomain::BitVector));
+        let mut declarations = String::new();
+        for domain in &self.backend_domains {
+            backend_domain_declaration(&mut declarations, *domain);
+            declarations.push('\n');
+        }
+        declarations + &self.output
+    }
+}
+
+fn bitvector_domain_name(size: u32) -> String {
+    format!("BitVectorDomain{size}")
+}
+
+fn float_domain_name(size: FloatSize) -> String {
+    let (exponent, significand) = size.exponent_and_significand();
+    format!("FloatDomain{significand}e{exponent}")
+}
+
+fn backend_domain_declaration(output: &mut String, domain: BackendDomain) {
+    match domain {
+        BackendDomain::BitVector(size) => {
+            let ty = bitvector_domain_name(size);
+            writeln!(
+                output,
+                "domain {ty} interpretation (SMTLIB: \"(_ BitVec {size})\", Boogie: \"bv{size}\") {{"
+            )
+            .unwrap();
+            writeln!(
+                output,
+                "  function bv{size}_from_int(i: Int): {ty} interpretation \"(_ int2bv {size})\""
+            )
+            .unwrap();
+            writeln!(
+                output,
+                "  function bv{size}_to_int(b: {ty}): Int interpretation \"bv2nat\""
+            )
+            .unwrap();
+            for (suffix, interpretation, arity) in BITVECTOR_FUNCTIONS {
+                let parameters = parameters(*arity, &ty);
+                writeln!(
+                    output,
+                    "  function bv{size}_{suffix}({parameters}): {ty} interpretation \"{interpretation}\""
+                )
+                .unwrap();
+            }
+        }
+        BackendDomain::Float(float_size) => {
+            let ty = float_domain_name(float_size);
+            let (exponent, significand) = float_size.exponent_and_significand();
+            let bits = float_size.bits();
+            writeln!(
+                output,
+                "domain {ty} interpretation (SMTLIB: \"(_ FloatingPoint {exponent} {significand})\", \
+                 Boogie: \"float{significand}e{exponent}\") {{"
+            )
+            .unwrap();
+            writeln!(
+                output,
+                "  function f{bits}_from_bv(b: {}): {ty} interpretation \"(_ to_fp {exponent} {significand})\"",
+                bitvector_domain_name(bits)
+            )
+            .unwrap();
+            for (suffix, interpretation, arity, is_predicate) in FLOAT_FUNCTIONS {
+                let parameters = parameters(*arity, &ty);
+                let return_type = if *is_predicate { "Bool" } else { ty.as_str() };
+                writeln!(
+                    output,
+                    "  function f{bits}_fp_{suffix}({parameters}): {return_type} interpretation \"{interpretation}\""
+                )
+                .unwrap();
+            }
+        }
+    }
+    output.push_str("}\n");
+}
+
+fn parameters(arity: usize, ty: &str) -> String {
+    (0..arity)
+        .map(|index| format!("_{index}: {ty}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Join the already printed items with commas.
+fn comma_separated<T: AsRef<str>>(items: impl IntoIterator<Item = T>) -> String {
+    let mut result = String::new();
+    for (index, item) in items.into_iter().enumerate() {
+        if index > 0 {
+            result.push_str(", ");
+        }
+        result.push_str(item.as_ref());
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vir::{
+        cfg::{CfgMethod, Successor},
+        BitVector, BitVectorConst, BitVectorSize, Const, Expr, Field, LocalVar, Position, Stmt,
+        Type,
+    };
+    use vir::{
+        common::check_mode::CheckMode,
+        low::{
+            self,
+            ast::{expression::BinaryOpKind, ty},
+            cfg::{BasicBlock, Label, ProcedureDecl},
+        },
+    };
+
+    fn legacy_program(methods: Vec<CfgMethod>) -> Program {
+        Program::Legacy(vir::legacy::Program {
+            name: "test".to_string(),
+            domains: vec![],
+            backend_types: vec![],
+            fields: vec![Field::new("val_int", Type::Int)],
+            builtin_methods: vec![],
+            methods,
+            functions: vec![],
+            viper_predicates: vec![],
+        })
+    }
+
+    #[test]
+    fn legacy_method_is_printed_as_labelled_blocks() {
+        let x = LocalVar::new("x", Type::Int);
+        let mut method = CfgMethod::new("m".to_string(), vec![], vec![x.clone()]);
+        let start = method.add_block(
+            "start",
+            vec![Stmt::Assign(
+                Expr::local(x.clone()),
+                Expr::Const(Const::Int(1), Position::default()),
+                vir::legacy::AssignKind::Copy,
+            )],
+        );
+        let then = method.add_block("then", vec![]);
+        let end = method.add_block("end", vec![]);
+        method.set_successor(
+            start,
+            Successor::GotoSwitch(vec![(Expr::eq_cmp(Expr::local(x), 1.into()), then)], end),
+        );
+        method.set_successor(then, Successor::Return);
+        method.set_successor(end, Successor::Return);
+        let silver = legacy_program(vec![method]).to_silver();
+        assert!(silver.contains("field val_int: Int\n"), "{silver}");
+        assert!(silver.contains("function read$(): Perm\n"), "{silver}");
+        assert!(
+            silver.contains(
+                "method m()\n{\n  var x: Int\n  //\n  label start\n  x := 1\n  \
+             if ((x == 1)) { goto then }\n  goto end\n"
+            ),
+            "{silver}"
+        );
+        assert!(silver.ends_with("  label end_of_method\n}\n\n"), "{silver}");
+        assert!(!silver.contains("domain"), "{silver}");
+    }
+
+    #[test]
+    fn backend_domains_are_declared_when_used() {
+        let x = LocalVar::new("x", Type::BitVector(BitVector::Signed(BitVectorSize::BV8)));
+        let mut method = CfgMethod::new("m".to_string(), vec![], vec![x.clone()]);
+        let start = method.add_block(
+            "start",
+            vec![Stmt::Inhale(Expr::eq_cmp(
+                Expr::local(x),
+                Expr::Const(
+                    Const::BitVector(BitVectorConst {
+                        value: "3".to_string(),
+                        typ: BitVector::Signed(BitVectorSize::BV8),
+                    }),
+                    Position::default(),
+                ),
+            ))],
+        );
+        method.set_successor(start, Successor::Return);
+        let silver = legacy_program(vec![method]).to_silver();
+        assert!(silver.starts_with(
+            "domain BitVectorDomain8 interpretation (SMTLIB: \"(_ BitVec 8)\", Boogie: \"bv8\") {\n"
+        ), "{silver}");
+        assert!(silver.contains("  var x: BitVectorDomain8\n"), "{silver}");
+        assert!(
+            silver.contains("  inhale (x == bv8_from_int(3))\n"),
+            "{silver}"
+        );
+    }
+
+    #[test]
+    fn low_procedure_is_printed_with_return_label() {
+        let b = low::VariableDecl::new("b", low::Type::Bool);
+        let s = low::VariableDecl::new("s", low::Type::Seq(ty::Seq::new(low::Type::Int)));
+        let length = low::Expression::container_op_no_pos(
+            low::ContainerOpKind::SeqLen,
+            s.ty.clone(),
+            vec![low::Expression::local_no_pos(s.clone())],
+        );
+        let statements = vec![low::Statement::assign_no_pos(
+            b.clone(),
+            low::Expression::binary_op_no_pos(
+                BinaryOpKind::LtCmp,
+                low::Expression::constant_no_pos(low::ConstantValue::Int(0), low::Type::Int),
+                length,
+            ),
+        )];
+        let procedure = ProcedureDecl::new(
+            "p",
+            vec![b, s],
+            vec![BasicBlock::new(
+                Label::new("start"),
+                statements,
+                low::Successor::Return,
+            )],
+        );
+        let program = Program::Low(low::Program {
+            name: "test".to_string(),
+            check_mode: CheckMode::Specifications,
+            domains: vec![],
+            predicates: vec![],
+            functions: vec![],
+            methods: vec![],
+            procedures: vec![procedure],
+        });
+        assert_eq!(
+            program.to_silver(),
+            "method p()\n{\n  var b: Bool\n  var s: Seq[Int]\n  label start\n  \
+             b := (0 < |s|)\n  goto end_of_method\n  label end_of_method\n}\n\n"
+        );
+    }
+}
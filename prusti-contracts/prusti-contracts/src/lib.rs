@@ -21,6 +21,14 @@ pub use prusti_contracts_proc_macros::trusted;
 /// A macro for marking a function as opted into verification.
 pub use prusti_contracts_proc_macros::verified;
 
+/// A macro for setting the time budget (in seconds) of the verification of a
+/// function, or of each function in an impl block or module.
+pub use prusti_contracts_proc_macros::timeout;
+
+/// A macro for overriding configuration flags for a function, or for all
+/// functions in an impl block or module.
+pub use prusti_contracts_proc_macros::flags;
+
 /// A macro for type invariants.
 pub use prusti_contracts_proc_macros::invariant;
 
@@ -57,10 +65,29 @@ pub use prusti_contracts_proc_macros::ghost;
 /// A macro to customize how a struct or enum should be printed in a counterexample
 pub use prusti_contracts_proc_macros::print_counterexample;
 
-/// A macro to annotate termination of a function
+/// A macro to annotate termination of a function, optionally with a
+/// termination measure such as `#[terminates(Int::new(n))]`. A measure with
+/// several components, e.g. `#[terminates(Int::new(m), Int::new(n))]`, is
+/// compared lexicographically.
 pub use prusti_contracts_proc_macros::terminates;
 
-/// A macro to annotate body variant of a loop to prove termination
+/// A macro to list the places behind `&mut` arguments that a function may
+/// modify, such as `#[modifies(self.len, self.buf)]`. All other places behind
+/// `&mut` arguments are guaranteed to be unchanged.
+pub use prusti_contracts_proc_macros::modifies;
+
+/// A macro to list the places behind `&mut` arguments that a function only
+/// reads, such as `#[reads(self.cap)]`. These places are guaranteed to be
+/// unchanged.
+pub use prusti_contracts_proc_macros::reads;
+
+/// A macro for marking a function as a lemma: a terminating function whose
+/// body proves its postcondition. Lemmas are erased from compiled code and
+/// can be called only inside `ghost!` blocks.
+pub use prusti_contracts_proc_macros::lemma;
+
+/// A macro to annotate body variant of a loop to prove termination; a variant
+/// with several components is compared lexicographically
 pub use prusti_contracts_proc_macros::body_variant;
 
 #[cfg(not(feature = "prusti"))]
@@ -107,6 +134,20 @@ mod private {
         _val_phantom: PhantomData<V>,
     }
 
+    /// A set type
+    #[non_exhaustive]
+    #[derive(PartialEq, Eq, Copy, Clone)]
+    pub struct Set<T> {
+        _phantom: PhantomData<T>,
+    }
+
+    /// A multiset type
+    #[non_exhaustive]
+    #[derive(PartialEq, Eq, Copy, Clone)]
+    pub struct Multiset<T> {
+        _phantom: PhantomData<T>,
+    }
+
     /// a mathematical (unbounded) integer type
     /// it should not be constructed from running rust code, hence the private unit inside
     pub struct Int(());
@@ -220,6 +261,42 @@ mod private {
         pub fn len(self) -> Int {
             panic!()
         }
+        /// The first `_count` elements of the sequence.
+        pub fn take(self, _count: usize) -> Self {
+            panic!()
+        }
+        /// The sequence without its first `_count` elements.
+        pub fn skip(self, _count: usize) -> Self {
+            panic!()
+        }
+        /// The sequence with the element at `_index` replaced by `_val`.
+        pub fn update(self, _index: usize, _val: T) -> Self {
+            panic!()
+        }
+        pub fn contains(self, _val: T) -> bool {
+            panic!()
+        }
+        /// The sequence of the elements of a slice. A `Vec<T>` can be
+        /// converted through its slice.
+        pub fn from_slice(_: &[T]) -> Self {
+            panic!()
+        }
+        /// The multiset of the elements of the sequence.
+        pub fn to_multiset(self) -> Multiset<T> {
+            panic!()
+        }
+        /// The set of the elements of the sequence.
+        pub fn to_set(self) -> Set<T> {
+            panic!()
+        }
+    }
+
+    impl Seq<Int> {
+        /// The sequence of integers from `_low` (inclusive) to `_high`
+        /// (exclusive).
+        pub fn range(_low: Int, _high: Int) -> Self {
+            panic!()
+        }
     }
 
     #[macro_export]
@@ -276,6 +353,10 @@ mod private {
         pub fn contains(self, _key: K) -> bool {
             panic!()
         }
+        /// The set of keys of the map.
+        pub fn domain(self) -> Set<K> {
+            panic!()
+        }
     }
 
     #[macro_export]
@@ -298,6 +379,101 @@ mod private {
         }
     }
 
+    /// A set type
+    #[non_exhaustive]
+    #[derive(PartialEq, Eq, Copy, Clone)]
+    pub struct Set<T> {
+        _phantom: PhantomData<T>,
+    }
+
+    impl<T> Set<T> {
+        pub fn empty() -> Self {
+            panic!()
+        }
+        pub fn single(_: T) -> Self {
+            panic!()
+        }
+        pub fn union(self, _: Self) -> Self {
+            panic!()
+        }
+        pub fn intersection(self, _: Self) -> Self {
+            panic!()
+        }
+        pub fn difference(self, _: Self) -> Self {
+            panic!()
+        }
+        pub fn is_subset(self, _: Self) -> bool {
+            panic!()
+        }
+        pub fn contains(self, _elem: T) -> bool {
+            panic!()
+        }
+        pub fn len(self) -> Int {
+            panic!()
+        }
+    }
+
+    #[macro_export]
+    macro_rules! set {
+        ($val:expr) => {
+            $crate::Set::single($val)
+        };
+        ($($val:expr),*) => {
+            $crate::Set::empty()
+            $(
+                .union(set![$val])
+            )*
+        };
+    }
+
+    /// A multiset type
+    #[non_exhaustive]
+    #[derive(PartialEq, Eq, Copy, Clone)]
+    pub struct Multiset<T> {
+        _phantom: PhantomData<T>,
+    }
+
+    impl<T> Multiset<T> {
+        pub fn empty() -> Self {
+            panic!()
+        }
+        pub fn single(_: T) -> Self {
+            panic!()
+        }
+        pub fn union(self, _: Self) -> Self {
+            panic!()
+        }
+        pub fn intersection(self, _: Self) -> Self {
+            panic!()
+        }
+        pub fn difference(self, _: Self) -> Self {
+            panic!()
+        }
+        pub fn is_subset(self, _: Self) -> bool {
+            panic!()
+        }
+        /// The number of occurrences of `_elem` in the multiset.
+        pub fn count(self, _elem: T) -> Int {
+            panic!()
+        }
+        pub fn len(self) -> Int {
+            panic!()
+        }
+    }
+
+    #[macro_export]
+    macro_rules! multiset {
+        ($val:expr) => {
+            $crate::Multiset::single($val)
+        };
+        ($($val:expr),*) => {
+            $crate::Multiset::empty()
+            $(
+                .union(multiset![$val])
+            )*
+        };
+    }
+
     #[non_exhaustive]
     #[derive(PartialEq, Eq, Copy, Clone)]
     pub struct Ghost<T> {
@@ -336,6 +512,36 @@ pub fn old<T>(arg: T) -> T {
     arg
 }
 
+/// The elements of a slice at the beginning of the method call. Together with
+/// `Seq::from_slice`, it relates the old and the current contents of a slice,
+/// for example `Seq::from_slice(s) == old_seq(s).update(i, v)`.
+pub fn old_seq<T: Copy>(_slice: &[T]) -> Seq<T> {
+    unimplemented!()
+}
+
+/// States that the snapshot of an expression is the same as at the beginning
+/// of the method call, for example `unchanged!(self.data)`. Unlike
+/// `x === old(x)`, the expression is not moved, so it can be a place behind a
+/// reference. Can be used only in specifications.
+#[macro_export]
+macro_rules! unchanged {
+    ($($tokens:tt)*) => {
+        compile_error!("`unchanged!` can be used only in specifications")
+    };
+}
+
+/// States that an indexable collection, such as a slice, has the same length
+/// and the same elements as at the beginning of the method call, except
+/// possibly at the given index, for example `unchanged_except!(self, index)`.
+/// The collection needs a `len()` method returning `usize` and must support
+/// indexing with `usize`. Can be used only in specifications.
+#[macro_export]
+macro_rules! unchanged_except {
+    ($($tokens:tt)*) => {
+        compile_error!("`unchanged_except!` can be used only in specifications")
+    };
+}
+
 /// Universal quantifier.
 ///
 /// This is a Prusti-internal representation of the `forall` syntax.
@@ -365,4 +571,19 @@ pub fn snapshot_equality<T>(_l: T, _r: T) -> bool {
     true
 }
 
+/// The number of elements that the enclosing `for` loop has visited so far,
+/// including the current one. Can be used only in `body_invariant!` of `for`
+/// loops over ranges and slices.
+pub fn iteration_index() -> usize {
+    unimplemented!()
+}
+
+/// The elements that the enclosing `for` loop has visited so far, including
+/// the current one. For slices, these are the values of the elements and not
+/// references to them. Can be used only in `body_invariant!` of `for` loops
+/// over ranges and slices.
+pub fn visited<T: Copy>() -> Seq<T> {
+    unimplemented!()
+}
+
 pub use private::*;
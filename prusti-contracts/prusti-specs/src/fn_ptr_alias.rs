@@ -0,0 +1,105 @@
+//! Processes specifications attached to type aliases of function pointer types.
+//!
+//! Given a type alias with a specification
+//! ```ignore
+//! #[requires(x < 100)]
+//! #[ensures(result == x + 1)]
+//! type IncFn = fn(x: u32) -> u32;
+//! ```
+//! this logic keeps the type alias and generates a trusted function with the
+//! signature of the function pointer type that carries the specification.
+//! The verifier requires the functions stored in places declared with type
+//! `IncFn` to satisfy this specification, and assumes it when calling them.
+
+use super::parse_quote_spanned;
+use crate::{
+    extract_prusti_attributes, generate_spec_and_assertions, rewriter::AstRewriter,
+    specifications::untyped, SpecAttributeKind, SPECS_VERSION,
+};
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+
+/// See module level documentation
+pub fn rewrite(
+    outer_attr_kind: SpecAttributeKind,
+    outer_attr_tokens: TokenStream,
+    mut item: syn::ItemType,
+) -> syn::Result<TokenStream> {
+    let syn::Type::BareFn(fn_ty) = &*item.ty else {
+        return Err(syn::Error::new(
+            item.ty.span(),
+            "specifications can only be attached to type aliases of function pointer types",
+        ));
+    };
+    if !item.generics.params.is_empty() || fn_ty.lifetimes.is_some() {
+        return Err(syn::Error::new(
+            item.span(),
+            "function pointer types with specifications cannot be generic",
+        ));
+    }
+    if let Some(variadic) = &fn_ty.variadic {
+        return Err(syn::Error::new(
+            variadic.span(),
+            "variadic function pointer types cannot have specifications",
+        ));
+    }
+    let mut inputs = vec![];
+    for input in &fn_ty.inputs {
+        let Some((name, _)) = &input.name else {
+            return Err(syn::Error::new(
+                input.span(),
+                "the parameters of a function pointer type with a specification need to be named",
+            ));
+        };
+        let ty = &input.ty;
+        inputs.push(quote! { #name: #ty });
+    }
+
+    let mut rewriter = AstRewriter::new();
+    let spec_id = rewriter.generate_spec_id();
+    let spec_id_str = spec_id.to_string();
+    let item_span = item.span();
+    let contract_fn_ident = syn::Ident::new(
+        &format!("prusti_fn_ptr_contract_{}_{spec_id}", item.ident),
+        item_span,
+    );
+    let output = &fn_ty.output;
+    let mut contract_fn = untyped::AnyFnItem::Fn(parse_quote_spanned! {item_span=>
+        fn #contract_fn_ident(#(#inputs),*) #output {
+            unreachable!()
+        }
+    });
+
+    // The specification attributes move to the generated function, the other
+    // attributes stay on the type alias.
+    *contract_fn.attrs_mut() = std::mem::take(&mut item.attrs);
+    let mut prusti_attributes = vec![(outer_attr_kind, outer_attr_tokens)];
+    prusti_attributes.extend(extract_prusti_attributes(&mut contract_fn));
+    item.attrs = std::mem::take(contract_fn.attrs_mut());
+    if prusti_attributes.iter().any(|(attr_kind, _)| {
+        !matches!(
+            attr_kind,
+            SpecAttributeKind::Requires | SpecAttributeKind::Ensures
+        )
+    }) {
+        return Err(syn::Error::new(
+            item_span,
+            "only `#[requires]` and `#[ensures]` can be attached to function pointer types",
+        ));
+    }
+    prusti_attributes.push((SpecAttributeKind::Trusted, TokenStream::new()));
+    let (generated_spec_items, generated_attributes) =
+        generate_spec_and_assertions(prusti_attributes, &contract_fn)?;
+
+    Ok(quote_spanned! {item_span=>
+        #[prusti::fn_ptr_spec_id_ref = #spec_id_str]
+        #item
+        #(#generated_spec_items)*
+        #(#generated_attributes)*
+        #[prusti::fn_ptr_spec_id = #spec_id_str]
+        #[prusti::specs_version = #SPECS_VERSION]
+        #[allow(unused_variables, dead_code, non_snake_case)]
+        #contract_fn
+    })
+}
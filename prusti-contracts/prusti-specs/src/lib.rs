@@ -12,6 +12,7 @@
 #[macro_use]
 mod common;
 mod extern_spec_rewriter;
+mod fn_ptr_alias;
 mod type_cond_specs;
 mod parse_closure_macro;
 mod parse_quote_spanned;
@@ -74,7 +75,11 @@ fn extract_prusti_attributes(
                     | SpecAttributeKind::Ensures
                     | SpecAttributeKind::AfterExpiry
                     | SpecAttributeKind::AssertOnExpiry
-                    | SpecAttributeKind::RefineSpec => {
+                    | SpecAttributeKind::RefineSpec
+                    | SpecAttributeKind::Timeout
+                    | SpecAttributeKind::Flags
+                    | SpecAttributeKind::Modifies
+                    | SpecAttributeKind::Reads => {
                         // We need to drop the surrounding parenthesis to make the
                         // tokens identical to the ones passed by the native procedural
                         // macro call.
@@ -88,7 +93,8 @@ fn extract_prusti_attributes(
                     | SpecAttributeKind::Terminates
                     | SpecAttributeKind::Trusted
                     | SpecAttributeKind::Predicate
-                    | SpecAttributeKind::Verified => {
+                    | SpecAttributeKind::Verified
+                    | SpecAttributeKind::Lemma => {
                         assert!(attr.tokens.is_empty(), "Unexpected shape of an attribute.");
                         attr.tokens
                     }
@@ -119,6 +125,15 @@ pub fn rewrite_prusti_attributes(
     outer_attr_tokens: TokenStream,
     item_tokens: TokenStream,
 ) -> TokenStream {
+    // Specifications of function pointer types are attached to type aliases.
+    if let Ok(item) = syn::parse2::<syn::ItemType>(item_tokens.clone()) {
+        return handle_result!(fn_ptr_alias::rewrite(
+            outer_attr_kind,
+            outer_attr_tokens,
+            item
+        ));
+    }
+
     let mut item: untyped::AnyFnItem = handle_result!(syn::parse2(item_tokens));
 
     // Start with the outer attribute
@@ -139,6 +154,38 @@ pub fn rewrite_prusti_attributes(
         .to_compile_error();
     }
 
+    if prusti_attributes
+        .iter()
+        .any(|(ak, _)| ak == &SpecAttributeKind::Lemma)
+    {
+        if prusti_attributes
+            .iter()
+            .any(|(ak, _)| ak == &SpecAttributeKind::Pure)
+        {
+            return syn::Error::new(item.span(), "`#[lemma]` is incompatible with `#[pure]`")
+                .to_compile_error();
+        }
+        // The proof given by the body of a lemma is sound only if it terminates.
+        // `#[terminates]` attributes after this one are expanded separately,
+        // so they are still attached to the item.
+        let has_terminates = prusti_attributes
+            .iter()
+            .any(|(ak, _)| ak == &SpecAttributeKind::Terminates)
+            || item.attrs().iter().any(|attr| {
+                attr.path
+                    .segments
+                    .last()
+                    .map_or(false, |segment| segment.ident == "terminates")
+            });
+        if !has_terminates {
+            return syn::Error::new(
+                item.span(),
+                "a lemma needs to terminate; add a `#[terminates]` attribute",
+            )
+            .to_compile_error();
+        }
+    }
+
     let (generated_spec_items, generated_attributes) =
         handle_result!(generate_spec_and_assertions(prusti_attributes, &item));
 
@@ -168,8 +215,13 @@ fn generate_spec_and_assertions(
             SpecAttributeKind::AssertOnExpiry => generate_for_assert_on_expiry(attr_tokens, item),
             SpecAttributeKind::Pure => generate_for_pure(attr_tokens, item),
             SpecAttributeKind::Verified => generate_for_verified(attr_tokens, item),
+            SpecAttributeKind::Timeout => generate_for_timeout(attr_tokens, item),
+            SpecAttributeKind::Flags => generate_for_flags(attr_tokens, item),
             SpecAttributeKind::Terminates => generate_for_terminates(attr_tokens, item),
             SpecAttributeKind::Trusted => generate_for_trusted(attr_tokens, item),
+            SpecAttributeKind::Modifies => generate_for_modifies(attr_tokens, item),
+            SpecAttributeKind::Reads => generate_for_reads(attr_tokens, item),
+            SpecAttributeKind::Lemma => generate_for_lemma(attr_tokens, item),
             // Predicates are handled separately below; the entry in the SpecAttributeKind enum
             // only exists so we successfully parse it and emit an error in
             // `check_incompatible_attrs`; so we'll never reach here.
@@ -267,16 +319,48 @@ fn generate_for_terminates(mut attr: TokenStream, item: &untyped::AnyFnItem) ->
         }
     }
 
+    // Each component of a lexicographic measure gets its own spec item; the
+    // order of the attributes determines the order of the components.
+    let mut rewriter = rewriter::AstRewriter::new();
+    let mut spec_items = vec![];
+    let mut attrs = vec![];
+    for (spec_id, spec_item) in rewriter.process_termination_measure(attr, item)? {
+        let spec_id_str = spec_id.to_string();
+        spec_items.push(spec_item);
+        attrs.push(parse_quote_spanned! {item.span()=>
+            #[prusti::terminates_spec_id_ref = #spec_id_str]
+        });
+    }
+
+    Ok((spec_items, attrs))
+}
+
+/// Generate spec items and attributes to typecheck and later retrieve "modifies" annotations.
+fn generate_for_modifies(attr: TokenStream, item: &untyped::AnyFnItem) -> GeneratedResult {
     let mut rewriter = rewriter::AstRewriter::new();
     let spec_id = rewriter.generate_spec_id();
     let spec_id_str = spec_id.to_string();
     let spec_item =
-        rewriter.process_assertion(rewriter::SpecItemType::Termination, spec_id, attr, item)?;
+        rewriter.process_frame_condition(rewriter::SpecItemType::Modifies, spec_id, attr, item)?;
+    Ok((
+        vec![spec_item],
+        vec![parse_quote_spanned! {item.span()=>
+            #[prusti::modifies_spec_id_ref = #spec_id_str]
+        }],
+    ))
+}
 
+/// Generate spec items and attributes to typecheck and later retrieve "reads" annotations.
+fn generate_for_reads(attr: TokenStream, item: &untyped::AnyFnItem) -> GeneratedResult {
+    let mut rewriter = rewriter::AstRewriter::new();
+    let spec_id = rewriter.generate_spec_id();
+    let spec_id_str = spec_id.to_string();
+    let spec_item =
+        rewriter.process_frame_condition(rewriter::SpecItemType::Reads, spec_id, attr, item)?;
     Ok((
         vec![spec_item],
         vec![parse_quote_spanned! {item.span()=>
-            #[prusti::terminates_spec_id_ref = #spec_id_str]
+            #[prusti::reads_spec_id_ref = #spec_id_str]
         }],
     ))
 }
@@ -315,6 +399,129 @@ fn generate_for_verified(attr: TokenStream, item: &untyped::AnyFnItem) -> Genera
     ))
 }
 
+/// Generate spec items and attributes to typecheck and later retrieve "lemma" annotations.
+fn generate_for_lemma(attr: TokenStream, item: &untyped::AnyFnItem) -> GeneratedResult {
+    if !attr.is_empty() {
+        return Err(syn::Error::new(
+            attr.span(),
+            "the `#[lemma]` attribute does not take parameters",
+        ));
+    }
+
+    Ok((
+        vec![],
+        vec![parse_quote_spanned! {item.span()=>
+            #[prusti::lemma]
+        }],
+    ))
+}
+
+/// Generate attributes to later retrieve the time budget set by "timeout"
+/// annotations.
+fn generate_for_timeout(attr: TokenStream, item: &untyped::AnyFnItem) -> GeneratedResult {
+    Ok((vec![], vec![timeout_attribute(attr, item.span())?]))
+}
+
+fn timeout_attribute(attr: TokenStream, span: Span) -> syn::Result<syn::Attribute> {
+    let seconds: syn::LitInt = syn::parse2(attr.clone()).map_err(|_| {
+        syn::Error::new(
+            attr.span(),
+            "the `#[timeout]` attribute expects the number of seconds, e.g. `#[timeout(30)]`",
+        )
+    })?;
+    let seconds_str = match seconds.base10_parse::<u64>()? {
+        0 => {
+            return Err(syn::Error::new(
+                seconds.span(),
+                "the timeout must be at least one second",
+            ))
+        }
+        seconds => seconds.to_string(),
+    };
+    Ok(parse_quote_spanned! {span=>
+        #[prusti::timeout = #seconds_str]
+    })
+}
+
+/// Generate attributes to later retrieve the configuration flags set by
+/// "flags" annotations.
+fn generate_for_flags(attr: TokenStream, item: &untyped::AnyFnItem) -> GeneratedResult {
+    Ok((vec![], vec![flags_attribute(attr, item.span())?]))
+}
+
+/// Which flags can be set for an item is checked by Prusti, so this only
+/// checks that the attribute is a list of `flag = value` pairs.
+fn flags_attribute(attr: TokenStream, span: Span) -> syn::Result<syn::Attribute> {
+    let parser =
+        syn::punctuated::Punctuated::<syn::MetaNameValue, syn::Token![,]>::parse_terminated;
+    let pairs = syn::parse::Parser::parse2(parser, attr.clone())?;
+    if pairs.is_empty() {
+        return Err(syn::Error::new(
+            attr.span(),
+            "the `#[flags]` attribute expects `flag = value` pairs, \
+            e.g. `#[flags(check_panics = false)]`",
+        ));
+    }
+    let mut flags = vec![];
+    for pair in pairs {
+        let Some(flag) = pair.path.get_ident() else {
+            return Err(syn::Error::new(pair.path.span(), "expected the name of a flag"));
+        };
+        let value = match &pair.lit {
+            syn::Lit::Bool(value) => value.value.to_string(),
+            syn::Lit::Int(value) => value.base10_digits().to_string(),
+            lit => {
+                return Err(syn::Error::new(
+                    lit.span(),
+                    "the value of a flag must be a boolean or an integer",
+                ))
+            }
+        };
+        flags.push(format!("{flag}={value}"));
+    }
+    let flags_str = flags.join(",");
+    Ok(parse_quote_spanned! {span=>
+        #[prusti::flags = #flags_str]
+    })
+}
+
+/// `#[timeout(..)]` and `#[flags(..)]` configure the verification of a
+/// function, or of all functions in an impl block or module.
+fn rewrite_configuration_attribute(
+    attr_kind: SpecAttributeKind,
+    attr: TokenStream,
+    tokens: TokenStream,
+) -> TokenStream {
+    if syn::parse2::<untyped::AnyFnItem>(tokens.clone()).is_ok() {
+        return rewrite_prusti_attributes(attr_kind, attr, tokens);
+    }
+    let item: syn::Item = handle_result!(syn::parse2(tokens));
+    if !matches!(item, syn::Item::Impl(_) | syn::Item::Mod(_)) {
+        return syn::Error::new(
+            item.span(),
+            "this attribute can only be applied to functions, impl blocks and modules",
+        )
+        .to_compile_error();
+    }
+    let generated_attribute = match attr_kind {
+        SpecAttributeKind::Timeout => handle_result!(timeout_attribute(attr, item.span())),
+        SpecAttributeKind::Flags => handle_result!(flags_attribute(attr, item.span())),
+        _ => unreachable!(),
+    };
+    quote_spanned! {item.span()=>
+        #generated_attribute
+        #item
+    }
+}
+
+pub fn timeout(attr: TokenStream, tokens: TokenStream) -> TokenStream {
+    rewrite_configuration_attribute(SpecAttributeKind::Timeout, attr, tokens)
+}
+
+pub fn flags(attr: TokenStream, tokens: TokenStream) -> TokenStream {
+    rewrite_configuration_attribute(SpecAttributeKind::Flags, attr, tokens)
+}
+
 /// Generate spec items and attributes to typecheck and later retrieve "pure" annotations, but encoded as a referenced separate function that type-conditional spec refinements can apply trait bounds to.
 fn generate_for_pure_refinements(item: &untyped::AnyFnItem) -> GeneratedResult {
     let mut rewriter = rewriter::AstRewriter::new();
@@ -851,9 +1058,14 @@ fn extract_prusti_attributes_for_types(
                     SpecAttributeKind::RefineSpec => unreachable!("refine_spec on type"),
                     SpecAttributeKind::Pure => unreachable!("pure on type"),
                     SpecAttributeKind::Verified => unreachable!("verified on type"),
+                    SpecAttributeKind::Timeout => unreachable!("timeout on type"),
+                    SpecAttributeKind::Flags => unreachable!("flags on type"),
                     SpecAttributeKind::Invariant => unreachable!("invariant on type"),
                     SpecAttributeKind::Predicate => unreachable!("predicate on type"),
                     SpecAttributeKind::Terminates => unreachable!("terminates on type"),
+                    SpecAttributeKind::Modifies => unreachable!("modifies on type"),
+                    SpecAttributeKind::Reads => unreachable!("reads on type"),
+                    SpecAttributeKind::Lemma => unreachable!("lemma on type"),
                     SpecAttributeKind::Trusted | SpecAttributeKind::Model => {
                         assert!(attr.tokens.is_empty(), "Unexpected shape of an attribute.");
                         attr.tokens
@@ -895,10 +1107,15 @@ fn generate_spec_and_assertions_for_types(
             SpecAttributeKind::AssertOnExpiry => unreachable!(),
             SpecAttributeKind::Pure => unreachable!(),
             SpecAttributeKind::Verified => unreachable!(),
+            SpecAttributeKind::Timeout => unreachable!(),
+            SpecAttributeKind::Flags => unreachable!(),
             SpecAttributeKind::Predicate => unreachable!(),
             SpecAttributeKind::Invariant => unreachable!(),
             SpecAttributeKind::RefineSpec => unreachable!(),
             SpecAttributeKind::Terminates => unreachable!(),
+            SpecAttributeKind::Modifies => unreachable!(),
+            SpecAttributeKind::Reads => unreachable!(),
+            SpecAttributeKind::Lemma => unreachable!(),
             SpecAttributeKind::Trusted => generate_for_trusted_for_types(attr_tokens, item),
             SpecAttributeKind::Model => generate_for_model(attr_tokens, item),
             SpecAttributeKind::PrintCounterexample => {
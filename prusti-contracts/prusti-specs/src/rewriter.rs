@@ -2,7 +2,10 @@ use crate::{
     common::HasSignature,
     specifications::{
         common::{SpecificationId, SpecificationIdGenerator},
-        preparser::{parse_prusti, parse_prusti_assert_pledge, parse_prusti_pledge},
+        preparser::{
+            parse_prusti, parse_prusti_assert_pledge, parse_prusti_measure, parse_prusti_places,
+            parse_prusti_pledge,
+        },
         untyped,
     },
 };
@@ -21,6 +24,8 @@ pub enum SpecItemType {
     Pledge,
     Predicate(TokenStream),
     Termination,
+    Modifies,
+    Reads,
 }
 
 impl std::fmt::Display for SpecItemType {
@@ -31,6 +36,8 @@ impl std::fmt::Display for SpecItemType {
             SpecItemType::Pledge => write!(f, "pledge"),
             SpecItemType::Predicate(_) => write!(f, "pred"),
             SpecItemType::Termination => write!(f, "term"),
+            SpecItemType::Modifies => write!(f, "modifies"),
+            SpecItemType::Reads => write!(f, "reads"),
         }
     }
 }
@@ -155,6 +162,27 @@ impl AstRewriter {
         self.generate_spec_item_fn(spec_type, spec_id, parse_prusti(tokens)?, item)
     }
 
+    /// Parse a termination measure into one spec item per component
+    pub fn process_termination_measure<T: HasSignature + Spanned>(
+        &mut self,
+        tokens: TokenStream,
+        item: &T,
+    ) -> syn::Result<Vec<(SpecificationId, syn::Item)>> {
+        parse_prusti_measure(tokens)?
+            .into_iter()
+            .map(|component| {
+                let spec_id = self.generate_spec_id();
+                let spec_item = self.generate_spec_item_fn(
+                    SpecItemType::Termination,
+                    spec_id,
+                    component,
+                    item,
+                )?;
+                Ok((spec_id, spec_item))
+            })
+            .collect()
+    }
+
     /// Parse a pledge with lhs into a Rust expression
     pub fn process_pledge(
         &mut self,
@@ -194,6 +222,43 @@ impl AstRewriter {
         Ok(syn::Item::Fn(spec_item))
     }
 
+    /// Parse the places of a `modifies` or `reads` clause into a function that
+    /// borrows each of them, so that the encoder can read the places off its
+    /// MIR body
+    pub fn process_frame_condition(
+        &mut self,
+        spec_type: SpecItemType,
+        spec_id: SpecificationId,
+        tokens: TokenStream,
+        item: &untyped::AnyFnItem,
+    ) -> syn::Result<syn::Item> {
+        // Errors about the frame condition are reported on the clause
+        let item_span = tokens.span();
+        let places = parse_prusti_places(tokens)?;
+        let item_name = syn::Ident::new(
+            &format!("prusti_{}_item_{}_{}", spec_type, item.sig().ident, spec_id),
+            item_span,
+        );
+        let spec_id_str = spec_id.to_string();
+        let borrows = places.iter().map(|place| {
+            quote_spanned! {place.span()=>
+                let _ = &(#place);
+            }
+        });
+        let mut spec_item: syn::ItemFn = parse_quote_spanned! {item_span=>
+            #[allow(unused_must_use, unused_parens, unused_variables, dead_code, non_snake_case)]
+            #[prusti::spec_only]
+            #[prusti::spec_id = #spec_id_str]
+            fn #item_name() {
+                #(#borrows)*
+            }
+        };
+
+        spec_item.sig.generics = item.sig().generics.clone();
+        spec_item.sig.inputs = item.sig().inputs.clone();
+        Ok(syn::Item::Fn(spec_item))
+    }
+
     /// Parse a pledge with lhs into a Rust expression
     pub fn process_assert_pledge(
         &mut self,
@@ -208,22 +273,35 @@ impl AstRewriter {
         Ok((lhs_item, rhs_item))
     }
 
-    /// Parse a loop invariant into a Rust expression
+    /// Parse a loop variant into a Rust expression, with one closure per
+    /// component of a lexicographic variant
     pub fn process_loop_variant(
         &mut self,
         spec_id: SpecificationId,
         tokens: TokenStream,
     ) -> syn::Result<TokenStream> {
-        let expr = parse_prusti(tokens)?;
-        let spec_id_str = spec_id.to_string();
-        Ok(quote_spanned! {expr.span()=>
-            {
+        let components = parse_prusti_measure(tokens)?;
+        let span = components[0].span();
+        let mut closures = TokenStream::new();
+        for (index, expr) in components.into_iter().enumerate() {
+            let spec_id = if index == 0 {
+                spec_id
+            } else {
+                self.generate_spec_id()
+            };
+            let spec_id_str = spec_id.to_string();
+            closures.extend(quote_spanned! {expr.span()=>
                 #[prusti::spec_only]
                 #[prusti::loop_body_variant_spec]
                 #[prusti::spec_id = #spec_id_str]
                 || -> Int {
                     #expr
                 };
+            });
+        }
+        Ok(quote_spanned! {span=>
+            {
+                #closures
             }
         })
     }
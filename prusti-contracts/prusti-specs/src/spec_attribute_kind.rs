@@ -18,6 +18,11 @@ pub enum SpecAttributeKind {
     Terminates = 10,
     PrintCounterexample = 11,
     Verified = 12,
+    Timeout = 13,
+    Flags = 14,
+    Modifies = 15,
+    Reads = 16,
+    Lemma = 17,
 }
 
 impl TryFrom<String> for SpecAttributeKind {
@@ -37,6 +42,11 @@ impl TryFrom<String> for SpecAttributeKind {
             "model" => Ok(SpecAttributeKind::Model),
             "print_counterexample" => Ok(SpecAttributeKind::PrintCounterexample),
             "verified" => Ok(SpecAttributeKind::Verified),
+            "timeout" => Ok(SpecAttributeKind::Timeout),
+            "flags" => Ok(SpecAttributeKind::Flags),
+            "modifies" => Ok(SpecAttributeKind::Modifies),
+            "reads" => Ok(SpecAttributeKind::Reads),
+            "lemma" => Ok(SpecAttributeKind::Lemma),
             _ => Err(name),
         }
     }
@@ -4,7 +4,7 @@ use proc_macro2::{Punct, Spacing::*};
 use quote::{quote, quote_spanned, ToTokens};
 use std::collections::VecDeque;
 use syn::{
-    parse::{Parse, ParseStream},
+    parse::{Parse, ParseStream, Parser},
     spanned::Spanned,
 };
 
@@ -22,6 +22,47 @@ pub fn parse_prusti(tokens: TokenStream) -> syn::Result<TokenStream> {
     syn::parse2::<syn::Expr>(parsed.clone())?;
     Ok(parsed)
 }
+/// Parses a termination measure or a loop variant, which is a comma-separated
+/// list of expressions that are compared lexicographically.
+pub fn parse_prusti_measure(tokens: TokenStream) -> syn::Result<Vec<TokenStream>> {
+    let components = PrustiTokenStream::new(tokens).parse_measure()?;
+    for component in &components {
+        syn::parse2::<syn::Expr>(component.clone())?;
+    }
+    Ok(components)
+}
+
+/// Parses the places of a `modifies` or `reads` clause, which is a
+/// comma-separated (possibly empty) list of places such as `self.len` or
+/// `(*x).f`.
+pub fn parse_prusti_places(tokens: TokenStream) -> syn::Result<Vec<syn::Expr>> {
+    let places = syn::punctuated::Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated
+        .parse2(tokens)?;
+    for place in &places {
+        if !is_place(place) {
+            return err(
+                place.span(),
+                "expected a place rooted at an argument, such as `self.len` or `(*x).f`",
+            );
+        }
+    }
+    Ok(places.into_iter().collect())
+}
+
+fn is_place(expr: &syn::Expr) -> bool {
+    match expr {
+        syn::Expr::Path(path) => path.qself.is_none() && path.path.get_ident().is_some(),
+        syn::Expr::Field(field) => is_place(&field.base),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Deref(_),
+            expr,
+            ..
+        }) => is_place(expr),
+        syn::Expr::Paren(paren) => is_place(&paren.expr),
+        _ => false,
+    }
+}
+
 pub fn parse_prusti_pledge(tokens: TokenStream) -> syn::Result<TokenStream> {
     // TODO: pledges with reference that is not "result" are not supported;
     // for this reason we assert here that the reference (if there is any) is "result"
@@ -143,6 +184,21 @@ impl PrustiTokenStream {
                     pos += 1;
                     op
                 }
+                (
+                    TokenTree::Ident(ident),
+                    Some(TokenTree::Punct(bang)),
+                    Some(TokenTree::Group(group)),
+                    _,
+                ) if let Some(kind) = TwoStateMacro::from_call(ident, bang, group) => {
+                    // this was a two-state macro call, consume the `!` and
+                    // the arguments
+                    pos += 2;
+                    PrustiToken::TwoStateMacro(
+                        join_spans(ident.span(), group.span()),
+                        kind,
+                        box Self::new(group.stream()),
+                    )
+                }
                 (TokenTree::Ident(ident), _, _, _) if ident == "outer" =>
                     PrustiToken::Outer(ident.span()),
                 (TokenTree::Ident(ident), _, _, _) if ident == "forall" =>
@@ -226,6 +282,18 @@ impl PrustiTokenStream {
         ))
     }
 
+    /// Processes a Prusti token stream for a termination measure, in the form
+    /// `a, b, ...` or just `a`.
+    fn parse_measure(self) -> syn::Result<Vec<TokenStream>> {
+        if self.is_empty() {
+            return err(self.source_span, "expected at least one expression");
+        }
+        self.split(PrustiBinaryOp::Rust(RustOp::Comma), true)
+            .into_iter()
+            .map(|mut component| component.expr_bp(0))
+            .collect()
+    }
+
     /// Processes a Prusti token stream for a pledge, in the form `a => b` or
     /// just `b`.
     fn parse_pledge(self) -> syn::Result<(Option<TokenStream>, TokenStream)> {
@@ -314,6 +382,15 @@ impl PrustiTokenStream {
                 kind.translate(span, triggers, args, body)
             }
 
+            Some(PrustiToken::TwoStateMacro(span, kind, box stream)) => {
+                let args = stream
+                    .split(PrustiBinaryOp::Rust(RustOp::Comma), true)
+                    .into_iter()
+                    .map(|stream| stream.parse())
+                    .collect::<Result<Vec<_>, _>>()?;
+                kind.translate(span, args)?
+            }
+
             Some(PrustiToken::SpecEnt(span, _)) | Some(PrustiToken::CallDesc(span, _)) => {
                 return err(span, "unexpected operator")
             }
@@ -372,6 +449,9 @@ impl PrustiTokenStream {
                 Some(PrustiToken::Quantifier(span, _)) => {
                     return err(*span, "unexpected quantifier")
                 }
+                Some(PrustiToken::TwoStateMacro(span, kind, _)) => {
+                    return err(*span, &format!("unexpected `{}!`", kind.name()))
+                }
 
                 None => break,
             };
@@ -649,6 +729,7 @@ enum PrustiToken {
     // TODO: add note about unops not sharing a variant, descriptions ...
     Outer(Span),
     Quantifier(Span, Quantifier),
+    TwoStateMacro(Span, TwoStateMacro, Box<PrustiTokenStream>),
     SpecEnt(Span, bool),
     CallDesc(Span, bool),
 }
@@ -767,6 +848,81 @@ impl Quantifier {
     }
 }
 
+/// Spec helpers that relate the current state to the state at the beginning of
+/// the method call.
+#[derive(Debug, Clone)]
+enum TwoStateMacro {
+    /// `unchanged!(e)`: `e` has the same snapshot as in the old state.
+    Unchanged,
+    /// `unchanged_except!(v, i)`: the indexable collection `v` has the same
+    /// length and the same elements as in the old state, except possibly at
+    /// index `i`.
+    UnchangedExcept,
+}
+
+impl TwoStateMacro {
+    fn from_call(
+        ident: &proc_macro2::Ident,
+        bang: &Punct,
+        group: &proc_macro2::Group,
+    ) -> Option<Self> {
+        if bang.as_char() != '!' || group.delimiter() != Delimiter::Parenthesis {
+            return None;
+        }
+        if ident == "unchanged" {
+            Some(Self::Unchanged)
+        } else if ident == "unchanged_except" {
+            Some(Self::UnchangedExcept)
+        } else {
+            None
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Unchanged => "unchanged",
+            Self::UnchangedExcept => "unchanged_except",
+        }
+    }
+
+    fn translate(&self, span: Span, args: Vec<TokenStream>) -> syn::Result<TokenStream> {
+        match (self, &args[..]) {
+            // The expression is borrowed rather than moved, so that places
+            // behind references can be used.
+            (Self::Unchanged, [expr]) => Ok(quote_spanned! { span =>
+                ::prusti_contracts::snapshot_equality(
+                    &(#expr),
+                    ::prusti_contracts::old(&(#expr)),
+                )
+            }),
+            (Self::UnchangedExcept, [seq, index]) => {
+                let element = quote_spanned! { span => (#seq)[__j] };
+                let body = quote_spanned! { span =>
+                    !(__j < (#seq).len() && __j != (#index))
+                    || ::prusti_contracts::snapshot_equality(
+                        &#element,
+                        ::prusti_contracts::old(&#element),
+                    )
+                };
+                let forall = Quantifier::Forall.translate(
+                    span,
+                    vec![vec![element]],
+                    quote_spanned! { span => __j: usize },
+                    body,
+                );
+                Ok(quote_spanned! { span =>
+                    ((#seq).len() == ::prusti_contracts::old((#seq).len()) && #forall)
+                })
+            }
+            (Self::Unchanged, _) => err(span, "expected a single expression in `unchanged!`"),
+            (Self::UnchangedExcept, _) => err(
+                span,
+                "expected a collection and an index in `unchanged_except!`",
+            ),
+        }
+    }
+}
+
 // For Prusti-specific operators, in [operator2], [operator3], and [operator4]
 // we mainly care about the spacing of the last [Punct], as this lets us
 // know that the last character is not itself part of an actual Rust
@@ -803,6 +959,7 @@ impl PrustiToken {
             | Self::BinOp(span, _)
             | Self::Outer(span)
             | Self::Quantifier(span, _)
+            | Self::TwoStateMacro(span, _, _)
             | Self::SpecEnt(span, _)
             | Self::CallDesc(span, _) => *span,
             Self::Token(tree) => tree.span(),
@@ -1107,6 +1264,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_two_state_macros() {
+        assert_eq!(
+            parse_prusti("unchanged!(self.v) && a ==> b".parse().unwrap())
+                .unwrap()
+                .to_string(),
+            "! ((:: prusti_contracts :: snapshot_equality (& (self . v) , :: prusti_contracts :: old (& (self . v)) ,)) && (a)) || (b)",
+        );
+        assert_eq!(
+            parse_prusti("unchanged_except!(s, i + 1)".parse().unwrap()).unwrap().to_string(),
+            "((s) . len () == :: prusti_contracts :: old ((s) . len ()) && :: prusti_contracts :: forall (((# [prusti :: spec_only] | __j : usize | ((s) [__j]) ,) ,) , # [prusti :: spec_only] | __j : usize | -> bool { ((! (__j < (s) . len () && __j != (i + 1)) || :: prusti_contracts :: snapshot_equality (& (s) [__j] , :: prusti_contracts :: old (& (s) [__j]) ,)) : bool) }))",
+        );
+        assert_error!(
+            parse_prusti(quote! { unchanged!(a, b) }),
+            "expected a single expression in `unchanged!`"
+        );
+        assert_error!(
+            parse_prusti(quote! { unchanged_except!(s) }),
+            "expected a collection and an index in `unchanged_except!`"
+        );
+    }
+
+    #[test]
+    fn test_measure() {
+        let components = parse_prusti_measure("Int::new(a), f(b, c) + d,".parse().unwrap())
+            .unwrap()
+            .into_iter()
+            .map(|component| component.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(components, ["Int :: new (a)", "f (b , c) + d"]);
+        assert_error!(
+            parse_prusti_measure(quote! {}),
+            "expected at least one expression"
+        );
+    }
+
+    #[test]
+    fn test_places() {
+        let places = parse_prusti_places("self.len, (*x).f.0, *y,".parse().unwrap())
+            .unwrap()
+            .into_iter()
+            .map(|place| place.to_token_stream().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(places, ["self . len", "(* x) . f . 0", "* y"]);
+        assert!(parse_prusti_places(quote! {}).unwrap().is_empty());
+        assert_error!(
+            parse_prusti_places(quote! { self.get(0) }),
+            "expected a place rooted at an argument, such as `self.len` or `(*x).f`"
+        );
+    }
+
     mod type_cond_specs {
         use std::assert_matches::assert_matches;
 
@@ -0,0 +1,95 @@
+use prusti_contracts::*;
+
+#[extern_spec]
+impl<T> ::std::vec::Vec<T> {
+    #[ensures(result.len() == 0)]
+    pub fn new() -> ::std::vec::Vec<T>;
+
+    #[ensures(result.len() == 0)]
+    pub fn with_capacity(capacity: usize) -> ::std::vec::Vec<T>;
+}
+
+#[extern_spec]
+impl<T, A: ::std::alloc::Allocator> ::std::vec::Vec<T, A> {
+    #[pure]
+    pub fn len(&self) -> usize;
+
+    #[pure]
+    #[ensures(result == (self.len() == 0))]
+    pub fn is_empty(&self) -> bool;
+
+    #[ensures(self.len() == old(self.len()) + 1)]
+    #[ensures(snap(&self[old(self.len())]) === snap(&value))]
+    #[ensures(forall(|i: usize| i < old(self.len()) ==> snap(&self[i]) === old(snap(&self[i]))))]
+    pub fn push(&mut self, value: T);
+
+    #[ensures(old(self.len()) == 0 ==> result.is_none() && self.len() == 0)]
+    #[ensures(old(self.len()) > 0 ==> result.is_some() && self.len() == old(self.len()) - 1)]
+    #[ensures(old(self.len()) > 0 ==> result === Some(old(snap(&self[self.len() - 1]))))]
+    #[ensures(forall(|i: usize| i < self.len() ==> snap(&self[i]) === old(snap(&self[i]))))]
+    pub fn pop(&mut self) -> Option<T>;
+
+    #[requires(index <= self.len())]
+    #[ensures(self.len() == old(self.len()) + 1)]
+    pub fn insert(&mut self, index: usize, element: T);
+
+    #[requires(index < self.len())]
+    #[ensures(self.len() == old(self.len()) - 1)]
+    pub fn remove(&mut self, index: usize) -> T;
+
+    #[requires(index < self.len())]
+    #[ensures(self.len() == old(self.len()) - 1)]
+    pub fn swap_remove(&mut self, index: usize) -> T;
+
+    #[ensures(self.len() == 0)]
+    pub fn clear(&mut self);
+
+    #[ensures(len < old(self.len()) ==> self.len() == len)]
+    #[ensures(len >= old(self.len()) ==> self.len() == old(self.len()))]
+    pub fn truncate(&mut self, len: usize);
+}
+
+#[extern_spec]
+impl<T, I: ::core::slice::SliceIndex<[T]>, A: ::std::alloc::Allocator> ::core::ops::Index<I>
+    for ::std::vec::Vec<T, A>
+{
+    #[refine_spec(where I: crate::core_spec::SliceIndexSpec, [
+        pure,
+        requires(index.is_in_bounds(self.len())),
+    ])]
+    fn index(&self, index: I) -> &<::std::vec::Vec<T, A> as ::core::ops::Index<I>>::Output;
+}
+
+#[extern_spec]
+impl<T, A: ::std::alloc::Allocator> ::core::ops::Deref for ::std::vec::Vec<T, A> {
+    #[ensures(result.len() == self.len())]
+    #[ensures(forall(|i: usize| i < self.len() ==> snap(&result[i]) === snap(&self[i])))]
+    fn deref(&self) -> &[T];
+}
+
+#[extern_spec]
+impl ::std::string::String {
+    #[ensures(result.len() == 0)]
+    pub fn new() -> ::std::string::String;
+
+    /// The contents of a `String`, on which its other specifications build.
+    #[pure]
+    pub fn as_str(&self) -> &str;
+
+    #[pure]
+    #[ensures(result == self.as_str().len())]
+    pub fn len(&self) -> usize;
+
+    #[pure]
+    #[ensures(result == (self.len() == 0))]
+    pub fn is_empty(&self) -> bool;
+
+    #[ensures(self.len() == 0)]
+    pub fn clear(&mut self);
+
+    #[ensures(self.len() == old(self.len()) + string.len())]
+    #[ensures(self.as_str().starts_with(old(self.as_str())))]
+    #[ensures(forall(|i: usize| i < string.len() ==>
+        self.as_str().as_bytes()[old(self.len()) + i] == string.as_bytes()[i]))]
+    pub fn push_str(&mut self, string: &str);
+}
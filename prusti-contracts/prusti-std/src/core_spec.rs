@@ -0,0 +1,248 @@
+use prusti_contracts::*;
+
+#[extern_spec]
+impl<T> ::core::option::Option<T> {
+    #[pure]
+    #[ensures(result == matches!(self, Some(_)))]
+    pub fn is_some(&self) -> bool;
+
+    #[pure]
+    #[ensures(result == matches!(self, None))]
+    pub fn is_none(&self) -> bool;
+
+    #[requires(self.is_some())]
+    #[ensures(old(snap(&self)) === Some(snap(&result)))]
+    pub fn unwrap(self) -> T;
+
+    #[requires(self.is_some())]
+    #[ensures(old(snap(&self)) === Some(snap(&result)))]
+    pub fn expect(self, msg: &str) -> T;
+
+    #[ensures(old(self.is_none()) ==> result === default)]
+    #[ensures(old(self.is_some()) ==> old(snap(&self)) === Some(snap(&result)))]
+    pub fn unwrap_or(self, default: T) -> T;
+
+    #[ensures(result.is_some() == old(self.is_some()))]
+    pub fn as_ref(&self) -> Option<&T>;
+
+    #[ensures(result.is_some() == old(self.is_some()))]
+    pub fn as_mut(&mut self) -> Option<&mut T>;
+
+    #[ensures(result === old(snap(self)))]
+    #[ensures(self.is_none())]
+    pub fn take(&mut self) -> Option<T>;
+
+    #[ensures(result === old(snap(self)))]
+    #[ensures(snap(self) === Some(value))]
+    pub fn replace(&mut self, value: T) -> Option<T>;
+
+    #[ensures(result.is_ok() == old(self.is_some()))]
+    pub fn ok_or<E>(self, err: E) -> Result<T, E>;
+}
+
+#[extern_spec]
+impl<T, E> ::core::result::Result<T, E> {
+    #[ensures(result.is_some() == old(self.is_ok()))]
+    pub fn ok(self) -> Option<T>;
+
+    #[ensures(result.is_some() == old(self.is_err()))]
+    pub fn err(self) -> Option<E>;
+}
+
+#[extern_spec]
+impl<T, E: ::core::fmt::Debug> ::core::result::Result<T, E> {
+    #[requires(self.is_ok())]
+    pub fn expect(self, msg: &str) -> T;
+}
+
+#[extern_spec]
+impl<T: ::core::fmt::Debug, E> ::core::result::Result<T, E> {
+    #[requires(self.is_err())]
+    pub fn unwrap_err(self) -> E;
+}
+
+#[extern_spec]
+impl<T> [T] {
+    #[pure]
+    #[ensures(result == (self.len() == 0))]
+    pub fn is_empty(&self) -> bool;
+
+    #[ensures(result.is_some() == (self.len() > 0))]
+    pub fn first(&self) -> Option<&T>;
+
+    #[ensures(result.is_some() == (self.len() > 0))]
+    pub fn last(&self) -> Option<&T>;
+
+    #[requires(a < self.len() && b < self.len())]
+    #[ensures(self.len() == old(self.len()))]
+    pub fn swap(&mut self, a: usize, b: usize);
+
+    #[refine_spec(where I: SliceIndexSpec, [
+        pure,
+        ensures(result.is_some() == index.is_in_bounds(self.len())),
+    ])]
+    pub fn get<I: ::core::slice::SliceIndex<[T]>>(&self, index: I) -> Option<&I::Output>;
+}
+
+#[extern_spec]
+impl str {
+    #[pure]
+    pub fn len(&self) -> usize;
+
+    #[pure]
+    #[ensures(result == (self.len() == 0))]
+    pub fn is_empty(&self) -> bool;
+}
+
+#[extern_spec]
+impl<T, I: ::core::slice::SliceIndex<[T]>> ::core::ops::Index<I> for [T] {
+    #[refine_spec(where I: SliceIndexSpec, [
+        pure,
+        requires(index.is_in_bounds(self.len())),
+    ])]
+    fn index(&self, index: I) -> &<[T] as ::core::ops::Index<I>>::Output;
+}
+
+/// The slice indices whose bounds can be checked in specifications, which
+/// refine the specifications of indexing slices and vectors.
+pub trait SliceIndexSpec: Copy {
+    #[pure]
+    fn is_in_bounds(self, len: usize) -> bool;
+}
+
+impl SliceIndexSpec for usize {
+    #[pure]
+    #[trusted]
+    #[ensures(result == (self < len))]
+    fn is_in_bounds(self, len: usize) -> bool {
+        self < len
+    }
+}
+
+#[extern_spec(core::cmp)]
+#[refine_spec(where T: Integer, [
+    ensures(result.at_most(v1) && result.at_most(v2)),
+])]
+#[ensures(result === v1 || result === v2)]
+fn min<T: Ord>(v1: T, v2: T) -> T;
+
+#[extern_spec(core::cmp)]
+#[refine_spec(where T: Integer, [
+    ensures(v1.at_most(result) && v2.at_most(result)),
+])]
+#[ensures(result === v1 || result === v2)]
+fn max<T: Ord>(v1: T, v2: T) -> T;
+
+#[extern_spec]
+impl<A: ::core::iter::Step> ::core::iter::Iterator for ::core::ops::Range<A> {
+    #[refine_spec(where A: Integer, [
+        ensures(old(self.end.at_most(self.start)) ==> result.is_none() && self.start === old(self.start)),
+        ensures(!old(self.end.at_most(self.start)) ==> result === Some(old(self.start))),
+        ensures(!old(self.end.at_most(self.start)) ==> self.start === old(self.start.successor())),
+        ensures(self.end === old(self.end)),
+    ])]
+    fn next(&mut self) -> Option<A>;
+}
+
+/// The integer types, whose values can be compared in specifications. Used to
+/// refine the specifications of generic functions such as `min` and `max` for
+/// integers.
+pub trait Integer: Copy {
+    #[pure]
+    fn at_most(self, other: Self) -> bool;
+
+    /// The next integer, which is only specified if it is in range.
+    #[pure]
+    fn successor(self) -> Self;
+}
+
+macro_rules! impl_integer {
+    ($($ty:ident),*) => {$(
+        impl Integer for $ty {
+            #[pure]
+            #[trusted]
+            #[ensures(result == (self <= other))]
+            fn at_most(self, other: Self) -> bool {
+                self <= other
+            }
+
+            #[pure]
+            #[trusted]
+            #[ensures(self == $ty::MAX || result as i128 == (self as i128) + 1)]
+            fn successor(self) -> Self {
+                self.wrapping_add(1)
+            }
+        }
+    )*};
+}
+
+impl_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Specifies the checked and saturating addition and subtraction of integer
+/// types. The exact result is computed in `i128`, which can represent all sums
+/// and differences of values of the specified types.
+///
+/// Prusti operators such as `==>` cannot be used here, because their tokens are
+/// not joint after a `macro_rules!` expansion; implications are written with
+/// `||` instead.
+macro_rules! specify_integer_arithmetic {
+    ($($ty:ident),*) => {$(
+        #[extern_spec]
+        impl $ty {
+            #[pure]
+            #[ensures(match result {
+                Some(value) => value as i128 == (self as i128) + (rhs as i128),
+                None => (self as i128) + (rhs as i128) > ($ty::MAX as i128) || (self as i128) + (rhs as i128) < ($ty::MIN as i128),
+            })]
+            pub fn checked_add(self, rhs: $ty) -> Option<$ty>;
+
+            #[pure]
+            #[ensures(match result {
+                Some(value) => value as i128 == (self as i128) - (rhs as i128),
+                None => (self as i128) - (rhs as i128) > ($ty::MAX as i128) || (self as i128) - (rhs as i128) < ($ty::MIN as i128),
+            })]
+            pub fn checked_sub(self, rhs: $ty) -> Option<$ty>;
+
+            #[pure]
+            #[ensures((self as i128) + (rhs as i128) <= ($ty::MAX as i128) || result == $ty::MAX)]
+            #[ensures((self as i128) + (rhs as i128) >= ($ty::MIN as i128) || result == $ty::MIN)]
+            #[ensures(
+                (self as i128) + (rhs as i128) > ($ty::MAX as i128)
+                    || (self as i128) + (rhs as i128) < ($ty::MIN as i128)
+                    || result as i128 == (self as i128) + (rhs as i128)
+            )]
+            pub fn saturating_add(self, rhs: $ty) -> $ty;
+
+            #[pure]
+            #[ensures((self as i128) - (rhs as i128) <= ($ty::MAX as i128) || result == $ty::MAX)]
+            #[ensures((self as i128) - (rhs as i128) >= ($ty::MIN as i128) || result == $ty::MIN)]
+            #[ensures(
+                (self as i128) - (rhs as i128) > ($ty::MAX as i128)
+                    || (self as i128) - (rhs as i128) < ($ty::MIN as i128)
+                    || result as i128 == (self as i128) - (rhs as i128)
+            )]
+            pub fn saturating_sub(self, rhs: $ty) -> $ty;
+        }
+    )*};
+}
+
+specify_integer_arithmetic!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+/// Specifies the checked multiplication of integer types. As above, the exact
+/// result is computed in `i128`, so `u64` and `usize` are left out: the product
+/// of two of their values may not fit.
+macro_rules! specify_integer_multiplication {
+    ($($ty:ident),*) => {$(
+        #[extern_spec]
+        impl $ty {
+            #[pure]
+            #[ensures(match result {
+                Some(value) => value as i128 == (self as i128) * (rhs as i128),
+                None => (self as i128) * (rhs as i128) > ($ty::MAX as i128) || (self as i128) * (rhs as i128) < ($ty::MIN as i128),
+            })]
+            pub fn checked_mul(self, rhs: $ty) -> Option<$ty>;
+        }
+    )*};
+}
+
+specify_integer_multiplication!(u8, u16, u32, i8, i16, i32, i64, isize);
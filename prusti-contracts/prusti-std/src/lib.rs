@@ -1,14 +1,14 @@
-use prusti_contracts::*;
-
-#[extern_spec]
-impl<K, V, S> ::std::collections::hash_map::HashMap<K, V, S>
-where
-    K: Eq + ::core::hash::Hash,
-    S: ::std::hash::BuildHasher,
-{
-    #[pure]
-    pub fn contains_key<Q>(&self, k: &Q) -> bool
-    where
-        K: ::core::borrow::Borrow<Q>,
-        Q: ::core::hash::Hash + Eq;
-}
+//! External specifications for items of `core`, `alloc` and `std`.
+//!
+//! The specifications of `alloc` and `std` items are enabled by the features
+//! of the same name, which are enabled by default.
+
+#![cfg_attr(prusti, feature(allocator_api, step_trait))]
+
+pub mod core_spec;
+
+#[cfg(feature = "alloc")]
+pub mod alloc_spec;
+
+#[cfg(feature = "std")]
+pub mod std_spec;
@@ -0,0 +1,234 @@
+//! Support for verifying `async fn` bodies.
+//!
+//! The body of an `async fn` is a generator that first moves the arguments of
+//! the function out of its upvars and then runs the user-written code. Every
+//! `.await` on a future returned by a call to another `async fn` is desugared
+//! into a call to `IntoFuture::into_future` followed by a loop that polls the
+//! future and yields while it is pending. We verify the generator modularly
+//! against the contract of the `async fn` by rewriting its body as follows:
+//!
+//! -   The statements that move the arguments out of the upvars are replaced by
+//!     assignments of the argument locals to themselves, so that the argument
+//!     locals are initialized from the start and can be used as the arguments
+//!     of the contract.
+//! -   The call that creates an awaited future is redirected to store its
+//!     result directly in the local that receives the value of the `.await`
+//!     and to continue after the polling loop. The polling loop becomes
+//!     unreachable and its `yield` is replaced by `unreachable`, so that it is
+//!     no longer a cycle.
+//!
+//! The number and the layout of the basic blocks and of the statements are
+//! preserved, so that the borrow checker facts of the generator still apply.
+
+use log::debug;
+use prusti_rustc_interface::{
+    index::vec::Idx,
+    middle::{
+        mir,
+        ty::{self, TyCtxt},
+    },
+    span::def_id::{DefId, LocalDefId},
+};
+
+use super::EnvQuery;
+
+/// Returns the generator that implements the body of the given `async fn`.
+pub(super) fn find_async_fn_generator(body: &mir::Body) -> Option<LocalDefId> {
+    body.basic_blocks
+        .iter()
+        .flat_map(|block| &block.statements)
+        .find_map(|statement| match &statement.kind {
+            mir::StatementKind::Assign(box (
+                _,
+                mir::Rvalue::Aggregate(box mir::AggregateKind::Generator(def_id, _, _), _),
+            )) => def_id.as_local(),
+            _ => None,
+        })
+}
+
+/// Rewrites the generator body of an `async fn` with `arg_count` arguments as
+/// described in the module documentation. Returns the locals that hold the
+/// arguments of the function, or `None` if they could not be identified.
+pub(super) fn rewrite_async_fn_body<'tcx>(
+    query: EnvQuery<'tcx>,
+    body: &mut mir::Body<'tcx>,
+    arg_count: usize,
+) -> Option<Vec<mir::Local>> {
+    let arg_locals = rewrite_argument_moves(body, arg_count);
+    for block in body.basic_blocks.indices() {
+        if let Some(await_call) = find_await(query, body, block) {
+            debug!("Rewriting await: {:?}", await_call);
+            let ready_statement = &mut body.basic_blocks_mut()[await_call.ready_block].statements
+                [await_call.ready_statement_index];
+            ready_statement.make_nop();
+            // Breaks the polling loop, which is unreachable now.
+            body.basic_blocks_mut()[await_call.yield_block]
+                .terminator_mut()
+                .kind = mir::TerminatorKind::Unreachable;
+            let terminator = body.basic_blocks_mut()[block].terminator_mut();
+            if let mir::TerminatorKind::Call {
+                destination,
+                target,
+                ..
+            } = &mut terminator.kind
+            {
+                *destination = await_call.result;
+                *target = Some(await_call.ready_block);
+            }
+        }
+    }
+    arg_locals
+}
+
+/// Replaces the statements `_i = (_1.j)` that move the `j`-th argument out of
+/// the generator by `_i = _i`.
+fn rewrite_argument_moves(body: &mut mir::Body, arg_count: usize) -> Option<Vec<mir::Local>> {
+    let generator_local = mir::Local::new(1);
+    let mut arg_locals = vec![None; arg_count];
+    for statement in &mut body.basic_blocks_mut()[mir::START_BLOCK].statements {
+        if let mir::StatementKind::Assign(box (target, mir::Rvalue::Use(operand))) =
+            &mut statement.kind
+        {
+            let (mir::Operand::Copy(source) | mir::Operand::Move(source)) = operand else {
+                continue;
+            };
+            if let (Some(target_local), [mir::ProjectionElem::Field(field, _)]) =
+                (target.as_local(), source.projection.as_slice())
+            {
+                if source.local == generator_local
+                    && field.index() < arg_count
+                    && arg_locals[field.index()].is_none()
+                {
+                    arg_locals[field.index()] = Some(target_local);
+                    *source = target_local.into();
+                }
+            }
+        }
+    }
+    arg_locals.into_iter().collect()
+}
+
+#[derive(Debug)]
+struct AwaitCall<'tcx> {
+    /// The place that receives the value of the `.await`.
+    result: mir::Place<'tcx>,
+    /// The block that suspends the generator while the future is pending.
+    yield_block: mir::BasicBlock,
+    /// The block that extracts the value from `Poll::Ready`.
+    ready_block: mir::BasicBlock,
+    ready_statement_index: usize,
+}
+
+/// Checks whether the given block ends with a call to an `async fn` whose
+/// result is immediately awaited and, if so, finds where the awaited value is
+/// extracted.
+fn find_await<'tcx>(
+    query: EnvQuery<'tcx>,
+    body: &mir::Body<'tcx>,
+    block: mir::BasicBlock,
+) -> Option<AwaitCall<'tcx>> {
+    let tcx = query.tcx();
+    let lang_items = tcx.lang_items();
+    // `future = async_fn(...)`
+    let (called_def_id, _, future, block) = called_function(tcx, body, block)?;
+    if !query.is_async_fn(called_def_id) {
+        return None;
+    }
+    // `into_future = IntoFuture::into_future(move future)`
+    let (called_def_id, args, _, block) = called_function(tcx, body, block?)?;
+    if Some(called_def_id) != lang_items.into_future_fn()
+        || !matches!(args, [mir::Operand::Move(arg)] if *arg == future)
+    {
+        return None;
+    }
+    // Follow the polling loop up to `poll = Future::poll(...)`.
+    let mut block = block?;
+    let mut poll = None;
+    for _ in 0..body.basic_blocks.len() {
+        match &body[block].terminator().kind {
+            mir::TerminatorKind::Goto { target }
+            | mir::TerminatorKind::FalseUnwind {
+                real_target: target,
+                ..
+            } => block = *target,
+            mir::TerminatorKind::Call { .. } => {
+                let (called_def_id, _, destination, target) = called_function(tcx, body, block)?;
+                block = target?;
+                if Some(called_def_id) == lang_items.future_poll_fn() {
+                    poll = Some(destination);
+                    break;
+                }
+            }
+            _ => return None,
+        }
+    }
+    let poll = poll?;
+    // `switchInt(discriminant(poll))` with `Poll::Ready` as variant 0.
+    let mir::TerminatorKind::SwitchInt { targets, .. } = &body[block].terminator().kind else {
+        return None;
+    };
+    // `yield` while the future is `Poll::Pending`
+    let yield_block = targets.target_for_value(1);
+    if !matches!(
+        body[yield_block].terminator().kind,
+        mir::TerminatorKind::Yield { .. }
+    ) {
+        return None;
+    }
+    let mut block = targets.target_for_value(0);
+    if let mir::TerminatorKind::FalseEdge { real_target, .. } = body[block].terminator().kind {
+        block = real_target;
+    }
+    // `result = move ((poll as Ready).0)`
+    body[block]
+        .statements
+        .iter()
+        .enumerate()
+        .find_map(|(index, statement)| match &statement.kind {
+            mir::StatementKind::Assign(box (
+                result,
+                mir::Rvalue::Use(mir::Operand::Copy(source) | mir::Operand::Move(source)),
+            )) if source.local == poll.local
+                && source.projection.len() == poll.projection.len() + 2
+                && matches!(
+                    source.projection[poll.projection.len()],
+                    mir::ProjectionElem::Downcast(_, variant) if variant.index() == 0
+                ) =>
+            {
+                Some(AwaitCall {
+                    result: *result,
+                    yield_block,
+                    ready_block: block,
+                    ready_statement_index: index,
+                })
+            }
+            _ => None,
+        })
+}
+
+/// If the block ends with a call to a function item, returns the called
+/// function, the arguments and the destination of the call and its target.
+fn called_function<'a, 'tcx>(
+    tcx: TyCtxt<'tcx>,
+    body: &'a mir::Body<'tcx>,
+    block: mir::BasicBlock,
+) -> Option<(
+    DefId,
+    &'a [mir::Operand<'tcx>],
+    mir::Place<'tcx>,
+    Option<mir::BasicBlock>,
+)> {
+    if let mir::TerminatorKind::Call {
+        func,
+        args,
+        destination,
+        target,
+        ..
+    } = &body[block].terminator().kind
+    {
+        if let ty::TyKind::FnDef(def_id, _) = func.ty(body, tcx).kind() {
+            return Some((*def_id, args, *destination, *target));
+        }
+    }
+    None
+}
@@ -23,6 +23,13 @@ impl<'tcx> std::ops::Deref for MirBody<'tcx> {
     }
 }
 
+impl<'tcx> MirBody<'tcx> {
+    /// Wraps a MIR body that was modified by Prusti.
+    pub(crate) fn new(body: mir::Body<'tcx>) -> Self {
+        MirBody(Rc::new(body))
+    }
+}
+
 /// Stores body of functions which we'll need to encode as impure
 struct BodyWithBorrowckFacts<'tcx> {
     body: MirBody<'tcx>,
@@ -40,8 +40,11 @@ impl<'env, 'tcx> Visitor<'tcx> for CollectClosureDefsVisitor<'env, 'tcx> {
 
     #[tracing::instrument(level = "trace", skip(self, expr))]
     fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        // Generators, such as the bodies of `async fn`s, are verified as part
+        // of the function that creates them.
         if let hir::ExprKind::Closure(hir::Closure {
             def_id: local_def_id,
+            movability: None,
             ..
         }) = expr.kind
         {
@@ -23,11 +23,12 @@ pub enum LoopAnalysisError {
 }
 
 /// Walk up the CFG graph an collect all basic blocks that belong to the loop body.
-#[tracing::instrument(level = "debug", skip(real_edges, body))]
+#[tracing::instrument(level = "debug", skip(real_edges, dominators, body))]
 fn collect_loop_body(
     head: BasicBlockIndex,
     back_edge_source: BasicBlockIndex,
     real_edges: &RealEdges,
+    dominators: &Dominators<BasicBlockIndex>,
     body: &mut FxHashSet<BasicBlockIndex>,
 ) {
     let mut work_queue = vec![back_edge_source];
@@ -35,7 +36,7 @@ fn collect_loop_body(
     while !work_queue.is_empty() {
         let current = work_queue.pop().unwrap();
         for &predecessor in real_edges.predecessors(current).iter() {
-            if body.contains(&predecessor) {
+            if body.contains(&predecessor) || !dominators.is_reachable(predecessor) {
                 continue;
             }
             body.insert(predecessor);
@@ -261,6 +262,9 @@ impl ProcedureLoops {
 
         let mut back_edges: FxHashSet<(_, _)> = FxHashSet::default();
         for bb in mir.basic_blocks.indices() {
+            if !dominators.is_reachable(bb) {
+                continue;
+            }
             for successor in real_edges.successors(bb) {
                 if dominators.dominates(*successor, bb) {
                     back_edges.insert((bb, *successor));
@@ -272,7 +276,7 @@ impl ProcedureLoops {
         let mut loop_bodies = FxHashMap::default();
         for &(source, target) in back_edges.iter() {
             let body = loop_bodies.entry(target).or_insert_with(FxHashSet::default);
-            collect_loop_body(target, source, real_edges, body);
+            collect_loop_body(target, source, real_edges, &dominators, body);
         }
 
         let mut enclosing_loop_heads_set: FxHashMap<BasicBlockIndex, FxHashSet<BasicBlockIndex>> =
@@ -76,6 +76,20 @@ impl Lifetimes {
         self.get_origin_contains_loan_at_location(RichLocation::Mid(location))
     }
 
+    /// Returns the origins that are live at the given location, that is,
+    /// the origins of the types of variables that might still be used.
+    pub fn get_origin_live_on_entry_at_mid(&self, location: mir::Location) -> BTreeSet<String> {
+        let point = self.location_to_point(RichLocation::Mid(location));
+        self.facts
+            .output_facts
+            .origin_live_on_entry
+            .get(&point)
+            .into_iter()
+            .flatten()
+            .map(|origin| origin.to_text())
+            .collect()
+    }
+
     pub fn get_origin_contains_loan_at_location(
         &self,
         location: RichLocation,
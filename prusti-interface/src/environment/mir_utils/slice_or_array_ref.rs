@@ -3,8 +3,10 @@ use prusti_rustc_interface::middle::ty::{Ty, TyKind};
 pub trait SliceOrArrayRef<'tcx> {
     fn is_slice_ref(&self) -> bool;
     fn is_array_ref(&self) -> bool;
+    fn is_str_ref(&self) -> bool;
     fn is_slice_or_ref(&self) -> bool;
     fn is_array_or_ref(&self) -> bool;
+    fn is_str_or_ref(&self) -> bool;
 }
 
 impl<'tcx> SliceOrArrayRef<'tcx> for Ty<'tcx> {
@@ -22,6 +24,13 @@ impl<'tcx> SliceOrArrayRef<'tcx> for Ty<'tcx> {
         }
     }
 
+    fn is_str_ref(&self) -> bool {
+        match self.kind() {
+            TyKind::Ref(_, ty, _) => ty.is_str(),
+            _ => false,
+        }
+    }
+
     fn is_slice_or_ref(&self) -> bool {
         self.is_slice() || self.is_slice_ref()
     }
@@ -29,4 +38,8 @@ impl<'tcx> SliceOrArrayRef<'tcx> for Ty<'tcx> {
     fn is_array_or_ref(&self) -> bool {
         self.is_array() || self.is_array_ref()
     }
+
+    fn is_str_or_ref(&self) -> bool {
+        self.is_str() || self.is_str_ref()
+    }
 }
@@ -0,0 +1,14 @@
+use prusti_rustc_interface::middle::ty::{Ty, TyKind};
+
+pub trait TraitObjectRef<'tcx> {
+    fn is_trait_object_ref(&self) -> bool;
+}
+
+impl<'tcx> TraitObjectRef<'tcx> for Ty<'tcx> {
+    fn is_trait_object_ref(&self) -> bool {
+        match self.kind() {
+            TyKind::Ref(_, ty, _) => ty.is_trait(),
+            _ => false,
+        }
+    }
+}
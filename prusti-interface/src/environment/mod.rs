@@ -8,6 +8,7 @@
 
 use prusti_rustc_interface::middle::ty::{self, TyCtxt};
 
+mod async_fns;
 pub mod body;
 pub mod borrowck;
 mod collect_closure_defs_visitor;
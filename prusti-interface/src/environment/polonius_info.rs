@@ -18,7 +18,7 @@ use crate::{
         },
         mir_utils::{
             AllPlaces, RealEdges, SliceOrArrayRef, SplitAggregateAssignment, StatementAsAssign,
-            StatementAt,
+            StatementAt, TraitObjectRef,
         },
         polonius_info::facts::AllInputFacts,
     },
@@ -585,12 +585,13 @@ fn get_borrowed_places<'a, 'tcx: 'a>(
                     })
                     .collect()),
 
-                // slice creation involves an unsize pointer cast like &[i32; 3] -> &[i32]
+                // slice creation involves an unsize pointer cast like &[i32; 3] -> &[i32],
+                // trait object creation one like &T -> &dyn Trait
                 &mir::Rvalue::Cast(
                     mir::CastKind::Pointer(ty::adjustment::PointerCast::Unsize),
                     ref operand,
                     ref cast_ty,
-                ) if cast_ty.is_slice_ref() => {
+                ) if cast_ty.is_slice_ref() || cast_ty.is_trait_object_ref() => {
                     trace!("slice: operand={:?}, ty={:?}", operand, cast_ty);
                     Ok(match operand {
                         mir::Operand::Copy(ref place) | mir::Operand::Move(ref place) => {
@@ -670,7 +671,7 @@ impl<'a, 'tcx: 'a> PoloniusInfo<'a, 'tcx> {
         _loop_invariant_block: &FxHashMap<mir::BasicBlock, mir::BasicBlock>,
     ) -> Result<Self, PoloniusInfoError> {
         let tcx = procedure.get_tcx();
-        let def_id = procedure.get_id();
+        let def_id = procedure.get_body_def_id();
         let mir = procedure.get_mir();
 
         // Read Polonius facts.
@@ -4,7 +4,7 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use super::{body::MirBody, loops, EnvName, EnvQuery};
+use super::{async_fns, body::MirBody, loops, EnvName, EnvQuery};
 use crate::{
     data::ProcedureDefId,
     environment::{debug_utils::to_text::ToText, mir_utils::RealEdges, Environment},
@@ -28,7 +28,9 @@ pub struct Procedure<'tcx> {
     tcx: TyCtxt<'tcx>,
     env_name: EnvName<'tcx>,
     proc_def_id: ProcedureDefId,
+    body_def_id: ProcedureDefId,
     mir: MirBody<'tcx>,
+    async_fn_arg_locals: Option<Vec<mir::Local>>,
     real_edges: RealEdges,
     loop_info: loops::ProcedureLoops,
     reachable_basic_blocks: FxHashSet<BasicBlock>,
@@ -40,9 +42,23 @@ impl<'tcx> Procedure<'tcx> {
     /// identifier of a procedure
     #[tracing::instrument(name = "Procedure::new", level = "debug", skip(env))]
     pub fn new(env: &Environment<'tcx>, proc_def_id: ProcedureDefId) -> Self {
-        let mir = env
+        let mut body_def_id = proc_def_id;
+        let mut mir = env
             .body
             .get_impure_fn_body_identity(proc_def_id.expect_local());
+        let mut async_fn_arg_locals = None;
+        if env.query.is_async_fn(proc_def_id) {
+            // We verify the generator that implements the body of the function.
+            if let Some(generator_def_id) = async_fns::find_async_fn_generator(&mir) {
+                let arg_count = mir.arg_count;
+                let mut generator_mir =
+                    (*env.body.get_impure_fn_body_identity(generator_def_id)).clone();
+                async_fn_arg_locals =
+                    async_fns::rewrite_async_fn_body(env.query, &mut generator_mir, arg_count);
+                body_def_id = generator_def_id.to_def_id();
+                mir = MirBody::new(generator_mir);
+            }
+        }
         let real_edges = RealEdges::new(&mir);
         let reachable_basic_blocks = build_reachable_basic_blocks(&mir, &real_edges);
         let nonspec_basic_blocks = build_nonspec_basic_blocks(env.query, &mir, &real_edges);
@@ -52,7 +68,9 @@ impl<'tcx> Procedure<'tcx> {
             tcx: env.tcx(),
             env_name: env.name,
             proc_def_id,
+            body_def_id,
             mir,
+            async_fn_arg_locals,
             real_edges,
             loop_info,
             reachable_basic_blocks,
@@ -128,6 +146,24 @@ impl<'tcx> Procedure<'tcx> {
         self.proc_def_id
     }
 
+    /// Get definition ID of the body of the procedure. This differs from the
+    /// ID of the procedure for `async fn`s, whose body is a generator.
+    pub fn get_body_def_id(&self) -> ProcedureDefId {
+        self.body_def_id
+    }
+
+    /// Whether the procedure is an `async fn`, whose MIR is the rewritten
+    /// generator that implements its body.
+    pub fn is_async_fn(&self) -> bool {
+        self.body_def_id != self.proc_def_id
+    }
+
+    /// For `async fn`s, get the locals of the generator that hold the
+    /// arguments of the function, if they could be identified.
+    pub fn get_async_fn_arg_locals(&self) -> Option<&[mir::Local]> {
+        self.async_fn_arg_locals.as_deref()
+    }
+
     /// Get the MIR of the procedure
     pub fn get_mir(&self) -> &Body<'tcx> {
         &self.mir
@@ -339,7 +375,7 @@ fn blocks_dominated_by(mir: &Body, dominator: BasicBlock) -> FxHashSet<BasicBloc
     let dominators = mir.basic_blocks.dominators();
     let mut blocks = FxHashSet::default();
     for bb in mir.basic_blocks.indices() {
-        if dominators.dominates(dominator, bb) {
+        if dominators.is_reachable(bb) && dominators.dominates(dominator, bb) {
             blocks.insert(bb);
         }
     }
@@ -378,7 +414,11 @@ fn build_nonspec_basic_blocks(
     let dominators = mir.basic_blocks.dominators();
     let mut loop_heads: FxHashSet<BasicBlock> = FxHashSet::default();
 
+    // Blocks can become unreachable when Prusti rewrites a MIR body.
     for source in mir.basic_blocks.indices() {
+        if !dominators.is_reachable(source) {
+            continue;
+        }
         for &target in real_edges.successors(source) {
             if dominators.dominates(target, source) {
                 loop_heads.insert(target);
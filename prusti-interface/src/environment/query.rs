@@ -4,7 +4,7 @@ use crate::data::ProcedureDefId;
 use log::debug;
 use prusti_rustc_interface::{
     ast::ast::Attribute,
-    hir::hir_id::HirId,
+    hir::{def::DefKind, hir_id::HirId, IsAsync},
     middle::{
         hir::map::Map,
         ty::{
@@ -185,6 +185,38 @@ impl<'tcx> EnvQuery<'tcx> {
         self.tcx.is_closure(def_id.into_param())
     }
 
+    /// Returns true iff `def_id` is an `async fn`.
+    pub fn is_async_fn(self, def_id: impl IntoParam<ProcedureDefId>) -> bool {
+        let def_id = def_id.into_param();
+        matches!(self.tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn)
+            && self.tcx.asyncness(def_id) == IsAsync::Async
+    }
+
+    /// Returns the type of the value produced by awaiting the future returned
+    /// by the given `async fn`, with subst applied.
+    pub fn get_async_fn_output_ty(
+        self,
+        def_id: impl IntoParam<ProcedureDefId>,
+        substs: SubstsRef<'tcx>,
+    ) -> Option<ty::Ty<'tcx>> {
+        let def_id = def_id.into_param();
+        if !self.is_async_fn(def_id) {
+            return None;
+        }
+        let future_ty = self.get_fn_sig(def_id, substs).skip_binder().output();
+        let ty::TyKind::Alias(ty::AliasKind::Opaque, opaque_ty) = future_ty.kind() else {
+            return None;
+        };
+        let future_trait = self.tcx.lang_items().future_trait()?;
+        self.tcx
+            .explicit_item_bounds(opaque_ty.def_id)
+            .iter()
+            .filter_map(|(predicate, _)| predicate.to_opt_poly_projection_pred())
+            .find(|projection| projection.trait_def_id(self.tcx) == future_trait)
+            .and_then(|projection| projection.skip_binder().term.ty())
+            .map(|output_ty| ty::EarlyBinder(output_ty).subst(self.tcx, opaque_ty.substs))
+    }
+
     // /// Returns the `DefId` of the corresponding trait method, if any.
     // /// This should not be used to resolve calls (where substs are known): use
     // /// `find_trait_method_substs` instead!
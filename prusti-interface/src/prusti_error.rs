@@ -88,6 +88,15 @@ impl PrustiError {
         error
     }
 
+    /// Report that the verification of an item exceeded its time budget
+    pub fn timeout<S: ToString>(message: S, span: MultiSpan) -> Self {
+        check_message(message.to_string());
+        PrustiError::new(
+            format!("[Prusti: verification timeout] {}", message.to_string()),
+            span,
+        )
+    }
+
     /// Report an unsupported feature of the verified Rust code (e.g. dereferencing raw pointers)
     pub fn unsupported<S: ToString>(message: S, span: MultiSpan) -> Self {
         check_message(message.to_string());
@@ -155,6 +164,14 @@ impl PrustiError {
         matches!(self.kind, PrustiErrorKind::Error)
     }
 
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn span(&self) -> &MultiSpan {
+        &self.span
+    }
+
     // FIXME: This flag is a temporary workaround for having duplicate errors
     // coming from verifying functions multiple times. We should verify each
     // function only once.
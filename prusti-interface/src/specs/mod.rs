@@ -85,6 +85,11 @@ pub struct SpecCollector<'a, 'tcx> {
     prusti_assumptions: Vec<LocalDefId>,
     ghost_begin: Vec<LocalDefId>,
     ghost_end: Vec<LocalDefId>,
+    /// Map from function pointer type aliases to the IDs of their contracts.
+    fn_ptr_spec_refs: FxHashMap<LocalDefId, SpecificationId>,
+    /// Map from the IDs of function pointer contracts to the functions
+    /// carrying them.
+    fn_ptr_contracts: FxHashMap<SpecificationId, LocalDefId>,
 }
 
 impl<'a, 'tcx> SpecCollector<'a, 'tcx> {
@@ -101,6 +106,8 @@ impl<'a, 'tcx> SpecCollector<'a, 'tcx> {
             prusti_assumptions: vec![],
             ghost_begin: vec![],
             ghost_end: vec![],
+            fn_ptr_spec_refs: FxHashMap::default(),
+            fn_ptr_contracts: FxHashMap::default(),
         }
     }
 
@@ -120,6 +127,7 @@ impl<'a, 'tcx> SpecCollector<'a, 'tcx> {
         self.determine_prusti_assertions(&mut def_spec);
         self.determine_prusti_assumptions(&mut def_spec);
         self.determine_ghost_begin_ends(&mut def_spec);
+        self.determine_fn_ptr_specs(&mut def_spec);
         // TODO: remove spec functions (make sure none are duplicated or left over)
         // Load all local spec MIR bodies, for export and later use
         self.ensure_local_mirs_fetched(&def_spec);
@@ -135,6 +143,8 @@ impl<'a, 'tcx> SpecCollector<'a, 'tcx> {
             // Instead, we require the user to explicitly make annotations.
             spec.set_kind(refs.into());
             let mut kind_override = None;
+            // The components of a lexicographic termination measure, in order.
+            let mut termination_measure = vec![];
 
             for spec_id_ref in &refs.spec_id_refs {
                 match spec_id_ref {
@@ -165,11 +175,21 @@ impl<'a, 'tcx> SpecCollector<'a, 'tcx> {
                         )));
                     }
                     SpecIdRef::Terminates(spec_id) => {
-                        spec.set_terminates(*self.spec_functions.get(spec_id).unwrap());
+                        termination_measure.push(*self.spec_functions.get(spec_id).unwrap());
+                    }
+                    SpecIdRef::Modifies(spec_id) => {
+                        spec.add_modifies(self.spec_functions.get(spec_id).unwrap().to_def_id());
+                    }
+                    SpecIdRef::Reads(spec_id) => {
+                        spec.add_reads(self.spec_functions.get(spec_id).unwrap().to_def_id());
                     }
                 }
             }
 
+            if !termination_measure.is_empty() {
+                spec.set_terminates(termination_measure);
+            }
+
             spec.set_trusted(refs.trusted);
 
             if let Some(kind) = kind_override {
@@ -289,6 +309,17 @@ impl<'a, 'tcx> SpecCollector<'a, 'tcx> {
         }
     }
 
+    fn determine_fn_ptr_specs(&self, def_spec: &mut typed::DefSpecificationMap) {
+        for (alias_id, spec_id) in self.fn_ptr_spec_refs.iter() {
+            def_spec.fn_ptr_specs.insert(
+                alias_id.to_def_id(),
+                typed::FnPointerSpecification {
+                    contract: *self.fn_ptr_contracts.get(spec_id).unwrap(),
+                },
+            );
+        }
+    }
+
     fn ensure_local_mirs_fetched(&mut self, def_spec: &typed::DefSpecificationMap) {
         let (specs, pure_fns, predicates) = def_spec.defid_for_export();
         for def_id in &specs {
@@ -378,6 +409,16 @@ fn get_procedure_spec_ids(def_id: DefId, attrs: &[ast::Attribute]) -> Option<Pro
             .into_iter()
             .map(|raw_spec_id| SpecIdRef::Terminates(parse_spec_id(raw_spec_id, def_id))),
     );
+    spec_id_refs.extend(
+        read_prusti_attrs("modifies_spec_id_ref", attrs)
+            .into_iter()
+            .map(|raw_spec_id| SpecIdRef::Modifies(parse_spec_id(raw_spec_id, def_id))),
+    );
+    spec_id_refs.extend(
+        read_prusti_attrs("reads_spec_id_ref", attrs)
+            .into_iter()
+            .map(|raw_spec_id| SpecIdRef::Reads(parse_spec_id(raw_spec_id, def_id))),
+    );
     spec_id_refs.extend(
         // TODO: pledges with LHS that is not "result" would need to carry the
         // LHS expression through typing
@@ -436,6 +477,20 @@ impl<'a, 'tcx> intravisit::Visitor<'tcx> for SpecCollector<'a, 'tcx> {
         self.env.query.hir()
     }
 
+    fn visit_item(&mut self, item: &'tcx hir::Item<'tcx>) {
+        intravisit::walk_item(self, item);
+
+        // Collect function pointer type aliases with specifications
+        if let hir::ItemKind::TyAlias(..) = item.kind {
+            let local_id = item.owner_id.def_id;
+            let attrs = self.env.query.get_local_attributes(local_id);
+            if let Some(raw_spec_id) = read_prusti_attr("fn_ptr_spec_id_ref", attrs) {
+                self.fn_ptr_spec_refs
+                    .insert(local_id, parse_spec_id(raw_spec_id, local_id.to_def_id()));
+            }
+        }
+    }
+
     fn visit_trait_item(&mut self, ti: &'tcx prusti_rustc_interface::hir::TraitItem) {
         intravisit::walk_trait_item(self, ti);
 
@@ -549,6 +604,12 @@ impl<'a, 'tcx> intravisit::Visitor<'tcx> for SpecCollector<'a, 'tcx> {
                 self.procedure_specs.insert(local_id, procedure_spec_ref);
             }
 
+            // Collect contracts of function pointer types
+            if let Some(raw_spec_id) = read_prusti_attr("fn_ptr_spec_id", attrs) {
+                self.fn_ptr_contracts
+                    .insert(parse_spec_id(raw_spec_id, def_id), local_id);
+            }
+
             // Collect model type flag
             if has_to_model_fn_attr(attrs) {
                 if let FnRetTy::Return(ty) = fn_decl.output {
@@ -19,6 +19,7 @@ pub struct DefSpecificationMap {
     pub prusti_assumptions: FxHashMap<DefId, PrustiAssumption>,
     pub ghost_begin: FxHashMap<DefId, GhostBegin>,
     pub ghost_end: FxHashMap<DefId, GhostEnd>,
+    pub fn_ptr_specs: FxHashMap<DefId, FnPointerSpecification>,
 }
 
 impl DefSpecificationMap {
@@ -54,6 +55,10 @@ impl DefSpecificationMap {
         self.ghost_end.get(def_id)
     }
 
+    pub fn get_fn_ptr_spec(&self, def_id: &DefId) -> Option<&FnPointerSpecification> {
+        self.fn_ptr_specs.get(def_id)
+    }
+
     pub(crate) fn defid_for_export(
         &self,
     ) -> (
@@ -78,7 +83,13 @@ impl DefSpecificationMap {
                     specs.extend(posts);
                 }
                 if let Some(Some(term)) = spec.terminates.extract_with_selective_replacement() {
-                    specs.push(term.to_def_id());
+                    specs.extend(term.iter().map(|component| component.to_def_id()));
+                }
+                if let Some(modifies) = spec.modifies.extract_with_selective_replacement() {
+                    specs.extend(modifies);
+                }
+                if let Some(reads) = spec.reads.extract_with_selective_replacement() {
+                    specs.extend(reads);
                 }
                 if let Some(pledges) = spec.pledges.extract_with_selective_replacement() {
                     specs.extend(pledges.iter().filter_map(|pledge| pledge.lhs));
@@ -201,7 +212,15 @@ pub struct ProcedureSpecification {
     pub posts: SpecificationItem<Vec<DefId>>,
     pub pledges: SpecificationItem<Vec<Pledge>>,
     pub trusted: SpecificationItem<bool>,
-    pub terminates: SpecificationItem<Option<LocalDefId>>,
+    /// The components of the termination measure, which are compared
+    /// lexicographically.
+    pub terminates: SpecificationItem<Option<Vec<LocalDefId>>>,
+    /// The `modifies` clauses; if there are any, all places behind `&mut`
+    /// arguments that are not listed in them are unchanged.
+    pub modifies: SpecificationItem<Vec<DefId>>,
+    /// The `reads` clauses, listing places behind `&mut` arguments that are
+    /// unchanged.
+    pub reads: SpecificationItem<Vec<DefId>>,
     pub purity: SpecificationItem<Option<DefId>>, // for type-conditional spec refinements
 }
 
@@ -217,6 +236,8 @@ impl ProcedureSpecification {
             pledges: SpecificationItem::Empty,
             trusted: SpecificationItem::Inherent(false),
             terminates: SpecificationItem::Inherent(None),
+            modifies: SpecificationItem::Empty,
+            reads: SpecificationItem::Empty,
             purity: SpecificationItem::Inherent(None),
         }
     }
@@ -302,6 +323,14 @@ pub struct GhostEnd {
     pub marker: LocalDefId,
 }
 
+/// The specification of a function pointer type alias.
+#[derive(Debug, Clone)]
+pub struct FnPointerSpecification {
+    /// A trusted function with the signature of the function pointer type
+    /// that carries the specification.
+    pub contract: LocalDefId,
+}
+
 /// The base container to store a contract of a procedure.
 /// A contract can be divided into multiple specifications:
 /// - **Base spec**: A spec without constraints.
@@ -462,6 +491,22 @@ impl SpecGraph<ProcedureSpecification> {
             .for_each(|s| s.pledges.push(pledge.clone()));
     }
 
+    /// Attaches the `modifies` clause to the base spec and all constrained specs.
+    pub fn add_modifies(&mut self, modifies: DefId) {
+        self.base_spec.modifies.push(modifies);
+        self.specs_with_constraints
+            .values_mut()
+            .for_each(|s| s.modifies.push(modifies));
+    }
+
+    /// Attaches the `reads` clause to the base spec and all constrained specs.
+    pub fn add_reads(&mut self, reads: DefId) {
+        self.base_spec.reads.push(reads);
+        self.specs_with_constraints
+            .values_mut()
+            .for_each(|s| s.reads.push(reads));
+    }
+
     /// Sets the trusted flag for the base spec and all constrained specs.
     pub fn set_trusted(&mut self, trusted: bool) {
         self.base_spec.trusted.set(trusted);
@@ -470,12 +515,12 @@ impl SpecGraph<ProcedureSpecification> {
             .for_each(|s| s.trusted.set(trusted));
     }
 
-    /// Sets the termination flag for the base spec and all constrained specs.
-    pub fn set_terminates(&mut self, terminates: LocalDefId) {
-        self.base_spec.terminates.set(Some(terminates));
+    /// Sets the termination measure for the base spec and all constrained specs.
+    pub fn set_terminates(&mut self, terminates: Vec<LocalDefId>) {
+        self.base_spec.terminates.set(Some(terminates.clone()));
         self.specs_with_constraints
             .values_mut()
-            .for_each(|s| s.terminates.set(Some(terminates)));
+            .for_each(|s| s.terminates.set(Some(terminates.clone())));
     }
 
     /// Sets the [ProcedureSpecificationKind] for the base spec and all constrained specs.
@@ -765,6 +810,10 @@ impl Refinable for ProcedureSpecification {
             kind: self.kind.refine(&other.kind),
             trusted: self.trusted.refine(&other.trusted),
             terminates: self.terminates.refine(&other.terminates),
+            modifies: self
+                .modifies
+                .refine(replace_empty(&EMPTYL, &other.modifies)),
+            reads: self.reads.refine(replace_empty(&EMPTYL, &other.reads)),
             purity: self.purity.refine(&other.purity),
         }
     }
@@ -1,17 +1,30 @@
-use crate::dump_viper_program;
+use crate::{dump_viper_program, SmtBackend};
 use prusti_common::{
     config,
     vir::{LoweringContext, ToViper},
     Stopwatch,
 };
-use viper::{VerificationContext, VerificationResult};
+use std::{
+    sync::mpsc,
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+use viper::{VerificationContext, VerificationResult, VerifierStopHandle};
 
 pub enum Backend<'a> {
     Viper(viper::Verifier<'a>, &'a VerificationContext<'a>),
+    Smt(SmtBackend),
 }
 
 impl<'a> Backend<'a> {
-    pub fn verify(&mut self, program: &prusti_common::vir::program::Program) -> VerificationResult {
+    /// Verifies the program. If `timeout` (in seconds) is given, the backend
+    /// is stopped once the verification takes longer than that, and the
+    /// result is `TimedOut`.
+    pub fn verify(
+        &mut self,
+        program: &prusti_common::vir::program::Program,
+        timeout: Option<u64>,
+    ) -> VerificationResult {
         match self {
             Backend::Viper(viper, context) => {
                 let mut stopwatch =
@@ -33,9 +46,54 @@ impl<'a> Backend<'a> {
                     }
 
                     stopwatch.start_next("viper verification");
-                    viper.verify(viper_program)
+                    let watchdog = timeout
+                        .map(|timeout_secs| Watchdog::start(timeout_secs, viper.stop_handle()));
+                    let result = viper.verify(viper_program);
+                    let stopped = watchdog.map_or(false, Watchdog::finish);
+                    match timeout {
+                        // A verification that finished just before it was
+                        // stopped keeps its result.
+                        Some(timeout_secs) if stopped && !result.is_success() => {
+                            VerificationResult::TimedOut { timeout_secs }
+                        }
+                        _ => result,
+                    }
                 })
             }
+            Backend::Smt(smt) => smt.verify(program, timeout),
         }
     }
 }
+
+/// Stops a Viper verifier that runs longer than its time budget.
+struct Watchdog {
+    verification_done: mpsc::Sender<()>,
+    thread: JoinHandle<bool>,
+}
+
+impl Watchdog {
+    fn start(timeout_secs: u64, stop_handle: VerifierStopHandle) -> Self {
+        let (verification_done, done_receiver) = mpsc::channel();
+        let thread = thread::spawn(move || {
+            let timed_out = matches!(
+                done_receiver.recv_timeout(Duration::from_secs(timeout_secs)),
+                Err(mpsc::RecvTimeoutError::Timeout)
+            );
+            if timed_out {
+                stop_handle.stop();
+            }
+            timed_out
+        });
+        Self {
+            verification_done,
+            thread,
+        }
+    }
+
+    /// Returns whether the verifier had to be stopped.
+    fn finish(self) -> bool {
+        // The watchdog might already have stopped waiting.
+        let _ = self.verification_done.send(());
+        self.thread.join().unwrap()
+    }
+}
@@ -0,0 +1,59 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use std::sync::{Arc, Mutex};
+
+type StopCallback = Box<dyn FnOnce() + Send>;
+
+/// Shared flag to cancel a verification request from another thread. While a
+/// backend is running, cancelling also stops it via the registered callback.
+#[derive(Clone, Default)]
+pub struct CancellationToken {
+    state: Arc<Mutex<CancellationState>>,
+}
+
+#[derive(Default)]
+struct CancellationState {
+    cancelled: bool,
+    stop_backend: Option<StopCallback>,
+}
+
+impl CancellationToken {
+    pub fn cancel(&self) {
+        let stop_backend = {
+            let mut state = self.state.lock().unwrap();
+            state.cancelled = true;
+            state.stop_backend.take()
+        };
+        // Call the callback without holding the lock, since stopping the
+        // backend might take a while.
+        if let Some(stop_backend) = stop_backend {
+            stop_backend();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state.lock().unwrap().cancelled
+    }
+
+    /// Registers the callback that stops the backend that is currently
+    /// running. If the token is already cancelled, the callback is called
+    /// immediately.
+    pub(crate) fn on_cancel(&self, stop_backend: impl FnOnce() + Send + 'static) {
+        let mut state = self.state.lock().unwrap();
+        if state.cancelled {
+            drop(state);
+            stop_backend();
+        } else {
+            state.stop_backend = Some(Box::new(stop_backend));
+        }
+    }
+
+    /// Unregisters the callback once the backend finished.
+    pub(crate) fn clear_on_cancel(&self) {
+        self.state.lock().unwrap().stop_backend = None;
+    }
+}
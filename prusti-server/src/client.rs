@@ -4,9 +4,9 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use crate::VerificationRequest;
+use crate::{JobId, JobStatus, QueueStatus, VerificationRequest};
 use prusti_common::config;
-use reqwest::Client;
+use reqwest::{Client, RequestBuilder, StatusCode};
 use url::{ParseError, Url};
 use viper::VerificationResult;
 
@@ -27,20 +27,83 @@ impl PrustiClient {
         })
     }
 
+    /// Sends a verification request and waits for its result. The request is
+    /// cancelled on the server if the returned future is dropped.
     pub async fn verify(
         &self,
         request: VerificationRequest,
     ) -> reqwest::Result<VerificationResult> {
-        let use_json = config::json_communication();
-        let base = self.client.post(
-            self.server_url
-                .join(if use_json { "json/" } else { "bincode/" })
-                .unwrap()
-                .join("verify/")
-                .unwrap(),
-        );
-        let response = if use_json {
-            base.json(&request)
+        let base = self.client.post(self.format_url("verify/"));
+        self.send_encoded(base, &request).await
+    }
+
+    /// Queues a verification request on the server without waiting for it.
+    /// Use `poll` to collect the result.
+    pub async fn submit(&self, request: VerificationRequest) -> reqwest::Result<JobId> {
+        let base = self.client.post(self.format_url("submit/"));
+        self.send_encoded(base, &request).await
+    }
+
+    /// Returns the status of a submitted job, or `None` if the server does
+    /// not know the job. The result of a finished job is returned only once.
+    pub async fn poll(&self, id: JobId) -> reqwest::Result<Option<JobStatus>> {
+        let base = self.client.get(self.format_url(&format!("poll/{id}")));
+        let response = base.send().await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let response = response.error_for_status()?;
+        let status = if config::json_communication() {
+            response.json().await?
+        } else {
+            let bytes = response.bytes().await?;
+            bincode::deserialize(&bytes).expect("error decoding job status")
+        };
+        Ok(Some(status))
+    }
+
+    /// Cancels a job that is queued or running. Returns `false` if the job is
+    /// unknown or already done.
+    pub async fn cancel(&self, id: JobId) -> reqwest::Result<bool> {
+        self.client
+            .post(self.server_url.join(&format!("cancel/{id}")).unwrap())
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+    }
+
+    pub async fn status(&self) -> reqwest::Result<QueueStatus> {
+        self.client
+            .get(self.server_url.join("status").unwrap())
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+    }
+
+    fn format_url(&self, endpoint: &str) -> Url {
+        self.server_url
+            .join(if config::json_communication() {
+                "json/"
+            } else {
+                "bincode/"
+            })
+            .unwrap()
+            .join(endpoint)
+            .unwrap()
+    }
+
+    async fn send_encoded<T: serde::de::DeserializeOwned>(
+        &self,
+        base: RequestBuilder,
+        request: &VerificationRequest,
+    ) -> reqwest::Result<T> {
+        let base = base.query(&[("priority", config::server_job_priority())]);
+        let response = if config::json_communication() {
+            base.json(request)
                 .send()
                 .await?
                 .error_for_status()?
@@ -48,13 +111,13 @@ impl PrustiClient {
                 .await?
         } else {
             let bytes = base
-                .body(bincode::serialize(&request).expect("error encoding verification request"))
+                .body(bincode::serialize(request).expect("error encoding verification request"))
                 .send()
                 .await?
                 .error_for_status()?
                 .bytes()
                 .await?;
-            bincode::deserialize(&bytes).expect("error decoding verification result")
+            bincode::deserialize(&bytes).expect("error decoding server response")
         };
         Ok(response)
     }
@@ -6,6 +6,8 @@
 
 use clap::Parser;
 use prusti_utils::config;
+use std::path::PathBuf;
+use viper::VerificationResult;
 
 /// A verification server to handle Prusti verification requests.
 #[derive(Parser, Debug)]
@@ -15,6 +17,17 @@ struct Args {
     /// Pass 0 to get a free one assigned by the OS.
     #[clap(short, long, value_name = "PORT", default_value_t = 0)]
     port: u16,
+
+    /// Instead of starting the server, verifies the serialized verification
+    /// request in FILE (JSON if the extension is `json`, bincode otherwise),
+    /// prints the result and exits.
+    #[clap(long, value_name = "FILE")]
+    replay: Option<PathBuf>,
+
+    /// Runs as a worker of another server, which verifies requests in a pool
+    /// of workers. The worker exits when its standard input is closed.
+    #[clap(long, hide = true)]
+    worker: bool,
 }
 
 fn main() {
@@ -26,5 +39,55 @@ fn main() {
 
     let args = Args::parse();
 
+    if let Some(path) = args.replay {
+        let request = prusti_server::load_verification_request(&path).unwrap_or_else(|err| {
+            eprintln!(
+                "Could not read verification request {}: {err}",
+                path.display()
+            );
+            std::process::exit(2);
+        });
+        let program_name = request.program.get_name_with_check_mode();
+        let result = prusti_server::replay_verification_request(request);
+        print_result(&program_name, &result);
+        std::process::exit(if result.is_success() { 0 } else { 1 });
+    }
+
+    if args.worker {
+        // Exit as soon as the supervising server exits, which closes the pipe.
+        std::thread::spawn(|| {
+            let _ = std::io::copy(&mut std::io::stdin(), &mut std::io::sink());
+            std::process::exit(0);
+        });
+    }
+
     prusti_server::start_server_on_port(args.port);
 }
+
+fn print_result(program_name: &str, result: &VerificationResult) {
+    match result {
+        VerificationResult::Success => println!("{program_name}: verification successful"),
+        VerificationResult::Failure(errors) => {
+            println!("{program_name}: {} verification error(s)", errors.len());
+            for error in errors {
+                println!("  [{}] {}", error.full_id, error.message);
+            }
+        }
+        VerificationResult::ConsistencyErrors(errors) => {
+            println!("{program_name}: {} consistency error(s)", errors.len());
+            for error in errors {
+                println!("  {error}");
+            }
+        }
+        VerificationResult::JavaException(exception) => {
+            println!("{program_name}: verifier raised a Java exception");
+            println!("{exception}");
+        }
+        VerificationResult::TimedOut { timeout_secs } => {
+            println!("{program_name}: verification timed out after {timeout_secs} second(s)")
+        }
+        VerificationResult::BackendFailure(message) => {
+            println!("{program_name}: {message}")
+        }
+    }
+}
@@ -0,0 +1,349 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+use crate::{CancellationToken, VerificationRequest};
+use rustc_hash::FxHashMap;
+use std::{
+    sync::{Condvar, Mutex},
+    time::{Duration, Instant},
+};
+use viper::VerificationResult;
+
+/// Identifies a verification request submitted to the server.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+pub struct JobId(pub u64);
+
+impl std::fmt::Display for JobId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub enum JobStatus {
+    /// The job waits for the verifier. `position` is the number of jobs that
+    /// will be verified before this one.
+    Queued {
+        position: usize,
+    },
+    Running,
+    Finished(VerificationResult),
+    Cancelled,
+    /// The verification panicked with the given message.
+    Failed(String),
+}
+
+/// A summary of the jobs known to the server.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct QueueStatus {
+    pub queued: usize,
+    pub running: Vec<JobId>,
+    /// Jobs that are done but whose outcome has not been collected yet, nor
+    /// expired.
+    pub done: usize,
+}
+
+struct QueuedJob {
+    id: JobId,
+    priority: i32,
+    request: VerificationRequest,
+    cancellation: CancellationToken,
+}
+
+#[derive(Default)]
+struct QueueState {
+    next_id: u64,
+    /// Sorted by decreasing priority; jobs with the same priority are kept in
+    /// submission order.
+    queued: Vec<QueuedJob>,
+    running: Vec<(JobId, CancellationToken)>,
+    /// The outcomes of the jobs that are done, with the time at which they
+    /// were done.
+    done: FxHashMap<JobId, (Instant, JobStatus)>,
+}
+
+/// How long the outcome of a job is kept if its client never collects it,
+/// e.g. because it disconnected.
+const DEFAULT_OUTCOME_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// The verification requests of all clients of the server. The jobs are
+/// verified by priority, by the worker threads that call `next_job` and
+/// `finish_job`; each worker takes the next job as soon as it is idle.
+pub struct JobQueue {
+    state: Mutex<QueueState>,
+    job_submitted: Condvar,
+    job_done: Condvar,
+    outcome_ttl: Duration,
+}
+
+impl QueueState {
+    fn is_running(&self, id: JobId) -> bool {
+        self.running.iter().any(|(running, _)| *running == id)
+    }
+
+    fn take_outcome(&mut self, id: JobId) -> Option<JobStatus> {
+        self.done.remove(&id).map(|(_, status)| status)
+    }
+
+    /// Forgets the outcomes that were not collected within `ttl`.
+    fn evict_expired_outcomes(&mut self, ttl: Duration) {
+        self.done
+            .retain(|_, (done_time, _)| done_time.elapsed() < ttl);
+    }
+}
+
+impl Default for JobQueue {
+    fn default() -> Self {
+        Self::new(DEFAULT_OUTCOME_TTL)
+    }
+}
+
+impl JobQueue {
+    /// Creates a queue that forgets the outcome of a job if it is not
+    /// collected within `outcome_ttl` after the job is done.
+    pub fn new(outcome_ttl: Duration) -> Self {
+        Self {
+            state: Mutex::default(),
+            job_submitted: Condvar::new(),
+            job_done: Condvar::new(),
+            outcome_ttl,
+        }
+    }
+
+    pub fn submit(&self, request: VerificationRequest, priority: i32) -> JobId {
+        let mut state = self.state.lock().unwrap();
+        state.evict_expired_outcomes(self.outcome_ttl);
+        let id = JobId(state.next_id);
+        state.next_id += 1;
+        let position = state
+            .queued
+            .iter()
+            .position(|job| job.priority < priority)
+            .unwrap_or(state.queued.len());
+        state.queued.insert(
+            position,
+            QueuedJob {
+                id,
+                priority,
+                request,
+                cancellation: CancellationToken::default(),
+            },
+        );
+        self.job_submitted.notify_one();
+        id
+    }
+
+    /// Returns the status of a job, or `None` if the job is unknown. Once a
+    /// job is done, its outcome is returned (and forgotten) exactly once, or
+    /// forgotten once it has not been collected for longer than the TTL of the
+    /// queue.
+    pub fn poll(&self, id: JobId) -> Option<JobStatus> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(status) = state.take_outcome(id) {
+            return Some(status);
+        }
+        if state.is_running(id) {
+            return Some(JobStatus::Running);
+        }
+        state
+            .queued
+            .iter()
+            .position(|job| job.id == id)
+            .map(|position| JobStatus::Queued { position })
+    }
+
+    /// Blocks until the job is done and returns its outcome, or `None` if the
+    /// job is unknown.
+    pub fn wait(&self, id: JobId) -> Option<JobStatus> {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(status) = state.take_outcome(id) {
+                return Some(status);
+            }
+            let is_pending = state.queued.iter().any(|job| job.id == id) || state.is_running(id);
+            if !is_pending {
+                return None;
+            }
+            state = self.job_done.wait(state).unwrap();
+        }
+    }
+
+    /// Cancels a job that is queued or running. Returns `false` if the job is
+    /// unknown or already done.
+    pub fn cancel(&self, id: JobId) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if let Some(position) = state.queued.iter().position(|job| job.id == id) {
+            state.queued.remove(position);
+            state
+                .done
+                .insert(id, (Instant::now(), JobStatus::Cancelled));
+            self.job_done.notify_all();
+            return true;
+        }
+        let running = state.running.iter().find(|(running, _)| *running == id);
+        let Some((_, cancellation)) = running else {
+            return false;
+        };
+        let cancellation = cancellation.clone();
+        // The running job is marked as cancelled by `finish_job`.
+        drop(state);
+        cancellation.cancel();
+        true
+    }
+
+    pub fn status(&self) -> QueueStatus {
+        let mut state = self.state.lock().unwrap();
+        state.evict_expired_outcomes(self.outcome_ttl);
+        QueueStatus {
+            queued: state.queued.len(),
+            running: state.running.iter().map(|(id, _)| *id).collect(),
+            done: state.done.len(),
+        }
+    }
+
+    /// Blocks until a job is queued and marks the one with the highest
+    /// priority as running.
+    pub fn next_job(&self) -> (JobId, VerificationRequest, CancellationToken) {
+        let mut state = self.state.lock().unwrap();
+        while state.queued.is_empty() {
+            state = self.job_submitted.wait(state).unwrap();
+        }
+        let job = state.queued.remove(0);
+        state.running.push((job.id, job.cancellation.clone()));
+        (job.id, job.request, job.cancellation)
+    }
+
+    /// Records the result of the running job, or the panic message if its
+    /// verification panicked.
+    pub fn finish_job(&self, id: JobId, result: Result<VerificationResult, String>) {
+        let mut state = self.state.lock().unwrap();
+        state.evict_expired_outcomes(self.outcome_ttl);
+        let Some(position) = state.running.iter().position(|(running, _)| *running == id) else {
+            unreachable!("job {id} finished, but it was not running");
+        };
+        let (_, cancellation) = state.running.remove(position);
+        let status = match result {
+            _ if cancellation.is_cancelled() => JobStatus::Cancelled,
+            Ok(result) => JobStatus::Finished(result),
+            Err(message) => JobStatus::Failed(message),
+        };
+        state.done.insert(id, (Instant::now(), status));
+        self.job_done.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ViperBackendConfig;
+    use prusti_common::vir::program::Program;
+    use vir::common::check_mode::CheckMode;
+
+    fn request(name: &str) -> VerificationRequest {
+        VerificationRequest {
+            program: Program::Low(vir::low::Program {
+                name: name.to_string(),
+                check_mode: CheckMode::Both,
+                domains: Vec::new(),
+                predicates: Vec::new(),
+                functions: Vec::new(),
+                methods: Vec::new(),
+                procedures: Vec::new(),
+            }),
+            backend_config: ViperBackendConfig::new(viper::VerificationBackend::Smt),
+        }
+    }
+
+    #[test]
+    fn jobs_are_ordered_by_priority() {
+        let queue = JobQueue::default();
+        let low = queue.submit(request("low"), 0);
+        let high = queue.submit(request("high"), 5);
+        let low2 = queue.submit(request("low2"), 0);
+        assert!(matches!(
+            queue.poll(low),
+            Some(JobStatus::Queued { position: 1 })
+        ));
+        assert_eq!(queue.next_job().0, high);
+        assert_eq!(queue.next_job().0, low);
+        assert_eq!(queue.next_job().0, low2);
+    }
+
+    #[test]
+    fn outcome_is_collected_once() {
+        let queue = JobQueue::default();
+        let id = queue.submit(request("p"), 0);
+        let (running, _, _) = queue.next_job();
+        assert_eq!(running, id);
+        assert!(matches!(queue.poll(id), Some(JobStatus::Running)));
+        assert_eq!(
+            queue.status(),
+            QueueStatus {
+                queued: 0,
+                running: vec![id],
+                done: 0
+            }
+        );
+        queue.finish_job(id, Ok(VerificationResult::Success));
+        assert!(matches!(
+            queue.wait(id),
+            Some(JobStatus::Finished(VerificationResult::Success))
+        ));
+        assert!(queue.poll(id).is_none());
+    }
+
+    #[test]
+    fn jobs_run_concurrently() {
+        let queue = JobQueue::default();
+        let first = queue.submit(request("first"), 0);
+        let second = queue.submit(request("second"), 0);
+        assert_eq!(queue.next_job().0, first);
+        assert_eq!(queue.next_job().0, second);
+        assert_eq!(queue.status().running, vec![first, second]);
+        queue.finish_job(second, Ok(VerificationResult::Success));
+        assert_eq!(queue.status().running, vec![first]);
+        assert!(matches!(queue.poll(first), Some(JobStatus::Running)));
+    }
+
+    #[test]
+    fn cancel_queued_and_running_jobs() {
+        let queue = JobQueue::default();
+        let running = queue.submit(request("running"), 0);
+        let queued = queue.submit(request("queued"), 0);
+        let (_, _, cancellation) = queue.next_job();
+        assert!(queue.cancel(queued));
+        assert!(matches!(queue.poll(queued), Some(JobStatus::Cancelled)));
+        assert!(queue.cancel(running));
+        assert!(cancellation.is_cancelled());
+        queue.finish_job(running, Ok(VerificationResult::Success));
+        assert!(matches!(queue.poll(running), Some(JobStatus::Cancelled)));
+        assert!(!queue.cancel(running));
+    }
+
+    #[test]
+    fn uncollected_outcomes_expire() {
+        let queue = JobQueue::new(Duration::ZERO);
+        let abandoned = queue.submit(request("abandoned"), 0);
+        queue.next_job();
+        queue.finish_job(abandoned, Ok(VerificationResult::Success));
+        let next = queue.submit(request("next"), 0);
+        assert_eq!(
+            queue.status(),
+            QueueStatus {
+                queued: 1,
+                running: Vec::new(),
+                done: 0
+            }
+        );
+        assert!(queue.poll(abandoned).is_none());
+        assert!(matches!(
+            queue.poll(next),
+            Some(JobStatus::Queued { position: 0 })
+        ));
+    }
+}
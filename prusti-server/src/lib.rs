@@ -6,16 +6,25 @@
 
 #![warn(clippy::disallowed_types)]
 
+mod cancellation;
 mod client;
+mod job_queue;
 mod process_verification;
+mod replay;
 mod server;
 mod verification_request;
 mod backend;
+mod smt_backend;
+mod worker;
 
 pub use backend::*;
+pub use cancellation::*;
 pub use client::*;
+pub use job_queue::*;
 pub use process_verification::*;
+pub use replay::*;
 pub use server::*;
+pub use smt_backend::SmtBackend;
 pub use verification_request::*;
 
 // Futures returned by `Client` need to be executed in a compatible tokio runtime.
@@ -4,13 +4,17 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use crate::{Backend, VerificationRequest, ViperBackendConfig};
+use crate::{Backend, CancellationToken, SmtBackend, VerificationRequest, ViperBackendConfig};
 use log::info;
 use once_cell::sync::Lazy;
 use prusti_common::{
     config,
     report::log::{report, to_legal_file_name},
-    vir::{program_normalization::NormalizationInfo, ToViper},
+    vir::{
+        dependency_cones::{position_ids, split_into_dependency_cones, union_of_cones},
+        program_normalization::NormalizationInfo,
+        ToViper,
+    },
     Stopwatch,
 };
 use std::{fs::create_dir_all, path::PathBuf};
@@ -21,11 +25,27 @@ use viper::{
 #[tracing::instrument(level = "debug", skip_all, fields(program = %request.program.get_name()))]
 pub fn process_verification_request<'v, 't: 'v>(
     verification_context: &'v Lazy<VerificationContext<'t>, impl Fn() -> VerificationContext<'t>>,
-    mut request: VerificationRequest,
+    request: VerificationRequest,
     cache: impl Cache,
 ) -> viper::VerificationResult {
-    let ast_utils = verification_context.new_ast_utils();
+    process_cancellable_verification_request(
+        verification_context,
+        request,
+        cache,
+        &CancellationToken::default(),
+    )
+}
 
+/// Like `process_verification_request`, but the verification can be aborted
+/// with `cancellation`, which also stops a running Viper verifier. The result
+/// of a cancelled request is meaningless and is not stored in the cache.
+#[tracing::instrument(level = "debug", skip_all, fields(program = %request.program.get_name()))]
+pub fn process_cancellable_verification_request<'v, 't: 'v>(
+    verification_context: &'v Lazy<VerificationContext<'t>, impl Fn() -> VerificationContext<'t>>,
+    request: VerificationRequest,
+    mut cache: impl Cache,
+    cancellation: &CancellationToken,
+) -> viper::VerificationResult {
     // Only for testing: Check that the normalization is reversible.
     if config::print_hash() {
         debug_assert!({
@@ -36,6 +56,135 @@ pub fn process_verification_request<'v, 't: 'v>(
         });
     }
 
+    // Cache each method separately, so that editing one method does not
+    // invalidate the cached results of the others.
+    if config::enable_cache() && config::fine_grained_cache() && !config::print_hash() {
+        return verify_dependency_cones(verification_context, request, &mut cache, cancellation);
+    }
+
+    verify_with_cache(verification_context, request, &mut cache, cancellation)
+}
+
+/// Look up the result of each dependency cone of the program in the cache and
+/// verify all the cones that are not cached in a single request. The result
+/// of that request is split among the cones by the positions of its errors.
+fn verify_dependency_cones<'v, 't: 'v>(
+    verification_context: &'v Lazy<VerificationContext<'t>, impl Fn() -> VerificationContext<'t>>,
+    request: VerificationRequest,
+    cache: &mut impl Cache,
+    cancellation: &CancellationToken,
+) -> viper::VerificationResult {
+    let VerificationRequest {
+        program,
+        backend_config,
+    } = request;
+    let mut results = Vec::new();
+    let mut missing_cones = Vec::new();
+    for cone in split_into_dependency_cones(&program) {
+        let mut cone_request = VerificationRequest {
+            program: cone.program.clone(),
+            backend_config: backend_config.clone(),
+        };
+        let normalization_info = NormalizationInfo::normalize_program(&mut cone_request.program);
+        let hash = cone_request.get_hash();
+        if let Some(mut result) = cache.get(hash) {
+            info!(
+                "Using cached result {:?} for program {}",
+                &result,
+                cone.program.get_name()
+            );
+            normalization_info.denormalize_result(&mut result);
+            results.push(result);
+        } else {
+            missing_cones.push((cone, hash, normalization_info));
+        }
+    }
+    if missing_cones.is_empty() {
+        return merge_results(results);
+    }
+
+    let mut missing_request = VerificationRequest {
+        program: union_of_cones(&program, missing_cones.iter().map(|(cone, ..)| cone)),
+        backend_config,
+    };
+    let normalization_info = NormalizationInfo::normalize_program(&mut missing_request.program);
+    info!(
+        "Verifying {} uncached dependency cone(s) of program {}",
+        missing_cones.len(),
+        missing_request.program.get_name()
+    );
+    let mut missing_result = run_backend(verification_context, missing_request, cancellation);
+    normalization_info.denormalize_result(&mut missing_result);
+
+    let errors = match &missing_result {
+        VerificationResult::Success => Vec::new(),
+        VerificationResult::Failure(errors) => errors.clone(),
+        // The result cannot be attributed to the cones, so it is not cached.
+        _ => {
+            results.push(missing_result);
+            return merge_results(results);
+        }
+    };
+    let cone_results: Vec<_> = missing_cones
+        .iter()
+        .map(|(cone, _, _)| {
+            let position_ids = position_ids(&cone.program);
+            let cone_errors: Vec<_> = errors
+                .iter()
+                .filter(|error| {
+                    error_position_ids(error).all(|pos_id| position_ids.contains(&pos_id))
+                })
+                .cloned()
+                .collect();
+            if cone_errors.is_empty() {
+                VerificationResult::Success
+            } else {
+                VerificationResult::Failure(cone_errors)
+            }
+        })
+        .collect();
+    // Don't cache anything if some error could not be attributed to a cone.
+    let all_errors_attributed = errors.iter().all(|error| {
+        cone_results.iter().any(|result| {
+            matches!(result, VerificationResult::Failure(cone_errors) if cone_errors.contains(error))
+        })
+    });
+    if all_errors_attributed {
+        for ((cone, hash, normalization_info), result) in missing_cones.iter().zip(&cone_results) {
+            if let Some(normalized_result) = normalization_info.normalize_result(result) {
+                info!(
+                    "Storing new cached result {:?} for program {}",
+                    &normalized_result,
+                    cone.program.get_name()
+                );
+                cache.insert(*hash, normalized_result);
+            }
+        }
+        results.extend(cone_results);
+    } else {
+        results.push(missing_result);
+    }
+    merge_results(results)
+}
+
+/// The ids of the positions at which an error is reported.
+fn error_position_ids(error: &viper::VerificationError) -> impl Iterator<Item = u64> + '_ {
+    [&error.pos_id, &error.offending_pos_id, &error.reason_pos_id]
+        .into_iter()
+        .flatten()
+        .map(|pos_id| pos_id.parse().unwrap_or(u64::MAX))
+}
+
+fn cancelled_result() -> VerificationResult {
+    VerificationResult::ConsistencyErrors(vec!["the verification was cancelled".to_string()])
+}
+
+fn verify_with_cache<'v, 't: 'v>(
+    verification_context: &'v Lazy<VerificationContext<'t>, impl Fn() -> VerificationContext<'t>>,
+    mut request: VerificationRequest,
+    cache: &mut impl Cache,
+    cancellation: &CancellationToken,
+) -> viper::VerificationResult {
     // Normalize the request before reaching the cache.
     let normalization_info = NormalizationInfo::normalize_program(&mut request.program);
 
@@ -46,23 +195,23 @@ pub fn process_verification_request<'v, 't: 'v>(
         request.program.get_name()
     );
 
-    let build_or_dump_viper_program = || {
-        let mut stopwatch = Stopwatch::start("prusti-server", "construction of JVM objects");
-        let ast_factory = verification_context.new_ast_factory();
-        let viper_program = request
-            .program
-            .to_viper(prusti_common::vir::LoweringContext::default(), &ast_factory);
-
-        if config::dump_viper_program() {
+    // The SMT backend does not need the JVM, so only start it for Viper.
+    let is_viper_backend = request.backend_config.backend != VerificationBackend::Smt;
+    let dump_viper_program_with_jvm = || {
+        let ast_utils = verification_context.new_ast_utils();
+        ast_utils.with_local_frame(16, || {
+            let mut stopwatch = Stopwatch::start("prusti-server", "construction of JVM objects");
+            let ast_factory = verification_context.new_ast_factory();
+            let viper_program = request
+                .program
+                .to_viper(prusti_common::vir::LoweringContext::default(), &ast_factory);
             stopwatch.start_next("dumping viper program");
             dump_viper_program(
                 &ast_utils,
                 viper_program,
                 &request.program.get_name_with_check_mode(),
             );
-        }
-
-        viper_program
+        });
     };
 
     // Only for testing: Print the hash and skip verification.
@@ -73,10 +222,8 @@ pub fn process_verification_request<'v, 't: 'v>(
         );
         println!("Hash of the request is: {hash}");
         // Some tests need the dump to report a diff of the Viper programs.
-        if config::dump_viper_program() {
-            ast_utils.with_local_frame(16, || {
-                let _ = build_or_dump_viper_program();
-            });
+        if config::dump_viper_program() && is_viper_backend {
+            dump_viper_program_with_jvm();
         }
         return viper::VerificationResult::Success;
     }
@@ -89,18 +236,56 @@ pub fn process_verification_request<'v, 't: 'v>(
                 &result,
                 request.program.get_name()
             );
-            if config::dump_viper_program() {
-                ast_utils.with_local_frame(16, || {
-                    let _ = build_or_dump_viper_program();
-                });
+            if config::dump_viper_program() && is_viper_backend {
+                dump_viper_program_with_jvm();
             }
             normalization_info.denormalize_result(&mut result);
             return result;
         }
     };
 
+    let program_name = request.program.get_name().to_string();
+    let mut result = run_backend(verification_context, request, cancellation);
+    if cancellation.is_cancelled() {
+        return result;
+    }
+
+    // Don't cache Java exceptions nor backend failures, which might be due to
+    // misconfigured paths, nor timeouts, which might not happen again on a
+    // less loaded machine.
+    if config::enable_cache()
+        && !matches!(
+            result,
+            VerificationResult::JavaException(_)
+                | VerificationResult::BackendFailure(_)
+                | VerificationResult::TimedOut { .. }
+        )
+    {
+        info!(
+            "Storing new cached result {:?} for program {}",
+            &result, program_name
+        );
+        cache.insert(hash, result.clone());
+    }
+
+    normalization_info.denormalize_result(&mut result);
+    result
+}
+
+/// Verify the (normalized) request with the backend it asks for. Returns the
+/// result without denormalizing it.
+fn run_backend<'v, 't: 'v>(
+    verification_context: &'v Lazy<VerificationContext<'t>, impl Fn() -> VerificationContext<'t>>,
+    request: VerificationRequest,
+    cancellation: &CancellationToken,
+) -> viper::VerificationResult {
+    if cancellation.is_cancelled() {
+        return cancelled_result();
+    }
+
     let mut stopwatch = Stopwatch::start("prusti-server", "verifier startup");
 
+    let timeout = request.backend_config.timeout;
     // Create a new verifier each time.
     // Workaround for https://github.com/viperproject/prusti-dev/issues/744
     let mut backend = match request.backend_config.backend {
@@ -112,25 +297,69 @@ pub fn process_verification_request<'v, 't: 'v>(
             ),
             verification_context,
         ),
+        VerificationBackend::Smt => Backend::Smt(SmtBackend::new(&request.backend_config)),
     };
 
-    stopwatch.start_next("backend verification");
-    let mut result = backend.verify(&request.program);
-
-    // Don't cache Java exceptions, which might be due to misconfigured paths.
-    if config::enable_cache() && !matches!(result, VerificationResult::JavaException(_)) {
-        info!(
-            "Storing new cached result {:?} for program {}",
-            &result,
-            request.program.get_name()
-        );
-        cache.insert(hash, result.clone());
+    if let Backend::Viper(verifier, _) = &backend {
+        let stop_handle = verifier.stop_handle();
+        cancellation.on_cancel(move || stop_handle.stop());
     }
 
-    normalization_info.denormalize_result(&mut result);
+    stopwatch.start_next("backend verification");
+    let result = backend.verify(&request.program, timeout);
+    cancellation.clear_on_cancel();
+    if cancellation.is_cancelled() {
+        return cancelled_result();
+    }
     result
 }
 
+/// Combine the results of the verification of the dependency cones of a
+/// program. Java exceptions and backend failures take precedence over timeouts, which take
+/// precedence over consistency errors, which take precedence over
+/// verification failures. Errors of declarations that are shared by several
+/// cones are reported only once.
+fn merge_results(results: Vec<VerificationResult>) -> VerificationResult {
+    let mut verification_errors = Vec::new();
+    let mut consistency_errors = Vec::new();
+    let mut timed_out = None;
+    for result in results {
+        match result {
+            VerificationResult::Success => {}
+            VerificationResult::Failure(errors) => {
+                for error in errors {
+                    if !verification_errors.contains(&error) {
+                        verification_errors.push(error);
+                    }
+                }
+            }
+            VerificationResult::ConsistencyErrors(errors) => {
+                for error in errors {
+                    if !consistency_errors.contains(&error) {
+                        consistency_errors.push(error);
+                    }
+                }
+            }
+            VerificationResult::JavaException(exception) => {
+                return VerificationResult::JavaException(exception);
+            }
+            VerificationResult::BackendFailure(message) => {
+                return VerificationResult::BackendFailure(message);
+            }
+            VerificationResult::TimedOut { timeout_secs } => timed_out = Some(timeout_secs),
+        }
+    }
+    if let Some(timeout_secs) = timed_out {
+        VerificationResult::TimedOut { timeout_secs }
+    } else if !consistency_errors.is_empty() {
+        VerificationResult::ConsistencyErrors(consistency_errors)
+    } else if !verification_errors.is_empty() {
+        VerificationResult::Failure(verification_errors)
+    } else {
+        VerificationResult::Success
+    }
+}
+
 pub fn dump_viper_program(
     ast_utils: &viper::AstUtils,
     program: viper::Program,
@@ -170,6 +399,7 @@ fn new_viper_verifier<'v, 't: 'v>(
                 format!("/logPrefix {log_dir_str}"),
                 //"--print".to_string(), "./log/boogie_program/program.bpl".to_string(),
             ]),
+            VerificationBackend::Smt => unreachable!("the SMT backend does not use Viper"),
         }
     } else {
         report_path = None;
@@ -220,3 +450,41 @@ fn new_viper_verifier<'v, 't: 'v>(
         smt_manager,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use viper::VerificationError;
+
+    fn error(pos_id: &str) -> VerificationError {
+        VerificationError::new(
+            "assert.failed:assertion.false".to_string(),
+            Some(pos_id.to_string()),
+            Some(pos_id.to_string()),
+            None,
+            "Assert might fail.".to_string(),
+            None,
+        )
+    }
+
+    #[test]
+    fn shared_errors_are_merged_once() {
+        let merged = merge_results(vec![
+            VerificationResult::Failure(vec![error("1"), error("2")]),
+            VerificationResult::Success,
+            VerificationResult::Failure(vec![error("2")]),
+        ]);
+        assert_eq!(
+            merged,
+            VerificationResult::Failure(vec![error("1"), error("2")])
+        );
+        let merged = merge_results(vec![
+            VerificationResult::ConsistencyErrors(vec!["shared".to_string()]),
+            VerificationResult::ConsistencyErrors(vec!["shared".to_string()]),
+        ]);
+        assert_eq!(
+            merged,
+            VerificationResult::ConsistencyErrors(vec!["shared".to_string()])
+        );
+    }
+}
@@ -0,0 +1,41 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Offline verification of serialized verification requests, as dumped with
+//! `DUMP_VERIFICATION_REQUESTS`.
+
+use crate::{process_verification_request, VerificationRequest};
+use once_cell::sync::Lazy;
+use prusti_common::{config, Stopwatch};
+use std::{fs, io, path::Path};
+use viper::{PersistentCache, VerificationResult, Viper};
+
+/// Reads a verification request from `path`. Files with a `json` extension
+/// are decoded as JSON, all other files as bincode; these are the two
+/// encodings accepted by the server endpoints.
+pub fn load_verification_request(path: &Path) -> io::Result<VerificationRequest> {
+    let bytes = fs::read(path)?;
+    let is_json = path
+        .extension()
+        .map_or(false, |extension| extension.eq_ignore_ascii_case("json"));
+    if is_json {
+        serde_json::from_slice(&bytes)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    } else {
+        bincode::deserialize(&bytes).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+/// Verifies a single request in the current process, exactly as the server
+/// would. The JVM is only started if the request needs it.
+pub fn replay_verification_request(request: VerificationRequest) -> VerificationResult {
+    let stopwatch = Stopwatch::start("prusti-server", "JVM startup");
+    let viper = Lazy::new(|| Viper::new_with_args(&config::viper_home(), config::extra_jvm_args()));
+    let viper_thread = Lazy::new(|| viper.attach_current_thread());
+    stopwatch.finish();
+    let mut cache = PersistentCache::load_cache(config::cache_path());
+    process_verification_request(&viper_thread, request, &mut cache)
+}
@@ -4,23 +4,30 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use crate::{process_verification_request, VerificationRequest};
+use crate::{worker::spawn_verification_workers, JobId, JobQueue, JobStatus, VerificationRequest};
 use log::info;
-use once_cell::sync::Lazy;
-use prusti_common::{config, Stopwatch};
+use prusti_common::config;
 use std::{
     net::{Ipv4Addr, SocketAddr},
     sync::{mpsc, Arc, Mutex},
     thread,
 };
 use tokio::runtime::Builder;
-use viper::{PersistentCache, Viper};
-use warp::Filter;
+use viper::PersistentCache;
+use warp::{http::StatusCode, Filter, Reply};
 
 #[derive(Debug)]
 struct BincodeReject(bincode::Error);
 impl warp::reject::Reject for BincodeReject {}
 
+/// The query parameters accepted when submitting a verification request.
+#[derive(Debug, Default, serde::Deserialize)]
+struct JobOptions {
+    /// Jobs with a higher priority are verified first.
+    #[serde(default)]
+    priority: i32,
+}
+
 pub fn start_server_on_port(port: u16) {
     listen_on_port_with_address_callback(port, move |address| {
         if port == 0 {
@@ -46,47 +53,92 @@ fn listen_on_port_with_address_callback<F>(port: u16, address_callback: F) -> !
 where
     F: FnOnce(SocketAddr),
 {
-    let stopwatch = Stopwatch::start("prusti-server", "JVM startup");
-    let viper = Arc::new(Lazy::new(|| {
-        Viper::new_with_args(&config::viper_home(), config::extra_jvm_args())
-    }));
-
-    stopwatch.finish();
-
     let cache_data = PersistentCache::load_cache(config::cache_path());
     let cache = Arc::new(Mutex::new(cache_data));
-    let build_verification_request_handler = |viper_arc: Arc<Lazy<Viper, _>>, cache| {
-        move |request: VerificationRequest| {
-            let stopwatch = Stopwatch::start("prusti-server", "attach thread to JVM");
-            let viper_thread = Lazy::new(|| viper_arc.attach_current_thread());
-            stopwatch.finish();
-            process_verification_request(&viper_thread, request, &cache)
-        }
-    };
+    let queue = Arc::new(JobQueue::default());
+    spawn_verification_workers(queue.clone(), cache.clone());
+
+    let with_queue = warp::any().map(move || queue.clone());
+    let bincode_request =
+        warp::body::bytes().and_then(|buf: warp::hyper::body::Bytes| async move {
+            bincode::deserialize::<VerificationRequest>(&buf).map_err(|err| {
+                info!("request bincode body error: {}", err);
+                warp::reject::custom(BincodeReject(err))
+            })
+        });
 
     let json_verify = warp::path!("json" / "verify")
+        .and(with_queue.clone())
         .and(warp::body::json())
-        .map(build_verification_request_handler(
-            viper.clone(),
-            cache.clone(),
-        ))
-        .map(|response| warp::reply::json(&response));
+        .and(warp::query::<JobOptions>())
+        .then(verify_job)
+        .map(|status| match status {
+            JobStatus::Finished(result) => warp::reply::json(&result).into_response(),
+            status => unfinished_job_reply(status),
+        });
 
     let bincode_verify = warp::path!("bincode" / "verify")
-        .and(warp::body::bytes())
-        .and_then(|buf: warp::hyper::body::Bytes| async move {
-            bincode::deserialize(&buf).map_err(|err| {
-                info!("request bincode body error: {}", err);
-                warp::reject::custom(BincodeReject(err))
-            })
-        })
-        .map(build_verification_request_handler(viper, cache.clone()))
-        .map(|result| {
-            warp::http::Response::new(
+        .and(with_queue.clone())
+        .and(bincode_request)
+        .and(warp::query::<JobOptions>())
+        .then(verify_job)
+        .map(|status| match status {
+            JobStatus::Finished(result) => warp::http::Response::new(
                 bincode::serialize(&result).expect("could not encode verification result"),
             )
+            .into_response(),
+            status => unfinished_job_reply(status),
+        });
+
+    let json_submit = warp::post()
+        .and(warp::path!("json" / "submit"))
+        .and(with_queue.clone())
+        .and(warp::body::json())
+        .and(warp::query::<JobOptions>())
+        .map(submit_job)
+        .map(|id| warp::reply::json(&id));
+
+    let bincode_submit = warp::post()
+        .and(warp::path!("bincode" / "submit"))
+        .and(with_queue.clone())
+        .and(bincode_request)
+        .and(warp::query::<JobOptions>())
+        .map(submit_job)
+        .map(|id| {
+            warp::http::Response::new(bincode::serialize(&id).expect("could not encode job id"))
+        });
+
+    let json_poll = warp::path!("json" / "poll" / u64)
+        .and(with_queue.clone())
+        .map(|id, queue: Arc<JobQueue>| match queue.poll(JobId(id)) {
+            Some(status) => warp::reply::json(&status).into_response(),
+            None => StatusCode::NOT_FOUND.into_response(),
+        });
+
+    let bincode_poll = warp::path!("bincode" / "poll" / u64)
+        .and(with_queue.clone())
+        .map(|id, queue: Arc<JobQueue>| match queue.poll(JobId(id)) {
+            Some(status) => warp::http::Response::new(
+                bincode::serialize(&status).expect("could not encode job status"),
+            )
+            .into_response(),
+            None => StatusCode::NOT_FOUND.into_response(),
+        });
+
+    let cancel = warp::post()
+        .and(warp::path!("cancel" / u64))
+        .and(with_queue.clone())
+        .map(|id, queue: Arc<JobQueue>| {
+            let id = JobId(id);
+            let cancelled = queue.cancel(id);
+            info!("Cancellation of job {}: {}", id, cancelled);
+            warp::reply::json(&cancelled)
         });
 
+    let status = warp::path!("status")
+        .and(with_queue)
+        .map(|queue: Arc<JobQueue>| warp::reply::json(&queue.status()));
+
     let save_cache = warp::post()
         .and(warp::path("save"))
         .and(warp::path::end())
@@ -95,11 +147,18 @@ where
             warp::reply::html("Saved")
         });
 
-    let endpoints = json_verify.or(bincode_verify).or(save_cache);
-
-    // Here we use a single thread because
-    // 1. Viper is not thread safe yet (Silicon issue #578), and
-    // 2. By default Silicon already uses as many cores as possible.
+    let endpoints = json_verify
+        .or(bincode_verify)
+        .or(json_submit)
+        .or(bincode_submit)
+        .or(json_poll)
+        .or(bincode_poll)
+        .or(cancel)
+        .or(status)
+        .or(save_cache);
+
+    // The jobs are verified by the worker threads, so the runtime only needs
+    // one thread to serve the endpoints.
     let runtime = Builder::new_current_thread()
         .thread_name("prusti-server")
         .enable_all()
@@ -120,3 +179,53 @@ where
 
     unreachable!("The server unexpectedly stopped.");
 }
+
+fn submit_job(queue: Arc<JobQueue>, request: VerificationRequest, options: JobOptions) -> JobId {
+    let id = queue.submit(request, options.priority);
+    info!("Submitted job {} with priority {}", id, options.priority);
+    id
+}
+
+/// Submits a job and waits until it is done. If the client disconnects before
+/// that, the job is cancelled.
+async fn verify_job(
+    queue: Arc<JobQueue>,
+    request: VerificationRequest,
+    options: JobOptions,
+) -> JobStatus {
+    let id = submit_job(queue.clone(), request, options);
+    let mut guard = CancelOnDrop {
+        queue: queue.clone(),
+        id: Some(id),
+    };
+    let status = tokio::task::spawn_blocking(move || queue.wait(id))
+        .await
+        .expect("failed to wait for the verification job");
+    guard.id = None;
+    // Only the `cancel` endpoint can make the job disappear from the queue.
+    status.unwrap_or(JobStatus::Cancelled)
+}
+
+/// The reply of the `verify` endpoints for jobs that did not finish.
+fn unfinished_job_reply(status: JobStatus) -> warp::reply::Response {
+    let (message, code) = match status {
+        JobStatus::Failed(message) => (message, StatusCode::INTERNAL_SERVER_ERROR),
+        _ => ("the job was cancelled".to_string(), StatusCode::CONFLICT),
+    };
+    warp::reply::with_status(message, code).into_response()
+}
+
+/// Cancels the job when dropped, unless `id` was reset to `None`.
+struct CancelOnDrop {
+    queue: Arc<JobQueue>,
+    id: Option<JobId>,
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if let Some(id) = self.id {
+            info!("The client of job {} disconnected", id);
+            self.queue.cancel(id);
+        }
+    }
+}
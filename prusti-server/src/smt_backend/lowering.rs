@@ -0,0 +1,1762 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Translation of `vir::low` programs into SMT-LIB commands.
+//!
+//! Procedures are encoded as verification conditions over variables in SSA
+//! form: every basic block gets a boolean constant that holds iff the block is
+//! reached, facts are asserted under the path condition that leads to them,
+//! and every assertion is a separate validity check. The heap is modelled
+//! only through versions that are passed to heap-dependent functions and
+//! changed whenever permissions are exhaled or inhaled. Permission amounts
+//! themselves are **not** checked: access predicates are translated to `true`.
+
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::fmt::Write;
+use viper::VerificationError;
+use vir::{
+    common::position::{Position, Positioned},
+    low::{
+        ast::{
+            expression::{self, ContainerOpKind, Expression},
+            function::FunctionDecl,
+            predicate::PredicateDecl,
+            statement::{self, Statement},
+            ty::Type,
+            variable::VariableDecl,
+        },
+        cfg::{MethodDecl, ProcedureDecl, Successor},
+        operations::ty::Typed,
+        Program,
+    },
+};
+
+pub(super) type TranslationResult<T> = Result<T, String>;
+
+const HEAP_SORT: &str = "|$Heap|";
+const REF_SORT: &str = "|$Ref|";
+const RESULT_VARIABLE: &str = "__result";
+
+/// A command to be sent to the SMT solver.
+#[derive(Debug)]
+pub(super) enum Command {
+    /// A declaration or an assertion that the solver must accept.
+    Declare(String),
+    /// A formula that must be valid in the current context.
+    Check {
+        formula: String,
+        error: Box<VerificationError>,
+    },
+}
+
+impl Command {
+    /// The SMT-LIB script that executes this command.
+    pub(super) fn to_smt_lib(&self) -> Vec<String> {
+        match self {
+            Command::Declare(command) => vec![command.clone()],
+            Command::Check { formula, .. } => vec![
+                "(push 1)".to_string(),
+                format!("(assert (not {formula}))"),
+                "(check-sat)".to_string(),
+                "(pop 1)".to_string(),
+            ],
+        }
+    }
+}
+
+/// Render `commands` as a single SMT-LIB script.
+pub(super) fn to_smt_lib_script(commands: &[Command]) -> String {
+    let mut script = String::new();
+    for command in commands {
+        if let Command::Check { error, .. } = command {
+            writeln!(script, "; check {}", error.full_id).unwrap();
+        }
+        for line in command.to_smt_lib() {
+            writeln!(script, "{line}").unwrap();
+        }
+    }
+    script
+}
+
+/// Translate `program` into the SMT-LIB commands that verify it.
+pub(super) fn lower_program(program: &Program) -> TranslationResult<Vec<Command>> {
+    let mut lowering = Lowering::new(program);
+    lowering.declarations(program)?;
+    for function in &program.functions {
+        lowering.function(program, function)?;
+    }
+    lowering.function_postcondition_axioms(program, None)?;
+    for method in &program.methods {
+        lowering.method(method)?;
+    }
+    for procedure in &program.procedures {
+        lowering.procedure(procedure)?;
+    }
+    Ok(lowering.commands)
+}
+
+fn unsupported<T>(what: impl std::fmt::Display) -> TranslationResult<T> {
+    Err(format!("the SMT backend does not support {what}"))
+}
+
+fn symbol(name: &str) -> String {
+    format!("|{name}|")
+}
+
+fn application(function: String, arguments: Vec<String>) -> String {
+    if arguments.is_empty() {
+        function
+    } else {
+        format!("({} {})", function, arguments.join(" "))
+    }
+}
+
+fn conjunction(terms: &[String]) -> String {
+    match terms {
+        [] => "true".to_string(),
+        [term] => term.clone(),
+        _ => format!("(and {})", terms.join(" ")),
+    }
+}
+
+fn implication(premise: &str, conclusion: String) -> String {
+    if premise == "true" {
+        conclusion
+    } else {
+        format!("(=> {premise} {conclusion})")
+    }
+}
+
+fn sort(ty: &Type) -> TranslationResult<String> {
+    Ok(match ty {
+        Type::Int => "Int".to_string(),
+        Type::Bool => "Bool".to_string(),
+        Type::Perm => "Real".to_string(),
+        Type::Ref => REF_SORT.to_string(),
+        Type::Domain(domain) => symbol(&domain.name),
+        Type::Seq(seq) => format!("(Seq {})", sort(&seq.element_type)?),
+        Type::Set(set) => format!("(Array {} Bool)", sort(&set.element_type)?),
+        Type::Float(_) | Type::BitVector(_) | Type::MultiSet(_) | Type::Map(_) => {
+            return unsupported(format!("values of type {ty}"));
+        }
+    })
+}
+
+fn sorted_variables(variables: &[VariableDecl]) -> TranslationResult<String> {
+    let mut declarations = Vec::new();
+    for variable in variables {
+        declarations.push(format!(
+            "({} {})",
+            symbol(&variable.name),
+            sort(&variable.ty)?
+        ));
+    }
+    Ok(declarations.join(" "))
+}
+
+/// Split a top-level conjunction into the conjuncts that are checked
+/// separately, so that errors point to the failing conjunct.
+fn conjuncts(expression: &Expression) -> Vec<&Expression> {
+    match expression {
+        Expression::BinaryOp(expression::BinaryOp {
+            op_kind: expression::BinaryOpKind::And,
+            left,
+            right,
+            ..
+        }) => {
+            let mut result = conjuncts(left);
+            result.extend(conjuncts(right));
+            result
+        }
+        _ => vec![expression],
+    }
+}
+
+/// Whether exhaling or inhaling `expression` changes the permissions held.
+fn has_permissions(expression: &Expression) -> bool {
+    match expression {
+        Expression::PredicateAccessPredicate(_)
+        | Expression::FieldAccessPredicate(_)
+        | Expression::MagicWand(_) => true,
+        Expression::BinaryOp(expression) => {
+            has_permissions(&expression.left) || has_permissions(&expression.right)
+        }
+        Expression::Conditional(expression) => {
+            has_permissions(&expression.then_expr) || has_permissions(&expression.else_expr)
+        }
+        Expression::Quantifier(expression) => has_permissions(&expression.body),
+        Expression::LetExpr(expression) => has_permissions(&expression.body),
+        Expression::InhaleExhale(expression) => {
+            has_permissions(&expression.inhale_expression)
+                || has_permissions(&expression.exhale_expression)
+        }
+        _ => false,
+    }
+}
+
+fn verification_error(
+    full_id: impl Into<String>,
+    message: impl Into<String>,
+    offending: Position,
+    reason: Position,
+) -> VerificationError {
+    let offending_pos_id = Some(offending.id.to_string());
+    VerificationError::new(
+        full_id.into(),
+        offending_pos_id.clone(),
+        offending_pos_id,
+        Some(reason.id.to_string()),
+        message.into(),
+        None,
+    )
+}
+
+fn push_obligation(
+    obligations: &mut Vec<Obligation>,
+    guards: &[String],
+    formula: String,
+    error: VerificationError,
+) {
+    obligations.push(Obligation {
+        formula: implication(&conjunction(guards), formula),
+        error,
+    });
+}
+
+/// The values of variables and the heap at a program point.
+#[derive(Clone, Debug)]
+struct Environment {
+    variables: FxHashMap<String, String>,
+    heap: String,
+    /// The heap referred to by `old(...)` without a label.
+    old_heap: String,
+    /// Whether `InhaleExhale` expressions take their inhale part.
+    inhale: bool,
+}
+
+impl Environment {
+    fn new(heap: String) -> Self {
+        Self {
+            variables: FxHashMap::default(),
+            old_heap: heap.clone(),
+            heap,
+            inhale: true,
+        }
+    }
+}
+
+/// A well-definedness condition of an expression.
+struct Obligation {
+    formula: String,
+    error: VerificationError,
+}
+
+struct Lowering<'p> {
+    functions: FxHashMap<&'p str, &'p FunctionDecl>,
+    heap_dependent_functions: FxHashSet<&'p str>,
+    predicates: FxHashMap<&'p str, &'p PredicateDecl>,
+    methods: FxHashMap<&'p str, &'p MethodDecl>,
+    /// The heap at each label of the current procedure.
+    labels: FxHashMap<String, String>,
+    /// The sorts of the variables that have SSA versions.
+    variable_sorts: FxHashMap<String, String>,
+    commands: Vec<Command>,
+    fresh_names: usize,
+}
+
+impl<'p> Lowering<'p> {
+    fn new(program: &'p Program) -> Self {
+        let heap_dependent_functions = program
+            .functions
+            .iter()
+            .filter(|function| function.pres.iter().any(has_permissions))
+            .map(|function| function.name.as_str())
+            .collect();
+        Self {
+            functions: program
+                .functions
+                .iter()
+                .map(|function| (function.name.as_str(), function))
+                .collect(),
+            heap_dependent_functions,
+            predicates: program
+                .predicates
+                .iter()
+                .map(|predicate| (predicate.name.as_str(), predicate))
+                .collect(),
+            methods: program
+                .methods
+                .iter()
+                .map(|method| (method.name.as_str(), method))
+                .collect(),
+            labels: FxHashMap::default(),
+            variable_sorts: FxHashMap::default(),
+            commands: Vec::new(),
+            fresh_names: 0,
+        }
+    }
+
+    fn declare(&mut self, command: impl Into<String>) {
+        self.commands.push(Command::Declare(command.into()));
+    }
+
+    fn fresh(&mut self, name: &str, sort: &str) -> String {
+        self.fresh_names += 1;
+        let fresh = symbol(&format!("{name}@{}", self.fresh_names));
+        self.declare(format!("(declare-const {fresh} {sort})"));
+        self.variable_sorts
+            .insert(name.to_string(), sort.to_string());
+        fresh
+    }
+
+    fn assume(&mut self, path: &str, fact: String) {
+        if fact != "true" {
+            self.declare(format!("(assert {})", implication(path, fact)));
+        }
+    }
+
+    fn check(&mut self, path: &str, formula: String, error: VerificationError) {
+        if formula != "true" {
+            self.commands.push(Command::Check {
+                formula: implication(path, formula),
+                error: Box::new(error),
+            });
+        }
+    }
+
+    /// Check and then assume each conjunct of `expression`.
+    fn check_conjuncts(
+        &mut self,
+        path: &str,
+        expression: &Expression,
+        environment: &Environment,
+        full_id: &str,
+        message: &str,
+        offending: Position,
+    ) -> TranslationResult<()> {
+        for conjunct in conjuncts(expression) {
+            let term = self.expression(conjunct, environment)?;
+            let error = verification_error(full_id, message, offending, conjunct.position());
+            self.check(path, term.clone(), error);
+            self.assume(path, term);
+        }
+        Ok(())
+    }
+
+    fn check_well_definedness(
+        &mut self,
+        path: &str,
+        expression: &Expression,
+        environment: &Environment,
+        error_kind: &str,
+        offending: Position,
+    ) -> TranslationResult<()> {
+        let mut obligations = Vec::new();
+        self.well_definedness(
+            expression,
+            environment,
+            error_kind,
+            offending,
+            &mut Vec::new(),
+            &mut obligations,
+        )?;
+        for obligation in obligations {
+            self.check(path, obligation.formula, obligation.error);
+        }
+        Ok(())
+    }
+
+    fn declarations(&mut self, program: &Program) -> TranslationResult<()> {
+        self.declare(format!("(declare-sort {REF_SORT} 0)"));
+        self.declare(format!("(declare-sort {HEAP_SORT} 0)"));
+        for domain in &program.domains {
+            self.declare(format!("(declare-sort {} 0)", symbol(&domain.name)));
+        }
+        for domain in &program.domains {
+            let mut unique = FxHashMap::<String, Vec<String>>::default();
+            for function in &domain.functions {
+                let mut parameters = Vec::new();
+                for parameter in &function.parameters {
+                    parameters.push(sort(&parameter.ty)?);
+                }
+                let return_sort = sort(&function.return_type)?;
+                self.declare(format!(
+                    "(declare-fun {} ({}) {})",
+                    symbol(&function.name),
+                    parameters.join(" "),
+                    return_sort
+                ));
+                if function.is_unique {
+                    unique
+                        .entry(return_sort)
+                        .or_default()
+                        .push(symbol(&function.name));
+                }
+            }
+            let mut unique: Vec<_> = unique.into_values().collect();
+            unique.sort();
+            for constants in unique {
+                if constants.len() > 1 {
+                    self.declare(format!("(assert (distinct {}))", constants.join(" ")));
+                }
+            }
+        }
+        for function in &program.functions {
+            let mut parameters = Vec::new();
+            if self
+                .heap_dependent_functions
+                .contains(function.name.as_str())
+            {
+                parameters.push(HEAP_SORT.to_string());
+            }
+            for parameter in &function.parameters {
+                parameters.push(sort(&parameter.ty)?);
+            }
+            self.declare(format!(
+                "(declare-fun {} ({}) {})",
+                symbol(&function.name),
+                parameters.join(" "),
+                sort(&function.return_type)?
+            ));
+        }
+        // Axioms do not depend on the heap.
+        let environment = Environment::new(symbol("$no_heap"));
+        for domain in &program.domains {
+            for axiom in &domain.axioms {
+                let body = self.expression(&axiom.body, &environment)?;
+                self.declare(format!("(assert {body})"));
+            }
+        }
+        for function in &program.functions {
+            if let Some(body) = &function.body {
+                let axiom = self.function_axiom(function, |lowering, environment| {
+                    lowering.expression(body, environment)
+                })?;
+                self.declare(format!("(assert {axiom})"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Build `forall parameters :: pres ==> f(parameters) == conclusion`,
+    /// triggered by the function application.
+    fn function_axiom(
+        &self,
+        function: &FunctionDecl,
+        conclusion: impl FnOnce(&Self, &Environment) -> TranslationResult<String>,
+    ) -> TranslationResult<String> {
+        let heap_dependent = self
+            .heap_dependent_functions
+            .contains(function.name.as_str());
+        let mut environment = Environment::new(symbol("$heap"));
+        let mut variables = sorted_variables(&function.parameters)?;
+        let mut arguments = Vec::new();
+        if heap_dependent {
+            variables = format!("({} {HEAP_SORT}) {variables}", environment.heap);
+            arguments.push(environment.heap.clone());
+        }
+        for parameter in &function.parameters {
+            let variable = symbol(&parameter.name);
+            arguments.push(variable.clone());
+            environment
+                .variables
+                .insert(parameter.name.clone(), variable);
+        }
+        let call = application(symbol(&function.name), arguments);
+        let mut pres = Vec::new();
+        for pre in &function.pres {
+            let pre = self.expression(pre, &environment)?;
+            if pre != "true" {
+                pres.push(pre);
+            }
+        }
+        environment
+            .variables
+            .insert(RESULT_VARIABLE.to_string(), call.clone());
+        let conclusion = conclusion(self, &environment)?;
+        let body = implication(&conjunction(&pres), conclusion);
+        Ok(if variables.trim().is_empty() {
+            body
+        } else {
+            format!("(forall ({variables}) (! {body} :pattern ({call})))")
+        })
+    }
+
+    fn function_postcondition_axioms(
+        &mut self,
+        program: &Program,
+        skip: Option<&str>,
+    ) -> TranslationResult<()> {
+        for function in &program.functions {
+            if function.posts.is_empty() || Some(function.name.as_str()) == skip {
+                continue;
+            }
+            let axiom = self.function_axiom(function, |lowering, environment| {
+                let mut posts = Vec::new();
+                for post in &function.posts {
+                    posts.push(lowering.expression(post, environment)?);
+                }
+                Ok(conjunction(&posts))
+            })?;
+            self.declare(format!("(assert {axiom})"));
+        }
+        Ok(())
+    }
+
+    /// Check that the body of `function` is well-defined and that it
+    /// satisfies the postconditions.
+    fn function(&mut self, program: &Program, function: &FunctionDecl) -> TranslationResult<()> {
+        if function.body.is_none() && function.posts.is_empty() {
+            return Ok(());
+        }
+        self.declare("(push 1)");
+        let heap = self.fresh("$heap", HEAP_SORT);
+        let mut environment = Environment::new(heap.clone());
+        let mut arguments = Vec::new();
+        if self
+            .heap_dependent_functions
+            .contains(function.name.as_str())
+        {
+            arguments.push(heap);
+        }
+        for parameter in &function.parameters {
+            let variable = self.fresh(&parameter.name, &sort(&parameter.ty)?);
+            arguments.push(variable.clone());
+            environment
+                .variables
+                .insert(parameter.name.clone(), variable);
+        }
+        for pre in &function.pres {
+            let pre = self.expression(pre, &environment)?;
+            self.assume("true", pre);
+        }
+        let call = application(symbol(&function.name), arguments);
+        if let Some(body) = &function.body {
+            self.check_well_definedness(
+                "true",
+                body,
+                &environment,
+                "function.not.wellformed",
+                body.position(),
+            )?;
+        }
+        environment.inhale = false;
+        environment
+            .variables
+            .insert(RESULT_VARIABLE.to_string(), call);
+        // Assume the postconditions of the other functions, but not the
+        // ones that are being checked.
+        self.function_postcondition_axioms(program, Some(&function.name))?;
+        for post in &function.posts {
+            self.check_conjuncts(
+                "true",
+                post,
+                &environment,
+                "postcondition.violated:assertion.false",
+                "Postcondition might not hold.",
+                post.position(),
+            )?;
+        }
+        self.declare("(pop 1)");
+        Ok(())
+    }
+
+    /// Check that the body of `method` satisfies the postconditions.
+    fn method(&mut self, method: &MethodDecl) -> TranslationResult<()> {
+        let Some(body) = &method.body else {
+            return Ok(());
+        };
+        self.declare("(push 1)");
+        self.labels.clear();
+        let heap = self.fresh("$heap", HEAP_SORT);
+        let mut environment = Environment::new(heap);
+        for variable in method.parameters.iter().chain(&method.targets) {
+            let value = self.fresh(&variable.name, &sort(&variable.ty)?);
+            environment.variables.insert(variable.name.clone(), value);
+        }
+        for pre in &method.pres {
+            let pre = self.expression(pre, &environment)?;
+            self.assume("true", pre);
+        }
+        for statement in body {
+            self.statement(statement, "true", &mut environment)?;
+        }
+        environment.inhale = false;
+        for post in &method.posts {
+            self.check_conjuncts(
+                "true",
+                post,
+                &environment,
+                "postcondition.violated:assertion.false",
+                "Postcondition might not hold.",
+                post.position(),
+            )?;
+        }
+        self.declare("(pop 1)");
+        Ok(())
+    }
+
+    fn procedure(&mut self, procedure: &ProcedureDecl) -> TranslationResult<()> {
+        let blocks = &procedure.basic_blocks;
+        if blocks.is_empty() {
+            return Ok(());
+        }
+        let indices: FxHashMap<&str, usize> = blocks
+            .iter()
+            .enumerate()
+            .map(|(index, block)| (block.label.name.as_str(), index))
+            .collect();
+        let mut successors = Vec::new();
+        for block in blocks {
+            let labels = match &block.successor {
+                Successor::Return => Vec::new(),
+                Successor::Goto(label) => vec![label],
+                Successor::GotoSwitch(targets) => targets.iter().map(|(_, label)| label).collect(),
+            };
+            let mut block_successors = Vec::new();
+            for label in labels {
+                let Some(&index) = indices.get(label.name.as_str()) else {
+                    return Err(format!("unknown label {} in {}", label.name, procedure.name));
+                };
+                block_successors.push(index);
+            }
+            successors.push(block_successors);
+        }
+        let order = topological_order(&successors).ok_or_else(|| {
+            format!(
+                "the SMT backend does not support loops (in {})",
+                procedure.name
+            )
+        })?;
+
+        self.declare("(push 1)");
+        self.labels.clear();
+        let heap = self.fresh("$heap", HEAP_SORT);
+        let mut initial = Environment::new(heap);
+        for local in &procedure.locals {
+            let value = self.fresh(&local.name, &sort(&local.ty)?);
+            initial.variables.insert(local.name.clone(), value);
+        }
+        let mut incoming: Vec<Vec<(String, Environment)>> = vec![Vec::new(); blocks.len()];
+        incoming[0].push(("true".to_string(), initial));
+        for index in order {
+            let block = &blocks[index];
+            let edges = std::mem::take(&mut incoming[index]);
+            let Some((path, mut environment)) = self.merge(edges)? else {
+                // The block is not reachable.
+                continue;
+            };
+            self.labels
+                .insert(block.label.name.clone(), environment.heap.clone());
+            for statement in &block.statements {
+                self.statement(statement, &path, &mut environment)?;
+            }
+            match &block.successor {
+                Successor::Return => {}
+                Successor::Goto(_) => {
+                    incoming[successors[index][0]].push((path, environment));
+                }
+                Successor::GotoSwitch(targets) => {
+                    let mut not_taken = vec![path.clone()];
+                    for ((guard, _), &target) in targets.iter().zip(&successors[index]) {
+                        let guard_path = conjunction(&not_taken);
+                        self.check_well_definedness(
+                            &guard_path,
+                            guard,
+                            &environment,
+                            "if.failed",
+                            guard.position(),
+                        )?;
+                        let guard = self.expression(guard, &environment)?;
+                        let mut edge = not_taken.clone();
+                        edge.push(guard.clone());
+                        incoming[target].push((conjunction(&edge), environment.clone()));
+                        not_taken.push(format!("(not {guard})"));
+                    }
+                    // Like the Viper encoding, fail if no target is taken.
+                    let error = verification_error(
+                        "assert.failed:assertion.false",
+                        "Assertion might fail.",
+                        Position::default(),
+                        Position::default(),
+                    );
+                    self.check(&conjunction(&not_taken), "false".to_string(), error);
+                }
+            }
+        }
+        self.declare("(pop 1)");
+        Ok(())
+    }
+
+    /// Join the states of the edges that enter a block. Returns the path
+    /// condition of the block and the merged environment.
+    fn merge(
+        &mut self,
+        edges: Vec<(String, Environment)>,
+    ) -> TranslationResult<Option<(String, Environment)>> {
+        if edges.is_empty() {
+            return Ok(None);
+        }
+        let path = self.fresh("$reach", "Bool");
+        let conditions: Vec<_> = edges
+            .iter()
+            .map(|(condition, _)| condition.clone())
+            .collect();
+        let reach = if conditions.len() == 1 {
+            conditions[0].clone()
+        } else {
+            format!("(or {})", conditions.join(" "))
+        };
+        self.declare(format!("(assert (= {path} {reach}))"));
+        let mut merged = edges[0].1.clone();
+        let mut names: Vec<_> = merged.variables.keys().cloned().collect();
+        names.sort();
+        for name in names {
+            let values: Vec<_> = edges
+                .iter()
+                .map(|(_, environment)| environment.variables[&name].clone())
+                .collect();
+            if values.iter().any(|value| value != &values[0]) {
+                let sort = self.variable_sorts[&name].clone();
+                let value = self.fresh(&name, &sort);
+                for (condition, incoming) in conditions.iter().zip(values) {
+                    self.assume(condition, format!("(= {value} {incoming})"));
+                }
+                merged.variables.insert(name, value);
+            }
+        }
+        let heaps: Vec<_> = edges
+            .iter()
+            .map(|(_, environment)| environment.heap.clone())
+            .collect();
+        if heaps.iter().any(|heap| heap != &heaps[0]) {
+            let heap = self.fresh("$heap", HEAP_SORT);
+            for (condition, incoming) in conditions.iter().zip(heaps) {
+                self.assume(condition, format!("(= {heap} {incoming})"));
+            }
+            merged.heap = heap;
+        }
+        Ok(Some((path, merged)))
+    }
+
+    fn statement(
+        &mut self,
+        statement: &Statement,
+        path: &str,
+        environment: &mut Environment,
+    ) -> TranslationResult<()> {
+        match statement {
+            Statement::Comment(_) => {}
+            Statement::LogEvent(statement) => {
+                let fact = self.expression(&statement.expression, environment)?;
+                self.assume(path, fact);
+            }
+            Statement::Assume(statement::Assume {
+                expression,
+                position,
+            }) => {
+                self.check_well_definedness(
+                    path,
+                    expression,
+                    environment,
+                    "inhale.failed",
+                    *position,
+                )?;
+                let fact = self.expression(expression, environment)?;
+                self.assume(path, fact);
+            }
+            Statement::Inhale(statement::Inhale {
+                expression,
+                position,
+            }) => {
+                self.check_well_definedness(
+                    path,
+                    expression,
+                    environment,
+                    "inhale.failed",
+                    *position,
+                )?;
+                if has_permissions(expression) {
+                    environment.heap = self.fresh("$heap", HEAP_SORT);
+                }
+                let fact = self.expression(expression, environment)?;
+                self.assume(path, fact);
+            }
+            Statement::Assert(statement::Assert {
+                expression,
+                position,
+            }) => {
+                environment.inhale = false;
+                self.check_well_definedness(
+                    path,
+                    expression,
+                    environment,
+                    "assert.failed",
+                    *position,
+                )?;
+                self.check_conjuncts(
+                    path,
+                    expression,
+                    environment,
+                    "assert.failed:assertion.false",
+                    "Assertion might fail.",
+                    *position,
+                )?;
+                environment.inhale = true;
+            }
+            Statement::Exhale(statement::Exhale {
+                expression,
+                position,
+            }) => {
+                environment.inhale = false;
+                self.check_well_definedness(
+                    path,
+                    expression,
+                    environment,
+                    "exhale.failed",
+                    *position,
+                )?;
+                self.check_conjuncts(
+                    path,
+                    expression,
+                    environment,
+                    "exhale.failed:assertion.false",
+                    "Exhale might fail.",
+                    *position,
+                )?;
+                environment.inhale = true;
+                if has_permissions(expression) {
+                    environment.heap = self.fresh("$heap", HEAP_SORT);
+                }
+            }
+            Statement::Fold(statement::Fold {
+                expression,
+                position,
+            }) => {
+                environment.inhale = false;
+                let body =
+                    self.predicate_body(path, expression, environment, "fold.failed", *position)?;
+                if let Some((body, body_environment)) = body {
+                    self.check_conjuncts(
+                        path,
+                        &body,
+                        &body_environment,
+                        "fold.failed:assertion.false",
+                        "Folding might fail.",
+                        *position,
+                    )?;
+                }
+                environment.inhale = true;
+            }
+            Statement::Unfold(statement::Unfold {
+                expression,
+                position,
+            }) => {
+                // Permissions are not tracked, so the body is not known to
+                // hold just because the instance was folded or inhaled
+                // earlier; check it before relying on it.
+                environment.inhale = false;
+                let body =
+                    self.predicate_body(path, expression, environment, "unfold.failed", *position)?;
+                if let Some((body, body_environment)) = body {
+                    self.check_conjuncts(
+                        path,
+                        &body,
+                        &body_environment,
+                        "unfold.failed:assertion.false",
+                        "Unfolding might fail.",
+                        *position,
+                    )?;
+                }
+                environment.inhale = true;
+            }
+            Statement::ApplyMagicWand(_) => return unsupported("magic wands"),
+            Statement::MethodCall(call) => self.method_call(call, path, environment)?,
+            Statement::Assign(statement::Assign {
+                target,
+                value,
+                position,
+            }) => {
+                self.check_well_definedness(
+                    path,
+                    value,
+                    environment,
+                    "assignment.failed",
+                    *position,
+                )?;
+                let value = self.expression(value, environment)?;
+                if !environment.variables.contains_key(&target.name) {
+                    return Err(format!("unknown variable {}", target.name));
+                }
+                let fresh = self.fresh(&target.name, &sort(&target.ty)?);
+                self.assume(path, format!("(= {fresh} {value})"));
+                environment.variables.insert(target.name.clone(), fresh);
+            }
+            Statement::Conditional(statement::Conditional {
+                guard,
+                then_branch,
+                else_branch,
+                position,
+            }) => {
+                self.check_well_definedness(path, guard, environment, "if.failed", *position)?;
+                let guard = self.expression(guard, environment)?;
+                let then_path = self.fresh("$then", "Bool");
+                self.declare(format!("(assert (= {then_path} (and {path} {guard})))"));
+                let else_path = self.fresh("$else", "Bool");
+                self.declare(format!(
+                    "(assert (= {else_path} (and {path} (not {guard}))))"
+                ));
+                let mut then_environment = environment.clone();
+                for statement in then_branch {
+                    self.statement(statement, &then_path, &mut then_environment)?;
+                }
+                let mut else_environment = environment.clone();
+                for statement in else_branch {
+                    self.statement(statement, &else_path, &mut else_environment)?;
+                }
+                let mut names: Vec<_> = environment.variables.keys().cloned().collect();
+                names.sort();
+                for name in names {
+                    let then_value = &then_environment.variables[&name];
+                    let else_value = &else_environment.variables[&name];
+                    if then_value != else_value {
+                        let sort = self.variable_sorts[&name].clone();
+                        let value = self.fresh(&name, &sort);
+                        self.assume(
+                            path,
+                            format!("(= {value} (ite {guard} {then_value} {else_value}))"),
+                        );
+                        environment.variables.insert(name, value);
+                    }
+                }
+                if then_environment.heap != else_environment.heap {
+                    let heap = self.fresh("$heap", HEAP_SORT);
+                    self.assume(
+                        path,
+                        format!(
+                            "(= {heap} (ite {guard} {} {}))",
+                            then_environment.heap, else_environment.heap
+                        ),
+                    );
+                    environment.heap = heap;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The body of the predicate instance `acc(P(args))` with the parameters
+    /// bound to the arguments, or `None` if the predicate is abstract.
+    fn predicate_body(
+        &mut self,
+        path: &str,
+        expression: &Expression,
+        environment: &Environment,
+        error_kind: &str,
+        position: Position,
+    ) -> TranslationResult<Option<(Expression, Environment)>> {
+        let Expression::PredicateAccessPredicate(access) = expression else {
+            return unsupported(format!("folding {expression}"));
+        };
+        let Some(predicate) = self.predicates.get(access.name.as_str()).copied() else {
+            return Err(format!("unknown predicate {}", access.name));
+        };
+        let mut body_environment = environment.clone();
+        body_environment.variables.clear();
+        for (parameter, argument) in predicate.parameters.iter().zip(&access.arguments) {
+            self.check_well_definedness(path, argument, environment, error_kind, position)?;
+            let argument = self.expression(argument, environment)?;
+            body_environment
+                .variables
+                .insert(parameter.name.clone(), argument);
+        }
+        Ok(predicate.body.clone().map(|body| (body, body_environment)))
+    }
+
+    fn method_call(
+        &mut self,
+        call: &statement::MethodCall,
+        path: &str,
+        environment: &mut Environment,
+    ) -> TranslationResult<()> {
+        let Some(method) = self.methods.get(call.method_name.as_str()).copied() else {
+            return Err(format!("unknown method {}", call.method_name));
+        };
+        let mut callee = Environment::new(environment.heap.clone());
+        callee.inhale = false;
+        for (parameter, argument) in method.parameters.iter().zip(&call.arguments) {
+            self.check_well_definedness(path, argument, environment, "call.failed", call.position)?;
+            let argument = self.expression(argument, environment)?;
+            callee.variables.insert(parameter.name.clone(), argument);
+        }
+        for pre in &method.pres {
+            self.check_conjuncts(
+                path,
+                pre,
+                &callee,
+                "call.precondition:assertion.false",
+                "The precondition of method might not hold.",
+                call.position,
+            )?;
+        }
+        callee.inhale = true;
+        if method.pres.iter().chain(&method.posts).any(has_permissions) {
+            environment.heap = self.fresh("$heap", HEAP_SORT);
+            callee.heap = environment.heap.clone();
+        }
+        for (target, declaration) in call.targets.iter().zip(&method.targets) {
+            let Expression::Local(target) = target else {
+                return unsupported(format!("assigning the result of a call to {target}"));
+            };
+            let name = &target.variable.name;
+            if !environment.variables.contains_key(name) {
+                return Err(format!("unknown variable {name}"));
+            }
+            let value = self.fresh(name, &sort(&declaration.ty)?);
+            environment.variables.insert(name.clone(), value.clone());
+            callee.variables.insert(declaration.name.clone(), value);
+        }
+        for post in &method.posts {
+            let fact = self.expression(post, &callee)?;
+            self.assume(path, fact);
+        }
+        Ok(())
+    }
+
+    fn expression(
+        &self,
+        expression: &Expression,
+        environment: &Environment,
+    ) -> TranslationResult<String> {
+        Ok(match expression {
+            Expression::Local(local) => {
+                let name = &local.variable.name;
+                match environment.variables.get(name) {
+                    Some(value) => value.clone(),
+                    None => return Err(format!("unknown variable {name}")),
+                }
+            }
+            Expression::Field(_) => return unsupported("field accesses"),
+            Expression::LabelledOld(expression) => {
+                let mut old_environment = environment.clone();
+                old_environment.heap = match &expression.label {
+                    None => environment.old_heap.clone(),
+                    Some(label) => match self.labels.get(label) {
+                        Some(heap) => heap.clone(),
+                        None => return unsupported(format!("the old label {label}")),
+                    },
+                };
+                self.expression(&expression.base, &old_environment)?
+            }
+            Expression::Constant(constant) => self.constant(constant)?,
+            Expression::MagicWand(_) => return unsupported("magic wands"),
+            Expression::PredicateAccessPredicate(_) | Expression::FieldAccessPredicate(_) => {
+                "true".to_string()
+            }
+            Expression::Unfolding(expression) => self.expression(&expression.base, environment)?,
+            Expression::UnaryOp(expression) => {
+                let argument = self.expression(&expression.argument, environment)?;
+                match expression.op_kind {
+                    expression::UnaryOpKind::Not => format!("(not {argument})"),
+                    expression::UnaryOpKind::Minus => format!("(- {argument})"),
+                }
+            }
+            Expression::BinaryOp(expression) => {
+                let left = self.expression(&expression.left, environment)?;
+                let right = self.expression(&expression.right, environment)?;
+                let operator = match expression.op_kind {
+                    expression::BinaryOpKind::EqCmp => "=",
+                    expression::BinaryOpKind::NeCmp => {
+                        return Ok(format!("(not (= {left} {right}))"));
+                    }
+                    expression::BinaryOpKind::GtCmp => ">",
+                    expression::BinaryOpKind::GeCmp => ">=",
+                    expression::BinaryOpKind::LtCmp => "<",
+                    expression::BinaryOpKind::LeCmp => "<=",
+                    expression::BinaryOpKind::Add => "+",
+                    expression::BinaryOpKind::Sub => "-",
+                    expression::BinaryOpKind::Mul => "*",
+                    expression::BinaryOpKind::Div => "div",
+                    expression::BinaryOpKind::Mod => "mod",
+                    expression::BinaryOpKind::And => "and",
+                    expression::BinaryOpKind::Or => "or",
+                    expression::BinaryOpKind::Implies => "=>",
+                };
+                format!("({operator} {left} {right})")
+            }
+            Expression::PermBinaryOp(expression) => {
+                let left = self.real(&expression.left, environment)?;
+                let right = self.real(&expression.right, environment)?;
+                let operator = match expression.op_kind {
+                    expression::PermBinaryOpKind::Add => "+",
+                    expression::PermBinaryOpKind::Sub => "-",
+                    expression::PermBinaryOpKind::Mul => "*",
+                    expression::PermBinaryOpKind::Div => "/",
+                };
+                format!("({operator} {left} {right})")
+            }
+            Expression::ContainerOp(expression) => self.container_op(expression, environment)?,
+            Expression::Conditional(expression) => format!(
+                "(ite {} {} {})",
+                self.expression(&expression.guard, environment)?,
+                self.expression(&expression.then_expr, environment)?,
+                self.expression(&expression.else_expr, environment)?
+            ),
+            Expression::Quantifier(expression) => {
+                let mut body_environment = environment.clone();
+                for variable in &expression.variables {
+                    body_environment
+                        .variables
+                        .insert(variable.name.clone(), symbol(&variable.name));
+                }
+                let body = self.expression(&expression.body, &body_environment)?;
+                let mut patterns = String::new();
+                for trigger in &expression.triggers {
+                    match self.trigger(trigger, &body_environment)? {
+                        Some(pattern) => write!(patterns, " :pattern ({pattern})").unwrap(),
+                        None => {
+                            // Let the solver infer the patterns.
+                            patterns.clear();
+                            break;
+                        }
+                    }
+                }
+                let body = if patterns.is_empty() {
+                    body
+                } else {
+                    format!("(! {body}{patterns})")
+                };
+                let quantifier = match expression.kind {
+                    expression::QuantifierKind::ForAll => "forall",
+                    expression::QuantifierKind::Exists => "exists",
+                };
+                format!(
+                    "({quantifier} ({}) {body})",
+                    sorted_variables(&expression.variables)?
+                )
+            }
+            Expression::LetExpr(expression) => {
+                let def = self.expression(&expression.def, environment)?;
+                let mut body_environment = environment.clone();
+                let variable = symbol(&expression.variable.name);
+                body_environment
+                    .variables
+                    .insert(expression.variable.name.clone(), variable.clone());
+                let body = self.expression(&expression.body, &body_environment)?;
+                format!("(let (({variable} {def})) {body})")
+            }
+            Expression::FuncApp(expression) => {
+                let mut arguments = Vec::new();
+                if self
+                    .heap_dependent_functions
+                    .contains(expression.function_name.as_str())
+                {
+                    arguments.push(environment.heap.clone());
+                }
+                for argument in &expression.arguments {
+                    arguments.push(self.expression(argument, environment)?);
+                }
+                application(symbol(&expression.function_name), arguments)
+            }
+            Expression::DomainFuncApp(expression) => {
+                let mut arguments = Vec::new();
+                for argument in &expression.arguments {
+                    arguments.push(self.expression(argument, environment)?);
+                }
+                application(symbol(&expression.function_name), arguments)
+            }
+            Expression::InhaleExhale(expression) => {
+                if environment.inhale {
+                    self.expression(&expression.inhale_expression, environment)?
+                } else {
+                    self.expression(&expression.exhale_expression, environment)?
+                }
+            }
+        })
+    }
+
+    /// Translate a numeric expression to a real number.
+    fn real(
+        &self,
+        expression: &Expression,
+        environment: &Environment,
+    ) -> TranslationResult<String> {
+        let term = self.expression(expression, environment)?;
+        Ok(if expression.get_type() == &Type::Int {
+            format!("(to_real {term})")
+        } else {
+            term
+        })
+    }
+
+    /// Translate the terms of a trigger, or return `None` if they cannot be
+    /// used as an SMT pattern.
+    fn trigger(
+        &self,
+        trigger: &expression::Trigger,
+        environment: &Environment,
+    ) -> TranslationResult<Option<String>> {
+        let mut terms = Vec::new();
+        for term in &trigger.terms {
+            if !matches!(term, Expression::FuncApp(_) | Expression::DomainFuncApp(_)) {
+                return Ok(None);
+            }
+            terms.push(self.expression(term, environment)?);
+        }
+        Ok(Some(terms.join(" ")))
+    }
+
+    fn constant(&self, constant: &expression::Constant) -> TranslationResult<String> {
+        Ok(match (&constant.ty, &constant.value) {
+            (Type::Bool, expression::ConstantValue::Bool(value)) => value.to_string(),
+            (Type::Int, expression::ConstantValue::Int(value)) => {
+                let value = i128::from(*value);
+                if value < 0 {
+                    format!("(- {})", -value)
+                } else {
+                    value.to_string()
+                }
+            }
+            (Type::Int, expression::ConstantValue::BigInt(value)) => {
+                match value.strip_prefix('-') {
+                    Some(value) => format!("(- {value})"),
+                    None => value.clone(),
+                }
+            }
+            (Type::Perm, expression::ConstantValue::Int(0)) => "0.0".to_string(),
+            (Type::Perm, expression::ConstantValue::Int(1)) => "1.0".to_string(),
+            _ => return unsupported(format!("the constant {constant} of type {}", constant.ty)),
+        })
+    }
+
+    fn container_op(
+        &self,
+        expression: &expression::ContainerOp,
+        environment: &Environment,
+    ) -> TranslationResult<String> {
+        let mut operands = Vec::new();
+        for operand in &expression.operands {
+            operands.push(self.expression(operand, environment)?);
+        }
+        let container_sort = sort(&expression.container_type)?;
+        Ok(match expression.kind {
+            ContainerOpKind::SeqEmpty => format!("(as seq.empty {container_sort})"),
+            ContainerOpKind::SeqConstructor => match operands.len() {
+                0 => format!("(as seq.empty {container_sort})"),
+                1 => format!("(seq.unit {})", operands[0]),
+                _ => {
+                    let units: Vec<_> = operands
+                        .iter()
+                        .map(|operand| format!("(seq.unit {operand})"))
+                        .collect();
+                    format!("(seq.++ {})", units.join(" "))
+                }
+            },
+            ContainerOpKind::SeqIndex => format!("(seq.nth {} {})", operands[0], operands[1]),
+            ContainerOpKind::SeqConcat => format!("(seq.++ {} {})", operands[0], operands[1]),
+            ContainerOpKind::SeqLen => format!("(seq.len {})", operands[0]),
+            ContainerOpKind::SeqTake => format!("(seq.extract {} 0 {})", operands[0], operands[1]),
+            ContainerOpKind::SeqDrop => format!(
+                "(seq.extract {} {} (- (seq.len {}) {}))",
+                operands[0], operands[1], operands[0], operands[1]
+            ),
+            ContainerOpKind::SeqContains => {
+                format!("(seq.contains {} (seq.unit {}))", operands[1], operands[0])
+            }
+            ContainerOpKind::SetEmpty => {
+                format!("((as const {container_sort}) false)")
+            }
+            ContainerOpKind::SetConstructor => {
+                let mut set = format!("((as const {container_sort}) false)");
+                for operand in &operands {
+                    set = format!("(store {set} {operand} true)");
+                }
+                set
+            }
+            ContainerOpKind::SetUnion => format!("((_ map or) {} {})", operands[0], operands[1]),
+            ContainerOpKind::SetIntersection => {
+                format!("((_ map and) {} {})", operands[0], operands[1])
+            }
+            ContainerOpKind::SetMinus => format!(
+                "((_ map and) {} ((_ map not) {}))",
+                operands[0], operands[1]
+            ),
+            ContainerOpKind::SetSubset => format!(
+                "(= ((_ map and) {} {}) {})",
+                operands[0], operands[1], operands[0]
+            ),
+            ContainerOpKind::SetContains => format!("(select {} {})", operands[1], operands[0]),
+            ContainerOpKind::SetCardinality => return unsupported("set cardinality"),
+            _ => return unsupported(format!("the container operation {}", expression.kind)),
+        })
+    }
+
+    /// Collect the well-definedness conditions of `expression`: function
+    /// preconditions, divisors and sequence indices. `guards` are the
+    /// conditions under which `expression` is evaluated.
+    fn well_definedness(
+        &self,
+        expression: &Expression,
+        environment: &Environment,
+        error_kind: &str,
+        offending: Position,
+        guards: &mut Vec<String>,
+        obligations: &mut Vec<Obligation>,
+    ) -> TranslationResult<()> {
+        macro_rules! recurse {
+            ($expression: expr) => {
+                self.well_definedness(
+                    $expression,
+                    environment,
+                    error_kind,
+                    offending,
+                    guards,
+                    obligations,
+                )?
+            };
+        }
+        match expression {
+            Expression::Local(_)
+            | Expression::Constant(_)
+            | Expression::Field(_)
+            | Expression::MagicWand(_)
+            | Expression::FieldAccessPredicate(_) => {}
+            Expression::LabelledOld(expression) => {
+                let mut old_environment = environment.clone();
+                old_environment.heap = match &expression.label {
+                    None => environment.old_heap.clone(),
+                    Some(label) => match self.labels.get(label) {
+                        Some(heap) => heap.clone(),
+                        None => return unsupported(format!("the old label {label}")),
+                    },
+                };
+                self.well_definedness(
+                    &expression.base,
+                    &old_environment,
+                    error_kind,
+                    offending,
+                    guards,
+                    obligations,
+                )?;
+            }
+            Expression::PredicateAccessPredicate(expression) => {
+                for argument in &expression.arguments {
+                    recurse!(argument);
+                }
+            }
+            Expression::Unfolding(expression) => {
+                for argument in &expression.arguments {
+                    recurse!(argument);
+                }
+                recurse!(&expression.base);
+            }
+            Expression::UnaryOp(expression) => recurse!(&expression.argument),
+            Expression::BinaryOp(expression) => {
+                recurse!(&expression.left);
+                let left = self.expression(&expression.left, environment)?;
+                let guard = match expression.op_kind {
+                    expression::BinaryOpKind::And | expression::BinaryOpKind::Implies => Some(left),
+                    expression::BinaryOpKind::Or => Some(format!("(not {left})")),
+                    _ => None,
+                };
+                let guarded = guard.is_some();
+                if let Some(guard) = guard {
+                    guards.push(guard);
+                }
+                recurse!(&expression.right);
+                if guarded {
+                    guards.pop();
+                }
+                if matches!(
+                    expression.op_kind,
+                    expression::BinaryOpKind::Div | expression::BinaryOpKind::Mod
+                ) {
+                    let right = self.expression(&expression.right, environment)?;
+                    push_obligation(
+                        obligations,
+                        guards,
+                        format!("(not (= {right} 0))"),
+                        verification_error(
+                            format!("{error_kind}:division.by.zero"),
+                            "Divisor might be zero.",
+                            offending,
+                            expression.right.position(),
+                        ),
+                    );
+                }
+            }
+            Expression::PermBinaryOp(expression) => {
+                recurse!(&expression.left);
+                recurse!(&expression.right);
+                if expression.op_kind == expression::PermBinaryOpKind::Div {
+                    let right = self.real(&expression.right, environment)?;
+                    push_obligation(
+                        obligations,
+                        guards,
+                        format!("(not (= {right} 0.0))"),
+                        verification_error(
+                            format!("{error_kind}:division.by.zero"),
+                            "Divisor might be zero.",
+                            offending,
+                            expression.right.position(),
+                        ),
+                    );
+                }
+            }
+            Expression::ContainerOp(expression) => {
+                for operand in &expression.operands {
+                    recurse!(operand);
+                }
+                if expression.kind == ContainerOpKind::SeqIndex {
+                    let sequence = self.expression(&expression.operands[0], environment)?;
+                    let index = self.expression(&expression.operands[1], environment)?;
+                    let index_position = expression.operands[1].position();
+                    push_obligation(
+                        obligations,
+                        guards,
+                        format!("(<= 0 {index})"),
+                        verification_error(
+                            format!("{error_kind}:seq.index.negative"),
+                            "Index might be negative.",
+                            offending,
+                            index_position,
+                        ),
+                    );
+                    push_obligation(
+                        obligations,
+                        guards,
+                        format!("(< {index} (seq.len {sequence}))"),
+                        verification_error(
+                            format!("{error_kind}:seq.index.length"),
+                            "Index might exceed the sequence length.",
+                            offending,
+                            index_position,
+                        ),
+                    );
+                }
+            }
+            Expression::Conditional(expression) => {
+                recurse!(&expression.guard);
+                let guard = self.expression(&expression.guard, environment)?;
+                guards.push(guard.clone());
+                recurse!(&expression.then_expr);
+                guards.pop();
+                guards.push(format!("(not {guard})"));
+                recurse!(&expression.else_expr);
+                guards.pop();
+            }
+            Expression::Quantifier(expression) => {
+                let mut body_environment = environment.clone();
+                for variable in &expression.variables {
+                    body_environment
+                        .variables
+                        .insert(variable.name.clone(), symbol(&variable.name));
+                }
+                let mut body_obligations = Vec::new();
+                self.well_definedness(
+                    &expression.body,
+                    &body_environment,
+                    error_kind,
+                    offending,
+                    &mut Vec::new(),
+                    &mut body_obligations,
+                )?;
+                let variables = sorted_variables(&expression.variables)?;
+                for Obligation { formula, error } in body_obligations {
+                    push_obligation(
+                        obligations,
+                        guards,
+                        format!("(forall ({variables}) {formula})"),
+                        error,
+                    );
+                }
+            }
+            Expression::LetExpr(expression) => {
+                recurse!(&expression.def);
+                let def = self.expression(&expression.def, environment)?;
+                let mut body_environment = environment.clone();
+                let variable = symbol(&expression.variable.name);
+                body_environment
+                    .variables
+                    .insert(expression.variable.name.clone(), variable.clone());
+                let mut body_obligations = Vec::new();
+                self.well_definedness(
+                    &expression.body,
+                    &body_environment,
+                    error_kind,
+                    offending,
+                    &mut Vec::new(),
+                    &mut body_obligations,
+                )?;
+                for Obligation { formula, error } in body_obligations {
+                    push_obligation(
+                        obligations,
+                        guards,
+                        format!("(let (({variable} {def})) {formula})"),
+                        error,
+                    );
+                }
+            }
+            Expression::FuncApp(expression) => {
+                for argument in &expression.arguments {
+                    recurse!(argument);
+                }
+                let Some(function) = self.functions.get(expression.function_name.as_str()) else {
+                    return Err(format!("unknown function {}", expression.function_name));
+                };
+                let mut callee = Environment::new(environment.heap.clone());
+                callee.inhale = false;
+                for (parameter, argument) in function.parameters.iter().zip(&expression.arguments) {
+                    callee.variables.insert(
+                        parameter.name.clone(),
+                        self.expression(argument, environment)?,
+                    );
+                }
+                for pre in &function.pres {
+                    for conjunct in conjuncts(pre) {
+                        let formula = self.expression(conjunct, &callee)?;
+                        if formula != "true" {
+                            push_obligation(
+                                obligations,
+                                guards,
+                                formula,
+                                verification_error(
+                                    "application.precondition:assertion.false",
+                                    format!(
+                                        "Precondition of function {} might not hold.",
+                                        function.name
+                                    ),
+                                    expression.position,
+                                    conjunct.position(),
+                                ),
+                            );
+                        }
+                    }
+                }
+            }
+            Expression::DomainFuncApp(expression) => {
+                for argument in &expression.arguments {
+                    recurse!(argument);
+                }
+            }
+            Expression::InhaleExhale(expression) => {
+                if environment.inhale {
+                    recurse!(&expression.inhale_expression);
+                } else {
+                    recurse!(&expression.exhale_expression);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Order the blocks so that every block comes after its predecessors, or
+/// return `None` if the control flow graph has a cycle.
+fn topological_order(successors: &[Vec<usize>]) -> Option<Vec<usize>> {
+    let mut in_degrees = vec![0; successors.len()];
+    for targets in successors {
+        for &target in targets {
+            in_degrees[target] += 1;
+        }
+    }
+    let mut ready: Vec<_> = (0..successors.len())
+        .rev()
+        .filter(|&index| in_degrees[index] == 0)
+        .collect();
+    let mut order = Vec::new();
+    while let Some(index) = ready.pop() {
+        order.push(index);
+        for &target in &successors[index] {
+            in_degrees[target] -= 1;
+            if in_degrees[target] == 0 {
+                ready.push(target);
+            }
+        }
+    }
+    (order.len() == successors.len()).then_some(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vir::{
+        common::check_mode::CheckMode,
+        low::{
+            ast::expression::{BinaryOpKind, ConstantValue},
+            cfg::{BasicBlock, Label},
+        },
+    };
+
+    fn int_variable(name: &str) -> VariableDecl {
+        VariableDecl::new(name, Type::Int)
+    }
+
+    fn int(value: i64) -> Expression {
+        Expression::constant_no_pos(ConstantValue::Int(value), Type::Int)
+    }
+
+    fn x() -> Expression {
+        Expression::local_no_pos(int_variable("x"))
+    }
+
+    fn program_with_procedure(basic_blocks: Vec<BasicBlock>) -> Program {
+        Program {
+            name: "test".to_string(),
+            check_mode: CheckMode::Specifications,
+            domains: Vec::new(),
+            predicates: Vec::new(),
+            functions: Vec::new(),
+            methods: Vec::new(),
+            procedures: vec![ProcedureDecl::new(
+                "procedure",
+                vec![int_variable("x")],
+                basic_blocks,
+            )],
+        }
+    }
+
+    fn checks(commands: &[Command]) -> Vec<(&str, &str)> {
+        commands
+            .iter()
+            .filter_map(|command| match command {
+                Command::Check { formula, error } => {
+                    Some((error.full_id.as_str(), formula.as_str()))
+                }
+                Command::Declare(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn assertions_are_checked_against_the_assigned_value() {
+        let assertion =
+            Expression::binary_op(BinaryOpKind::GtCmp, x(), int(0), Position::new(1, 1, 42));
+        let program = program_with_procedure(vec![BasicBlock::new(
+            Label::new("start"),
+            vec![
+                Statement::assign_no_pos(int_variable("x"), int(5)),
+                Statement::assert(assertion, Position::new(1, 1, 7)),
+            ],
+            Successor::Return,
+        )]);
+        let commands = lower_program(&program).unwrap();
+        let checks = checks(&commands);
+        assert_eq!(checks.len(), 1);
+        let (full_id, formula) = checks[0];
+        assert_eq!(full_id, "assert.failed:assertion.false");
+        assert!(formula.starts_with("(=> |$reach@"), "{formula}");
+        assert!(formula.contains("(> |x@"), "{formula}");
+        let Command::Check { error, .. } = commands
+            .iter()
+            .find(|command| matches!(command, Command::Check { .. }))
+            .unwrap() else { unreachable!() };
+        assert_eq!(error.offending_pos_id.as_deref(), Some("7"));
+        assert_eq!(error.reason_pos_id.as_deref(), Some("42"));
+        let script = to_smt_lib_script(&commands);
+        assert!(script.contains("(assert (=> |$reach@"), "{script}");
+        assert!(script.contains("(check-sat)"), "{script}");
+    }
+
+    #[test]
+    fn divisions_must_have_nonzero_divisors() {
+        let division = Expression::binary_op_no_pos(BinaryOpKind::Div, int(1), x());
+        let program = program_with_procedure(vec![BasicBlock::new(
+            Label::new("start"),
+            vec![Statement::assign_no_pos(int_variable("x"), division)],
+            Successor::Return,
+        )]);
+        let commands = lower_program(&program).unwrap();
+        let checks = checks(&commands);
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].0, "assignment.failed:division.by.zero");
+        assert!(checks[0].1.contains("(not (= |x@"), "{}", checks[0].1);
+    }
+
+    #[test]
+    fn branches_are_merged_with_path_conditions() {
+        let guard = Expression::binary_op_no_pos(BinaryOpKind::GtCmp, x(), int(0));
+        let program = program_with_procedure(vec![
+            BasicBlock::new(
+                Label::new("start"),
+                vec![],
+                Successor::GotoSwitch(vec![
+                    (guard, Label::new("then")),
+                    (
+                        Expression::constant_no_pos(ConstantValue::Bool(true), Type::Bool),
+                        Label::new("else"),
+                    ),
+                ]),
+            ),
+            BasicBlock::new(
+                Label::new("then"),
+                vec![Statement::assign_no_pos(int_variable("x"), int(1))],
+                Successor::Goto(Label::new("end")),
+            ),
+            BasicBlock::new(
+                Label::new("else"),
+                vec![Statement::assign_no_pos(int_variable("x"), int(2))],
+                Successor::Goto(Label::new("end")),
+            ),
+            BasicBlock::new(
+                Label::new("end"),
+                vec![Statement::assert_no_pos(Expression::binary_op_no_pos(
+                    BinaryOpKind::GtCmp,
+                    x(),
+                    int(0),
+                ))],
+                Successor::Return,
+            ),
+        ]);
+        let commands = lower_program(&program).unwrap();
+        let script = to_smt_lib_script(&commands);
+        // The value of `x` at the end block is selected by the incoming edge.
+        assert_eq!(script.matches("(declare-const |x@").count(), 4, "{script}");
+        assert_eq!(script.matches("(= |$reach@").count(), 4, "{script}");
+        assert!(script.contains("(or "), "{script}");
+    }
+
+    #[test]
+    fn unfolded_bodies_are_checked() {
+        let predicate = PredicateDecl::new(
+            "Positive",
+            vec![int_variable("value")],
+            Some(Expression::binary_op_no_pos(
+                BinaryOpKind::GtCmp,
+                Expression::local_no_pos(int_variable("value")),
+                int(0),
+            )),
+        );
+        let mut program = program_with_procedure(vec![BasicBlock::new(
+            Label::new("start"),
+            vec![Statement::unfold_no_pos(
+                Expression::predicate_access_predicate_no_pos(
+                    "Positive".to_string(),
+                    vec![x()],
+                    Expression::full_permission(),
+                ),
+            )],
+            Successor::Return,
+        )]);
+        program.predicates.push(predicate);
+        let commands = lower_program(&program).unwrap();
+        let checks = checks(&commands);
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].0, "unfold.failed:assertion.false");
+        assert!(checks[0].1.contains("(> |x@"), "{}", checks[0].1);
+    }
+
+    #[test]
+    fn loops_are_rejected() {
+        let program = program_with_procedure(vec![BasicBlock::new(
+            Label::new("start"),
+            vec![],
+            Successor::Goto(Label::new("start")),
+        )]);
+        let error = lower_program(&program).unwrap_err();
+        assert!(error.contains("does not support loops"), "{error}");
+    }
+}
@@ -0,0 +1,253 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! A backend that verifies `vir::low` programs by encoding them directly into
+//! SMT-LIB and running the SMT solver as a subprocess, without a JVM.
+//!
+//! The backend checks functional properties only: permissions are not
+//! tracked, so it accepts only programs verified in the `Specifications`
+//! check mode (see `VERIFY_SPECIFICATIONS_BACKEND`).
+
+mod lowering;
+
+use self::lowering::{lower_program, to_smt_lib_script, Command};
+use crate::ViperBackendConfig;
+use log::info;
+use prusti_common::{config, report::log::report, vir::program::Program, Stopwatch};
+use prusti_smt_solver::SmtSolverProcess;
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+use viper::VerificationResult;
+use vir::common::check_mode::CheckMode;
+
+pub struct SmtBackend {
+    solver_path: String,
+    solver_env: Vec<(String, String)>,
+    /// The timeout (in milliseconds) of each check; `0` disables it.
+    assert_timeout: u64,
+}
+
+impl SmtBackend {
+    /// Creates a backend that uses the `--assertTimeout` of `backend_config`,
+    /// if given.
+    pub fn new(backend_config: &ViperBackendConfig) -> Self {
+        let assert_timeout = backend_config
+            .verifier_args
+            .iter()
+            .skip_while(|arg| *arg != "--assertTimeout")
+            .nth(1)
+            .and_then(|timeout| timeout.parse().ok())
+            .unwrap_or_else(config::assert_timeout);
+        let mut solver_env = Vec::new();
+        let solver_path = if config::use_smt_wrapper() {
+            solver_env.push((
+                "PRUSTI_ORIGINAL_SMT_SOLVER_PATH".to_string(),
+                config::smt_solver_path(),
+            ));
+            if let Some(bound) = config::smt_qi_bound_global() {
+                solver_env.push(("PRUSTI_SMT_QI_BOUND_GLOBAL".to_string(), bound.to_string()));
+            }
+            config::smt_solver_wrapper_path()
+        } else {
+            config::smt_solver_path()
+        };
+        Self {
+            solver_path,
+            solver_env,
+            assert_timeout,
+        }
+    }
+
+    /// Verifies the program. If `timeout` (in seconds) is given and the
+    /// solver is still busy after that time, the result is `TimedOut`.
+    pub fn verify(&mut self, program: &Program, timeout: Option<u64>) -> VerificationResult {
+        let Program::Low(program) = program else {
+            return VerificationResult::ConsistencyErrors(vec![
+                "the SMT backend supports only programs of the core proof encoding".to_string(),
+            ]);
+        };
+        if program.check_mode != CheckMode::Specifications {
+            return VerificationResult::ConsistencyErrors(vec![format!(
+                "the SMT backend supports only the {} check mode, not {}",
+                CheckMode::Specifications,
+                program.check_mode
+            )]);
+        }
+        let mut stopwatch = Stopwatch::start("prusti-server backend", "SMT-LIB encoding");
+        let commands = match lower_program(program) {
+            Ok(commands) => commands,
+            Err(error) => return VerificationResult::ConsistencyErrors(vec![error]),
+        };
+        if config::dump_viper_program() {
+            stopwatch.start_next("dumping SMT-LIB program");
+            let namespace = "smt_program";
+            let filename = format!("{}-{}.smt2", program.name, program.check_mode);
+            info!("Dumping SMT-LIB program to '{}/{}'", namespace, filename);
+            report(namespace, filename, to_smt_lib_script(&commands));
+        }
+        stopwatch.start_next("SMT verification");
+        let deadline =
+            timeout.map(|timeout_secs| Instant::now() + Duration::from_secs(timeout_secs));
+        match self.run(&commands, deadline) {
+            Ok(Some(errors)) if errors.is_empty() => VerificationResult::Success,
+            Ok(Some(errors)) => VerificationResult::Failure(errors),
+            Ok(None) => VerificationResult::TimedOut {
+                timeout_secs: timeout.unwrap(),
+            },
+            Err(error) => VerificationResult::BackendFailure(format!(
+                "failed to run the SMT solver '{}': {}",
+                self.solver_path, error
+            )),
+        }
+    }
+
+    /// Returns the failed checks, or `None` if the deadline passed before all
+    /// checks were done.
+    fn run(
+        &self,
+        commands: &[Command],
+        deadline: Option<Instant>,
+    ) -> io::Result<Option<Vec<viper::VerificationError>>> {
+        let mut solver = SmtSolverProcess::start(&self.solver_path, self.solver_env.clone())?;
+        if self.assert_timeout > 0 {
+            solver.execute(&format!("(set-option :timeout {})", self.assert_timeout))?;
+        }
+        let mut errors = Vec::new();
+        for command in commands {
+            if let (Some(deadline), Command::Check { .. }) = (deadline, command) {
+                // Limit each check to the remaining time, so that the solver
+                // gives up in time.
+                let remaining = deadline
+                    .saturating_duration_since(Instant::now())
+                    .as_millis();
+                if remaining == 0 {
+                    return Ok(None);
+                }
+                let check_timeout = match self.assert_timeout {
+                    0 => remaining,
+                    assert_timeout => remaining.min(assert_timeout.into()),
+                };
+                solver.execute(&format!("(set-option :timeout {check_timeout})"))?;
+            }
+            let mut responses = Vec::new();
+            for line in command.to_smt_lib() {
+                responses.push(solver.execute(&line)?);
+            }
+            match command {
+                Command::Declare(_) => {}
+                Command::Check { error, .. } => {
+                    // The responses are `success`, `success`, the result of
+                    // `check-sat` and `success`.
+                    if responses[2] != "unsat" {
+                        if deadline.map_or(false, |deadline| Instant::now() >= deadline) {
+                            return Ok(None);
+                        }
+                        errors.push((**error).clone());
+                    }
+                }
+            }
+        }
+        Ok(Some(errors))
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::{fs, os::unix::fs::PermissionsExt, path::PathBuf};
+    use vir::{
+        common::{check_mode::CheckMode, position::Position},
+        low::{
+            ast::{
+                expression::{BinaryOpKind, ConstantValue, Expression},
+                statement::Statement,
+                ty::Type,
+                variable::VariableDecl,
+            },
+            cfg::{BasicBlock, Label, ProcedureDecl, Successor},
+        },
+    };
+
+    /// A fake solver that acknowledges every command and needs two seconds to
+    /// give up on each `check-sat`.
+    fn slow_solver() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("prusti-smt-backend-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("slow-solver.sh");
+        fs::write(
+            &path,
+            "#!/bin/sh\n\
+            while read line; do\n\
+                case \"$line\" in\n\
+                    *check-sat*) sleep 2; echo unknown ;;\n\
+                    *) echo success ;;\n\
+                esac\n\
+            done\n",
+        )
+        .unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    fn program_with_assertion() -> Program {
+        let x = VariableDecl::new("x", Type::Int);
+        let assertion = Expression::binary_op(
+            BinaryOpKind::GtCmp,
+            Expression::local_no_pos(x.clone()),
+            Expression::constant_no_pos(ConstantValue::Int(0), Type::Int),
+            Position::new(1, 1, 42),
+        );
+        Program::Low(vir::low::Program {
+            name: "test".to_string(),
+            check_mode: CheckMode::Specifications,
+            domains: Vec::new(),
+            predicates: Vec::new(),
+            functions: Vec::new(),
+            methods: Vec::new(),
+            procedures: vec![ProcedureDecl::new(
+                "procedure",
+                vec![x],
+                vec![BasicBlock::new(
+                    Label::new("start"),
+                    vec![Statement::assert(assertion, Position::new(1, 1, 7))],
+                    Successor::Return,
+                )],
+            )],
+        })
+    }
+
+    #[test]
+    fn slow_checks_time_out() {
+        let mut backend = SmtBackend {
+            solver_path: slow_solver().to_str().unwrap().to_string(),
+            solver_env: Vec::new(),
+            assert_timeout: 0,
+        };
+        assert!(matches!(
+            backend.verify(&program_with_assertion(), None),
+            VerificationResult::Failure(_)
+        ));
+        assert_eq!(
+            backend.verify(&program_with_assertion(), Some(1)),
+            VerificationResult::TimedOut { timeout_secs: 1 }
+        );
+    }
+
+    #[test]
+    fn missing_solver_is_a_backend_failure() {
+        let mut backend = SmtBackend {
+            solver_path: "/nonexistent/z3".to_string(),
+            solver_env: Vec::new(),
+            assert_timeout: 0,
+        };
+        assert!(matches!(
+            backend.verify(&program_with_assertion(), None),
+            VerificationResult::BackendFailure(_)
+        ));
+    }
+}
@@ -26,10 +26,24 @@ impl VerificationRequest {
 /// The configuration for the viper backend, (i.e. verifier).
 /// Expresses which backend (silicon or carbon) should be used, and provides command-line arguments
 /// to the viper verifier.
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Eq, PartialEq, Hash)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Eq, PartialEq)]
 pub struct ViperBackendConfig {
     pub backend: VerificationBackend,
     pub verifier_args: Vec<String>,
+    /// The wall-clock budget (in seconds) of the verification, after which
+    /// the backend is stopped and the result is `TimedOut`.
+    #[serde(default)]
+    pub timeout: Option<u64>,
+}
+
+// The timeout is not part of the hash, because it does not change the result
+// of a verification that finishes in time, and timed out results are not
+// cached.
+impl Hash for ViperBackendConfig {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.backend.hash(state);
+        self.verifier_args.hash(state);
+    }
 }
 
 impl ViperBackendConfig {
@@ -72,10 +86,17 @@ impl ViperBackendConfig {
             VerificationBackend::Carbon => {
                 verifier_args.extend(vec!["--disableAllocEncoding".to_string()]);
             }
+            VerificationBackend::Smt => {
+                verifier_args.extend(vec![
+                    "--assertTimeout".to_string(),
+                    config::assert_timeout().to_string(),
+                ]);
+            }
         }
         Self {
             backend,
             verifier_args,
+            timeout: config::verification_timeout(),
         }
     }
 }
@@ -0,0 +1,273 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! The workers that verify the jobs of the server's `JobQueue`.
+//!
+//! By default, a single worker thread verifies the jobs inside the server
+//! process. If `SERVER_MAX_CONCURRENCY` is larger than one, the server instead
+//! supervises a pool of worker processes. Each worker process is itself a
+//! `prusti-server-driver` with its own JVM, so that several requests can be
+//! verified at the same time even though Viper is not thread safe. The pool
+//! shares the verification cache of the server: the cache is consulted before
+//! a job is dispatched to a worker process and updated with its result.
+
+use crate::{process_cancellable_verification_request, CancellationToken, JobQueue, PrustiClient};
+use log::{debug, error, info, warn};
+use once_cell::sync::Lazy;
+use prusti_common::{config, vir::program_normalization::NormalizationInfo, Stopwatch};
+use prusti_utils::launch;
+use std::{
+    io::{self, BufRead, BufReader},
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process::{Child, Command, Stdio},
+    sync::{Arc, Mutex},
+    thread,
+};
+use tokio::runtime::{Builder, Runtime};
+use viper::{Cache, PersistentCache, VerificationResult, Viper};
+
+pub(crate) fn spawn_verification_workers(queue: Arc<JobQueue>, cache: Arc<Mutex<PersistentCache>>) {
+    let pool_size = config::server_max_concurrency().unwrap_or(1);
+    if pool_size > 1 {
+        let driver_path = worker_driver_path();
+        if driver_path.is_file() {
+            info!("Starting a pool of {} verification workers", pool_size);
+            for index in 0..pool_size {
+                spawn_process_worker(index, driver_path.clone(), queue.clone(), cache.clone());
+            }
+            return;
+        }
+        warn!(
+            "Cannot start a pool of verification workers because {} does not exist; \
+            verifying one request at a time instead",
+            driver_path.display()
+        );
+    }
+    spawn_thread_worker(queue, cache);
+}
+
+fn worker_driver_path() -> PathBuf {
+    let mut path = launch::get_current_executable_dir().join("prusti-server-driver");
+    if cfg!(windows) {
+        path.set_extension("exe");
+    }
+    path
+}
+
+/// Verifies the jobs one at a time inside the server process, because
+/// 1. Viper is not thread safe yet (Silicon issue #578), and
+/// 2. By default Silicon already uses as many cores as possible.
+fn spawn_thread_worker(queue: Arc<JobQueue>, cache: Arc<Mutex<PersistentCache>>) {
+    thread::Builder::new()
+        .name("prusti-server-worker".to_string())
+        .spawn(move || {
+            let stopwatch = Stopwatch::start("prusti-server", "JVM startup");
+            let viper =
+                Lazy::new(|| Viper::new_with_args(&config::viper_home(), config::extra_jvm_args()));
+            let viper_thread = Lazy::new(|| viper.attach_current_thread());
+            stopwatch.finish();
+            loop {
+                let (id, request, cancellation) = queue.next_job();
+                info!(
+                    "Verifying job {} for program {}",
+                    id,
+                    request.program.get_name()
+                );
+                // A panic fails only the job that caused it, like a panic in
+                // a request handler fails only that request.
+                let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                    process_cancellable_verification_request(
+                        &viper_thread,
+                        request,
+                        &cache,
+                        &cancellation,
+                    )
+                }))
+                .map_err(|payload| {
+                    payload
+                        .downcast_ref::<&str>()
+                        .map(|message| message.to_string())
+                        .or_else(|| payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_else(|| "unknown panic".to_string())
+                });
+                queue.finish_job(id, result);
+            }
+        })
+        .expect("failed to spawn the verification worker");
+}
+
+/// Forwards the jobs, one at a time, to a worker process, unless their result
+/// is in the cache of the server. The process is restarted when it crashes,
+/// when it reports a Java exception (which might leave its JVM in a bad state)
+/// and when its job is cancelled.
+fn spawn_process_worker(
+    index: usize,
+    driver_path: PathBuf,
+    queue: Arc<JobQueue>,
+    cache: Arc<Mutex<PersistentCache>>,
+) {
+    thread::Builder::new()
+        .name(format!("prusti-server-worker-{index}"))
+        .spawn(move || {
+            let runtime = Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .expect("failed to construct Tokio runtime");
+            let mut worker: Option<WorkerProcess> = None;
+            loop {
+                let (id, mut request, cancellation) = queue.next_job();
+                // Normalize the request before reaching the cache, as
+                // `process_verification_request` does. The worker verifies the
+                // normalized request, so its result can be cached as is.
+                let normalization_info = NormalizationInfo::normalize_program(&mut request.program);
+                let use_cache = config::enable_cache() && !config::print_hash();
+                let hash = request.get_hash();
+                if use_cache {
+                    if let Some(mut result) = (&cache).get(hash) {
+                        info!(
+                            "Using cached result {:?} for job {} for program {}",
+                            &result,
+                            id,
+                            request.program.get_name()
+                        );
+                        normalization_info.denormalize_result(&mut result);
+                        queue.finish_job(id, Ok(result));
+                        continue;
+                    }
+                }
+                if worker.is_none() {
+                    worker = WorkerProcess::start(index, &driver_path)
+                        .map_err(|err| error!("Failed to start verification worker {index}: {err}"))
+                        .ok();
+                }
+                let Some(running_worker) = &worker else {
+                    queue.finish_job(
+                        id,
+                        Err(format!("verification worker {index} could not be started")),
+                    );
+                    continue;
+                };
+                info!(
+                    "Verifying job {} for program {} in worker {}",
+                    id,
+                    request.program.get_name(),
+                    index
+                );
+                let result = running_worker.verify(&runtime, request, &cancellation);
+                let needs_restart = cancellation.is_cancelled()
+                    || !matches!(
+                        result,
+                        Ok(VerificationResult::Success
+                            | VerificationResult::Failure(_)
+                            | VerificationResult::ConsistencyErrors(_)
+                            | VerificationResult::BackendFailure(_)
+                            | VerificationResult::TimedOut { .. })
+                    );
+                if needs_restart {
+                    info!("Restarting verification worker {}", index);
+                    worker = None;
+                }
+                // Like `process_verification_request`, don't cache Java
+                // exceptions, backend failures nor timeouts.
+                let result = result.map(|mut result| {
+                    if use_cache
+                        && !cancellation.is_cancelled()
+                        && !matches!(
+                            result,
+                            VerificationResult::JavaException(_)
+                                | VerificationResult::BackendFailure(_)
+                                | VerificationResult::TimedOut { .. }
+                        )
+                    {
+                        (&cache).insert(hash, result.clone());
+                    }
+                    normalization_info.denormalize_result(&mut result);
+                    result
+                });
+                queue.finish_job(id, result);
+            }
+        })
+        .expect("failed to spawn the verification worker");
+}
+
+struct WorkerProcess {
+    index: usize,
+    process: Arc<Mutex<Child>>,
+    client: PrustiClient,
+}
+
+impl WorkerProcess {
+    fn start(index: usize, driver_path: &Path) -> io::Result<Self> {
+        let mut process = Command::new(driver_path)
+            .arg("--port")
+            .arg("0")
+            .arg("--worker")
+            // A worker must not start a pool of its own.
+            .env("PRUSTI_SERVER_MAX_CONCURRENCY", "1")
+            // The server keeps the persistent cache; the workers would
+            // overwrite each other's cache files.
+            .env("PRUSTI_CACHE_PATH", "")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let mut stdout = BufReader::new(process.stdout.take().unwrap());
+        let mut line = String::new();
+        stdout.read_line(&mut line)?;
+        let Some(port) = line.trim().strip_prefix("port: ") else {
+            let _ = process.kill();
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unexpected output of the worker: {line:?}"),
+            ));
+        };
+        let client = PrustiClient::new(format!("127.0.0.1:{port}"))
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        // Keep draining the output, so that the worker never blocks on a full
+        // pipe.
+        thread::spawn(move || {
+            for line in stdout.lines().map_while(Result::ok) {
+                debug!("worker {}: {}", index, line);
+            }
+        });
+        info!(
+            "Started verification worker {} (pid {}) on port {}",
+            index,
+            process.id(),
+            port
+        );
+        Ok(Self {
+            index,
+            process: Arc::new(Mutex::new(process)),
+            client,
+        })
+    }
+
+    fn verify(
+        &self,
+        runtime: &Runtime,
+        request: crate::VerificationRequest,
+        cancellation: &CancellationToken,
+    ) -> Result<VerificationResult, String> {
+        // Killing the worker is the only way to abort its verification; the
+        // worker is restarted for the next job.
+        let process = self.process.clone();
+        cancellation.on_cancel(move || {
+            let _ = process.lock().unwrap().kill();
+        });
+        let result = runtime.block_on(self.client.verify(request));
+        cancellation.clear_on_cancel();
+        result.map_err(|err| format!("verification worker {} failed: {err}", self.index))
+    }
+}
+
+impl Drop for WorkerProcess {
+    fn drop(&mut self) {
+        let mut process = self.process.lock().unwrap();
+        let _ = process.kill();
+        let _ = process.wait();
+    }
+}
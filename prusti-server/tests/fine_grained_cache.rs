@@ -0,0 +1,176 @@
+#![cfg(unix)]
+
+use once_cell::sync::Lazy;
+use prusti_common::vir::program::Program;
+use prusti_server::{process_verification_request, VerificationRequest, ViperBackendConfig};
+use rustc_hash::FxHashMap;
+use std::{env, fs, os::unix::fs::PermissionsExt, path::PathBuf};
+use viper::{Cache, VerificationBackend, VerificationContext, VerificationResult};
+use vir::{
+    common::{check_mode::CheckMode, position::Position},
+    low::{self as vir_low, macros::expr},
+};
+
+/// An in-memory cache that counts how many results were stored.
+#[derive(Default)]
+struct CountingCache {
+    data: FxHashMap<u64, VerificationResult>,
+    inserts: usize,
+}
+
+impl Cache for &mut CountingCache {
+    fn get(&self, request: u64) -> Option<VerificationResult> {
+        self.data.get(&request).cloned()
+    }
+    fn insert(&mut self, request: u64, result: VerificationResult) -> Option<VerificationResult> {
+        self.inserts += 1;
+        self.data.insert(request, result)
+    }
+}
+
+/// A fake solver that acknowledges every command and refutes exactly the
+/// checks that mention the function `unrelated`. Each start of the solver is
+/// recorded in the returned file.
+fn stub_solver() -> (PathBuf, PathBuf) {
+    let dir = env::temp_dir().join(format!("prusti-fine-grained-cache-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let runs = dir.join("runs");
+    let _ = fs::remove_file(&runs);
+    let path = dir.join("stub-solver.sh");
+    fs::write(
+        &path,
+        format!(
+            "#!/bin/sh\n\
+            echo run >> '{}'\n\
+            failing=false\n\
+            while read line; do\n\
+                case \"$line\" in\n\
+                    *check-sat*) if [ $failing = true ]; then echo sat; else echo unsat; fi ;;\n\
+                    *'(assert (not'*'|unrelated|'*) failing=true; echo success ;;\n\
+                    *'(assert (not'*) failing=false; echo success ;;\n\
+                    *) echo success ;;\n\
+                esac\n\
+            done\n",
+            runs.display()
+        ),
+    )
+    .unwrap();
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    (path, runs)
+}
+
+/// test that changing a function only re-verifies the methods that depend on
+/// it, and that all uncached methods are verified by a single solver run
+///
+/// The configuration is read from the environment once per process, so this
+/// file must not contain other tests.
+#[test]
+fn unrelated_change_keeps_cached_cone() {
+    let (solver_path, runs) = stub_solver();
+    env::set_var("PRUSTI_FINE_GRAINED_CACHE", "true");
+    env::set_var("PRUSTI_ENABLE_CACHE", "true");
+    env::set_var("PRUSTI_SMT_SOLVER_PATH", &solver_path);
+    env::set_var("PRUSTI_USE_SMT_WRAPPER", "false");
+    let verification_context: Lazy<VerificationContext, _> =
+        Lazy::new(|| unreachable!("the SMT backend does not need a JVM"));
+    let mut cache = CountingCache::default();
+    let solver_runs = || {
+        fs::read_to_string(&runs)
+            .unwrap_or_default()
+            .lines()
+            .count()
+    };
+
+    let result = verify(&verification_context, &mut cache, expr!(true));
+    assert_failure_of_unrelated_method(&result);
+    assert_eq!(
+        solver_runs(),
+        1,
+        "the uncached methods are verified at once"
+    );
+    assert_eq!(cache.inserts, 2, "the result of each method is cached");
+
+    let result = verify(&verification_context, &mut cache, expr!(true));
+    assert_failure_of_unrelated_method(&result);
+    assert_eq!(solver_runs(), 1, "an unchanged program is not re-verified");
+    assert_eq!(cache.inserts, 2);
+
+    let result = verify(&verification_context, &mut cache, expr!(false));
+    assert_failure_of_unrelated_method(&result);
+    assert_eq!(solver_runs(), 2);
+    assert_eq!(
+        cache.inserts, 3,
+        "only the method that depends on the changed function is re-verified"
+    );
+}
+
+fn verify(
+    verification_context: &Lazy<VerificationContext, impl Fn() -> VerificationContext>,
+    cache: &mut CountingCache,
+    unrelated_body: vir_low::Expression,
+) -> VerificationResult {
+    process_verification_request(verification_context, request(unrelated_body), &mut *cache)
+}
+
+fn assert_failure_of_unrelated_method(result: &VerificationResult) {
+    let VerificationResult::Failure(errors) = result else {
+        panic!("expected a verification failure, found {result:?}");
+    };
+    assert_eq!(errors.len(), 1, "unexpected errors: {errors:?}");
+    assert_eq!(
+        errors[0].offending_pos_id.as_deref(),
+        Some(UNRELATED_ASSERTION_ID.to_string().as_str())
+    );
+}
+
+const USED_ASSERTION_ID: u64 = 1;
+const UNRELATED_ASSERTION_ID: u64 = 2;
+
+/// A program with two methods, each of which asserts a different function.
+fn request(unrelated_body: vir_low::Expression) -> VerificationRequest {
+    VerificationRequest {
+        program: Program::Low(vir_low::Program {
+            name: "cache_test".to_string(),
+            check_mode: CheckMode::Specifications,
+            domains: Vec::new(),
+            predicates: Vec::new(),
+            functions: vec![
+                function("used", expr!(true)),
+                function("unrelated", unrelated_body),
+            ],
+            methods: vec![
+                method("m_used", "used", USED_ASSERTION_ID),
+                method("m_unrelated", "unrelated", UNRELATED_ASSERTION_ID),
+            ],
+            procedures: Vec::new(),
+        }),
+        backend_config: ViperBackendConfig::new(VerificationBackend::Smt),
+    }
+}
+
+fn function(name: &str, body: vir_low::Expression) -> vir_low::FunctionDecl {
+    vir_low::FunctionDecl::new(
+        name.to_string(),
+        vir_low::FunctionKind::CallerFor,
+        Vec::new(),
+        vir_low::Type::Bool,
+        Vec::new(),
+        Vec::new(),
+        Some(body),
+    )
+}
+
+fn method(name: &str, callee: &str, assertion_id: u64) -> vir_low::MethodDecl {
+    vir_low::MethodDecl::new(
+        name.to_string(),
+        vir_low::MethodKind::MirOperation,
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+        Some(vec![vir_low::Statement::assert(
+            vir_low::Expression::function_call(callee, Vec::new(), vir_low::Type::Bool),
+            Position::new(1, 1, assertion_id),
+        )]),
+    )
+}
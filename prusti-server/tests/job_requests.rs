@@ -0,0 +1,79 @@
+use lazy_static::lazy_static;
+use prusti_common::vir::program::Program;
+use prusti_server::{
+    spawn_server_thread, tokio::runtime::Builder, JobId, JobStatus, PrustiClient,
+    VerificationRequest, ViperBackendConfig,
+};
+use viper::{VerificationBackend, VerificationResult};
+use vir::common::check_mode::CheckMode;
+
+lazy_static! {
+    // only start the server once
+    static ref SERVER_ADDRESS: String = {
+        // Make the SMT backend fail deterministically, without starting a JVM.
+        std::env::set_var("PRUSTI_SMT_SOLVER_PATH", "/nonexistent/z3");
+        std::env::set_var("PRUSTI_USE_SMT_WRAPPER", "false");
+        spawn_server_thread().to_string()
+    };
+}
+
+#[test]
+fn submit_and_poll() {
+    block_on(async {
+        let client = client();
+        let id = client.submit(empty_request()).await.unwrap();
+        loop {
+            match client.poll(id).await.unwrap() {
+                Some(JobStatus::Queued { .. } | JobStatus::Running) => {
+                    std::thread::sleep(std::time::Duration::from_millis(10))
+                }
+                Some(JobStatus::Finished(result)) => {
+                    assert!(matches!(result, VerificationResult::BackendFailure(_)));
+                    break;
+                }
+                other => panic!("unexpected status of job {id}: {other:?}"),
+            }
+        }
+        // The result of a finished job is collected only once.
+        assert!(client.poll(id).await.unwrap().is_none());
+    });
+}
+
+#[test]
+fn unknown_jobs() {
+    block_on(async {
+        let client = client();
+        let unknown = JobId(u64::MAX);
+        assert!(client.poll(unknown).await.unwrap().is_none());
+        assert!(!client.cancel(unknown).await.unwrap());
+        let status = client.status().await.unwrap();
+        assert!(!status.running.contains(&unknown));
+    });
+}
+
+fn client() -> PrustiClient {
+    PrustiClient::new(SERVER_ADDRESS.clone()).expect("Could not connect to server!")
+}
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("failed to construct Tokio runtime")
+        .block_on(future)
+}
+
+fn empty_request() -> VerificationRequest {
+    VerificationRequest {
+        program: Program::Low(vir::low::Program {
+            name: "empty".to_string(),
+            check_mode: CheckMode::Specifications,
+            domains: Vec::new(),
+            predicates: Vec::new(),
+            functions: Vec::new(),
+            methods: Vec::new(),
+            procedures: Vec::new(),
+        }),
+        backend_config: ViperBackendConfig::new(VerificationBackend::Smt),
+    }
+}
@@ -0,0 +1,62 @@
+use prusti_common::vir::*;
+use prusti_server::{load_verification_request, VerificationRequest, ViperBackendConfig};
+use std::{fs, path::PathBuf};
+
+#[test]
+fn load_json_request() {
+    let request = dummy_request();
+    let path = temp_file("request.json");
+    fs::write(&path, serde_json::to_vec(&request).unwrap()).unwrap();
+    check_loaded(&request, load_verification_request(&path).unwrap());
+}
+
+#[test]
+fn load_bincode_request() {
+    let request = dummy_request();
+    let path = temp_file("request.bin");
+    fs::write(&path, bincode::serialize(&request).unwrap()).unwrap();
+    check_loaded(&request, load_verification_request(&path).unwrap());
+}
+
+#[test]
+fn load_malformed_request() {
+    let path = temp_file("malformed.json");
+    fs::write(&path, "{ \"program\": 42 }").unwrap();
+    let error = load_verification_request(&path).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
+
+fn check_loaded(expected: &VerificationRequest, loaded: VerificationRequest) {
+    assert_eq!(loaded.program.get_name(), expected.program.get_name());
+    assert_eq!(loaded.backend_config, expected.backend_config);
+    assert_eq!(
+        bincode::serialize(&loaded).unwrap(),
+        bincode::serialize(expected).unwrap()
+    );
+}
+
+fn temp_file(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("prusti-server-replay-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir.join(name)
+}
+
+fn dummy_request() -> VerificationRequest {
+    let program = Program {
+        name: "dummy".to_string(),
+        backend_types: vec![],
+        domains: vec![],
+        fields: vec![Field {
+            name: "f$value".to_string(),
+            typ: Type::Int,
+        }],
+        builtin_methods: vec![],
+        methods: vec![],
+        functions: vec![],
+        viper_predicates: vec![],
+    };
+    VerificationRequest {
+        program: prusti_common::vir::program::Program::Legacy(program),
+        backend_config: ViperBackendConfig::new("Silicon".parse().unwrap()),
+    }
+}
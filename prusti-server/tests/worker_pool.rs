@@ -0,0 +1,88 @@
+use prusti_common::vir::program::Program;
+use prusti_server::{
+    tokio::runtime::Builder, JobStatus, PrustiClient, VerificationRequest, ViperBackendConfig,
+};
+use std::{
+    env,
+    io::{BufRead, BufReader},
+    process::{Command, Stdio},
+    thread,
+    time::Duration,
+};
+use viper::{VerificationBackend, VerificationResult};
+use vir::common::check_mode::CheckMode;
+
+/// test that a server with a pool of workers verifies all submitted jobs
+#[test]
+fn pool_verifies_jobs() {
+    let server_path = env::current_exe()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .with_file_name("prusti-server-driver")
+        .with_extension(env::consts::EXE_EXTENSION);
+
+    let mut server = Command::new(server_path)
+        .arg("--port")
+        .arg("0")
+        .env("PRUSTI_SERVER_MAX_CONCURRENCY", "2")
+        // Make the SMT backend fail deterministically, without starting a JVM.
+        .env("PRUSTI_SMT_SOLVER_PATH", "/nonexistent/z3")
+        .env("PRUSTI_USE_SMT_WRAPPER", "false")
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to run prusti-server");
+    let mut line = String::new();
+    BufReader::new(server.stdout.take().unwrap())
+        .read_line(&mut line)
+        .unwrap();
+    let port = line
+        .trim()
+        .strip_prefix("port: ")
+        .expect("port message as first stdout line");
+    let client = PrustiClient::new(format!("127.0.0.1:{port}")).unwrap();
+
+    let runtime = Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("failed to construct Tokio runtime");
+    let ids: Vec<_> = (0..4)
+        .map(|index| {
+            runtime
+                .block_on(client.submit(empty_request(index)))
+                .unwrap()
+        })
+        .collect();
+    for id in ids {
+        loop {
+            match runtime.block_on(client.poll(id)).unwrap() {
+                Some(JobStatus::Queued { .. } | JobStatus::Running) => {
+                    thread::sleep(Duration::from_millis(50))
+                }
+                Some(JobStatus::Finished(result)) => {
+                    assert!(matches!(result, VerificationResult::BackendFailure(_)));
+                    break;
+                }
+                other => panic!("unexpected status of job {id}: {other:?}"),
+            }
+        }
+    }
+
+    server.kill().expect("server stopped prematurely");
+    server.wait().unwrap();
+}
+
+fn empty_request(index: usize) -> VerificationRequest {
+    VerificationRequest {
+        program: Program::Low(vir::low::Program {
+            name: format!("empty{index}"),
+            check_mode: CheckMode::Specifications,
+            domains: Vec::new(),
+            predicates: Vec::new(),
+            functions: Vec::new(),
+            methods: Vec::new(),
+            procedures: Vec::new(),
+        }),
+        backend_config: ViperBackendConfig::new(VerificationBackend::Smt),
+    }
+}
@@ -0,0 +1,97 @@
+//! Helpers for talking to an SMT solver that runs as a subprocess.
+//!
+//! The `prusti-smt-solver` binary wraps the solver used by Viper; the
+//! synchronous [`SmtSolverProcess`] is used by backends that generate SMT-LIB
+//! themselves.
+
+use std::{
+    ffi::OsStr,
+    io::{self, BufRead, BufReader, Write},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+};
+
+/// Whether `line` contains a complete SMT-LIB command or response.
+pub fn are_parens_balanced(line: &str) -> bool {
+    let openning_parens = line.chars().filter(|c| *c == '(').count();
+    let closing_parens = line.chars().filter(|c| *c == ')').count();
+    openning_parens == closing_parens
+}
+
+/// An SMT solver that reads SMT-LIB commands from its standard input.
+///
+/// The solver is configured to acknowledge every command with `success`, so
+/// that each command has exactly one response. The process is killed when
+/// this value is dropped.
+pub struct SmtSolverProcess {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl SmtSolverProcess {
+    /// Start `solver_path` in interactive SMT-LIB mode. `envs` are set only
+    /// for the spawned process.
+    pub fn start<K, V>(
+        solver_path: impl AsRef<OsStr>,
+        envs: impl IntoIterator<Item = (K, V)>,
+    ) -> io::Result<Self>
+    where
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+    {
+        let mut child = Command::new(solver_path)
+            .args(["-smt2", "-in"])
+            .envs(envs)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let stdin = child.stdin.take().expect("failed to create stdin pipe");
+        let stdout = child.stdout.take().expect("failed to create stdout pipe");
+        let mut solver = Self {
+            child,
+            stdin,
+            stdout: BufReader::new(stdout),
+        };
+        solver.execute("(set-option :print-success true)")?;
+        Ok(solver)
+    }
+
+    /// Send a single command to the solver and return its trimmed response.
+    /// An `(error ...)` response is turned into an error.
+    pub fn execute(&mut self, command: &str) -> io::Result<String> {
+        debug_assert!(
+            are_parens_balanced(command),
+            "unbalanced command: {command}"
+        );
+        writeln!(self.stdin, "{command}")?;
+        self.stdin.flush()?;
+        let mut response = String::new();
+        loop {
+            if self.stdout.read_line(&mut response)? == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!("the SMT solver exited while executing {command}"),
+                ));
+            }
+            if are_parens_balanced(&response) && !response.trim().is_empty() {
+                break;
+            }
+        }
+        let response = response.trim();
+        if response.starts_with("(error") {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                format!("the SMT solver rejected {command}: {response}"),
+            ));
+        }
+        Ok(response.to_string())
+    }
+}
+
+impl Drop for SmtSolverProcess {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
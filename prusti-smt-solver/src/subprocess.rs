@@ -3,12 +3,7 @@ use async_std::{
     io::{prelude::BufReadExt, BufReader, WriteExt},
     process::{ChildStderr, ChildStdin, ChildStdout},
 };
-
-fn are_parens_balanced(line: &str) -> bool {
-    let openning_parens = line.chars().filter(|c| *c == '(').count();
-    let closing_parens = line.chars().filter(|c| *c == ')').count();
-    openning_parens == closing_parens
-}
+use prusti_smt_solver::are_parens_balanced;
 
 async fn read_command(command: &mut String) -> Result<bool, std::io::Error> {
     command.clear();
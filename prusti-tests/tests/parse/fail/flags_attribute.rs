@@ -0,0 +1,13 @@
+use prusti_contracts::*;
+
+#[flags(check_panics = "no")] //~ ERROR the value of a flag must be a boolean or an integer
+fn string_value(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+#[flags()] //~ ERROR the `#[flags]` attribute expects `flag = value` pairs
+fn no_flags(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use prusti_contracts::*;
+
+#[pure]
+type PureFn = fn(x: u32) -> u32; //~ ERROR only `#[requires]` and `#[ensures]` can be attached to function pointer types
+
+#[requires(x > 0)]
+type UnnamedFn = fn(u32) -> u32; //~ ERROR the parameters of a function pointer type with a specification need to be named
+
+#[requires(true)]
+type GenericFn<T> = fn(x: T) -> T; //~ ERROR function pointer types with specifications cannot be generic
+
+#[requires(true)]
+type NotAFn = u32; //~ ERROR specifications can only be attached to type aliases of function pointer types
+
+fn main() {}
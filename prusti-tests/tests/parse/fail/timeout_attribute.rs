@@ -0,0 +1,13 @@
+use prusti_contracts::*;
+
+#[timeout(0)] //~ ERROR the timeout must be at least one second
+fn zero(x: i32) -> i32 {
+    x
+}
+
+#[timeout(slow)] //~ ERROR the `#[timeout]` attribute expects the number of seconds
+fn not_a_number(x: i32) -> i32 {
+    x
+}
+
+fn main() {}
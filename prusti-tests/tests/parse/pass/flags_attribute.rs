@@ -0,0 +1,26 @@
+use prusti_contracts::*;
+
+#[flags(encode_bitvectors = true, smt_qi_eager_threshold = 100)]
+fn unchecked_add(a: u32, b: u32) -> u32 {
+    a + b
+}
+
+struct Counter {
+    value: u32,
+}
+
+#[flags(assert_timeout = 30000)]
+impl Counter {
+    fn increment(&mut self) {
+        self.value += 1;
+    }
+}
+
+#[flags(check_panics = false)]
+mod unchecked {
+    pub fn first(v: &[i32]) -> i32 {
+        v[0]
+    }
+}
+
+fn main() {}
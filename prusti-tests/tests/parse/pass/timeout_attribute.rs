@@ -0,0 +1,16 @@
+use prusti_contracts::*;
+
+#[timeout(30)]
+#[requires(x > 0)]
+#[ensures(result > x)]
+fn slow_proof(x: u32) -> u32 {
+    x + 1
+}
+
+#[ensures(result == x)]
+#[timeout(5)]
+fn quick_proof(x: i32) -> i32 {
+    x
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use prusti_contracts::*;
+
+#[lemma(dont write things here)]
+#[terminates]
+fn with_parameters() {}
+
+#[lemma]
+fn without_terminates() {}
+
+#[lemma]
+#[pure]
+#[terminates]
+fn pure_lemma() -> bool {
+    true
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use prusti_contracts::*;
+
+fn main() {}
+
+#[requires(bytes.len() >= 2)]
+fn wrong_rest(bytes: &[u8]) {
+    match bytes {
+        [_, rest @ ..] => {
+            assert!(rest.len() == bytes.len() - 1);
+            assert!(rest[0] == bytes[0]); //~ ERROR the asserted expression might not hold
+        }
+        [] => unreachable!(),
+    }
+}
+
+#[requires(lo <= hi)]
+fn range_inclusive_out_of_bounds(a: &[i32], lo: usize, hi: usize) {
+    let s = &a[lo..=hi]; //~ ERROR the range end value may be out of bounds when slicing
+}
+
+#[requires(hi < a.len())]
+fn range_inclusive_reversed(a: &[i32], lo: usize, hi: usize) {
+    let s = &a[lo..=hi]; //~ ERROR the range end may be smaller than the start when slicing
+}
@@ -0,0 +1,37 @@
+use prusti_contracts::*;
+
+#[requires(x < 100)]
+#[ensures(result == x + 1)]
+async fn inc(x: u32) -> u32 {
+    x + 1
+}
+
+#[requires(x < 100)]
+#[ensures(result == x)] //~ ERROR postcondition might not hold
+async fn wrong_result(x: u32) -> u32 {
+    x + 1
+}
+
+async fn missing_precondition(x: u32) -> u32 {
+    inc(x).await //~ ERROR precondition might not hold
+}
+
+#[requires(x < 10)]
+#[ensures(result == x + 1)]
+async fn awaited_contract(x: u32) -> u32 {
+    let a = inc(x).await;
+    assert!(a == x + 2); //~ ERROR the asserted expression might not hold
+    a
+}
+
+#[requires(n < 10)]
+async fn count(n: u32) -> u32 {
+    let mut i = 0;
+    while i < n {
+        body_invariant!(i < 5); //~ ERROR loop invariant might not hold
+        i = inc(i).await;
+    }
+    i
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+// compile-flags: -Ppipelined_verification=true -Ppanic_while_encoding=panic::crashing
+
+use prusti_contracts::*;
+
+#[requires(x < 100)]
+#[ensures(result == x + 1)]
+fn increment(x: u32) -> u32 {
+    x + 1
+}
+
+// The encoder is made to panic while encoding this function. The crash is
+// reported once, here, whether the other functions are encoded before or
+// after this one.
+#[ensures(result == 3)]
+fn crashing() -> u32 { //~ ERROR Prusti encountered an unexpected internal error
+    3
+}
+
+#[requires(x < 100)]
+#[ensures(result == x + 2)]
+fn increment_twice(x: u32) -> u32 {
+    x + 2
+}
+
+fn main() {}
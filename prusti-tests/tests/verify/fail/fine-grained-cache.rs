@@ -0,0 +1,33 @@
+// compile-flags: -Pfine_grained_cache=true
+
+use prusti_contracts::*;
+
+#[pure]
+#[ensures(result == x + 1)] //~ ERROR postcondition might not hold
+fn wrong_successor(x: u32) -> u32 {
+    x
+}
+
+#[pure]
+#[requires(x < 100)]
+fn successor(x: u32) -> u32 {
+    x + 1
+}
+
+#[requires(x < 50)]
+#[ensures(result == x + 2)]
+fn add_two(x: u32) -> u32 {
+    successor(successor(x))
+}
+
+#[requires(x < 50)]
+#[ensures(result == x + 3)] //~ ERROR postcondition might not hold
+fn add_three(x: u32) -> u32 {
+    successor(successor(x))
+}
+
+fn uses_wrong_successor() {
+    let _ = wrong_successor(1);
+}
+
+fn main() {}
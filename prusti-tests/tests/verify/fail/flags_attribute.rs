@@ -0,0 +1,27 @@
+use prusti_contracts::*;
+
+#[flags(no_such_flag = true)]
+fn unknown_flag() {} //~ ERROR invalid `#[flags]` attribute
+
+#[flags(check_panics = false)]
+fn unchecked_first(v: &[i32]) -> i32 {
+    v[0]
+}
+
+fn checked_first(v: &[i32]) -> i32 {
+    v[0] //~ ERROR the array or slice index may be out of bounds
+}
+
+#[flags(check_panics = true)]
+mod checked {
+    #[prusti_contracts::flags(check_panics = false)]
+    pub fn unchecked_second(v: &[i32]) -> i32 {
+        v[1]
+    }
+
+    pub fn checked_second(v: &[i32]) -> i32 {
+        v[1] //~ ERROR the array or slice index may be out of bounds
+    }
+}
+
+fn main() {}
@@ -0,0 +1,50 @@
+use prusti_contracts::*;
+
+#[requires(x < 100)]
+#[ensures(result == x + 1)]
+type IncFn = fn(x: u32) -> u32;
+
+#[requires(x < 10)]
+#[ensures(result == x + 1)]
+fn inc_small(x: u32) -> u32 {
+    x + 1
+}
+
+#[requires(x < 100)]
+#[ensures(result > x)]
+fn inc_loose(x: u32) -> u32 {
+    x + 1
+}
+
+fn inc_unspecified(x: u32) -> u32 {
+    x
+}
+
+struct Callbacks {
+    on_tick: IncFn,
+}
+
+fn stronger_precondition() {
+    let _f: IncFn = inc_small; //~ ERROR the function's precondition may not be implied by the precondition of the function pointer type
+}
+
+fn weaker_postcondition() {
+    let _callbacks = Callbacks {
+        on_tick: inc_loose, //~ ERROR the function's postcondition may not imply the postcondition of the function pointer type
+    };
+}
+
+fn missing_postcondition() -> IncFn {
+    inc_unspecified //~ ERROR the function's postcondition may not imply the postcondition of the function pointer type
+}
+
+fn call_outside_precondition(f: IncFn) -> u32 {
+    f(100) //~ ERROR precondition might not hold
+}
+
+#[ensures(result == x + 2)] //~ ERROR postcondition might not hold
+fn wrong_result(f: IncFn, x: u32) -> u32 {
+    f(x)
+}
+
+fn main() {}
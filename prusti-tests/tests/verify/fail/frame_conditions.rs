@@ -0,0 +1,37 @@
+use prusti_contracts::*;
+
+pub struct Buffer {
+    len: usize,
+    cap: usize,
+}
+
+#[modifies(b.len)]
+fn writes_unlisted_place(b: &mut Buffer) {
+    b.len = 1;
+    b.cap = 0; //~ ERROR the procedure modifies a place that is not listed in its `modifies` clause
+}
+
+fn reset_cap(b: &mut Buffer) {
+    b.cap = 0;
+}
+
+#[modifies(b.len)] //~ ERROR postcondition might not hold
+fn writes_unlisted_place_via_call(b: &mut Buffer) {
+    reset_cap(b);
+}
+
+#[modifies(b.len)]
+#[reads(b.len)] //~ ERROR this place overlaps with a place listed in a `modifies` clause
+fn overlapping_clauses(b: &mut Buffer) {}
+
+#[modifies(x)] //~ ERROR only places behind `&mut` arguments can be listed in a `modifies` clause
+fn not_behind_reference(x: u32) {}
+
+#[reads(b.cap)]
+fn client(b: &mut Buffer) {
+    let len = b.len;
+    writes_unlisted_place_via_call(b);
+    assert!(b.len == len); //~ ERROR the asserted expression might not hold
+}
+
+fn main() {}
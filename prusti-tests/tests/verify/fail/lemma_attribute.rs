@@ -0,0 +1,76 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+
+#[pure]
+#[terminates(Int::new(n))]
+#[requires(n >= 0)]
+fn sum(n: i64) -> i64 {
+    if n == 0 {
+        0
+    } else {
+        n + sum(n - 1)
+    }
+}
+
+#[lemma]
+#[terminates(Int::new(n))]
+#[requires(n >= 0)]
+#[ensures(sum(n) > n)] //~ ERROR: postcondition might not hold
+fn wrong(n: i64) {
+    if n > 0 {
+        wrong(n - 1);
+    }
+}
+
+#[lemma]
+#[terminates(Int::new(n))]
+#[requires(n >= 0)]
+#[ensures(sum(n) >= n)]
+fn sum_at_least(n: i64) {
+    if n > 0 {
+        sum_at_least(n - 1);
+    }
+}
+
+#[lemma]
+#[terminates(Int::new(n))]
+#[ensures(sum(n) >= n)]
+fn not_decreasing(n: i64) {
+    not_decreasing(n); //~ ERROR: the termination measure of this call is not necessarily lower
+}
+
+#[lemma]
+#[terminates]
+#[ensures(true)]
+fn trivial() {}
+
+fn not_in_ghost_block() {
+    trivial(); //~ ERROR: lemmas can be called only in ghost blocks
+}
+
+#[ensures({ sum_at_least(n); sum(n) >= n })] //~ ERROR: precondition of pure function call might not hold
+fn missing_precondition(n: i64) {}
+
+#[requires(n >= 0)]
+fn wrong_use(n: i64) {
+    prusti_assert!({ sum_at_least(n); sum(n) > n }); //~ ERROR: the asserted expression might not hold
+}
+
+#[lemma]
+#[terminates]
+fn mutates(x: &mut u32) {} //~ ERROR: lemmas cannot take mutable references or raw pointers
+
+#[lemma]
+#[terminates]
+fn reads_raw_pointer(x: *const u32) {} //~ ERROR: lemmas cannot take mutable references or raw pointers
+
+fn impure() {}
+
+#[lemma]
+#[terminates]
+fn calls_impure() {
+    impure(); //~ ERROR: lemmas can call only pure functions and other lemmas
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+// compile-flags: -Ppipelined_verification=true
+
+use prusti_contracts::*;
+
+#[requires(x < 100)]
+#[ensures(result == x + 2)] //~ ERROR postcondition might not hold
+fn increment_twice(x: u32) -> u32 {
+    x + 1
+}
+
+#[pure]
+#[requires(y != 0)]
+fn divide(x: u32, y: u32) -> u32 {
+    x / y
+}
+
+fn call_divide() {
+    let _ = divide(4, 0); //~ ERROR precondition might not hold
+}
+
+#[requires(x < 100)]
+#[ensures(result == x + 1)]
+fn increment(x: u32) -> u32 {
+    x + 1
+}
+
+fn main() {}
@@ -2,6 +2,7 @@ use prusti_contracts::*;
 
 #[pure]
 #[ensures(result == n*(n+1)/2)] //~ ERROR postcondition
+#[terminates(Int::new(n as i64))]
 fn sum1(n: i32) -> i32 {
     if n <= 0 { 0 } else { sum1(n-1)+n }
 }
@@ -9,6 +10,7 @@ fn sum1(n: i32) -> i32 {
 #[pure]
 #[requires(n >= 0)]
 #[ensures(result == n*(n+1)/2)]
+#[terminates(Int::new(n as i64))]
 fn sum2(n: i32) -> i32 {
     if n <= 0 { 0 } else { sum2(n-1)+n }
 }
@@ -0,0 +1,100 @@
+use prusti_contracts::*;
+
+#[pure]
+fn no_measure(n: i64) -> i64 {
+    if n <= 0 {
+        0
+    } else {
+        no_measure(n - 1) //~ ERROR: this recursive call of a pure function might not terminate
+    }
+}
+
+#[pure]
+#[terminates(Int::new(n))]
+#[requires(n >= 0)]
+fn not_decreasing(n: i64) -> i64 {
+    if n == 0 {
+        0
+    } else {
+        not_decreasing(n) //~ ERROR: the termination measure of this call is not necessarily lower
+    }
+}
+
+#[pure]
+#[terminates(Int::new(n))]
+fn negative(n: i64) -> i64 {
+    if n == 0 {
+        0
+    } else {
+        negative(n - 1) //~ ERROR: the termination measure of this call might become negative
+    }
+}
+
+#[pure]
+#[terminates(Int::new(m), Int::new(n))]
+#[requires(m >= 0 && n >= 0)]
+fn first_increases(m: i64, n: i64) -> i64 {
+    if m == 0 || n == 0 {
+        0
+    } else {
+        first_increases(m + 1, n - 1) //~ ERROR: component 1 might increase
+    }
+}
+
+pub enum List {
+    Nil,
+    Cons(i32, Box<List>),
+}
+
+/// Each call passes a part of one of the arguments, but no argument decreases
+/// or stays the same in both calls.
+#[pure]
+fn crossing(x: &List, y: &List) -> bool {
+    match x {
+        List::Nil => true,
+        List::Cons(_, x_tail) => match y {
+            List::Nil => crossing(x_tail, x),
+            List::Cons(_, y_tail) => crossing(y, y_tail), //~ ERROR: this recursive call of a pure function might not terminate
+        },
+    }
+}
+
+#[pure]
+fn mutual_a(n: i64) -> bool {
+    if n <= 0 {
+        true
+    } else {
+        mutual_b(n - 1) //~ ERROR: this recursive call of a pure function might not terminate
+    }
+}
+
+#[pure]
+fn mutual_b(n: i64) -> bool {
+    if n <= 0 {
+        false
+    } else {
+        mutual_a(n - 1) //~ ERROR: this recursive call of a pure function might not terminate
+    }
+}
+
+pub trait Weight {
+    #[pure]
+    fn weight(&self) -> i64;
+}
+
+pub struct Heavy;
+
+impl Weight for Heavy {
+    #[pure]
+    fn weight(&self) -> i64 {
+        weight_of(self) //~ ERROR: this recursive call of a pure function might not terminate
+    }
+}
+
+/// The call of `weight` might execute the implementation for `Heavy`.
+#[pure]
+fn weight_of<T: Weight>(x: &T) -> i64 {
+    x.weight() //~ ERROR: this recursive call of a pure function might not terminate
+}
+
+fn main() {}
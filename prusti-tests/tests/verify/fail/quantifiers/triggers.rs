@@ -1,6 +1,7 @@
 use prusti_contracts::*;
 
 #[pure]
+#[terminates(Int::new(n as i64))]
 fn fib(n: isize) -> isize {
     if n <= 1 {
         1
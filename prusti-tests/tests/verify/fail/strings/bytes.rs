@@ -0,0 +1,19 @@
+use prusti_contracts::*;
+
+fn main() {}
+
+#[requires(k <= s.len())]
+fn prefix(s: &str, k: usize) -> &str {
+    &s[..k] //~ ERROR the range end may not be at a character boundary when slicing a string
+}
+
+#[requires(s.starts_with("GET "))]
+#[ensures(result.len() == s.len() - 3)] //~ ERROR postcondition might not hold
+fn request_path(s: &str) -> &str {
+    &s[4..]
+}
+
+fn literals() {
+    let s = "héllo";
+    assert!(s.len() == 5); //~ ERROR the asserted expression might not hold
+}
@@ -0,0 +1,56 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+
+#[terminates(Int::new(a), Int::new(b))]
+#[requires(a >= 0 && b >= 0)]
+fn second_component_increases(a: i64, b: i64) {
+    if a > 0 {
+        second_component_increases(a, b + 1); //~ ERROR: the termination measure of this call is not necessarily lower
+    }
+}
+
+#[terminates(Int::new(a), Int::new(b))]
+#[requires(a >= 0 && b >= 0)]
+fn first_component_increases(a: i64, b: i64) {
+    if b > 0 {
+        first_component_increases(a + 1, b - 1); //~ ERROR: component 1 might increase
+    }
+}
+
+#[terminates(Int::new(a), Int::new(b))]
+#[requires(a >= 0 && b >= 0)]
+fn different_number_of_components(a: i64, b: i64) {
+    if a > 0 {
+        single_component(a - 1); //~ ERROR: must have the same number of components
+    }
+}
+
+#[terminates(Int::new(a))]
+#[requires(a >= 0)]
+fn single_component(a: i64) {
+    different_number_of_components(a, 0);
+}
+
+#[terminates]
+#[requires(a > 0 && b >= 0)]
+fn loop_without_progress(a: i64, mut b: i64) {
+    while b < 10 {
+        body_variant!(Int::new(a), Int::new(b)); //~ ERROR: The loop variant might not have decreased
+        body_invariant!(b >= 0);
+        b += 1;
+    }
+}
+
+#[terminates]
+#[requires(a > 0 && b >= 0)]
+fn loop_with_increasing_component(mut a: i64, mut b: i64) {
+    while b > 0 {
+        body_variant!(Int::new(a), Int::new(b)); //~ ERROR: component 1 might have increased
+        body_invariant!(a > 0 && b >= 0);
+        a += 1;
+        b -= 1;
+    }
+}
+
+fn main() {}
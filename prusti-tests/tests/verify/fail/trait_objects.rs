@@ -0,0 +1,64 @@
+use prusti_contracts::*;
+
+pub trait Counter {
+    #[pure]
+    fn value(&self) -> u32;
+
+    #[requires(self.value() < 100)]
+    #[ensures(self.value() == old(self.value()) + 1)]
+    fn inc(&mut self);
+
+    #[ensures(result <= 10)]
+    fn small(&self) -> u32;
+}
+
+struct C {
+    v: u32,
+}
+
+#[refine_trait_spec]
+impl Counter for C {
+    #[pure]
+    fn value(&self) -> u32 {
+        self.v
+    }
+
+    #[requires(self.value() < 100)]
+    #[ensures(self.value() == old(self.value()) + 1)]
+    fn inc(&mut self) {
+        self.v += 1;
+    }
+
+    #[ensures(result <= 5)]
+    fn small(&self) -> u32 {
+        5
+    }
+}
+
+fn only_trait_spec(c: &dyn Counter) {
+    let s = c.small();
+    assert!(s <= 5); //~ ERROR the asserted expression might not hold
+}
+
+fn missing_precondition(c: &mut dyn Counter) {
+    c.inc(); //~ ERROR precondition might not hold
+}
+
+#[requires(c.value() < 50)]
+#[ensures(c.value() == old(c.value()) + 2)] //~ ERROR postcondition might not hold
+fn wrong_postcondition(c: &mut dyn Counter) {
+    c.inc();
+}
+
+#[requires(c.value() < 50)]
+fn use_mut(c: &mut dyn Counter) {
+    c.inc();
+}
+
+fn modified_through_object() {
+    let mut c = C { v: 3 };
+    use_mut(&mut c);
+    assert!(c.v == 3); //~ ERROR the asserted expression might not hold
+}
+
+fn main() {}
@@ -0,0 +1,39 @@
+use prusti_contracts::*;
+
+#[requires(i < a.len() && 0 < i)]
+#[ensures(unchanged_except!(a, i))] //~ ERROR postcondition might not hold
+fn store_array(a: &mut [u32; 10], i: usize, v: u32) {
+    a[i] = v;
+    a[0] = v;
+}
+
+pub struct Inner {
+    value: u32,
+}
+
+pub struct Pair {
+    first: u32,
+    second: Inner,
+}
+
+#[ensures(unchanged!(p.second))] //~ ERROR postcondition might not hold
+fn set_second(p: &mut Pair) {
+    p.second.value = 0;
+}
+
+#[requires(i < a.len() && j < a.len())]
+#[ensures(unchanged_except!(a, i))]
+fn store_other(a: &mut [u32; 10], i: usize, j: usize) {
+    if i == j {
+        a[i] = 0;
+    }
+}
+
+#[requires(i < a.len() && j < a.len())]
+fn client(a: &mut [u32; 10], i: usize, j: usize) {
+    let old_j = a[j];
+    store_other(a, i, j);
+    assert!(a[j] == old_j); //~ ERROR the asserted expression might not hold
+}
+
+fn main() {}
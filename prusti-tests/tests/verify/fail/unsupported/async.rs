@@ -1,7 +1,13 @@
-pub async fn connect<D>(dst: D)
-where
-    D: std::convert::TryInto<u32>
-{ //~ ERROR unsupported type
+async fn inc(x: u32) -> u32 {
+    x
 }
 
-fn main(){}
+async fn ready() -> u32 {
+    std::future::ready(5).await //~ ERROR only futures returned by calls to `async fn`s can be awaited
+}
+
+fn not_awaited() {
+    let _future = inc(3); //~ ERROR the future returned by an `async fn` must be awaited immediately
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+use prusti_contracts::*;
+
+#[requires(x < 100)]
+#[ensures(result == x + 1)]
+type IncFn = fn(x: u32) -> u32;
+
+fn call_unspecified(f: fn(u32) -> u32) -> u32 {
+    f(1) //~ ERROR calling a function pointer is only supported if its type is a type alias with a specification
+}
+
+fn store_unspecified(f: fn(u32) -> u32) -> IncFn {
+    let g: IncFn = f; //~ ERROR only functions and function pointers with the same specification can be stored in a function pointer with a specification
+    g
+}
+
+#[requires(*x < 100)]
+type RefFn = fn(x: &u32) -> u32;
+
+fn deref(x: &u32) -> u32 {
+    *x
+}
+
+fn take(_f: RefFn) {}
+
+fn with_reference() {
+    take(deref); //~ ERROR function pointer types with specifications cannot have references in their signature
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+// The ghost variables of `for` loops are supported only in the unsafe core proof.
+
+use prusti_contracts::*;
+
+fn range(n: usize) {
+    for i in 0..n { //~ ERROR iterators are not fully supported yet
+        body_invariant!(i + 1 == iteration_index());
+    }
+}
+
+fn slice(s: &[u32]) {
+    for x in s { //~ ERROR iterators are not fully supported yet
+        body_invariant!(*x == visited::<u32>()[iteration_index() - 1]);
+    }
+}
+
+fn while_loop(n: usize) {
+    let mut i = 0;
+    while i < n {
+        body_invariant!(iteration_index() == i); //~ ERROR use of impure function "prusti_contracts::iteration_index" in pure code is not allowed
+        i += 1;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+// Lemmas are supported only in the unsafe core proof.
+
+use prusti_contracts::*;
+
+#[lemma]
+#[terminates]
+#[ensures(true)]
+fn trivial() {}
+
+fn outside_ghost_block() {
+    trivial(); //~ ERROR lemmas are supported only with the unsafe_core_proof flag
+}
+
+fn in_ghost_block() {
+    ghost! {
+        trivial(); //~ ERROR lemmas are supported only with the unsafe_core_proof flag
+    };
+}
+
+#[ensures({ trivial(); true })] //~ ERROR lemmas are supported only with the unsafe_core_proof flag
+fn in_specification() {}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use prusti_contracts::*;
+
+#[requires(s.contains(e))]
+fn set_param(s: Set<u32>, e: u32) {} //~ ERROR sets and multisets are supported only in the unsafe core proof
+
+#[requires(s.count(e) == Int::new(1))]
+fn multiset_param(s: Multiset<u32>, e: u32) {} //~ ERROR sets and multisets are supported only in the unsafe core proof
+
+fn main() {}
@@ -0,0 +1,16 @@
+fn main() {}
+
+fn mutable_rest(bytes: &mut [u8]) -> usize {
+    match bytes {
+        [first, rest @ ..] => { //~ ERROR mutably borrowing the rest of a slice pattern is not supported yet
+            *first = 0;
+            rest.len()
+        }
+        [] => 0,
+    }
+}
+
+fn array_rest_by_value(v: [u8; 4]) -> u8 {
+    let [a, rest @ .., d] = v; //~ ERROR the rest of a slice pattern can only be bound by shared reference
+    a + d
+}
@@ -0,0 +1,68 @@
+use prusti_contracts::*;
+
+#[requires(x < 100)]
+#[ensures(result == x + 1)]
+async fn inc(x: u32) -> u32 {
+    x + 1
+}
+
+#[ensures(result == x)]
+async fn id(x: u32) -> u32 {
+    x
+}
+
+#[requires(x < 10)]
+#[ensures(result == x + 3)]
+async fn three(x: u32) -> u32 {
+    let a = inc(x).await;
+    let b = inc(a).await;
+    inc(b).await
+}
+
+#[requires(n < 10)]
+#[ensures(result == n)]
+async fn count(n: u32) -> u32 {
+    let mut i = 0;
+    while i < n {
+        body_invariant!(i < n);
+        i = inc(i).await;
+    }
+    i
+}
+
+#[requires(*x < 100)]
+#[ensures(*x == old(*x) + 1)]
+async fn increment(x: &mut u32) {
+    *x = inc(*x).await;
+}
+
+struct S {
+    v: u32,
+}
+
+impl S {
+    #[ensures(result == self.v)]
+    async fn get(self) -> u32 {
+        id(self.v).await
+    }
+}
+
+async fn unit() {}
+
+async fn client() {
+    unit().await;
+    let s = S { v: 3 };
+    let v = s.get().await;
+    assert!(v == 3);
+    let mut x = 5;
+    increment(&mut x).await;
+    assert!(x == 6);
+}
+
+pub async fn connect<D>(_dst: D)
+where
+    D: std::convert::TryInto<u32>,
+{
+}
+
+fn main() {}
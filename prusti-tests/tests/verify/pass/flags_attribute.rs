@@ -0,0 +1,26 @@
+use prusti_contracts::*;
+
+#[flags(check_panics = false)]
+fn first(v: &[i32]) -> i32 {
+    v[0]
+}
+
+struct Buffer {
+    data: [u8; 4],
+}
+
+#[flags(check_panics = false)]
+impl Buffer {
+    fn get(&self, index: usize) -> u8 {
+        self.data[index]
+    }
+}
+
+#[flags(check_panics = false)]
+mod unchecked {
+    pub fn last(v: &[i32]) -> i32 {
+        v[v.len() - 1]
+    }
+}
+
+fn main() {}
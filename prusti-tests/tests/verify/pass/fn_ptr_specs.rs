@@ -0,0 +1,66 @@
+use prusti_contracts::*;
+
+#[requires(x < 100)]
+#[ensures(result == x + 1)]
+type IncFn = fn(x: u32) -> u32;
+
+#[requires(x < 1000)]
+#[ensures(result == x + 1)]
+fn inc(x: u32) -> u32 {
+    x + 1
+}
+
+#[requires(x < 100)]
+#[ensures(result == x + 1)]
+fn inc_checked(x: u32) -> u32 {
+    assert!(x < 100);
+    x + 1
+}
+
+struct Callbacks {
+    on_tick: IncFn,
+    on_reset: IncFn,
+}
+
+#[requires(x < 100)]
+#[ensures(result == x + 1)]
+fn apply(f: IncFn, x: u32) -> u32 {
+    f(x)
+}
+
+#[requires(x < 50)]
+#[ensures(result == x + 2)]
+fn apply_twice(f: IncFn, x: u32) -> u32 {
+    let y = f(x);
+    f(y)
+}
+
+#[requires(x < 100)]
+#[ensures(result == x + 1)]
+fn tick(callbacks: &Callbacks, x: u32) -> u32 {
+    (callbacks.on_tick)(x)
+}
+
+fn pick(checked: bool) -> IncFn {
+    if checked {
+        inc_checked
+    } else {
+        inc
+    }
+}
+
+#[ensures(result == 8)]
+fn client() -> u32 {
+    let f: IncFn = inc;
+    let g = pick(true);
+    let callbacks = Callbacks {
+        on_tick: inc,
+        on_reset: g,
+    };
+    let a = apply(f, 1);
+    let b = apply_twice(g, a);
+    let c = tick(&callbacks, b);
+    (callbacks.on_reset)(c)
+}
+
+fn main() {}
@@ -0,0 +1,57 @@
+use prusti_contracts::*;
+
+pub struct Buffer {
+    len: usize,
+    cap: usize,
+    data: (u32, u32),
+}
+
+impl Buffer {
+    #[requires(self.len < self.cap)]
+    #[ensures(self.len == old(self.len) + 1)]
+    #[ensures(self.data.0 == value)]
+    #[modifies(self.len, self.data.0)]
+    pub fn push(&mut self, value: u32) {
+        self.len += 1;
+        self.data.0 = value;
+    }
+
+    #[modifies()]
+    pub fn check(&mut self) -> bool {
+        self.len <= self.cap
+    }
+
+    #[reads(self.cap)]
+    pub fn set_second(&mut self, value: u32) {
+        self.data.1 = value;
+    }
+}
+
+fn reset_len(b: &mut Buffer) {
+    b.len = 0;
+}
+
+#[modifies(b.len, b.data)]
+fn reset_via_call(b: &mut Buffer) {
+    reset_len(b);
+    b.data = (0, 0);
+}
+
+#[requires(b.len < b.cap)]
+fn client(b: &mut Buffer) {
+    let cap = b.cap;
+    let second = b.data.1;
+    b.push(5);
+    assert!(b.cap == cap);
+    assert!(b.data.1 == second);
+    assert!(b.data.0 == 5);
+    b.set_second(7);
+    assert!(b.cap == cap);
+    let len = b.len;
+    b.check();
+    assert!(b.len == len);
+    reset_via_call(b);
+    assert!(b.cap == cap);
+}
+
+fn main() {}
@@ -7,6 +7,7 @@ struct T {
 impl T {
     #[pure]
     #[ensures(result == (self.f * (self.f + 1)) / 2)]
+    #[terminates(Int::new(self.f as i64))]
     fn sum(&self) -> u32 {
         if self.f == 0 {
             0
@@ -0,0 +1,64 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+
+#[pure]
+#[terminates(Int::new(n))]
+#[requires(n >= 0)]
+fn sum(n: i64) -> i64 {
+    if n == 0 {
+        0
+    } else {
+        n + sum(n - 1)
+    }
+}
+
+#[lemma]
+#[terminates(Int::new(n))]
+#[requires(n >= 0)]
+#[ensures(sum(n) >= n)]
+fn sum_at_least(n: i64) {
+    if n > 0 {
+        sum_at_least(n - 1);
+    }
+}
+
+#[lemma]
+#[terminates]
+#[requires(n >= 1)]
+#[ensures(sum(n) >= 1)]
+fn sum_positive(n: i64) {
+    sum_at_least(n);
+}
+
+#[requires(n >= 1)]
+fn client(n: i64) {
+    let s = sum(n);
+    ghost! {
+        sum_positive(n);
+    };
+    assert!(s >= 1);
+}
+
+#[lemma]
+#[terminates]
+#[requires(*x >= 0)]
+#[ensures(sum(*x) >= *x)]
+fn sum_at_least_ref(x: &i64) {
+    sum_at_least(*x);
+}
+
+#[requires(n >= 0)]
+#[ensures({ sum_at_least(n); sum(n) >= n })]
+fn in_postcondition(n: i64) {}
+
+#[requires(n >= 1 && { sum_positive(n); sum(n) >= 1 })]
+fn in_precondition(n: i64) {}
+
+#[requires(n >= 1)]
+fn spec_client(n: i64) {
+    in_precondition(n);
+    prusti_assert!({ sum_at_least_ref(&n); sum(n) >= n });
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+// compile-flags: -Pno_verify=true -Pdump_viper_program=true
+
+// The programs are encoded and dumped, but not verified.
+
+use prusti_contracts::*;
+
+#[ensures(result == x + 2)]
+fn wrong_successor(x: u32) -> u32 {
+    x + 1
+}
+
+#[requires(v.len() > 0)]
+#[ensures(result == v[0])]
+fn first(v: &[i32]) -> i32 {
+    v[0]
+}
+
+fn main() {}
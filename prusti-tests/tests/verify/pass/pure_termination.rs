@@ -0,0 +1,108 @@
+use prusti_contracts::*;
+
+#[pure]
+#[terminates(Int::new(n))]
+#[requires(n >= 0)]
+fn fib(n: i64) -> i64 {
+    if n <= 1 {
+        n
+    } else {
+        fib(n - 1) + fib(n - 2)
+    }
+}
+
+#[pure]
+#[terminates(Int::new(n) * Int::new(2))]
+#[requires(n >= 0)]
+fn is_even(n: i64) -> bool {
+    if n == 0 {
+        true
+    } else {
+        is_odd(n - 1)
+    }
+}
+
+#[pure]
+#[terminates(Int::new(n) * Int::new(2) + Int::new(1))]
+#[requires(n >= 0)]
+fn is_odd(n: i64) -> bool {
+    if n == 0 {
+        false
+    } else {
+        is_even(n)
+    }
+}
+
+#[pure]
+#[terminates(Int::new(m), Int::new(n))]
+#[requires(m >= 0 && n >= 0)]
+fn grid_paths(m: i64, n: i64) -> i64 {
+    if m == 0 || n == 0 {
+        1
+    } else if n == 1 {
+        grid_paths(m - 1, 100)
+    } else {
+        grid_paths(m, n - 1)
+    }
+}
+
+pub enum List {
+    Nil,
+    Cons(i32, Box<List>),
+}
+
+#[pure]
+fn len(list: &List) -> usize {
+    match list {
+        List::Nil => 0,
+        List::Cons(_, tail) => 1 + len(tail),
+    }
+}
+
+pub enum Tree {
+    Leaf,
+    Node(Box<Tree>, Box<Tree>),
+}
+
+/// Each call either passes a part of `b`, or passes `b` unchanged and a part
+/// of `a`.
+#[pure]
+fn lexicographic(a: &Tree, b: &Tree) -> usize {
+    match b {
+        Tree::Node(_, right) => lexicographic(a, right),
+        Tree::Leaf => match a {
+            Tree::Node(left, _) => lexicographic(left, b),
+            Tree::Leaf => 0,
+        },
+    }
+}
+
+#[pure]
+#[terminates(trusted)]
+fn trusted(n: i64) -> i64 {
+    if n == 0 {
+        0
+    } else {
+        trusted(n + 1)
+    }
+}
+
+pub trait Size {
+    #[pure]
+    fn size(&self) -> usize;
+}
+
+impl Size for Tree {
+    #[pure]
+    fn size(&self) -> usize {
+        1
+    }
+}
+
+/// None of the implementations of `size` in this crate calls `size_of`.
+#[pure]
+fn size_of<T: Size>(x: &T) -> usize {
+    x.size()
+}
+
+fn main() {}
@@ -30,6 +30,7 @@ use prusti_contracts::*;
 use std::mem;
 
 #[pure]
+#[terminates(Int::new_usize(i))]
 fn fib(i: usize) -> usize {
     match i {
         0 => 0,
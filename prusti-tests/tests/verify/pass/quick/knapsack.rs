@@ -172,6 +172,7 @@ fn max(a: usize, b: usize) -> usize {
 #[pure]
 #[requires(0 <= i && i <= items.len())]
 #[requires(0 <= w && w <= max_weight)]
+#[terminates(Int::new_usize(i))]
 fn m(items: &Items, i: usize, w: usize, max_weight: usize) -> usize {
     match (i, w) {
         (0, _w) => 0,
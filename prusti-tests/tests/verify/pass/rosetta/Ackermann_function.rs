@@ -18,6 +18,7 @@ use prusti_contracts::*;
 #[pure]
 #[requires(0 <= m && 0 <= n)]
 #[ensures(result >= 0)]
+#[terminates(Int::new(m as i64), Int::new(n as i64))]
 fn ack_pure(m: isize, n: isize) -> isize {
     if m == 0 {
         n + 1
@@ -1,47 +1,32 @@
-// ignore-test: slicing with RangeInclusive (e.g. [x..=y]) currently not supported
-
-#![feature(const_panic)]
-
 use prusti_contracts::*;
 
-// #[extern_spec]
-// impl std::ops::RangeInclusive<usize> {
-//     #[ensures(*result.start() == start)]
-//     #[ensures(*result.end() == end)]
-//     pub const fn new(start: usize, end: usize) -> std::ops::RangeInclusive<usize>;
-
-//     #[pure]
-//     pub const fn start(&self) -> &usize;
-//     #[pure]
-//     pub const fn end(&self) -> &usize;
-// }
-
-
-#[extern_spec]
-impl<T> std::ops::Index<std::ops::Range<usize>> for [T] {
-    #[ensures( result.len() == index.end - index.start )]
-    #[ensures( forall(|i: usize| (0 <= i && i < result.len()) ==> result[i] == self[i+index.start]) )]
-    fn index(&self, index: std::ops::Range<usize>) -> &[T];
-}
-
 fn main() {}
 
 #[requires(a.len() > 6)]
 fn slice(a: &[i32]) {
-    let s = &a[1..4];
-    assert!(s[0] == a[1]);
+    let s = &a[1..=4];
+    assert!(s.len() == 4);
+    assert!(s[3] == a[4]);
+
+    let s = &a[..=5];
+    assert!(s[5] == a[5]);
+}
 
-    /*
-    let s = &a[..2];
-    assert!(s[1] == a[1]);
-    let s = &a[1..];
-    assert!(s[2] == a[3]);
-    let s = &a[..];
-    assert!(s[3] == a[3]);*/
+#[requires(a.len() > 3)]
+fn slice_array(a: &[i32; 5]) {
+    let s = &a[2..=3];
+    assert!(s.len() == 2);
+    assert!(s[0] == a[2]);
+}
 
-    // let s = &a[1..=4];
-    // assert!(s[3] == a[4]);
+#[pure]
+#[requires(lo <= hi && hi < a.len())]
+#[ensures(result == hi - lo + 1)]
+fn range_len(a: &[i32], lo: usize, hi: usize) -> usize {
+    a[lo..=hi].len()
+}
 
-    /*let s = &a[..=5];
-    assert!(s[5] == a[5]);*/
+#[requires(a.len() > 6)]
+fn pure_client(a: &[i32]) {
+    assert!(range_len(a, 2, 4) == 3);
 }
@@ -0,0 +1,54 @@
+use prusti_contracts::*;
+
+fn main() {}
+
+#[requires(bytes.len() > 0)]
+#[ensures(result == bytes[0])]
+fn first(bytes: &[u8]) -> u8 {
+    match bytes {
+        [first, ..] => *first,
+        [] => unreachable!(),
+    }
+}
+
+#[ensures(bytes.len() > 0 ==> result == bytes.len() - 1)]
+#[ensures(bytes.len() == 0 ==> result == 0)]
+fn tail_len(bytes: &[u8]) -> usize {
+    match bytes {
+        [_, rest @ ..] => rest.len(),
+        [] => 0,
+    }
+}
+
+#[requires(bytes.len() >= 3)]
+fn middle(bytes: &[u8]) {
+    match bytes {
+        [first, middle @ .., last] => {
+            assert!(middle.len() == bytes.len() - 2);
+            assert!(middle[0] == bytes[1]);
+            assert!(*first == bytes[0]);
+            assert!(*last == bytes[bytes.len() - 1]);
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn array_rest(v: &[u8; 4]) {
+    let [a, rest @ ..] = v;
+    assert!(rest.len() == 3);
+    assert!(rest[2] == v[3]);
+    assert!(*a == v[0]);
+}
+
+#[pure]
+fn pure_tail_len(bytes: &[u8]) -> usize {
+    match bytes {
+        [_, rest @ ..] => rest.len(),
+        [] => 0,
+    }
+}
+
+#[requires(bytes.len() > 2)]
+fn pure_client(bytes: &[u8]) {
+    assert!(pure_tail_len(bytes) == bytes.len() - 1);
+}
@@ -0,0 +1,42 @@
+use prusti_contracts::*;
+
+fn main() {}
+
+#[requires(s.len() > 0)]
+#[ensures(result == s.as_bytes()[0])]
+fn first_byte(s: &str) -> u8 {
+    s.as_bytes()[0]
+}
+
+#[requires(s.len() > 0)]
+#[ensures(!(0x80 <= result && result <= 0xBF))]
+fn first_byte_starts_a_char(s: &str) -> u8 {
+    s.as_bytes()[0]
+}
+
+#[requires(k <= s.len() && s.is_char_boundary(k))]
+#[ensures(result.len() == k)]
+#[ensures(forall(|i: usize| i < k ==> result.as_bytes()[i] == s.as_bytes()[i]))]
+fn prefix(s: &str, k: usize) -> &str {
+    &s[..k]
+}
+
+#[requires(s.starts_with("GET "))]
+#[ensures(result.len() == s.len() - 4)]
+fn request_path(s: &str) -> &str {
+    &s[4..]
+}
+
+#[ensures(result ==> s.len() >= 4 && s[0..4].as_bytes()[0] == b'G')]
+fn is_get_request(s: &str) -> bool {
+    s.starts_with("GET ")
+}
+
+fn literals() {
+    let s = "héllo";
+    assert!(s.len() == 6);
+    assert!(!s.is_char_boundary(2));
+    assert!(s.starts_with("h"));
+    let head = &s[..1];
+    assert!(head.as_bytes()[0] == b'h');
+}
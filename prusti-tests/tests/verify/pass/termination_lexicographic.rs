@@ -0,0 +1,50 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+
+#[terminates(Int::new(m), Int::new(n))]
+#[requires(m >= 0 && n >= 0)]
+#[ensures(result >= 0)]
+fn ackermann(m: i64, n: i64) -> i64 {
+    if m == 0 {
+        n + 1
+    } else if n == 0 {
+        ackermann(m - 1, 1)
+    } else {
+        let inner = ackermann(m, n - 1);
+        ackermann(m - 1, inner)
+    }
+}
+
+#[terminates(Int::new(depth), Int::new(2) * Int::new(width))]
+#[requires(depth >= 0 && width >= 0)]
+fn visit_level(depth: i64, width: i64) {
+    if width > 0 {
+        visit_node(depth, width - 1);
+    } else if depth > 0 {
+        visit_level(depth - 1, 100);
+    }
+}
+
+#[terminates(Int::new(depth), Int::new(2) * Int::new(width) + Int::new(1))]
+#[requires(depth >= 0 && width >= 0)]
+fn visit_node(depth: i64, width: i64) {
+    visit_level(depth, width);
+}
+
+#[terminates]
+#[requires(a >= 0 && b >= 0)]
+fn countdown(mut a: i64, mut b: i64) {
+    while a > 0 {
+        body_variant!(Int::new(a), Int::new(b));
+        body_invariant!(a > 0 && b >= 0);
+        if b > 0 {
+            b -= 1;
+        } else {
+            a -= 1;
+            b = 10;
+        }
+    }
+}
+
+fn main() {}
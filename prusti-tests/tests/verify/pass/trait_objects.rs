@@ -0,0 +1,90 @@
+use prusti_contracts::*;
+
+pub trait Counter {
+    #[pure]
+    fn value(&self) -> u32;
+
+    #[requires(self.value() < 100)]
+    #[ensures(self.value() == old(self.value()) + 1)]
+    fn inc(&mut self);
+
+    #[ensures(result <= 10)]
+    fn small(&self) -> u32;
+}
+
+struct C {
+    v: u32,
+}
+
+#[refine_trait_spec]
+impl Counter for C {
+    #[pure]
+    fn value(&self) -> u32 {
+        self.v
+    }
+
+    #[requires(self.value() < 100)]
+    #[ensures(self.value() == old(self.value()) + 1)]
+    fn inc(&mut self) {
+        self.v += 1;
+    }
+
+    #[ensures(result <= 5)]
+    fn small(&self) -> u32 {
+        5
+    }
+}
+
+pub trait Get<T> {
+    #[ensures(result == 1)]
+    fn get(&self, x: T) -> u32;
+}
+
+#[refine_trait_spec]
+impl Get<u32> for C {
+    #[ensures(result == 1)]
+    fn get(&self, _x: u32) -> u32 {
+        1
+    }
+}
+
+fn use_ref(c: &dyn Counter) -> u32 {
+    let s = c.small();
+    assert!(s <= 10);
+    s
+}
+
+#[requires(c.value() < 50)]
+#[ensures(c.value() == old(c.value()) + 2)]
+fn use_mut(c: &mut dyn Counter) {
+    c.inc();
+    c.inc();
+}
+
+#[ensures(result <= 10)]
+fn use_box(b: Box<dyn Counter>) -> u32 {
+    b.small()
+}
+
+fn use_generic(g: &dyn Get<u32>) {
+    let r = g.get(3);
+    assert!(r == 1);
+}
+
+fn client() {
+    let c = C { v: 3 };
+    let d: &dyn Counter = &c;
+    assert!(d.value() == 3);
+    let r = use_ref(&c);
+    assert!(r <= 10);
+    use_generic(&c);
+
+    let mut c = C { v: 3 };
+    use_mut(&mut c);
+
+    let b: Box<dyn Counter> = Box::new(C { v: 3 });
+    let s = use_box(b);
+    assert!(s <= 10);
+}
+
+fn main() {}
@@ -0,0 +1,44 @@
+use prusti_contracts::*;
+
+#[requires(i < a.len())]
+#[ensures(a[i] == v)]
+#[ensures(unchanged_except!(a, i))]
+fn store_array(a: &mut [u32; 10], i: usize, v: u32) {
+    a[i] = v;
+}
+
+#[requires(i < s.len())]
+#[ensures(s[i] == v)]
+#[ensures(unchanged_except!(s, i))]
+fn store_slice(s: &mut [u32], i: usize, v: u32) {
+    s[i] = v;
+}
+
+pub struct Inner {
+    value: u32,
+}
+
+pub struct Pair {
+    first: u32,
+    second: Inner,
+}
+
+#[ensures(p.first == 1)]
+#[ensures(unchanged!(p.second))]
+fn set_first(p: &mut Pair) {
+    p.first = 1;
+}
+
+#[requires(i < a.len() && j < a.len() && i != j)]
+fn client(a: &mut [u32; 10], p: &mut Pair, i: usize, j: usize) {
+    let old_j = a[j];
+    store_array(a, i, 5);
+    assert!(a[i] == 5);
+    assert!(a[j] == old_j);
+
+    let second = p.second.value;
+    set_first(p);
+    assert!(p.second.value == second);
+}
+
+fn main() {}
@@ -1,4 +1,4 @@
-// compile-flags: -Pcounterexample=true
+// compile-flags: -Pcounterexample=true -Pcheck_pure_function_termination=false
 
 use prusti_contracts::*;
 
@@ -1,6 +1,7 @@
 use prusti_contracts::*;
 
 #[pure]
+#[terminates(Int::new_usize(n))]
 fn count(n: usize) -> usize {
     if n == 0 {
         0
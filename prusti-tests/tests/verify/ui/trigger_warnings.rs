@@ -0,0 +1,26 @@
+// compile-flags: -W warnings
+// The verifier tests run with `-A warnings`, which would hide the warnings
+// checked by this test.
+
+#![allow(dead_code)]
+use prusti_contracts::*;
+
+#[pure]
+fn f(_x: i32) -> bool {
+    true
+}
+
+#[ensures(forall(|x: i32| x + 1 > x))]
+fn no_trigger() {}
+
+#[ensures(forall(|x: i32| f(x) ==> f(x + 1)))]
+fn matching_loop() {}
+
+// The warnings are reported only for the functions that own the quantifiers,
+// not again for their callers.
+fn client() {
+    no_trigger();
+    matching_loop();
+}
+
+fn main() {}
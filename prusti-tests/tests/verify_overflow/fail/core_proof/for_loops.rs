@@ -0,0 +1,29 @@
+// compile-flags: -Punsafe_core_proof=true -Puse_smt_wrapper=true -Psmt_qi_bound_global=10000 -Psmt_qi_bound_trace=200 -Psmt_qi_bound_trace_kind=20 -Psmt_qi_bound_global_kind=100
+
+use prusti_contracts::*;
+
+fn range_index() {
+    for i in 0..10usize {
+        body_invariant!(i == iteration_index()); //~ ERROR: loop invariant might not hold
+    }
+}
+
+fn range_bounds() {
+    for i in 0..10usize {
+        assert!(i < 9); //~ ERROR: the asserted expression might not hold
+    }
+}
+
+fn slice_visited(s: &[u32]) {
+    for _ in s.iter() {
+        body_invariant!(visited::<u32>().len() == Int::new(0)); //~ ERROR: loop invariant might not hold
+    }
+}
+
+fn slice_values(s: &[u32]) {
+    for x in s.iter() {
+        assert!(*x == 0); //~ ERROR: the asserted expression might not hold
+    }
+}
+
+fn main() {}
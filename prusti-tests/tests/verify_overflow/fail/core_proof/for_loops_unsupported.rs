@@ -0,0 +1,22 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+
+#[requires(iteration_index() == 0)] //~ ERROR: can only be used in `body_invariant!` of `for` loops
+fn precondition() {}
+
+fn while_loop(n: usize) {
+    let mut i = 0;
+    while i < n {
+        body_invariant!(iteration_index() == i); //~ ERROR: can only be used in `body_invariant!` of `for` loops
+        i += 1;
+    }
+}
+
+fn visited_type(s: &[u32]) {
+    for _ in s {
+        body_invariant!(visited::<u64>().len() > Int::new(0)); //~ ERROR: `visited()` of this loop has type
+    }
+}
+
+fn main() {}
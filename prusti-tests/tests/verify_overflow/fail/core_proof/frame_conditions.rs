@@ -0,0 +1,22 @@
+// compile-flags: -Punsafe_core_proof=true
+
+use prusti_contracts::*;
+
+pub struct Buffer {
+    len: usize,
+    cap: usize,
+}
+
+#[modifies(b.len)] //~ ERROR: `modifies` and `reads` clauses are not supported with `unsafe_core_proof`
+fn set_len(b: &mut Buffer) {
+    b.len = 0;
+}
+
+#[reads(b.cap)] //~ ERROR: `modifies` and `reads` clauses are not supported with `unsafe_core_proof`
+fn keeps_cap(b: &mut Buffer) {}
+
+fn client(b: &mut Buffer) {
+    set_len(b); //~ ERROR: calling a procedure with `modifies` or `reads` clauses is not supported
+}
+
+fn main() {}
@@ -0,0 +1,43 @@
+// compile-flags: -Punsafe_core_proof=true
+
+#![allow(unused)]
+
+use prusti_contracts::*;
+
+type Multiset = prusti_contracts::Multiset<u32>;
+
+fn empty_multiset_zero_len() {
+    prusti_assert!(Multiset::empty().len() == Int::new(0));
+}
+
+fn multiset_eq() {
+    prusti_assert!(Multiset::empty() == Multiset::empty());
+    prusti_assert!(multiset![1, 2] == multiset![2, 1]);
+}
+
+fn multiset_neq() {
+    prusti_assert!(multiset![1, 1] == multiset![1]); //~ ERROR: asserted expression might not hold
+}
+
+fn count() {
+    let multiset = multiset![1, 2, 1];
+    prusti_assert!(multiset.count(1) == Int::new(2));
+    prusti_assert!(multiset.count(2) == Int::new(1));
+    prusti_assert!(multiset.count(3) == Int::new(0));
+    prusti_assert!(multiset.len() == Int::new(3));
+}
+
+fn union_intersection_difference() {
+    let a = multiset![1, 1, 2];
+    let b = multiset![1, 3];
+    prusti_assert!(a.union(b).count(1) == Int::new(3));
+    prusti_assert!(a.intersection(b) == multiset![1]);
+    prusti_assert!(a.difference(b) == multiset![1, 2]);
+    prusti_assert!(b.difference(a).is_subset(b));
+}
+
+fn count_fails(multiset: Multiset, x: u32) {
+    prusti_assert!(multiset.count(x) > Int::new(0)); //~ ERROR: asserted expression might not hold
+}
+
+fn main() {}
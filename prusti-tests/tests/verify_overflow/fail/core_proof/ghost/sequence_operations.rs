@@ -0,0 +1,127 @@
+// compile-flags: -Punsafe_core_proof=true
+
+#![allow(unused)]
+
+use prusti_contracts::*;
+
+type Seq = prusti_contracts::Seq<u32>;
+type Map = prusti_contracts::Map<u32, u32>;
+
+fn take_skip() {
+    let seq = seq![1, 2, 3, 4];
+    prusti_assert!(seq.take(2) == seq![1, 2]);
+    prusti_assert!(seq.skip(2) == seq![3, 4]);
+    prusti_assert!(seq.take(2).concat(seq.skip(2)) == seq);
+    prusti_assert!(seq.take(0) == Seq::empty());
+}
+
+#[requires(Int::new_usize(n) <= seq.len())]
+fn take_len(seq: Seq, n: usize) {
+    prusti_assert!(seq.take(n).len() == Int::new_usize(n));
+    prusti_assert!(seq.skip(n).len() == seq.len() - Int::new_usize(n));
+}
+
+fn take_len_fails(seq: Seq, n: usize) {
+    prusti_assert!(seq.take(n).len() == Int::new_usize(n)); //~ ERROR: asserted expression might not hold
+}
+
+fn update() {
+    let seq = seq![1, 2, 3];
+    prusti_assert!(seq.update(1, 5) == seq![1, 5, 3]);
+    prusti_assert!(seq.update(1, 5)[1] == 5);
+    prusti_assert!(seq.update(1, 5)[0] == 1);
+}
+
+fn update_fails() {
+    let seq = seq![1, 2, 3];
+    prusti_assert!(seq.update(1, 5) == seq); //~ ERROR: asserted expression might not hold
+}
+
+fn contains() {
+    let seq = seq![1, 2, 3];
+    prusti_assert!(seq.contains(2));
+    prusti_assert!(!seq.contains(4));
+}
+
+fn contains_fails(seq: Seq, x: u32) {
+    prusti_assert!(seq.contains(x)); //~ ERROR: asserted expression might not hold
+}
+
+fn range() {
+    let range = prusti_contracts::Seq::range(Int::new(2), Int::new(5));
+    prusti_assert!(range.len() == Int::new(3));
+    prusti_assert!(range[Int::new(0)] == Int::new(2));
+    prusti_assert!(range[Int::new(2)] == Int::new(4));
+    prusti_assert!(prusti_contracts::Seq::range(Int::new(5), Int::new(2)).len() == Int::new(0));
+}
+
+fn range_fails() {
+    let range = prusti_contracts::Seq::range(Int::new(2), Int::new(5));
+    prusti_assert!(range[Int::new(0)] == Int::new(0)); //~ ERROR: asserted expression might not hold
+}
+
+#[requires(slice.len() == 2 && slice[0] == 1)]
+fn from_slice(slice: &[u32]) {
+    prusti_assert!(Seq::from_slice(slice).len() == Int::new(2));
+    prusti_assert!(Seq::from_slice(slice)[0] == 1);
+}
+
+fn from_vec_slice(vec: &Vec<u32>) {
+    let slice: &[u32] = vec;
+    prusti_assert!(Seq::from_slice(slice).len() == Int::new_usize(slice.len()));
+}
+
+fn from_vec_slice_fails(vec: &Vec<u32>) {
+    let slice: &[u32] = vec;
+    prusti_assert!(Seq::from_slice(slice).len() == Int::new(0)); //~ ERROR: asserted expression might not hold
+}
+
+#[requires(i < slice.len())]
+#[ensures(Seq::from_slice(slice) == old_seq(slice).update(i, v))]
+fn old_seq_update(slice: &mut [u32], i: usize, v: u32) {
+    slice[i] = v;
+}
+
+#[requires(i < slice.len())]
+#[ensures(old_seq(slice)[i] == old(slice[i]))]
+#[ensures(Seq::from_slice(slice) == old_seq(slice))] //~ ERROR: postcondition might not hold.
+fn old_seq_fails(slice: &mut [u32], i: usize) {
+    slice[i] = 0;
+}
+
+fn to_set(seq: Seq, x: u32) {
+    prusti_assert!(Seq::empty().to_set() == prusti_contracts::Set::empty());
+    prusti_assert!(seq![1, 2, 1].to_set() == set![1, 2]);
+    prusti_assert!(seq.to_set().contains(x) == seq.contains(x));
+    prusti_assert!(seq.to_set().len() <= seq.len());
+}
+
+fn to_set_fails(seq: Seq) {
+    prusti_assert!(seq.to_set().len() == seq.len()); //~ ERROR: asserted expression might not hold
+}
+
+fn to_multiset(seq: Seq, x: u32) {
+    prusti_assert!(Seq::empty().to_multiset() == prusti_contracts::Multiset::empty());
+    prusti_assert!(seq![1, 2, 1].to_multiset() == multiset![1, 1, 2]);
+    prusti_assert!(seq![1, 2, 1].to_multiset().count(1) == Int::new(2));
+    prusti_assert!(seq.to_multiset().len() == seq.len());
+    prusti_assert!((seq.to_multiset().count(x) > Int::new(0)) == seq.contains(x));
+    prusti_assert!(seq.concat(seq![x]).to_multiset().count(x) > Int::new(0));
+}
+
+fn to_multiset_fails() {
+    prusti_assert!(seq![1, 2, 1].to_multiset() == multiset![1, 2]); //~ ERROR: asserted expression might not hold
+}
+
+fn map_domain(map: Map, k: u32, v: u32) {
+    prusti_assert!(Map::empty().domain() == prusti_contracts::Set::empty());
+    prusti_assert!(map![1 => 2, 3 => 4].domain() == set![1, 3]);
+    prusti_assert!(map.insert(k, v).domain().contains(k));
+    prusti_assert!(map.domain().len() == map.len());
+}
+
+fn map_domain_fails(map: Map, k: u32) {
+    prusti_assert!(map.domain().contains(k)); //~ ERROR: asserted expression might not hold
+}
+
+fn main() {}
@@ -0,0 +1,58 @@
+// compile-flags: -Punsafe_core_proof=true
+
+#![allow(unused)]
+
+use prusti_contracts::{self as pc, *};
+
+type Set = prusti_contracts::Set<u32>;
+
+fn empty_set_zero_len() {
+    prusti_assert!(Set::empty().len() == Int::new(0));
+}
+
+fn empty_set_not_one_len() {
+    prusti_assert!(Set::empty().len() == Int::new(1)); //~ ERROR: asserted expression might not hold
+}
+
+fn set_eq() {
+    prusti_assert!(Set::empty() == Set::empty());
+    prusti_assert!(Set::single(1) == Set::single(1));
+    prusti_assert!(set![1, 2] == set![2, 1]);
+    prusti_assert!(set![1, 1] == set![1]);
+}
+
+fn set_neq() {
+    prusti_assert!(Set::single(1) == Set::single(2)); //~ ERROR: asserted expression might not hold
+}
+
+fn set_construction() {
+    let set: pc::Set<pc::Set<Int>> = pc::Set::empty();
+}
+
+fn membership() {
+    let set = set![1, 2, 3];
+    prusti_assert!(set.contains(2));
+    prusti_assert!(!set.contains(4));
+    prusti_assert!(set.len() == Int::new(3));
+}
+
+fn union_intersection_difference() {
+    let a = set![1, 2];
+    let b = set![2, 3];
+    prusti_assert!(a.union(b) == set![1, 2, 3]);
+    prusti_assert!(a.intersection(b) == set![2]);
+    prusti_assert!(a.difference(b) == set![1]);
+    prusti_assert!(a.intersection(b).is_subset(a));
+}
+
+#[requires(set.contains(x))]
+fn membership_precondition(set: Set, x: u32) {
+    prusti_assert!(set.union(Set::single(x)) == set);
+    prusti_assert!(set.len() > Int::new(0));
+}
+
+fn membership_fails(set: Set, x: u32) {
+    prusti_assert!(set.contains(x)); //~ ERROR: asserted expression might not hold
+}
+
+fn main() {}
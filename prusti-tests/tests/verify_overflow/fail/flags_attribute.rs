@@ -0,0 +1,24 @@
+use prusti_contracts::*;
+
+// The encoding of an integer type is shared by all items, so its bounds cannot
+// differ between items and these flags cannot be set for a single item. Prusti
+// does not look for conflicting overrides: `check_overflows` and
+// `encode_unsigned_num_constraint` are rejected on every item, whatever their
+// value, and can only be set for the whole crate.
+
+#[flags(check_overflows = false)]
+fn unchecked_half(a: u32) -> u32 { //~ ERROR invalid `#[flags]` attribute
+    a / 2
+}
+
+#[flags(check_overflows = true)]
+fn checked_half(a: u32) -> u32 { //~ ERROR invalid `#[flags]` attribute
+    a / 2
+}
+
+#[flags(encode_unsigned_num_constraint = true)]
+fn identity(a: u32) -> u32 { //~ ERROR invalid `#[flags]` attribute
+    a
+}
+
+fn main() {}
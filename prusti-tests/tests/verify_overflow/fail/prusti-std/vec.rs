@@ -0,0 +1,35 @@
+extern crate prusti_std;
+
+fn remove_from_empty() {
+    let mut v: Vec<i32> = Vec::new();
+    v.remove(0); //~ ERROR: precondition might not hold
+}
+
+fn index_out_of_bounds() {
+    let mut v = Vec::new();
+    v.push(1);
+    let _x = v[1]; //~ ERROR: precondition might not hold
+}
+
+fn popped_value() {
+    let mut v = Vec::new();
+    v.push(1);
+    v.push(2);
+    assert!(v.pop().unwrap() == 1); //~ ERROR: the asserted expression might not hold
+}
+
+fn unwrap_none() {
+    let x: Option<i32> = None;
+    x.unwrap(); //~ ERROR: precondition might not hold
+}
+
+fn overflowing_add(a: u8) -> u8 {
+    let r = a.checked_add(1);
+    r.unwrap() //~ ERROR: precondition might not hold
+}
+
+fn main() {
+    let mut v = Vec::new();
+    v.push(1);
+    assert!(v.len() == 2); //~ ERROR: the asserted expression might not hold
+}
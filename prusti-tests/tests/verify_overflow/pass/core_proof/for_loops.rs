@@ -0,0 +1,60 @@
+// compile-flags: -Punsafe_core_proof=true -Puse_smt_wrapper=true -Psmt_qi_bound_global=10000 -Psmt_qi_bound_trace=200 -Psmt_qi_bound_trace_kind=20 -Psmt_qi_bound_global_kind=100
+
+use prusti_contracts::*;
+
+fn range_index() {
+    let mut count = 0;
+    for i in 0..10usize {
+        body_invariant!(i + 1 == iteration_index());
+        body_invariant!(count == i);
+        count += 1;
+    }
+}
+
+fn range_visited() {
+    for i in 5..10u8 {
+        body_invariant!(visited::<u8>().len() == Int::new_usize(iteration_index()));
+        body_invariant!(visited::<u8>()[0] == 5);
+        assert!(i >= 5);
+    }
+}
+
+fn range_bounds(n: u32) {
+    for i in 0..n {
+        assert!(i < n);
+    }
+}
+
+fn slice_index(s: &[u32]) {
+    let mut count = 0;
+    for _ in s.iter() {
+        body_invariant!(count + 1 == iteration_index());
+        body_invariant!(iteration_index() <= s.len());
+        count += 1;
+    }
+}
+
+fn slice_visited(s: &[u32]) {
+    for x in s {
+        body_invariant!(*x == visited::<u32>()[iteration_index() - 1]);
+    }
+}
+
+struct Counter {
+    value: usize,
+}
+
+#[trusted]
+fn advance(counter: &mut Counter) -> Option<usize> {
+    unimplemented!()
+}
+
+// The same shape as the desugaring of a `for` loop over an unknown iterator.
+fn manual_next() {
+    let mut counter = Counter { value: 0 };
+    while let Some(_) = advance(&mut counter) {
+        body_invariant!(true);
+    }
+}
+
+fn main() {}
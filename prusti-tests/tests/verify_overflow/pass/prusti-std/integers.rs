@@ -0,0 +1,33 @@
+extern crate prusti_std;
+
+use prusti_contracts::*;
+
+#[ensures(a as u64 + b as u64 <= u32::MAX as u64 ==> result === Some(a + b))]
+#[ensures(a as u64 + b as u64 > u32::MAX as u64 ==> result.is_none())]
+fn add(a: u32, b: u32) -> Option<u32> {
+    a.checked_add(b)
+}
+
+#[ensures(a >= b ==> result == a - b)]
+#[ensures(a < b ==> result == 0)]
+fn distance_or_zero(a: usize, b: usize) -> usize {
+    a.saturating_sub(b)
+}
+
+#[ensures(result >= a && result >= b)]
+fn larger(a: i64, b: i64) -> i64 {
+    std::cmp::max(a, b)
+}
+
+fn main() {
+    assert!(255u8.checked_add(1).is_none());
+    assert!(254u8.checked_add(1).unwrap() == 255);
+    assert!(0u8.checked_sub(1).is_none());
+    assert!((-128i8).checked_mul(-1).is_none());
+    assert!(250u8.saturating_add(10) == 255);
+    assert!((-100i8).saturating_sub(100) == -128);
+    assert!(std::cmp::min(3, 5) == 3);
+    assert!(std::cmp::max(3u8, 5u8) == 5);
+    assert!(6i32.checked_mul(-7).unwrap() == -42);
+    assert!(u32::MAX.checked_mul(2).is_none());
+}
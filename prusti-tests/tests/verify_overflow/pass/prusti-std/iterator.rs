@@ -0,0 +1,28 @@
+extern crate prusti_std;
+
+use prusti_contracts::*;
+
+#[requires(r.start < r.end)]
+#[ensures(result == old(r.start))]
+#[ensures(r.start == old(r.start) + 1 && r.end == old(r.end))]
+fn first(r: &mut std::ops::Range<i32>) -> i32 {
+    r.next().unwrap()
+}
+
+fn main() {
+    let mut range = 3..5usize;
+    assert!(range.next().unwrap() == 3);
+    assert!(range.next().unwrap() == 4);
+    assert!(range.next().is_none());
+    assert!(range.next().is_none());
+    assert!(range.start == 5 && range.end == 5);
+
+    let mut empty = 2..1u8;
+    assert!(empty.next().is_none());
+    assert!(empty.start == 2);
+
+    let mut r = -1..1;
+    assert!(first(&mut r) == -1);
+    assert!(first(&mut r) == 0);
+    assert!(r.next().is_none());
+}
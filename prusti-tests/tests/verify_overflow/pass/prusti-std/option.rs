@@ -0,0 +1,27 @@
+extern crate prusti_std;
+
+use prusti_contracts::*;
+
+#[ensures(x.is_none() ==> result == 0)]
+fn value_or_zero(x: Option<u32>) -> u32 {
+    x.unwrap_or(0)
+}
+
+fn main() {
+    let mut x = Some(3);
+    assert!(x.is_some());
+    assert!(x.unwrap() == 3);
+    let y = x.take();
+    assert!(x.is_none());
+    assert!(y.expect("was some") == 3);
+    let old = x.replace(5);
+    assert!(old.is_none());
+    assert!(x.is_some());
+    assert!(value_or_zero(None) == 0);
+    assert!(x.ok_or(()).is_ok());
+    assert!(None::<u32>.ok_or(()).is_err());
+
+    let r: Result<u32, ()> = Ok(1);
+    assert!(r.ok().is_some());
+    assert!(r.err().is_none());
+}
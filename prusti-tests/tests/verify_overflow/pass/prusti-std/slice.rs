@@ -0,0 +1,26 @@
+extern crate prusti_std;
+
+use prusti_contracts::*;
+
+#[requires(s.len() > 1)]
+fn swap_ends(s: &mut [i32]) {
+    let last = s.len() - 1;
+    s.swap(0, last);
+    assert!(!s.is_empty());
+}
+
+fn main() {
+    let mut a = [1, 2, 3];
+    swap_ends(&mut a);
+    let s: &[i32] = &a;
+    assert!(s.first().is_some());
+    assert!(s.last().is_some());
+    let e: &[i32] = &[];
+    assert!(e.is_empty());
+    assert!(e.first().is_none());
+    assert!(s.get(2).is_some());
+    assert!(s.get(3).is_none());
+    assert!(e.get(0).is_none());
+    assert!("abc".len() == 3);
+    assert!(!"abc".is_empty());
+}
@@ -0,0 +1,23 @@
+extern crate prusti_std;
+
+use prusti_contracts::*;
+
+#[ensures(result.len() == 4 + name.len())]
+#[ensures(result.as_str().as_bytes()[0] == b'c')]
+fn command(name: &str) -> String {
+    let mut s = String::new();
+    s.push_str("cmd:");
+    s.push_str(name);
+    s
+}
+
+#[requires(s.as_str().starts_with("cmd:"))]
+#[ensures(result == s.len() - 4)]
+fn argument_len(s: &String) -> usize {
+    s.len() - 4
+}
+
+fn main() {
+    let c = command("ls");
+    assert!(c.len() == 6);
+}
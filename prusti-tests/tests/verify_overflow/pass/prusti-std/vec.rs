@@ -0,0 +1,53 @@
+extern crate prusti_std;
+
+use prusti_contracts::*;
+
+#[requires(v.len() > 0)]
+#[ensures(v.len() == old(v.len()) - 1)]
+fn drop_last(v: &mut Vec<i32>) {
+    let last = v.pop();
+    assert!(last.is_some());
+}
+
+#[requires(v.len() > 0)]
+#[ensures(v.len() == old(v.len()) + 1)]
+#[ensures(v[v.len() - 1] == old(v[0]))]
+fn push_first(v: &mut Vec<i32>) {
+    let first = v[0];
+    v.push(first);
+}
+
+fn main() {
+    let mut v = Vec::new();
+    assert!(v.is_empty());
+    v.push(1);
+    v.push(2);
+    v.push(3);
+    assert!(v.len() == 3);
+    v.insert(0, 4);
+    v.remove(1);
+    v.swap_remove(0);
+    assert!(v.len() == 2);
+    drop_last(&mut v);
+    v.truncate(5);
+    assert!(v.len() == 1);
+    v.clear();
+    assert!(v.pop().is_none());
+
+    v.push(7);
+    v.push(8);
+    assert!(v[0] == 7 && v[1] == 8);
+    assert!(v.get(1).is_some());
+    assert!(v.get(2).is_none());
+    push_first(&mut v);
+    assert!(v[2] == 7);
+    assert!(v.pop().unwrap() == 7);
+    assert!(v.pop().unwrap() == 8);
+    assert!(v[0] == 7);
+
+    let mut s = String::new();
+    s.push_str("abc");
+    assert!(s.len() == 3);
+    s.clear();
+    assert!(s.is_empty());
+}
@@ -20,6 +20,7 @@ impl A {
 
     #[pure]
     #[ensures(forall(|i: usize| (result && index < self.inner.len() && 0 <= i && i <= index) ==> (self.is_valid(i))) )]
+    #[terminates(Int::new_usize(index))]
     pub fn test(&self, index: usize) -> bool {
         match index {
             index if 0 < index && index < self.inner.len() => {
@@ -23,6 +23,7 @@ fn fib(n: i32) -> i32 {
     1 => 1,
     _ => fib2(n - 1) + fib2(n - 2),
 })]
+#[terminates(Int::new(n as i64))]
 fn fib2(n: i32) -> i32 {
     //~^ ERROR: only trusted functions can call themselves in their contracts
     //~^^ ERROR: Prusti encountered an unexpected internal error
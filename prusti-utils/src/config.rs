@@ -13,7 +13,7 @@ use ::config::{Config, Environment, File};
 use log::warn;
 use rustc_hash::FxHashSet;
 use serde::Deserialize;
-use std::{env, path::PathBuf, sync::RwLock};
+use std::{cell::RefCell, env, path::PathBuf, sync::RwLock};
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Optimizations {
@@ -80,6 +80,7 @@ lazy_static::lazy_static! {
         settings.set_default("check_foldunfold_state", false).unwrap();
         settings.set_default("check_overflows", true).unwrap();
         settings.set_default("check_panics", true).unwrap();
+        settings.set_default("check_pure_function_termination", true).unwrap();
         settings.set_default("encode_unsigned_num_constraint", true).unwrap();
         settings.set_default("encode_bitvectors", false).unwrap();
         settings.set_default("simplify_encoding", true).unwrap();
@@ -97,11 +98,13 @@ lazy_static::lazy_static! {
         settings.set_default("dump_reborrowing_dag_in_debug_info", false).unwrap();
         settings.set_default("dump_borrowck_info", false).unwrap();
         settings.set_default("dump_viper_program", false).unwrap();
+        settings.set_default("dump_verification_requests", false).unwrap();
         settings.set_default("foldunfold_state_filter", "").unwrap();
         settings.set_default::<Vec<String>>("extra_jvm_args", vec![]).unwrap();
         settings.set_default::<Vec<String>>("extra_verifier_args", vec![]).unwrap();
         settings.set_default("quiet", false).unwrap();
         settings.set_default("assert_timeout", 10_000).unwrap();
+        settings.set_default::<Option<u64>>("verification_timeout", None).unwrap();
         settings.set_default("smt_qi_eager_threshold", 1000).unwrap();
         settings.set_default("use_more_complete_exhale", true).unwrap();
         settings.set_default("skip_unsupported_features", false).unwrap();
@@ -112,6 +115,8 @@ lazy_static::lazy_static! {
         settings.set_default("opt_in_verification", false).unwrap();
         settings.set_default("full_compilation", false).unwrap();
         settings.set_default("json_communication", false).unwrap();
+        settings.set_default("server_job_priority", 0).unwrap();
+        settings.set_default("pipelined_verification", false).unwrap();
         settings.set_default("optimizations", "all").unwrap();
         settings.set_default("intern_names", true).unwrap();
         settings.set_default("enable_purification_optimization", false).unwrap();
@@ -140,7 +145,9 @@ lazy_static::lazy_static! {
         settings.set_default::<Option<String>>("execute_only_failing_trace", None).unwrap();
         settings.set_default::<Option<String>>("dump_fold_unfold_state_of_blocks", None).unwrap();
         settings.set_default("print_hash", false).unwrap();
+        settings.set_default::<Option<String>>("report_file", None).unwrap();
         settings.set_default("enable_cache", true).unwrap();
+        settings.set_default("fine_grained_cache", false).unwrap();
 
         settings.set_default("cargo_path", "cargo").unwrap();
         settings.set_default("cargo_command", "check").unwrap();
@@ -167,6 +174,7 @@ lazy_static::lazy_static! {
         settings.set_default("enable_verify_only_basic_block_path", false).unwrap();
         settings.set_default::<Vec<String>>("verify_only_basic_block_path", vec![]).unwrap();
         settings.set_default::<Vec<String>>("delete_basic_blocks", vec![]).unwrap();
+        settings.set_default::<Option<String>>("panic_while_encoding", None).unwrap();
 
         // Get the list of all allowed flags.
         let mut allowed_keys = get_keys(&settings);
@@ -262,17 +270,106 @@ pub fn dump() -> String {
     pairs.join("\n\n")
 }
 
+/// The type of the value of a flag that can be set for a single item.
+#[derive(Clone, Copy)]
+enum ItemFlagType {
+    Bool,
+    U32,
+    U64,
+}
+
+/// The flags that can be overridden for a single function, impl block or
+/// module with the `#[flags(..)]` attribute of `prusti_contracts`.
+/// `check_overflows` and `encode_unsigned_num_constraint` are not among them,
+/// because they determine the bounds of the integer types, whose encoding is
+/// shared by all items.
+const ITEM_FLAGS: &[(&str, ItemFlagType)] = &[
+    ("assert_timeout", ItemFlagType::U64),
+    ("check_panics", ItemFlagType::Bool),
+    ("check_timeout", ItemFlagType::U32),
+    ("encode_bitvectors", ItemFlagType::Bool),
+    ("smt_qi_eager_threshold", ItemFlagType::U64),
+    ("use_more_complete_exhale", ItemFlagType::Bool),
+    ("verification_timeout", ItemFlagType::U64),
+];
+
+thread_local! {
+    /// The flags of the item that is currently encoded, which take precedence
+    /// over all other sources of settings.
+    static ITEM_FLAGS_OVERRIDES: RefCell<Vec<(String, String)>> = RefCell::new(Vec::new());
+}
+
+/// Parse the comma-separated `flag=value` pairs of a `#[flags(..)]`
+/// attribute. Only the flags in `ITEM_FLAGS` can be set for an item.
+pub fn parse_item_flags(flags: &str) -> Result<Vec<(String, String)>, String> {
+    flags
+        .split(',')
+        .filter(|pair| !pair.trim().is_empty())
+        .map(|pair| {
+            let Some((flag, value)) = pair.split_once('=') else {
+                return Err(format!("expected `flag=value`, found `{pair}`"));
+            };
+            let (flag, value) = (flag.trim().to_lowercase(), value.trim());
+            let Some(&(_, flag_type)) = ITEM_FLAGS.iter().find(|(name, _)| *name == flag) else {
+                let supported: Vec<_> = ITEM_FLAGS.iter().map(|(name, _)| *name).collect();
+                return Err(format!(
+                    "the flag `{flag}` cannot be set for a single item; the supported flags are {}",
+                    supported.join(", ")
+                ));
+            };
+            let is_valid = match flag_type {
+                ItemFlagType::Bool => value.parse::<bool>().is_ok(),
+                ItemFlagType::U32 => value.parse::<u32>().is_ok(),
+                ItemFlagType::U64 => value.parse::<u64>().is_ok(),
+            };
+            if !is_valid {
+                return Err(format!("invalid value `{value}` for the flag `{flag}`"));
+            }
+            Ok((flag, value.to_string()))
+        })
+        .collect()
+}
+
+/// Call `f` with `flags` (as returned by `parse_item_flags`) overriding the
+/// settings on the current thread. Later pairs take precedence over earlier
+/// ones. The overrides of an enclosing call are replaced, not extended.
+pub fn with_item_flags<R>(flags: &[(String, String)], f: impl FnOnce() -> R) -> R {
+    let outer_flags = ITEM_FLAGS_OVERRIDES.with(|overrides| overrides.replace(flags.to_vec()));
+    let result = f();
+    ITEM_FLAGS_OVERRIDES.with(|overrides| *overrides.borrow_mut() = outer_flags);
+    result
+}
+
+fn read_item_flag<T>(name: &'static str) -> Option<T>
+where
+    T: Deserialize<'static>,
+{
+    ITEM_FLAGS_OVERRIDES.with(|overrides| {
+        let overrides = overrides.borrow();
+        let (_, value) = overrides.iter().rev().find(|(flag, _)| flag == name)?;
+        let value = config::Value::new(None, value.as_str());
+        Some(
+            value
+                .try_deserialize()
+                .unwrap_or_else(|e| panic!("Failed to read item flag {name} due to {e}")),
+        )
+    })
+}
+
 fn read_optional_setting<T>(name: &'static str) -> Option<T>
 where
     T: Deserialize<'static>,
 {
-    SETTINGS.read().unwrap().get(name).ok()
+    read_item_flag(name).or_else(|| SETTINGS.read().unwrap().get(name).ok())
 }
 
 fn read_setting<T>(name: &'static str) -> T
 where
     T: Deserialize<'static>,
 {
+    if let Some(value) = read_item_flag(name) {
+        return value;
+    }
     SETTINGS
         .read()
         .unwrap()
@@ -310,6 +407,8 @@ pub fn check_foldunfold_state() -> bool {
 ///   [Carbon](https://github.com/viperproject/carbon).
 /// - `Silicon` - symbolic-execution-based backend
 ///   [Silicon](https://github.com/viperproject/silicon/).
+/// - `Smt` - encodes core proof programs directly into SMT-LIB and runs the
+///   SMT solver without starting a JVM. Permissions are not checked.
 pub fn viper_backend() -> String {
     read_setting::<String>("viper_backend")
         .to_lowercase()
@@ -366,6 +465,13 @@ pub fn check_panics() -> bool {
     read_setting("check_panics")
 }
 
+/// When enabled, recursive calls in pure functions must decrease the
+/// termination measure of the function or, if it has none, be structurally
+/// recursive.
+pub fn check_pure_function_termination() -> bool {
+    read_setting("check_pure_function_termination")
+}
+
 /// When enabled, the encoded program is simplified before it is passed to
 /// the Viper backend.
 pub fn simplify_encoding() -> bool {
@@ -415,10 +521,21 @@ pub fn dump_borrowck_info() -> bool {
     read_setting("dump_borrowck_info")
 }
 
-/// When enabled, the encoded Viper program will be output.
+/// When enabled, the encoded Viper program will be output. Together with
+/// `no_verify`, the programs are encoded and printed without verifying them.
 pub fn dump_viper_program() -> bool {
     read_setting("dump_viper_program")
 }
+pub fn set_dump_viper_program(value: bool) {
+    write_setting("dump_viper_program", value);
+}
+
+/// When enabled, every verification request sent to the verifier is
+/// serialized as JSON, so that it can be replayed offline with
+/// `prusti-server --replay <FILE>`.
+pub fn dump_verification_requests() -> bool {
+    read_setting("dump_verification_requests")
+}
 
 /// Filter for `fold`/`unfold` nodes when debug info is dumped.
 pub fn foldunfold_state_filter() -> String {
@@ -509,6 +626,15 @@ pub fn check_timeout() -> Option<u32> {
     read_setting("check_timeout")
 }
 
+/// Maximum wall-clock time (in seconds) for the verification of a single
+/// procedure. Unlike `ASSERT_TIMEOUT` and `CHECK_TIMEOUT`, the budget is
+/// enforced by Prusti for every backend, and exceeding it is reported as a
+/// timeout instead of a verification error. Set to None to disable the
+/// timeout. Can be overridden for a single function with `#[timeout(..)]`.
+pub fn verification_timeout() -> Option<u64> {
+    read_setting("verification_timeout")
+}
+
 /// When enabled, a more complete `exhale` version is used in the verifier.
 /// See [`consolidate`](https://github.com/viperproject/silicon/blob/f48de7f6e2d90d9020812869c713a5d3e2035995/src/main/scala/rules/StateConsolidator.scala#L29-L46).
 /// Equivalent to the verifier command-line argument
@@ -614,6 +740,13 @@ pub fn print_hash() -> bool {
     read_setting("print_hash")
 }
 
+/// When set, a machine-readable report of the verification outcome of each
+/// item is written in JSON to the given file, and in SARIF to the same path
+/// with the `sarif` extension.
+pub fn report_file() -> Option<String> {
+    read_setting("report_file")
+}
+
 /// When enabled, verification requests (to verify individual `fn`s) are cached
 /// to improve future verification. By default the cache is only saved in
 /// memory (of the `prusti-server` if enabled). For long-running verification
@@ -622,6 +755,14 @@ pub fn enable_cache() -> bool {
     read_setting("enable_cache")
 }
 
+/// When enabled together with `ENABLE_CACHE`, each method of a verification
+/// request is verified and cached separately, keyed by the declarations it
+/// transitively depends on. Editing one method then does not invalidate the
+/// cached results of unrelated methods.
+pub fn fine_grained_cache() -> bool {
+    read_setting("fine_grained_cache")
+}
+
 /// Maximum amount of instantiated Viper verifiers the server will keep around
 /// for reuse. If not set, defaults to
 /// `SERVER_MAX_CONCURRENT_VERIFICATION_OPERATIONS`. It also doesn't make much
@@ -639,8 +780,9 @@ pub fn server_max_stored_verifiers() -> Option<usize> {
 }
 
 /// Maximum amount of verification requests the server will work on
-/// concurrently. If not set, defaults to the number of (logical) cores on
-/// the system.
+/// concurrently. If larger than one, the server verifies the requests in a
+/// pool of this many worker processes, each with its own JVM. If not set, the
+/// server verifies one request at a time in its own process.
 pub fn server_max_concurrency() -> Option<usize> {
     read_optional_setting("server_max_concurrency")
 }
@@ -654,12 +796,27 @@ pub fn server_address() -> Option<String> {
     read_optional_setting("server_address")
 }
 
+/// The priority of the verification requests sent to the server. Requests
+/// with a higher priority are verified first; requests with the same priority
+/// are verified in the order in which they were received.
+pub fn server_job_priority() -> i32 {
+    read_setting("server_job_priority")
+}
+
 /// When enabled, communication with the server will be encoded as JSON
 /// instead of the default bincode.
 pub fn json_communication() -> bool {
     read_setting("json_communication")
 }
 
+/// When enabled, each procedure is sent to the verification backend as soon
+/// as its encoding is finished, and its verification errors are reported
+/// while the remaining procedures are still being encoded. Otherwise, the
+/// whole crate is encoded before the first program is verified.
+pub fn pipelined_verification() -> bool {
+    read_setting("pipelined_verification")
+}
+
 /// When enabled, Viper name mangling will be disabled.
 ///
 /// **Note:** This is very likely to result in invalid programs being generated
@@ -958,6 +1115,12 @@ pub fn delete_basic_blocks() -> Vec<String> {
     read_setting("delete_basic_blocks")
 }
 
+/// The encoder will panic when it starts encoding the item with the given
+/// unique name. Used for testing.
+pub fn panic_while_encoding() -> Option<String> {
+    read_setting("panic_while_encoding")
+}
+
 /// When enabled, features not supported by Prusti will be reported as warnings
 /// rather than errors.
 pub fn skip_unsupported_features() -> bool {
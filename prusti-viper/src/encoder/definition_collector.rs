@@ -242,6 +242,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> Collector<'p, 'v, 'tcx> {
                         && !contains(&self.new_unfolded_predicates, predicate_name)
                         && !predicate_name.starts_with("Slice$")
                         && !predicate_name.starts_with("Array$")
+                        && predicate_name != "Str"
                     {
                         // The type is never unfolded, so the snapshot could be
                         // abstract (not always, see #738). An exception is the discriminant
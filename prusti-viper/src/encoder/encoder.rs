@@ -16,20 +16,25 @@ use crate::error_unsupported;
 use prusti_common::{vir_expr, vir_local};
 use prusti_common::config;
 use prusti_common::report::log;
+use prusti_common::Stopwatch;
 use prusti_interface::data::ProcedureDefId;
 use prusti_interface::environment::Environment;
 use prusti_interface::specs::typed;
 use prusti_interface::PrustiError;
+use prusti_interface::utils::{read_prusti_attr, read_prusti_attrs};
 use vir_crate::polymorphic::{self as vir};
 use vir_crate::common::identifier::WithIdentifier;
 use prusti_rustc_interface::hir::def_id::DefId;
 use prusti_rustc_interface::middle::mir;
 use prusti_rustc_interface::middle::ty;
+use prusti_rustc_interface::span::{fatal_error::FatalErrorMarker, Span, DUMMY_SP};
 use std::cell::{Cell, RefCell, RefMut, Ref};
 use std::fmt::Debug;
 use rustc_hash::{FxHashSet, FxHashMap};
 use std::io::Write;
+use std::panic::{self, AssertUnwindSafe};
 use std::rc::Rc;
+use std::time::Duration;
 use crate::encoder::stub_procedure_encoder::StubProcedureEncoder;
 use std::ops::AddAssign;
 use prusti_interface::specs::typed::ProcedureSpecificationKind;
@@ -95,6 +100,19 @@ pub struct Encoder<'v, 'tcx: 'v> {
     vir_program_before_foldunfold_writer: Option<RefCell<Box<dyn Write>>>,
     vir_program_before_viper_writer: Option<RefCell<Box<dyn Write>>>,
     encoding_errors_counter: RefCell<usize>,
+    /// The encoding errors reported since the encoding of the current task
+    /// started. Only collected when a verification report is requested.
+    reported_encoding_errors: RefCell<Vec<SpannedEncodingError>>,
+    procedure_encoding_summaries: Vec<ProcedureEncodingSummary>,
+    /// The span of the item during whose encoding the encoder panicked. The
+    /// caches of the encoder may then be in an inconsistent state, so no
+    /// further items are encoded.
+    crashed_item_span: Option<Span>,
+    /// The error reporting the crash, emitted once the encoding queue is
+    /// empty together with the number of items that were not encoded.
+    crash_error: Option<PrustiError>,
+    /// The number of items that were not encoded because of the crash.
+    items_skipped_after_crash: usize,
     name_interner: RefCell<NameInterner>,
     /// Maps locals to the local of their discriminant.
     pub(super) discriminants_state: DiscriminantsState,
@@ -104,6 +122,15 @@ pub struct Encoder<'v, 'tcx: 'v> {
     /// this requires special care when encoding array/slice accesses which may come with
     /// bound checks included in the MIR.
     pub(super) is_encoding_trigger: Cell<bool>,
+    /// Whether the specification that's being encoded is the contract of a
+    /// called procedure. Warnings about the quantifiers of a specification
+    /// are reported only when encoding the item that owns it.
+    pub(super) is_encoding_callee_contract: Cell<bool>,
+    /// The quantifiers whose trigger warnings have already been reported.
+    pub(super) quantifiers_with_reported_triggers: RefCell<FxHashSet<DefId>>,
+    /// The flags set by the `#[flags(..)]` and `#[timeout(..)]` attributes of
+    /// each item, without the flags of the enclosing items.
+    own_item_flags: RefCell<FxHashMap<DefId, Vec<(String, String)>>>,
 }
 
 pub enum EncodingTask<'tcx> {
@@ -116,6 +143,17 @@ pub enum EncodingTask<'tcx> {
     },
 }
 
+/// A summary of the encoding of a procedure, used to report the outcome of its
+/// verification.
+pub struct ProcedureEncodingSummary {
+    pub def_id: ProcedureDefId,
+    /// The time spent encoding the procedure.
+    pub duration: Duration,
+    pub is_trusted: bool,
+    /// The encoding errors reported while encoding the procedure.
+    pub encoding_errors: Vec<SpannedEncodingError>,
+}
+
 // If the field name is an identifier, removing the leading prefix r#
 pub fn encode_field_name(field_name: &str) -> String {
    format!("f${}", field_name.trim_start_matches("r#"))
@@ -177,8 +215,16 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
             snapshot_encoder_state: Default::default(),
             mirror_encoder: RefCell::new(MirrorEncoder::new()),
             encoding_errors_counter: RefCell::new(0),
+            reported_encoding_errors: RefCell::new(vec![]),
+            procedure_encoding_summaries: Vec::new(),
+            crashed_item_span: None,
+            crash_error: None,
+            items_skipped_after_crash: 0,
             name_interner: RefCell::new(NameInterner::new()),
             is_encoding_trigger: Cell::new(false),
+            is_encoding_callee_contract: Cell::new(false),
+            quantifiers_with_reported_triggers: RefCell::new(FxHashSet::default()),
+            own_item_flags: RefCell::new(FxHashMap::default()),
             specifications_state: SpecificationsState::new(def_spec),
             mir_procedure_mapping: Default::default(),
             discriminants_state: Default::default(),
@@ -255,6 +301,9 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
 
     #[tracing::instrument(level = "debug", skip(self))]
     pub(in crate::encoder) fn register_encoding_error(&self, encoding_error: SpannedEncodingError) {
+        if config::report_file().is_some() {
+            self.reported_encoding_errors.borrow_mut().push(encoding_error.clone());
+        }
         let prusti_error: PrustiError = encoding_error.into();
         if prusti_error.is_error() {
             self.encoding_errors_counter.borrow_mut().add_assign(1);
@@ -266,6 +315,47 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         *self.encoding_errors_counter.borrow()
     }
 
+    /// The configuration flags that are overridden for the given item by the
+    /// `#[flags(..)]` and `#[timeout(..)]` attributes of the item and of its
+    /// enclosing impl blocks and modules, to be used with
+    /// `config::with_item_flags`. The flags of inner items come last, so that
+    /// they take precedence.
+    pub fn item_flags(&self, def_id: DefId) -> Vec<(String, String)> {
+        let tcx = self.env.tcx();
+        let mut items = vec![def_id];
+        while let Some(parent) = tcx.opt_parent(*items.last().unwrap()) {
+            items.push(parent);
+        }
+        items.into_iter().rev().flat_map(|item| self.own_item_flags(item)).collect()
+    }
+
+    fn own_item_flags(&self, def_id: DefId) -> Vec<(String, String)> {
+        if let Some(flags) = self.own_item_flags.borrow().get(&def_id) {
+            return flags.clone();
+        }
+        let attrs = self.env.query.get_attributes(def_id);
+        let mut flags = vec![];
+        for raw_flags in read_prusti_attrs("flags", attrs) {
+            match config::parse_item_flags(&raw_flags) {
+                Ok(item_flags) => flags.extend(item_flags),
+                Err(message) => self.register_encoding_error(SpannedEncodingError::incorrect(
+                    format!("invalid `#[flags]` attribute: {message}"),
+                    self.env.query.get_def_span(def_id),
+                )),
+            }
+        }
+        if let Some(timeout) = read_prusti_attr("timeout", attrs) {
+            flags.push(("verification_timeout".to_string(), timeout));
+        }
+        self.own_item_flags.borrow_mut().insert(def_id, flags.clone());
+        flags
+    }
+
+    /// Take the summaries of the procedures encoded so far.
+    pub fn take_procedure_encoding_summaries(&mut self) -> Vec<ProcedureEncodingSummary> {
+        std::mem::take(&mut self.procedure_encoding_summaries)
+    }
+
     pub(super) fn get_mirror_domain(&self) -> Option<vir::Domain> {
         self.mirror_encoder.borrow().get_domain().cloned()
     }
@@ -607,6 +697,12 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         ty: ty::Ty<'tcx>,
         value: mir::ConstantKind<'tcx>
     ) -> EncodingResult<vir::Expr> {
+        if let ty::TyKind::Ref(_, str_ty, _) = ty.kind() {
+            if str_ty.is_str() {
+                return self.encode_str_const_expr(*str_ty, value);
+            }
+        }
+
         let scalar_value = self.const_eval_intlike(value)?;
 
         let expr = match ty.kind() {
@@ -651,6 +747,28 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
         Ok(expr)
     }
 
+    /// Encodes a string literal as the snapshot of its bytes.
+    fn encode_str_const_expr(
+        &self,
+        str_ty: ty::Ty<'tcx>,
+        value: mir::ConstantKind<'tcx>,
+    ) -> EncodingResult<vir::Expr> {
+        let bytes = match value {
+            mir::ConstantKind::Val(const_value @ mir::interpret::ConstValue::Slice { .. }, _) => {
+                mir::interpret::get_slice_bytes(&self.env.tcx(), const_value)
+            }
+            _ => error_unsupported!("unsupported string constant {:?}", value),
+        };
+        let elems = vir::Expr::Seq(vir::Seq {
+            typ: vir::Type::Seq(vir::SeqType {
+                typ: box vir::Type::Int,
+            }),
+            elements: bytes.iter().map(|&byte| byte.into()).collect(),
+            position: vir::Position::default(),
+        });
+        self.encode_snapshot(str_ty, None, vec![elems])
+    }
+
     #[tracing::instrument(level = "debug", skip(self), ret)]
     pub fn encode_int_cast(&self, value: u128, ty: ty::Ty<'tcx>) -> vir::Expr {
         let expr = match ty.kind() {
@@ -699,102 +817,216 @@ impl<'v, 'tcx> Encoder<'v, 'tcx> {
 
     #[tracing::instrument(level = "debug", skip(self))]
     pub fn process_encoding_queue(&mut self) {
+        self.initialize_encoding();
+        while self.process_next_encoding_task() {}
+    }
+
+    /// Prepares the encoder for processing the encoding queue. Has to be
+    /// called once before the first call to `process_next_encoding_task`.
+    pub fn initialize_encoding(&mut self) {
         if let Err(error) = self.initialize() {
             panic!("The initialization of the encoder failed with the error: {error:?}");
         }
-        while let Some(task) = {
-            let mut queue = self.encoding_queue.borrow_mut();
-            queue.pop()
-        } {
-            match task {
-                EncodingTask::Procedure { def_id: proc_def_id, substs } => {
-                    let proc_name = self.env.name.get_unique_item_name(proc_def_id);
-                    let proc_def_path = self.env.name.get_item_def_path(proc_def_id);
-                    info!("Encoding: {} ({})", proc_name, proc_def_path);
-                    assert!(substs.is_empty());
-
-                    if config::unsafe_core_proof() {
-                        if self.env.query.is_unsafe_function(proc_def_id) {
-                            if let Err(error) = self.encode_lifetimes_core_proof(proc_def_id, CheckMode::Both) {
-                                self.register_encoding_error(error);
-                                debug!("Error encoding function: {:?} {}", proc_def_id, CheckMode::Both);
-                            }
-                        } else {
-                            if config::verify_core_proof() {
-                                if let Err(error) = self.encode_lifetimes_core_proof(proc_def_id, CheckMode::CoreProof) {
-                                    self.register_encoding_error(error);
-                                    debug!("Error encoding function: {:?} {}", proc_def_id, CheckMode::CoreProof);
-                                }
-                            }
-                            if config::verify_specifications() {
-                                let check_mode = if config::verify_specifications_with_core_proof() {
-                                    CheckMode::Both
-                                } else {
-                                    CheckMode::Specifications
-                                };
-                                if let Err(error) = self.encode_lifetimes_core_proof(proc_def_id, check_mode) {
-                                    self.register_encoding_error(error);
-                                    debug!("Error encoding function: {:?} {}", proc_def_id, check_mode);
-                                }
-                            }
-                        }
-                        continue;
+    }
+
+    /// Encodes the next task of the encoding queue. The resulting programs can
+    /// be obtained with `get_viper_programs` and `get_core_proof_programs`.
+    /// Returns `false` if the queue was already empty.
+    pub fn process_next_encoding_task(&mut self) -> bool {
+        let Some(task) = self.encoding_queue.borrow_mut().pop() else {
+            self.report_crash();
+            return false;
+        };
+        self.encode_task(task);
+        true
+    }
+
+    fn encode_task(&mut self, task: EncodingTask<'tcx>) {
+        match task {
+            EncodingTask::Procedure { def_id: proc_def_id, substs } => {
+                let stopwatch = Stopwatch::start_debug("prusti-viper", "encoding of a procedure");
+                let item_flags = self.item_flags(proc_def_id);
+                let span = self.env.query.get_def_span(proc_def_id);
+                if self.crashed_item_span.is_some() {
+                    self.items_skipped_after_crash += 1;
+                    // Only recorded in the verification report; the skipped
+                    // items are mentioned in the error reporting the crash.
+                    if config::report_file().is_some() {
+                        self.reported_encoding_errors.borrow_mut().push(SpannedEncodingError::internal(
+                            "the procedure was not encoded because the encoder crashed while encoding another item",
+                            span,
+                        ));
                     }
+                } else {
+                    config::with_item_flags(&item_flags, || {
+                        self.catch_encoding_panic(span, |encoder| {
+                            encoder.encode_procedure_task(proc_def_id, substs)
+                        })
+                    });
+                }
+                let summary = ProcedureEncodingSummary {
+                    def_id: proc_def_id,
+                    duration: stopwatch.finish(),
+                    is_trusted: self.is_trusted(proc_def_id, None),
+                    encoding_errors: self.reported_encoding_errors.take(),
+                };
+                self.procedure_encoding_summaries.push(summary);
+            }
+            EncodingTask::Type { ty } => {
+                if config::unsafe_core_proof() && config::verify_core_proof() && config::verify_types() && self.crashed_item_span.is_none() {
+                    let span = match ty.kind() {
+                        ty::TyKind::Adt(adt_def, _) => self.env.query.get_def_span(adt_def.did()),
+                        _ => DUMMY_SP,
+                    };
+                    self.catch_encoding_panic(span, |encoder| {
+                        if let Err(error) = encoder.encode_core_proof_for_type(ty, CheckMode::CoreProof) {
+                            encoder.register_encoding_error(error);
+                            debug!("Error encoding type: {:?} {}", ty, CheckMode::CoreProof);
+                        }
+                    });
+                    // The verification report only has entries for procedures,
+                    // so the errors of a type must not be attributed to the
+                    // next encoded procedure.
+                    self.reported_encoding_errors.take();
+                }
+            }
+        }
+    }
 
-                    let proc_kind = self.get_proc_kind(proc_def_id, None);
+    /// Runs `encode`, reporting a panic as an internal error at `span` instead
+    /// of aborting. The programs encoded before the panic are kept, but the
+    /// encoder is poisoned: the programs of the crashed item are dropped and
+    /// the remaining items are not encoded, because the caches of the encoder
+    /// may have been left half-updated. The crash is reported by `report_crash`
+    /// once all items have been processed. Fatal errors of the compiler are
+    /// propagated.
+    fn catch_encoding_panic(&mut self, span: Span, encode: impl FnOnce(&mut Self)) {
+        let program_count = self.programs.len();
+        let core_proof_program_count = self.core_proof_program_count();
+        // The panic hook still reports the panic; we only avoid aborting.
+        let result = panic::catch_unwind(AssertUnwindSafe(|| encode(self)));
+        if let Err(payload) = result {
+            if payload.is::<FatalErrorMarker>() {
+                panic::resume_unwind(payload);
+            }
+            self.crashed_item_span = Some(span);
+            self.programs.truncate(program_count);
+            self.truncate_core_proof_programs(core_proof_program_count);
+            let message = if let Some(message) = payload.downcast_ref::<&str>() {
+                message.to_string()
+            } else if let Some(message) = payload.downcast_ref::<String>() {
+                message.clone()
+            } else {
+                "unknown panic".to_string()
+            };
+            let crash_error =
+                SpannedEncodingError::internal(format!("the encoder crashed: {message}"), span);
+            if config::report_file().is_some() {
+                self.reported_encoding_errors.borrow_mut().push(crash_error.clone());
+            }
+            self.crash_error = Some(crash_error.into());
+        }
+    }
 
-                    if matches!(proc_kind, ProcedureSpecificationKind::Pure) {
-                        // Check that the pure Rust function satisfies the basic
-                        // requirements by trying to encode it as a Viper function,
-                        // which will automatically run the validity checks.
+    /// Emits a single error for a crash of the encoder, which also mentions
+    /// the items that were not encoded because of it.
+    fn report_crash(&mut self) {
+        let Some(mut crash_error) = self.crash_error.take() else {
+            return;
+        };
+        let skipped_items = match self.items_skipped_after_crash {
+            0 => None,
+            1 => Some("1 other item was".to_string()),
+            count => Some(format!("{count} other items were")),
+        };
+        if let Some(skipped_items) = skipped_items {
+            crash_error.add_note_mut(format!("{skipped_items} not verified because of the crash"), None);
+        }
+        if crash_error.is_error() {
+            self.encoding_errors_counter.borrow_mut().add_assign(1);
+        }
+        crash_error.emit(&self.env.diagnostic);
+    }
 
-                        // TODO: Make sure that this encoded function does not end up in
-                        // the Viper file because that would be unsound.
-                        let identity_substs = self.env.query.identity_substs(proc_def_id);
-                        if let Err(error) = self.encode_pure_function_def(proc_def_id, proc_def_id, identity_substs) {
-                            self.register_encoding_error(error);
-                            debug!("Error encoding function: {:?}", proc_def_id);
-                            // Skip encoding the function as a method.
-                            continue;
-                        }
-                    }
+    fn encode_procedure_task(&mut self, proc_def_id: ProcedureDefId, substs: Vec<(ty::Ty<'tcx>, ty::Ty<'tcx>)>) {
+        let proc_name = self.env.name.get_unique_item_name(proc_def_id);
+        let proc_def_path = self.env.name.get_item_def_path(proc_def_id);
+        info!("Encoding: {} ({})", proc_name, proc_def_path);
+        assert!(substs.is_empty());
+        if config::panic_while_encoding().as_ref() == Some(&proc_name) {
+            panic!("the encoder was asked to panic while encoding {proc_name}");
+        }
 
-                    match proc_kind {
-                        _ if self.is_trusted(proc_def_id, None) => {
-                            debug!(
-                                "Trusted procedure will not be encoded or verified: {:?}",
-                                proc_def_id
-                            );
-                        },
-                        ProcedureSpecificationKind::Predicate(_) => {
-                            debug!(
-                                "Predicates will not be encoded or verified: {:?}",
-                                proc_def_id
-                            );
-                        },
-                        ProcedureSpecificationKind::Pure |
-                        ProcedureSpecificationKind::Impure => {
-                            if let Err(error) = self.encode_procedure(proc_def_id) {
-                                self.register_encoding_error(error);
-                                debug!("Error encoding function: {:?}", proc_def_id);
-                            } else {
-                                match self.finalize_viper_program(proc_name, proc_def_id) {
-                                    Ok(program) => self.programs.push(program),
-                                    Err(error) => {
-                                        self.register_encoding_error(error);
-                                        debug!("Error finalizing program: {:?}", proc_def_id);
-                                    }
-                                }
-                            }
-                        }
+        if config::unsafe_core_proof() {
+            if self.env.query.is_unsafe_function(proc_def_id) {
+                if let Err(error) = self.encode_lifetimes_core_proof(proc_def_id, CheckMode::Both) {
+                    self.register_encoding_error(error);
+                    debug!("Error encoding function: {:?} {}", proc_def_id, CheckMode::Both);
+                }
+            } else {
+                if config::verify_core_proof() {
+                    if let Err(error) = self.encode_lifetimes_core_proof(proc_def_id, CheckMode::CoreProof) {
+                        self.register_encoding_error(error);
+                        debug!("Error encoding function: {:?} {}", proc_def_id, CheckMode::CoreProof);
                     }
-
                 }
-                EncodingTask::Type { ty } => {
-                    if config::unsafe_core_proof() && config::verify_core_proof() && config::verify_types() {
-                        if let Err(error) = self.encode_core_proof_for_type(ty, CheckMode::CoreProof) {
+                if config::verify_specifications() {
+                    let check_mode = if config::verify_specifications_with_core_proof() {
+                        CheckMode::Both
+                    } else {
+                        CheckMode::Specifications
+                    };
+                    if let Err(error) = self.encode_lifetimes_core_proof(proc_def_id, check_mode) {
+                        self.register_encoding_error(error);
+                        debug!("Error encoding function: {:?} {}", proc_def_id, check_mode);
+                    }
+                }
+            }
+            return;
+        }
+
+        let proc_kind = self.get_proc_kind(proc_def_id, None);
+
+        if matches!(proc_kind, ProcedureSpecificationKind::Pure) {
+            // Check that the pure Rust function satisfies the basic
+            // requirements by trying to encode it as a Viper function,
+            // which will automatically run the validity checks.
+
+            // TODO: Make sure that this encoded function does not end up in
+            // the Viper file because that would be unsound.
+            let identity_substs = self.env.query.identity_substs(proc_def_id);
+            if let Err(error) = self.encode_pure_function_def(proc_def_id, proc_def_id, identity_substs) {
+                self.register_encoding_error(error);
+                debug!("Error encoding function: {:?}", proc_def_id);
+                // Skip encoding the function as a method.
+                return;
+            }
+        }
+
+        match proc_kind {
+            _ if self.is_trusted(proc_def_id, None) => {
+                debug!(
+                    "Trusted procedure will not be encoded or verified: {:?}",
+                    proc_def_id
+                );
+            },
+            ProcedureSpecificationKind::Predicate(_) => {
+                debug!(
+                    "Predicates will not be encoded or verified: {:?}",
+                    proc_def_id
+                );
+            },
+            ProcedureSpecificationKind::Pure |
+            ProcedureSpecificationKind::Impure => {
+                if let Err(error) = self.encode_procedure(proc_def_id) {
+                    self.register_encoding_error(error);
+                    debug!("Error encoding function: {:?}", proc_def_id);
+                } else {
+                    match self.finalize_viper_program(proc_name, proc_def_id) {
+                        Ok(program) => self.programs.push(program),
+                        Err(error) => {
                             self.register_encoding_error(error);
-                            debug!("Error encoding type: {:?} {}", ty, CheckMode::CoreProof);
+                            debug!("Error finalizing program: {:?}", proc_def_id);
                         }
                     }
                 }
@@ -86,12 +86,20 @@ pub enum ErrorCtxt {
     LoopVariantOnEntry,
     LoopVariantAfterIteration,
     LoopVariantNonDecreased,
+    /// A component of a lexicographic loop variant might have increased while
+    /// all components before it stayed the same.
+    /// Arguments: the index of the component
+    LoopVariantComponentIncreased(usize),
     /// If a loop needs to terminate and no loop variant is provided
     UnexpectedReachableLoop,
     /// If a call needs to terminate and it does not necessarily terminate
     UnexpectedReachableCall,
     /// Termination measure of a call might not be lower
     CallTerminationMeasureLower,
+    /// A component of the lexicographic termination measure of a call might
+    /// be larger while all components before it are the same.
+    /// Arguments: the index of the component
+    CallTerminationMeasureComponentIncreased(usize),
     /// The termination measure of a call might be negative
     CallTerminationMeasureNonNegative,
     /// Finding the value of the termination measure at the begin of a method unexpectedly caused an error
@@ -149,6 +157,14 @@ pub enum ErrorCtxt {
     /// A Viper `assert e1 ==> e2` that encodes a strengthening of the precondition
     /// of a method implementation of a trait.
     AssertMethodPostconditionStrengthening,
+    /// A Viper `assert` that checks that the precondition of a function is
+    /// implied by the precondition of the function pointer type it is
+    /// coerced to.
+    AssertFnPointerPreconditionWeakening,
+    /// A Viper `assert` that checks that the postcondition of a function
+    /// implies the postcondition of the function pointer type it is coerced
+    /// to.
+    AssertFnPointerPostconditionStrengthening,
     /// A cast like `usize as u32`.
     TypeCast,
     /// A Viper `assert false` that encodes an unsupported feature.
@@ -262,6 +278,13 @@ impl<'tcx> ErrorManager<'tcx> {
             .and_then(|id| self.position_manager.def_id.get(&id).copied())
     }
 
+    /// The context registered on the offending position of the error, if any.
+    pub fn get_error_ctxt(&self, ver_error: &VerificationError) -> Option<&ErrorCtxt> {
+        ver_error.offending_pos_id.as_ref()
+            .and_then(|id| id.parse::<u64>().ok())
+            .and_then(|id| self.error_contexts.get(&id))
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     pub fn translate_verification_error(&self, ver_error: &VerificationError) -> PrustiError {
         let opt_pos_id: Option<u64> = match ver_error.offending_pos_id {
@@ -605,6 +628,18 @@ impl<'tcx> ErrorManager<'tcx> {
                     .set_help("The implemented method's postcondition should imply the trait's postcondition.")
             }
 
+            ("assert.failed:assertion.false", ErrorCtxt::AssertFnPointerPreconditionWeakening) => {
+                PrustiError::verification("the function's precondition may not be implied by the precondition of the function pointer type.".to_string(), error_span)
+                    .set_failing_assertion(opt_cause_span)
+                    .set_help("The precondition of the function pointer type should imply the precondition of the function.")
+            }
+
+            ("assert.failed:assertion.false", ErrorCtxt::AssertFnPointerPostconditionStrengthening) => {
+                PrustiError::verification("the function's postcondition may not imply the postcondition of the function pointer type.".to_string(), error_span)
+                    .set_failing_assertion(opt_cause_span)
+                    .set_help("The postcondition of the function should imply the postcondition of the function pointer type.")
+            }
+
             ("assert.failed:assertion.false", ErrorCtxt::BoundsCheckAssert) |
             ("application.precondition:assertion.false", ErrorCtxt::BoundsCheckAssert) => {
                 PrustiError::verification(
@@ -670,6 +705,17 @@ impl<'tcx> ErrorManager<'tcx> {
                 )
             }
 
+            ("assert.failed:assertion.false", ErrorCtxt::CallTerminationMeasureComponentIncreased(component)) => {
+                PrustiError::verification(
+                    format!(
+                        "the termination measure of this call is not necessarily lower: \
+                        component {} might increase",
+                        component + 1
+                    ),
+                    error_span
+                )
+            }
+
             ("assert.failed:assertion.false", ErrorCtxt::CallTerminationMeasureNonNegative) => {
                 PrustiError::verification(
                     "the termination measure of this call might become negative".to_string(),
@@ -689,6 +735,15 @@ impl<'tcx> ErrorManager<'tcx> {
                     error_span
                 )
             }
+            ("assert.failed:assertion.false", ErrorCtxt::LoopVariantComponentIncreased(component)) => {
+                PrustiError::verification(
+                    format!(
+                        "The loop variant might not have decreased: component {} might have increased",
+                        component + 1
+                    ),
+                    error_span
+                )
+            }
             ("assert.failed:assertion.false", ErrorCtxt::LoopVariantAfterIteration) => {
                 PrustiError::verification(
                     "The loop variant might go below zero while the loop continues".to_string(),
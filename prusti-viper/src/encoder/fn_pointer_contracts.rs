@@ -0,0 +1,327 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Module that determines the contracts of the function pointers of a
+//! procedure.
+//!
+//! The contract of a function pointer comes from the declared type of the
+//! place that stores it: a parameter, the result, a `let` binding with a type
+//! annotation or a struct field whose type is a function pointer type alias
+//! with a specification. The remaining locals of function pointer type (e.g.
+//! temporaries) get the contract of the place they are moved into or, if
+//! there is none, of the place they are copied from.
+
+use crate::encoder::{mir::specifications::SpecificationsInterface, Encoder};
+use prusti_rustc_interface::{
+    hir::{
+        self,
+        def::{DefKind, Res},
+        def_id::DefId,
+        intravisit,
+    },
+    index::vec::Idx,
+    middle::{
+        hir::map::Map,
+        mir,
+        ty::{self, TyCtxt},
+    },
+    span::Span,
+};
+use rustc_hash::FxHashMap;
+
+pub struct FnPointerContracts<'p, 'v: 'p, 'tcx: 'v> {
+    encoder: &'p Encoder<'v, 'tcx>,
+    mir: &'p mir::Body<'tcx>,
+    /// The contracts of the function pointers stored in locals.
+    locals: FxHashMap<mir::Local, DefId>,
+}
+
+impl<'p, 'v: 'p, 'tcx: 'v> FnPointerContracts<'p, 'v, 'tcx> {
+    pub fn new(
+        encoder: &'p Encoder<'v, 'tcx>,
+        proc_def_id: DefId,
+        body_def_id: DefId,
+        mir: &'p mir::Body<'tcx>,
+    ) -> Self {
+        let mut contracts = FnPointerContracts {
+            encoder,
+            mir,
+            locals: FxHashMap::default(),
+        };
+        if !mir
+            .local_decls
+            .iter()
+            .any(|local_decl| local_decl.ty.is_fn_ptr())
+        {
+            return contracts;
+        }
+
+        // The parameters and the result of closures and `async fn`s do not
+        // correspond to the locals of their MIR body.
+        if proc_def_id == body_def_id && !encoder.env().tcx().is_closure(proc_def_id) {
+            if let Some(fn_decl) = contracts.fn_decl(proc_def_id) {
+                for (index, input) in fn_decl.inputs.iter().enumerate() {
+                    if let Some(contract) = contracts.of_hir_ty(input) {
+                        contracts
+                            .locals
+                            .insert(mir::Local::new(index + 1), contract);
+                    }
+                }
+                if let hir::FnRetTy::Return(output) = fn_decl.output {
+                    if let Some(contract) = contracts.of_hir_ty(output) {
+                        contracts.locals.insert(mir::RETURN_PLACE, contract);
+                    }
+                }
+            }
+        }
+        contracts.collect_annotated_bindings(body_def_id);
+        contracts.infer_remaining_locals();
+        contracts
+    }
+
+    /// The contract of the function pointer stored in `place`, if any.
+    pub fn of_place(&self, place: mir::Place<'tcx>) -> Option<DefId> {
+        let Some((base, elem)) = place.iter_projections().last() else {
+            return self.locals.get(&place.local).copied();
+        };
+        let mir::ProjectionElem::Field(field, _) = elem else {
+            return None;
+        };
+        let base_ty = base.ty(self.mir, self.tcx());
+        match base_ty.ty.kind() {
+            ty::TyKind::Adt(adt_def, _) => {
+                let variant = match base_ty.variant_index {
+                    Some(variant_index) => adt_def.variant(variant_index),
+                    None => adt_def.non_enum_variant(),
+                };
+                self.of_field(variant.fields[field.index()].did)
+            }
+            _ => None,
+        }
+    }
+
+    /// The contract of the function pointer passed as the `index`-th argument
+    /// to `called_def_id`, if any.
+    pub fn of_argument(&self, called_def_id: DefId, index: usize) -> Option<DefId> {
+        let fn_decl = self.fn_decl(called_def_id)?;
+        fn_decl
+            .inputs
+            .get(index)
+            .and_then(|input| self.of_hir_ty(input))
+    }
+
+    /// The contract of the function pointer returned by `called_def_id`, if
+    /// any.
+    pub fn of_result(&self, called_def_id: DefId) -> Option<DefId> {
+        match self.fn_decl(called_def_id)?.output {
+            hir::FnRetTy::Return(output) => self.of_hir_ty(output),
+            hir::FnRetTy::DefaultReturn(_) => None,
+        }
+    }
+
+    /// The contract of the function pointer stored in the `index`-th field of
+    /// an aggregate, if any.
+    pub fn of_aggregate_operand(
+        &self,
+        aggregate: &mir::AggregateKind<'tcx>,
+        index: usize,
+    ) -> Option<DefId> {
+        match *aggregate {
+            mir::AggregateKind::Adt(adt_did, variant_index, _, _, active_field) => {
+                let adt_def = self.tcx().adt_def(adt_did);
+                let field_index = active_field.unwrap_or(index);
+                self.of_field(adt_def.variant(variant_index).fields[field_index].did)
+            }
+            _ => None,
+        }
+    }
+
+    /// The contract of the function pointer read by `operand`, if any.
+    pub fn of_operand(&self, operand: &mir::Operand<'tcx>) -> Option<DefId> {
+        match operand {
+            mir::Operand::Copy(place) | mir::Operand::Move(place) => self.of_place(*place),
+            mir::Operand::Constant(_) => None,
+        }
+    }
+
+    fn tcx(&self) -> TyCtxt<'tcx> {
+        self.encoder.env().tcx()
+    }
+
+    fn fn_decl(&self, def_id: DefId) -> Option<&'tcx hir::FnDecl<'tcx>> {
+        let local_def_id = def_id.as_local()?;
+        let hir = self.tcx().hir();
+        hir.fn_decl_by_hir_id(hir.local_def_id_to_hir_id(local_def_id))
+    }
+
+    fn of_field(&self, field_did: DefId) -> Option<DefId> {
+        match self.tcx().hir().get_if_local(field_did)? {
+            hir::Node::Field(field) => self.of_hir_ty(field.ty),
+            _ => None,
+        }
+    }
+
+    fn of_hir_ty(&self, ty: &hir::Ty) -> Option<DefId> {
+        let hir::TyKind::Path(hir::QPath::Resolved(None, path)) = ty.kind else {
+            return None;
+        };
+        let Res::Def(DefKind::TyAlias, alias_def_id) = path.res else {
+            return None;
+        };
+        self.encoder
+            .get_fn_ptr_spec(alias_def_id)
+            .map(|spec| spec.contract.to_def_id())
+    }
+
+    /// Assigns contracts to the locals bound by `let` statements whose type
+    /// annotation is a function pointer type alias with a specification.
+    fn collect_annotated_bindings(&mut self, body_def_id: DefId) {
+        let Some(local_def_id) = body_def_id.as_local() else {
+            return;
+        };
+        let hir = self.tcx().hir();
+        let Some(body_id) = hir.maybe_body_owned_by(local_def_id) else {
+            return;
+        };
+        let mut visitor = AnnotatedBindingsVisitor {
+            map: hir,
+            bindings: Vec::new(),
+        };
+        intravisit::Visitor::visit_body(&mut visitor, hir.body(body_id));
+        for (pat_span, ty) in visitor.bindings {
+            let Some(contract) = self.of_hir_ty(ty) else {
+                continue;
+            };
+            for (local, local_decl) in self.mir.local_decls.iter_enumerated() {
+                if let Some(box mir::LocalInfo::User(mir::ClearCrossCrate::Set(
+                    mir::BindingForm::Var(mir::VarBindingForm {
+                        pat_span: local_pat_span,
+                        ..
+                    }),
+                ))) = &local_decl.local_info
+                {
+                    if *local_pat_span == pat_span {
+                        self.locals.insert(local, contract);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Assigns contracts to the locals of function pointer type that are not
+    /// declared with one, until reaching a fixed point.
+    fn infer_remaining_locals(&mut self) {
+        let declared_locals = 1 + self.mir.arg_count;
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (local, local_decl) in self.mir.local_decls.iter_enumerated().skip(declared_locals)
+            {
+                if !local_decl.ty.is_fn_ptr() || self.locals.contains_key(&local) {
+                    continue;
+                }
+                if let Some(contract) = self.infer_local(local) {
+                    self.locals.insert(local, contract);
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    fn infer_local(&self, local: mir::Local) -> Option<DefId> {
+        let is_local = |operand: &mir::Operand<'tcx>| match operand {
+            mir::Operand::Copy(place) | mir::Operand::Move(place) => {
+                place.as_local() == Some(local)
+            }
+            mir::Operand::Constant(_) => false,
+        };
+        let mut source = None;
+        for block_data in self.mir.basic_blocks.iter() {
+            for statement in &block_data.statements {
+                let mir::StatementKind::Assign(box (dest, rvalue)) = &statement.kind else {
+                    continue;
+                };
+                match rvalue {
+                    mir::Rvalue::Use(operand) if is_local(operand) => {
+                        if let Some(contract) = self.of_place(*dest) {
+                            return Some(contract);
+                        }
+                    }
+                    mir::Rvalue::Use(mir::Operand::Copy(src) | mir::Operand::Move(src))
+                        if dest.as_local() == Some(local) =>
+                    {
+                        source = source.or_else(|| self.of_place(*src));
+                    }
+                    mir::Rvalue::Aggregate(box aggregate, operands) => {
+                        for (index, operand) in operands.iter().enumerate() {
+                            if is_local(operand) {
+                                if let Some(contract) = self.of_aggregate_operand(aggregate, index)
+                                {
+                                    return Some(contract);
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            if let mir::TerminatorKind::Call {
+                func,
+                args,
+                destination,
+                ..
+            } = &block_data.terminator().kind
+            {
+                let called_def_id = match func.const_fn_def() {
+                    Some((called_def_id, _)) => Some(called_def_id),
+                    None => self.of_operand(func),
+                };
+                if let Some(called_def_id) = called_def_id {
+                    for (index, arg) in args.iter().enumerate() {
+                        if is_local(arg) {
+                            if let Some(contract) = self.of_argument(called_def_id, index) {
+                                return Some(contract);
+                            }
+                        }
+                    }
+                    if destination.as_local() == Some(local) {
+                        source = source.or_else(|| self.of_result(called_def_id));
+                    }
+                }
+            }
+        }
+        source
+    }
+}
+
+/// Collects the `let` statements that bind a single variable and have a type
+/// annotation.
+struct AnnotatedBindingsVisitor<'tcx> {
+    map: Map<'tcx>,
+    bindings: Vec<(Span, &'tcx hir::Ty<'tcx>)>,
+}
+
+impl<'tcx> intravisit::Visitor<'tcx> for AnnotatedBindingsVisitor<'tcx> {
+    type NestedFilter = prusti_rustc_interface::middle::hir::nested_filter::OnlyBodies;
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.map
+    }
+
+    fn visit_local(&mut self, local: &'tcx hir::Local<'tcx>) {
+        if let (hir::PatKind::Binding(..), Some(ty)) = (&local.pat.kind, local.ty) {
+            self.bindings.push((local.pat.span, ty));
+        }
+        intravisit::walk_local(self, local)
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        // The bindings of closures belong to their own MIR bodies.
+        if !matches!(expr.kind, hir::ExprKind::Closure(..)) {
+            intravisit::walk_expr(self, expr)
+        }
+    }
+}
@@ -35,11 +35,17 @@ impl IntoPredicates for vir_high::TypeDecl {
             vir_high::TypeDecl::Slice(ty_decl) => ty_decl.lower(ty, encoder),
             vir_high::TypeDecl::Sequence(_ty_decl) => unimplemented!(),
             vir_high::TypeDecl::Map(_ty_decl) => unimplemented!(),
+            vir_high::TypeDecl::Set(_) | vir_high::TypeDecl::MultiSet(_) => {
+                unreachable!("Sets are used only in the unsafe core proof")
+            }
             vir_high::TypeDecl::Reference(ty_decl) => ty_decl.lower(ty, encoder),
             vir_high::TypeDecl::Pointer(ty_decl) => ty_decl.lower(ty, encoder),
             vir_high::TypeDecl::Never => construct_never_predicate(encoder),
             vir_high::TypeDecl::Closure(ty_decl) => ty_decl.lower(ty, encoder),
             vir_high::TypeDecl::Unsupported(ty_decl) => ty_decl.lower(ty, encoder),
+            vir_high::TypeDecl::Opaque(_ty_decl) => {
+                Ok(vec![Predicate::new_abstract(ty.lower(encoder))])
+            }
             vir_high::TypeDecl::Trusted(_ty_decl) => {
                 unreachable!("Trusted types are not supported")
             }
@@ -139,6 +139,10 @@ impl PredicateStateOnPath {
         match permission {
             Permission::MemoryBlock(place) => {
                 assert!(self.memory_block_stack.remove(place));
+                // The borrows of the targets of the references stored in the
+                // memory block cannot be restored through it anymore.
+                self.mut_borrowed
+                    .retain(|borrowed, _| borrowed == place || !borrowed.has_prefix(place));
             }
             Permission::Owned(place) => {
                 assert!(self.owned_non_aliased.remove(place));
@@ -330,6 +334,15 @@ impl PredicateStateOnPath {
         }
     }
 
+    /// Marks a lifetime that is taken again, for example, in the next
+    /// iteration of a loop, as alive.
+    pub(in super::super) fn mark_lifetime_alive(
+        &mut self,
+        lifetime: &vir_typed::ty::LifetimeConst,
+    ) {
+        self.dead_lifetimes.remove(lifetime);
+    }
+
     /// When a lifetime dies, we can three kinds of places:
     ///
     /// 1.  Places that end with a type containing the dead lifetime as a
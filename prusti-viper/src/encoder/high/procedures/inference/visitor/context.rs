@@ -111,6 +111,8 @@ impl<'p, 'v, 'tcx> super::super::ensurer::Context for Visitor<'p, 'v, 'tcx> {
             }
             vir_typed::TypeDecl::Sequence(_) => unimplemented!("ty: {}", ty),
             vir_typed::TypeDecl::Map(_) => unimplemented!("ty: {}", ty),
+            vir_typed::TypeDecl::Set(_) => unimplemented!("ty: {}", ty),
+            vir_typed::TypeDecl::MultiSet(_) => unimplemented!("ty: {}", ty),
             vir_typed::TypeDecl::Never => unimplemented!("ty: {}", ty),
             vir_typed::TypeDecl::Closure(_) => unimplemented!("ty: {}", ty),
             vir_typed::TypeDecl::Unsupported(_) => unimplemented!("ty: {}", ty),
@@ -198,6 +198,9 @@ impl<'p, 'v, 'tcx> Visitor<'p, 'v, 'tcx> {
             self.process_dead_lifetime(dead_lifetime, state)?;
             return Ok(());
         }
+        if let vir_typed::Statement::LifetimeTake(lifetime_take) = &statement {
+            self.process_lifetime_take(lifetime_take, state)?;
+        }
         if let vir_typed::Statement::Assign(vir_typed::Assign {
             target,
             value: vir_typed::Rvalue::Discriminant(discriminant),
@@ -445,6 +448,29 @@ impl<'p, 'v, 'tcx> Visitor<'p, 'v, 'tcx> {
         Ok(())
     }
 
+    fn process_lifetime_take(
+        &mut self,
+        statement: &vir_typed::LifetimeTake,
+        state: &mut FoldUnfoldState,
+    ) -> SpannedEncodingResult<()> {
+        let lifetime = vir_typed::ty::LifetimeConst {
+            name: statement.target.name.clone(),
+        };
+        for predicate in state.iter_mut()? {
+            match predicate {
+                PredicateState::Unconditional(state) => {
+                    state.mark_lifetime_alive(&lifetime);
+                }
+                PredicateState::Conditional(states) => {
+                    for conditional_predicate_state in states.values_mut() {
+                        conditional_predicate_state.mark_lifetime_alive(&lifetime);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn process_assign_discriminant(
         &mut self,
         target: vir_typed::Expression,
@@ -25,6 +25,13 @@ pub(crate) trait HighPureFunctionEncoderInterface<'tcx> {
         container: vir_high::Expression,
         range: vir_high::Expression,
     ) -> EncodingResult<vir_high::Expression>;
+    fn encode_subslice_bounds_call(
+        &self,
+        container: vir_high::Expression,
+        from: vir_high::Expression,
+        to: vir_high::Expression,
+        return_type: vir_high::Type,
+    ) -> EncodingResult<vir_high::Expression>;
     fn encode_len_call(
         &self,
         container: vir_high::Expression,
@@ -113,6 +120,25 @@ impl<'v, 'tcx: 'v> HighPureFunctionEncoderInterface<'tcx>
         ))
     }
 
+    /// Encode the subslice `container[from..to]` bound by a slice pattern.
+    fn encode_subslice_bounds_call(
+        &self,
+        container: vir_high::Expression,
+        from: vir_high::Expression,
+        to: vir_high::Expression,
+        return_type: vir_high::Type,
+    ) -> EncodingResult<vir_high::Expression> {
+        // FIXME: Should use encode_builtin_function_use.
+        let name = "subslice_bounds";
+        let element_type = extract_container_element_type(&container)?;
+        Ok(vir_high::Expression::function_call(
+            name,
+            vec![element_type.clone()],
+            vec![container, from, to],
+            return_type,
+        ))
+    }
+
     /// Encode len of a slice.
     fn encode_len_call(
         &self,
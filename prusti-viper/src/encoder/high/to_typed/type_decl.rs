@@ -115,6 +115,17 @@ impl<'v, 'tcx> HighToTypedTypeDeclLowerer for crate::encoder::Encoder<'v, 'tcx>
         ))
     }
 
+    fn high_to_typed_type_decl_type_decl_opaque(
+        &mut self,
+        decl: vir_high::type_decl::Opaque,
+    ) -> Result<vir_typed::TypeDecl, Self::Error> {
+        Ok(vir_typed::TypeDecl::trusted(
+            decl.name,
+            decl.lifetimes.high_to_typed_type(self)?,
+            decl.const_parameters.high_to_typed_expression(self)?,
+        ))
+    }
+
     fn high_to_typed_type_decl_enum(
         &mut self,
         decl: vir_high::type_decl::Enum,
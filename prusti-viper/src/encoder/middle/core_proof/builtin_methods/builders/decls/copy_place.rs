@@ -1,4 +1,5 @@
 use super::{
+    super::calls::builder::BuiltinMethodCallBuilder,
     common::{BuiltinMethodBuilder, BuiltinMethodBuilderMethods},
     move_copy_place_common::MoveCopyPlaceMethodBuilder,
 };
@@ -185,4 +186,29 @@ impl<'l, 'p, 'v, 'tcx> CopyPlaceMethodBuilder<'l, 'p, 'v, 'tcx> {
         self.add_statement(statement);
         Ok(())
     }
+
+    pub(in super::super::super::super) fn duplicate_frac_ref(
+        &mut self,
+    ) -> SpannedEncodingResult<()> {
+        self.inner
+            .inner
+            .lowerer
+            .encode_duplicate_frac_ref_method(self.inner.inner.ty)?;
+        let mut builder = BuiltinMethodCallBuilder::new(
+            self.inner.inner.lowerer,
+            CallContext::BuiltinMethod,
+            "duplicate_frac_ref",
+            self.inner.inner.ty,
+            self.inner.inner.type_decl,
+            self.inner.inner.position,
+        )?;
+        builder.add_argument(self.inner.target_place.clone().into());
+        builder.add_argument(self.inner.source_place.clone().into());
+        builder.add_argument(self.inner.source_snapshot.clone().into());
+        builder.add_lifetime_arguments()?;
+        builder.add_const_arguments()?;
+        let statement = builder.build();
+        self.add_statement(statement);
+        Ok(())
+    }
 }
@@ -27,6 +27,10 @@ pub(crate) trait MidCoreProofEncoderInterface<'tcx> {
         check_mode: CheckMode,
     ) -> SpannedEncodingResult<()>;
     fn take_core_proof_programs(&mut self) -> Vec<vir_low::Program>;
+    fn core_proof_program_count(&self) -> usize;
+    /// Drops the encoded programs that were added after the first `count`
+    /// ones.
+    fn truncate_core_proof_programs(&mut self, count: usize);
 }
 
 impl<'v, 'tcx: 'v> MidCoreProofEncoderInterface<'tcx> for super::super::super::Encoder<'v, 'tcx> {
@@ -123,4 +127,14 @@ impl<'v, 'tcx: 'v> MidCoreProofEncoderInterface<'tcx> for super::super::super::E
     fn take_core_proof_programs(&mut self) -> Vec<vir_low::Program> {
         std::mem::take(&mut self.mid_core_proof_encoder_state.encoded_programs)
     }
+
+    fn core_proof_program_count(&self) -> usize {
+        self.mid_core_proof_encoder_state.encoded_programs.len()
+    }
+
+    fn truncate_core_proof_programs(&mut self, count: usize) {
+        self.mid_core_proof_encoder_state
+            .encoded_programs
+            .truncate(count);
+    }
 }
@@ -484,7 +484,9 @@ impl IntoLow for vir_mid::Statement {
                 // TODO: Remove code duplication with Self::MovePlace
                 let target_ty = statement.target.get_type();
                 let source_ty = statement.source.get_type();
-                assert_eq!(target_ty, source_ty);
+                let target_ty_without_lifetime = target_ty.clone().erase_lifetimes();
+                let source_ty_without_lifetime = source_ty.clone().erase_lifetimes();
+                assert_eq!(target_ty_without_lifetime, source_ty_without_lifetime);
                 lowerer.encode_copy_place_method(target_ty)?;
                 let target_place = lowerer.encode_expression_as_place(&statement.target)?;
                 let target_root_address = lowerer.extract_root_address(&statement.target)?;
@@ -2,11 +2,14 @@ use crate::encoder::{
     errors::SpannedEncodingResult,
     middle::core_proof::{
         lowerer::{DomainsLowererInterface, Lowerer},
-        snapshots::{IntoSnapshot, SnapshotDomainsInterface, SnapshotValidityInterface},
+        snapshots::{
+            IntoSnapshot, SnapshotDomainsInterface, SnapshotValidityInterface,
+            SnapshotValuesInterface,
+        },
     },
 };
 use vir_crate::{
-    common::expression::QuantifierHelpers,
+    common::expression::{BinaryOperationHelpers, QuantifierHelpers},
     low::{self as vir_low},
     middle::{self as vir_mid},
 };
@@ -16,6 +19,13 @@ trait Private {
         &mut self,
         ty: &vir_mid::Type,
     ) -> SpannedEncodingResult<()>;
+    fn encode_sequence_range_constructor_def(
+        &mut self,
+        ty: &vir_mid::Type,
+    ) -> SpannedEncodingResult<()>;
+    fn encode_map_domain_def(&mut self, ty: &vir_mid::Type) -> SpannedEncodingResult<()>;
+    fn encode_sequence_to_set_def(&mut self, ty: &vir_mid::Type) -> SpannedEncodingResult<()>;
+    fn encode_sequence_to_multiset_def(&mut self, ty: &vir_mid::Type) -> SpannedEncodingResult<()>;
 }
 
 impl<'p, 'v: 'p, 'tcx: 'v> Private for Lowerer<'p, 'v, 'tcx> {
@@ -96,6 +106,335 @@ impl<'p, 'v: 'p, 'tcx: 'v> Private for Lowerer<'p, 'v, 'tcx> {
         }
         Ok(())
     }
+    fn encode_sequence_range_constructor_def(
+        &mut self,
+        ty: &vir_mid::Type,
+    ) -> SpannedEncodingResult<()> {
+        if !self
+            .snapshots_state
+            .encoded_sequence_range_constructor
+            .contains(ty)
+        {
+            self.snapshots_state
+                .encoded_sequence_range_constructor
+                .insert(ty.clone());
+
+            let vir_mid::Type::Sequence(vir_mid::ty::Sequence { element_type, .. }) = ty else {
+                unreachable!("ty: {}", ty);
+            };
+
+            let domain_name = self.encode_snapshot_domain_name(ty)?;
+            use vir_low::macros::*;
+            var_decls! {
+                low: Int,
+                high: Int,
+                index: Int
+            };
+            let sequence = self.encode_sequence_range_constructor_call(
+                ty,
+                low.clone().into(),
+                high.clone().into(),
+            )?;
+            let valid_sequence = self.encode_snapshot_valid_call(&domain_name, sequence.clone())?;
+            let element = vir_low::Expression::container_op_no_pos(
+                vir_low::expression::ContainerOpKind::SeqIndex,
+                vir_low::Type::seq(element_type.to_snapshot(self)?),
+                vec![sequence.clone(), index.clone().into()],
+            );
+            let len = vir_low::Expression::container_op_no_pos(
+                vir_low::expression::ContainerOpKind::SeqLen,
+                vir_low::Type::seq(element_type.to_snapshot(self)?),
+                vec![sequence.clone()],
+            );
+            let element_value = self.construct_constant_snapshot(
+                element_type,
+                vir_low::Expression::add(low.clone().into(), index.clone().into()),
+                Default::default(),
+            )?;
+            let count = vir_low::Expression::subtract(high.clone().into(), low.clone().into());
+            let elements = vir_low::Expression::forall(
+                vec![index.clone()],
+                vec![vir_low::Trigger::new(vec![element.clone()])],
+                expr! {
+                    (([0.into()] <= index) && (index < [len.clone()])) ==>
+                        ([element] == [element_value])
+                },
+            );
+            let body = vir_low::Expression::forall(
+                vec![low.clone(), high.clone()],
+                vec![vir_low::Trigger::new(vec![sequence])],
+                expr! {
+                    [valid_sequence] &&
+                    ((low <= high) ==> ([len.clone()] == [count])) &&
+                    ((high < low) ==> ([len] == [0.into()])) &&
+                    [elements]
+                },
+            );
+            let axiom = vir_low::DomainAxiomDecl {
+                comment: None,
+                name: format!("{domain_name}$sequence_range_constructor_definition"),
+                body,
+            };
+            self.declare_axiom(&domain_name, axiom)?;
+        }
+        Ok(())
+    }
+    fn encode_map_domain_def(&mut self, ty: &vir_mid::Type) -> SpannedEncodingResult<()> {
+        if !self.snapshots_state.encoded_map_domain.contains(ty) {
+            self.snapshots_state.encoded_map_domain.insert(ty.clone());
+
+            let vir_mid::Type::Map(vir_mid::ty::Map { key_type, val_type, .. }) = ty else {
+                unreachable!("ty: {}", ty);
+            };
+
+            let domain_name = self.encode_snapshot_domain_name(ty)?;
+            let key_type_snapshot = key_type.to_snapshot(self)?;
+            let map_type_snapshot =
+                vir_low::Type::map(key_type_snapshot.clone(), val_type.to_snapshot(self)?);
+            let set_type_snapshot = vir_low::Type::set(key_type_snapshot.clone());
+            use vir_low::macros::*;
+            var_decls! {
+                map: {map_type_snapshot.clone()},
+                key: {key_type_snapshot}
+            };
+            let keys = self.encode_map_domain_call(ty, map.clone().into())?;
+            let keys_len = vir_low::Expression::container_op_no_pos(
+                vir_low::expression::ContainerOpKind::SetCardinality,
+                set_type_snapshot.clone(),
+                vec![keys.clone()],
+            );
+            let map_len = vir_low::Expression::container_op_no_pos(
+                vir_low::expression::ContainerOpKind::MapLen,
+                map_type_snapshot.clone(),
+                vec![map.clone().into()],
+            );
+            let key_in_keys = vir_low::Expression::container_op_no_pos(
+                vir_low::expression::ContainerOpKind::SetContains,
+                set_type_snapshot,
+                vec![key.clone().into(), keys.clone()],
+            );
+            let key_in_map = vir_low::Expression::container_op_no_pos(
+                vir_low::expression::ContainerOpKind::MapContains,
+                map_type_snapshot,
+                vec![map.clone().into(), key.clone().into()],
+            );
+            let elements = vir_low::Expression::forall(
+                vec![key],
+                vec![
+                    vir_low::Trigger::new(vec![key_in_keys.clone()]),
+                    vir_low::Trigger::new(vec![key_in_map.clone()]),
+                ],
+                expr! { [key_in_keys] == [key_in_map] },
+            );
+            let body = vir_low::Expression::forall(
+                vec![map],
+                vec![vir_low::Trigger::new(vec![keys])],
+                expr! { ([keys_len] == [map_len]) && [elements] },
+            );
+            let axiom = vir_low::DomainAxiomDecl {
+                comment: None,
+                name: format!("{domain_name}$map_domain_definition"),
+                body,
+            };
+            self.declare_axiom(&domain_name, axiom)?;
+        }
+        Ok(())
+    }
+
+    fn encode_sequence_to_set_def(&mut self, ty: &vir_mid::Type) -> SpannedEncodingResult<()> {
+        if !self.snapshots_state.encoded_sequence_to_set.contains(ty) {
+            self.snapshots_state
+                .encoded_sequence_to_set
+                .insert(ty.clone());
+
+            let vir_mid::Type::Sequence(vir_mid::ty::Sequence { element_type, .. }) = ty else {
+                unreachable!("ty: {}", ty);
+            };
+
+            let domain_name = self.encode_snapshot_domain_name(ty)?;
+            let element_type_snapshot = element_type.to_snapshot(self)?;
+            let seq_type_snapshot = vir_low::Type::seq(element_type_snapshot.clone());
+            let set_type_snapshot = vir_low::Type::set(element_type_snapshot.clone());
+            use vir_low::macros::*;
+            var_decls! {
+                sequence: {seq_type_snapshot.clone()},
+                element: {element_type_snapshot}
+            };
+            let set = self.encode_sequence_to_set_call(ty, sequence.clone().into())?;
+            let set_len = vir_low::Expression::container_op_no_pos(
+                vir_low::expression::ContainerOpKind::SetCardinality,
+                set_type_snapshot.clone(),
+                vec![set.clone()],
+            );
+            let sequence_len = vir_low::Expression::container_op_no_pos(
+                vir_low::expression::ContainerOpKind::SeqLen,
+                seq_type_snapshot.clone(),
+                vec![sequence.clone().into()],
+            );
+            let element_in_set = vir_low::Expression::container_op_no_pos(
+                vir_low::expression::ContainerOpKind::SetContains,
+                set_type_snapshot,
+                vec![element.clone().into(), set.clone()],
+            );
+            let element_in_sequence = vir_low::Expression::container_op_no_pos(
+                vir_low::expression::ContainerOpKind::SeqContains,
+                seq_type_snapshot,
+                vec![element.clone().into(), sequence.clone().into()],
+            );
+            let elements = vir_low::Expression::forall(
+                vec![element],
+                vec![
+                    vir_low::Trigger::new(vec![element_in_set.clone()]),
+                    vir_low::Trigger::new(vec![element_in_sequence.clone()]),
+                ],
+                expr! { [element_in_set] == [element_in_sequence] },
+            );
+            let body = vir_low::Expression::forall(
+                vec![sequence],
+                vec![vir_low::Trigger::new(vec![set])],
+                expr! { ([set_len] <= [sequence_len]) && [elements] },
+            );
+            let axiom = vir_low::DomainAxiomDecl {
+                comment: None,
+                name: format!("{domain_name}$sequence_to_set_definition"),
+                body,
+            };
+            self.declare_axiom(&domain_name, axiom)?;
+        }
+        Ok(())
+    }
+    fn encode_sequence_to_multiset_def(&mut self, ty: &vir_mid::Type) -> SpannedEncodingResult<()> {
+        if !self
+            .snapshots_state
+            .encoded_sequence_to_multiset
+            .contains(ty)
+        {
+            self.snapshots_state
+                .encoded_sequence_to_multiset
+                .insert(ty.clone());
+
+            let vir_mid::Type::Sequence(vir_mid::ty::Sequence { element_type, .. }) = ty else {
+                unreachable!("ty: {}", ty);
+            };
+
+            let domain_name = self.encode_snapshot_domain_name(ty)?;
+            let element_type_snapshot = element_type.to_snapshot(self)?;
+            let seq_type_snapshot = vir_low::Type::seq(element_type_snapshot.clone());
+            let multi_set_type_snapshot = vir_low::Type::multi_set(element_type_snapshot.clone());
+            use vir_low::macros::*;
+            var_decls! {
+                sequence: {seq_type_snapshot.clone()},
+                left: {seq_type_snapshot.clone()},
+                right: {seq_type_snapshot.clone()},
+                element: {element_type_snapshot}
+            };
+
+            // The cardinality and the elements of the multiset.
+            let multi_set = self.encode_sequence_to_multiset_call(ty, sequence.clone().into())?;
+            let multi_set_len = vir_low::Expression::container_op_no_pos(
+                vir_low::expression::ContainerOpKind::MultiSetCardinality,
+                multi_set_type_snapshot.clone(),
+                vec![multi_set.clone()],
+            );
+            let sequence_len = vir_low::Expression::container_op_no_pos(
+                vir_low::expression::ContainerOpKind::SeqLen,
+                seq_type_snapshot.clone(),
+                vec![sequence.clone().into()],
+            );
+            let element_count = vir_low::Expression::container_op_no_pos(
+                vir_low::expression::ContainerOpKind::MultiSetContains,
+                multi_set_type_snapshot.clone(),
+                vec![element.clone().into(), multi_set.clone()],
+            );
+            let element_in_sequence = vir_low::Expression::container_op_no_pos(
+                vir_low::expression::ContainerOpKind::SeqContains,
+                seq_type_snapshot.clone(),
+                vec![element.clone().into(), sequence.clone().into()],
+            );
+            let elements = vir_low::Expression::forall(
+                vec![element.clone()],
+                vec![
+                    vir_low::Trigger::new(vec![element_count.clone()]),
+                    vir_low::Trigger::new(vec![element_in_sequence.clone()]),
+                ],
+                expr! { ([0.into()] < [element_count]) == [element_in_sequence] },
+            );
+            let len_and_elements = vir_low::Expression::forall(
+                vec![sequence],
+                vec![vir_low::Trigger::new(vec![multi_set])],
+                expr! { ([multi_set_len] == [sequence_len]) && [elements] },
+            );
+
+            // The multiset of the empty sequence is empty.
+            let empty_sequence = vir_low::Expression::container_op_no_pos(
+                vir_low::expression::ContainerOpKind::SeqEmpty,
+                seq_type_snapshot.clone(),
+                Vec::new(),
+            );
+            let empty_multi_set = vir_low::Expression::container_op_no_pos(
+                vir_low::expression::ContainerOpKind::MultiSetEmpty,
+                multi_set_type_snapshot.clone(),
+                Vec::new(),
+            );
+            let empty_multi_set_of_sequence =
+                self.encode_sequence_to_multiset_call(ty, empty_sequence)?;
+            let empty = expr! { [empty_multi_set_of_sequence] == [empty_multi_set] };
+
+            // The multiset of a singleton sequence is a singleton.
+            let single_sequence = vir_low::Expression::container_op_no_pos(
+                vir_low::expression::ContainerOpKind::SeqConstructor,
+                seq_type_snapshot.clone(),
+                vec![element.clone().into()],
+            );
+            let single_multi_set = vir_low::Expression::container_op_no_pos(
+                vir_low::expression::ContainerOpKind::MultiSetConstructor,
+                multi_set_type_snapshot.clone(),
+                vec![element.clone().into()],
+            );
+            let single_multi_set_of_sequence =
+                self.encode_sequence_to_multiset_call(ty, single_sequence)?;
+            let single = vir_low::Expression::forall(
+                vec![element],
+                vec![vir_low::Trigger::new(vec![
+                    single_multi_set_of_sequence.clone()
+                ])],
+                expr! { [single_multi_set_of_sequence] == [single_multi_set] },
+            );
+
+            // The multiset of a concatenation is the union of the multisets.
+            let concatenation = vir_low::Expression::container_op_no_pos(
+                vir_low::expression::ContainerOpKind::SeqConcat,
+                seq_type_snapshot,
+                vec![left.clone().into(), right.clone().into()],
+            );
+            let multi_set_of_concatenation =
+                self.encode_sequence_to_multiset_call(ty, concatenation)?;
+            let left_multi_set = self.encode_sequence_to_multiset_call(ty, left.clone().into())?;
+            let right_multi_set =
+                self.encode_sequence_to_multiset_call(ty, right.clone().into())?;
+            let union = vir_low::Expression::container_op_no_pos(
+                vir_low::expression::ContainerOpKind::MultiSetUnion,
+                multi_set_type_snapshot,
+                vec![left_multi_set, right_multi_set],
+            );
+            let concat = vir_low::Expression::forall(
+                vec![left, right],
+                vec![vir_low::Trigger::new(vec![
+                    multi_set_of_concatenation.clone()
+                ])],
+                expr! { [multi_set_of_concatenation] == [union] },
+            );
+
+            let body = expr! { [len_and_elements] && [empty] && [single] && [concat] };
+            let axiom = vir_low::DomainAxiomDecl {
+                comment: None,
+                name: format!("{domain_name}$sequence_to_multiset_definition"),
+                body,
+            };
+            self.declare_axiom(&domain_name, axiom)?;
+        }
+        Ok(())
+    }
 }
 
 pub(in super::super::super) trait BuiltinFunctionsInterface {
@@ -105,6 +444,31 @@ pub(in super::super::super) trait BuiltinFunctionsInterface {
         argument: vir_low::Expression,
         count: vir_low::Expression,
     ) -> SpannedEncodingResult<vir_low::Expression>;
+    /// The sequence of integers from `low` (inclusive) to `high` (exclusive).
+    fn encode_sequence_range_constructor_call(
+        &mut self,
+        ty: &vir_mid::Type,
+        low: vir_low::Expression,
+        high: vir_low::Expression,
+    ) -> SpannedEncodingResult<vir_low::Expression>;
+    /// The set of keys of `map`.
+    fn encode_map_domain_call(
+        &mut self,
+        ty: &vir_mid::Type,
+        map: vir_low::Expression,
+    ) -> SpannedEncodingResult<vir_low::Expression>;
+    /// The set of elements of `sequence`.
+    fn encode_sequence_to_set_call(
+        &mut self,
+        ty: &vir_mid::Type,
+        sequence: vir_low::Expression,
+    ) -> SpannedEncodingResult<vir_low::Expression>;
+    /// The multiset of elements of `sequence`.
+    fn encode_sequence_to_multiset_call(
+        &mut self,
+        ty: &vir_mid::Type,
+        sequence: vir_low::Expression,
+    ) -> SpannedEncodingResult<vir_low::Expression>;
 }
 
 impl<'p, 'v: 'p, 'tcx: 'v> BuiltinFunctionsInterface for Lowerer<'p, 'v, 'tcx> {
@@ -126,4 +490,82 @@ impl<'p, 'v: 'p, 'tcx: 'v> BuiltinFunctionsInterface for Lowerer<'p, 'v, 'tcx> {
             Default::default(),
         )
     }
+    fn encode_sequence_range_constructor_call(
+        &mut self,
+        ty: &vir_mid::Type,
+        low: vir_low::Expression,
+        high: vir_low::Expression,
+    ) -> SpannedEncodingResult<vir_low::Expression> {
+        self.encode_sequence_range_constructor_def(ty)?;
+        let domain_name = self.encode_snapshot_domain_name(ty)?;
+        let function_name = format!("sequence_range_constructor${domain_name}");
+        let result_type = ty.to_snapshot(self)?;
+        self.create_domain_func_app(
+            domain_name,
+            function_name,
+            vec![low, high],
+            result_type,
+            Default::default(),
+        )
+    }
+    fn encode_map_domain_call(
+        &mut self,
+        ty: &vir_mid::Type,
+        map: vir_low::Expression,
+    ) -> SpannedEncodingResult<vir_low::Expression> {
+        self.encode_map_domain_def(ty)?;
+        let vir_mid::Type::Map(vir_mid::ty::Map { key_type, .. }) = ty else {
+            unreachable!("ty: {}", ty);
+        };
+        let domain_name = self.encode_snapshot_domain_name(ty)?;
+        let function_name = format!("map_domain${domain_name}");
+        let result_type = vir_low::Type::set(key_type.to_snapshot(self)?);
+        self.create_domain_func_app(
+            domain_name,
+            function_name,
+            vec![map],
+            result_type,
+            Default::default(),
+        )
+    }
+    fn encode_sequence_to_set_call(
+        &mut self,
+        ty: &vir_mid::Type,
+        sequence: vir_low::Expression,
+    ) -> SpannedEncodingResult<vir_low::Expression> {
+        self.encode_sequence_to_set_def(ty)?;
+        let vir_mid::Type::Sequence(vir_mid::ty::Sequence { element_type, .. }) = ty else {
+            unreachable!("ty: {}", ty);
+        };
+        let domain_name = self.encode_snapshot_domain_name(ty)?;
+        let function_name = format!("sequence_to_set${domain_name}");
+        let result_type = vir_low::Type::set(element_type.to_snapshot(self)?);
+        self.create_domain_func_app(
+            domain_name,
+            function_name,
+            vec![sequence],
+            result_type,
+            Default::default(),
+        )
+    }
+    fn encode_sequence_to_multiset_call(
+        &mut self,
+        ty: &vir_mid::Type,
+        sequence: vir_low::Expression,
+    ) -> SpannedEncodingResult<vir_low::Expression> {
+        self.encode_sequence_to_multiset_def(ty)?;
+        let vir_mid::Type::Sequence(vir_mid::ty::Sequence { element_type, .. }) = ty else {
+            unreachable!("ty: {}", ty);
+        };
+        let domain_name = self.encode_snapshot_domain_name(ty)?;
+        let function_name = format!("sequence_to_multiset${domain_name}");
+        let result_type = vir_low::Type::multi_set(element_type.to_snapshot(self)?);
+        self.create_domain_func_app(
+            domain_name,
+            function_name,
+            vec![sequence],
+            result_type,
+            Default::default(),
+        )
+    }
 }
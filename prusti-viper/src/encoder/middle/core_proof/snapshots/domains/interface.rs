@@ -54,9 +54,10 @@ pub(in super::super::super) trait SnapshotDomainsInterface {
 }
 
 impl<'p, 'v: 'p, 'tcx: 'v> SnapshotDomainsInterface for Lowerer<'p, 'v, 'tcx> {
-    /// Note: Even though we directly use Viper maps and sequences as snapshots
-    /// for `vir_mid::Type::Map(_)` and `vir_mid::Type::Sequence(_)`
-    /// respectively, we still need a domain in which we put their custom
+    /// Note: Even though we directly use Viper maps, sequences, sets, and
+    /// multisets as snapshots for `vir_mid::Type::Map(_)`,
+    /// `vir_mid::Type::Sequence(_)`, `vir_mid::Type::Set(_)`, and
+    /// `vir_mid::Type::MultiSet(_)` respectively, we still need a domain in which we put their custom
     /// `validity` and `to_bytes` functions.
     fn encode_snapshot_domain_name(&mut self, ty: &vir_mid::Type) -> SpannedEncodingResult<String> {
         assert!(
@@ -68,8 +69,11 @@ impl<'p, 'v: 'p, 'tcx: 'v> SnapshotDomainsInterface for Lowerer<'p, 'v, 'tcx> {
         );
         let domain_name = match ty {
             vir_mid::Type::Lifetime => self.lifetime_domain_name()?,
+            // Sequences, arrays, and slices are all encoded as Viper
+            // sequences, so they need to share the domain.
             vir_mid::Type::Array(vir_mid::ty::Array { element_type, .. })
-            | vir_mid::Type::Slice(vir_mid::ty::Slice { element_type, .. }) => {
+            | vir_mid::Type::Slice(vir_mid::ty::Slice { element_type, .. })
+            | vir_mid::Type::Sequence(vir_mid::ty::Sequence { element_type, .. }) => {
                 format!("Snap$array${}", element_type.get_identifier())
             }
             _ => format!("Snap${}", ty.get_identifier()),
@@ -108,6 +112,18 @@ impl<'p, 'v: 'p, 'tcx: 'v> SnapshotDomainsInterface for Lowerer<'p, 'v, 'tcx> {
                 self.register_type_domain(ty, &low_ty)?;
                 Ok(low_ty)
             }
+            vir_mid::Type::Set(set) => {
+                let enc_elem = self.encode_snapshot_domain_type(&set.element_type)?;
+                let low_ty = vir_low::Type::set(enc_elem);
+                self.register_type_domain(ty, &low_ty)?;
+                Ok(low_ty)
+            }
+            vir_mid::Type::MultiSet(multi_set) => {
+                let enc_elem = self.encode_snapshot_domain_type(&multi_set.element_type)?;
+                let low_ty = vir_low::Type::multi_set(enc_elem);
+                self.register_type_domain(ty, &low_ty)?;
+                Ok(low_ty)
+            }
             vir_mid::Type::Array(array) => {
                 let enc_elem = self.encode_snapshot_domain_type(&array.element_type)?;
                 let low_ty = vir_low::Type::seq(enc_elem);
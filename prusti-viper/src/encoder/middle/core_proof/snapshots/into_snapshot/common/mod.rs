@@ -6,7 +6,10 @@ use crate::encoder::{
         lifetimes::*,
         lowerer::DomainsLowererInterface,
         references::ReferencesInterface,
-        snapshots::{IntoSnapshot, SnapshotDomainsInterface, SnapshotValuesInterface},
+        snapshots::{
+            BuiltinFunctionsInterface, IntoSnapshot, SnapshotDomainsInterface,
+            SnapshotValuesInterface,
+        },
         types::TypesInterface,
     },
 };
@@ -492,6 +495,24 @@ pub(super) trait IntoSnapshotLowerer<'p, 'v: 'p, 'tcx: 'v> {
             ))
         };
 
+        let set = |low_kind| {
+            Ok(vir_low::Expression::container_op(
+                low_kind,
+                vir_low::Type::set(ty_args[0].clone()),
+                args.clone(),
+                app.position,
+            ))
+        };
+
+        let multi_set = |low_kind| {
+            Ok(vir_low::Expression::container_op(
+                low_kind,
+                vir_low::Type::multi_set(ty_args[0].clone()),
+                args.clone(),
+                app.position,
+            ))
+        };
+
         match app.function {
             BuiltinFunc::Size => {
                 let return_type = self.type_to_snapshot(lowerer, &app.return_type)?;
@@ -585,6 +606,80 @@ pub(super) trait IntoSnapshotLowerer<'p, 'v: 'p, 'tcx: 'v> {
                 let value = seq(ContainerOpKind::SeqLen)?;
                 lowerer.construct_constant_snapshot(app.get_type(), value, app.position)
             }
+            BuiltinFunc::TakeSeq | BuiltinFunc::SkipSeq | BuiltinFunc::UpdateSeq => {
+                let kind = match app.function {
+                    BuiltinFunc::TakeSeq => ContainerOpKind::SeqTake,
+                    BuiltinFunc::SkipSeq => ContainerOpKind::SeqDrop,
+                    _ => ContainerOpKind::SeqUpdate,
+                };
+                args[1] = lowerer.obtain_constant_value(
+                    app.arguments[1].get_type(),
+                    args[1].clone(),
+                    app.position,
+                )?;
+                Ok(vir_low::Expression::container_op(
+                    kind,
+                    vir_low::Type::seq(ty_args[0].clone()),
+                    args,
+                    app.position,
+                ))
+            }
+            BuiltinFunc::SeqContains => {
+                // Viper expects the element before the sequence.
+                let value = vir_low::Expression::container_op(
+                    ContainerOpKind::SeqContains,
+                    vir_low::Type::seq(ty_args[0].clone()),
+                    vec![args[1].clone(), args[0].clone()],
+                    app.position,
+                );
+                let value =
+                    lowerer.construct_constant_snapshot(app.get_type(), value, app.position)?;
+                self.ensure_bool_expression(lowerer, app.get_type(), value, expect_math_bool)
+            }
+            BuiltinFunc::RangeSeq => {
+                assert_eq!(args.len(), 2);
+                let low = lowerer.obtain_constant_value(
+                    app.arguments[0].get_type(),
+                    args[0].clone(),
+                    app.position,
+                )?;
+                let high = lowerer.obtain_constant_value(
+                    app.arguments[1].get_type(),
+                    args[1].clone(),
+                    app.position,
+                )?;
+                lowerer.encode_sequence_range_constructor_call(app.get_type(), low, high)
+            }
+            BuiltinFunc::SeqFromSlice => {
+                // The snapshot of a slice is already a sequence of the
+                // snapshots of its elements.
+                assert_eq!(args.len(), 1);
+                Ok(args.pop().unwrap())
+            }
+            BuiltinFunc::MapDomain => {
+                assert_eq!(args.len(), 1);
+                let map_type = match app.arguments[0].get_type() {
+                    vir_mid::Type::Reference(reference) => &*reference.target_type,
+                    map_type => map_type,
+                };
+                lowerer.encode_map_domain_call(map_type, args.pop().unwrap())
+            }
+            BuiltinFunc::SeqToSet => {
+                assert_eq!(args.len(), 1);
+                let seq_type = match app.arguments[0].get_type() {
+                    vir_mid::Type::Reference(reference) => &*reference.target_type,
+                    seq_type => seq_type,
+                };
+                lowerer.encode_sequence_to_set_call(seq_type, args.pop().unwrap())
+            }
+            BuiltinFunc::SeqToMultiSet => {
+                assert_eq!(args.len(), 1);
+                let seq_type = match app.arguments[0].get_type() {
+                    vir_mid::Type::Reference(reference) => &*reference.target_type,
+                    seq_type => seq_type,
+                };
+                lowerer.encode_sequence_to_multiset_call(seq_type, args.pop().unwrap())
+            }
             BuiltinFunc::LifetimeIncluded => {
                 assert_eq!(args.len(), 2);
                 lowerer.encode_lifetime_included()?;
@@ -622,6 +717,58 @@ pub(super) trait IntoSnapshotLowerer<'p, 'v: 'p, 'tcx: 'v> {
                     app.position,
                 ))
             }
+            BuiltinFunc::EmptySet | BuiltinFunc::SingleSet => set(ContainerOpKind::SetConstructor),
+            BuiltinFunc::SetUnion => set(ContainerOpKind::SetUnion),
+            BuiltinFunc::SetIntersection => set(ContainerOpKind::SetIntersection),
+            BuiltinFunc::SetMinus => set(ContainerOpKind::SetMinus),
+            BuiltinFunc::SetSubset => {
+                let value = set(ContainerOpKind::SetSubset)?;
+                let value =
+                    lowerer.construct_constant_snapshot(app.get_type(), value, app.position)?;
+                self.ensure_bool_expression(lowerer, app.get_type(), value, expect_math_bool)
+            }
+            BuiltinFunc::SetContains => {
+                // Viper expects the element before the set.
+                let value = vir_low::Expression::container_op(
+                    ContainerOpKind::SetContains,
+                    vir_low::Type::set(ty_args[0].clone()),
+                    vec![args[1].clone(), args[0].clone()],
+                    app.position,
+                );
+                let value =
+                    lowerer.construct_constant_snapshot(app.get_type(), value, app.position)?;
+                self.ensure_bool_expression(lowerer, app.get_type(), value, expect_math_bool)
+            }
+            BuiltinFunc::SetLen => {
+                let value = set(ContainerOpKind::SetCardinality)?;
+                lowerer.construct_constant_snapshot(app.get_type(), value, app.position)
+            }
+            BuiltinFunc::EmptyMultiSet | BuiltinFunc::SingleMultiSet => {
+                multi_set(ContainerOpKind::MultiSetConstructor)
+            }
+            BuiltinFunc::MultiSetUnion => multi_set(ContainerOpKind::MultiSetUnion),
+            BuiltinFunc::MultiSetIntersection => multi_set(ContainerOpKind::MultiSetIntersection),
+            BuiltinFunc::MultiSetMinus => multi_set(ContainerOpKind::MultiSetMinus),
+            BuiltinFunc::MultiSetSubset => {
+                let value = multi_set(ContainerOpKind::MultiSetSubset)?;
+                let value =
+                    lowerer.construct_constant_snapshot(app.get_type(), value, app.position)?;
+                self.ensure_bool_expression(lowerer, app.get_type(), value, expect_math_bool)
+            }
+            BuiltinFunc::MultiSetCount => {
+                // Viper expects the element before the multiset.
+                let value = vir_low::Expression::container_op(
+                    ContainerOpKind::MultiSetContains,
+                    vir_low::Type::multi_set(ty_args[0].clone()),
+                    vec![args[1].clone(), args[0].clone()],
+                    app.position,
+                );
+                lowerer.construct_constant_snapshot(app.get_type(), value, app.position)
+            }
+            BuiltinFunc::MultiSetLen => {
+                let value = multi_set(ContainerOpKind::MultiSetCardinality)?;
+                lowerer.construct_constant_snapshot(app.get_type(), value, app.position)
+            }
             BuiltinFunc::NewInt => {
                 assert_eq!(args.len(), 1);
                 let arg = args.pop().unwrap();
@@ -233,6 +233,8 @@ impl<'p, 'v: 'p, 'tcx: 'v> Private for Lowerer<'p, 'v, 'tcx> {
                 }
                 vir_mid::TypeDecl::Sequence(_) => unimplemented!("ty: {}", type_decl),
                 vir_mid::TypeDecl::Map(_) => unimplemented!("ty: {}", type_decl),
+                vir_mid::TypeDecl::Set(_) => unimplemented!("ty: {}", type_decl),
+                vir_mid::TypeDecl::MultiSet(_) => unimplemented!("ty: {}", type_decl),
                 vir_mid::TypeDecl::Never => unimplemented!("ty: {}", type_decl),
                 vir_mid::TypeDecl::Closure(_) => unimplemented!("ty: {}", type_decl),
                 vir_mid::TypeDecl::Unsupported(_) => unimplemented!("ty: {}", type_decl),
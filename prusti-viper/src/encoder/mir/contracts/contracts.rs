@@ -39,6 +39,12 @@ where
     /// Magic wands passed out of the procedure.
     /// TODO: Implement support for `blocked_lifetimes` via nested magic wands.
     pub borrow_infos: Vec<BorrowInfo<P>>,
+    /// Places behind `&mut` arguments that are unchanged because of the
+    /// `modifies` and `reads` clauses. For example, if `(*_2).1` is in the
+    /// vector, this means that we have
+    /// `snap(old[precondition](_2.val_ref.1)) == snap(_2.val_ref.1)` in the
+    /// postcondition.
+    pub framed_places: Vec<P>,
     /// The functional specification: precondition and postcondition
     pub specification: typed::ProcedureSpecification,
 }
@@ -105,19 +111,21 @@ impl<L: fmt::Debug, P: fmt::Debug> ProcedureContractGeneric<L, P> {
         }
     }
 
+    /// Returns the components of the termination measure, which are compared
+    /// lexicographically.
     pub fn functional_termination_measure<'a, 'tcx>(
         &'a self,
         env: &'a Environment<'tcx>,
         substs: SubstsRef<'tcx>,
-    ) -> Option<(LocalDefId, SubstsRef<'tcx>)> {
-        match self.specification.terminates {
+    ) -> Option<(&'a [LocalDefId], SubstsRef<'tcx>)> {
+        match &self.specification.terminates {
             typed::SpecificationItem::Empty => None,
-            typed::SpecificationItem::Inherent(t) | typed::SpecificationItem::Refined(_, t) => {
-                t.map(|inherent_def_id| (inherent_def_id, substs))
-            }
-            typed::SpecificationItem::Inherited(t) => t.map(|inherited_def_id| {
+            typed::SpecificationItem::Inherent(t) | typed::SpecificationItem::Refined(_, t) => t
+                .as_ref()
+                .map(|inherent_def_ids| (inherent_def_ids.as_slice(), substs)),
+            typed::SpecificationItem::Inherited(t) => t.as_ref().map(|inherited_def_ids| {
                 (
-                    inherited_def_id,
+                    inherited_def_ids.as_slice(),
                     // Same comment as `functional_precondition` applies.
                     env.query
                         .find_trait_method_substs(self.def_id, substs)
@@ -128,6 +136,52 @@ impl<L: fmt::Debug, P: fmt::Debug> ProcedureContractGeneric<L, P> {
         }
     }
 
+    /// Returns the `modifies` clauses of the procedure.
+    pub fn modifies_clauses<'a, 'tcx>(
+        &'a self,
+        env: &'a Environment<'tcx>,
+        substs: SubstsRef<'tcx>,
+    ) -> Vec<(DefId, SubstsRef<'tcx>)> {
+        self.frame_clauses(&self.specification.modifies, env, substs)
+    }
+
+    /// Returns the `reads` clauses of the procedure.
+    pub fn reads_clauses<'a, 'tcx>(
+        &'a self,
+        env: &'a Environment<'tcx>,
+        substs: SubstsRef<'tcx>,
+    ) -> Vec<(DefId, SubstsRef<'tcx>)> {
+        self.frame_clauses(&self.specification.reads, env, substs)
+    }
+
+    fn frame_clauses<'a, 'tcx>(
+        &'a self,
+        clauses: &'a typed::SpecificationItem<Vec<DefId>>,
+        env: &'a Environment<'tcx>,
+        substs: SubstsRef<'tcx>,
+    ) -> Vec<(DefId, SubstsRef<'tcx>)> {
+        match clauses {
+            typed::SpecificationItem::Empty => vec![],
+            typed::SpecificationItem::Inherent(clauses)
+            | typed::SpecificationItem::Refined(_, clauses) => clauses
+                .iter()
+                .map(|inherent_def_id| (*inherent_def_id, substs))
+                .collect(),
+            typed::SpecificationItem::Inherited(clauses) => {
+                // Same comment as `functional_precondition` applies.
+                let trait_substs = env
+                    .query
+                    .find_trait_method_substs(self.def_id, substs)
+                    .unwrap()
+                    .1;
+                clauses
+                    .iter()
+                    .map(|inherited_def_id| (*inherited_def_id, trait_substs))
+                    .collect()
+            }
+        }
+    }
+
     pub fn pledges(&self) -> impl Iterator<Item = &typed::Pledge> + '_ {
         self.specification
             .pledges
@@ -199,6 +253,7 @@ impl<'tcx> ProcedureContractMirDef<'tcx> {
                 .collect(),
             returned_value: self.returned_value.into(),
             borrow_infos,
+            framed_places: self.framed_places.iter().map(|p| p.into()).collect(),
             specification: self.specification.clone(),
         }
     }
@@ -215,14 +270,15 @@ impl<'tcx> ProcedureContractMirDef<'tcx> {
         for (from, to) in self.args.iter().zip(args) {
             substitutions.insert(*from, *to);
         }
-        let substitute = |(place, mutability): &(_, Mutability)| {
+        let substitute_place = |place: &mir::Place<'tcx>| {
             let root = get_place_root(*place);
-            let substitute_place = places::Place::SubstitutedPlace {
+            places::Place::SubstitutedPlace {
                 substituted_root: *substitutions.get(&root).unwrap(),
                 place: *place,
-            };
-            (substitute_place, *mutability)
+            }
         };
+        let substitute =
+            |(place, mutability): &(_, Mutability)| (substitute_place(place), *mutability);
         let borrow_infos = self
             .borrow_infos
             .iter()
@@ -233,12 +289,14 @@ impl<'tcx> ProcedureContractMirDef<'tcx> {
             })
             .collect();
         let returned_refs = self.returned_refs.iter().map(&substitute).collect();
+        let framed_places = self.framed_places.iter().map(substitute_place).collect();
         ProcedureContract {
             def_id: self.def_id,
             args: args.to_vec(),
             returned_refs,
             returned_value: target,
             borrow_infos,
+            framed_places,
             specification: self.specification.clone(),
         }
     }
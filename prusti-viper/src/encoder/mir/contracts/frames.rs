@@ -0,0 +1,265 @@
+//! Frame conditions given by the `modifies` and `reads` clauses.
+//!
+//! Every place listed in a clause must be behind a `&mut` argument. If a
+//! procedure has a `modifies` clause, all places behind its `&mut` arguments
+//! that are not listed in it are unchanged; the places listed in a `reads`
+//! clause are always unchanged. The body of a procedure with a `modifies`
+//! clause is checked syntactically to only write to the listed places; writes
+//! through reborrows passed to other procedures are checked by the verifier
+//! instead, as part of the postcondition.
+
+use super::interface::ContractsEncoderInterface;
+use crate::encoder::{
+    errors::{SpannedEncodingError, SpannedEncodingResult, WithSpan},
+    Encoder,
+};
+use prusti_interface::environment::Environment;
+use prusti_rustc_interface::{
+    hir::{def_id::DefId, Mutability},
+    index::vec::Idx,
+    middle::{
+        mir,
+        ty::{self, subst::SubstsRef, Ty, TyCtxt},
+    },
+    span::Span,
+};
+
+/// Returns the places listed in the given `modifies` or `reads` clauses,
+/// together with the spans at which they are listed.
+pub(super) fn clause_places<'tcx>(
+    env: &Environment<'tcx>,
+    clauses: Vec<(DefId, SubstsRef<'tcx>)>,
+    proc_def_id: DefId,
+) -> Vec<(mir::Place<'tcx>, Span)> {
+    let mut places = vec![];
+    for (clause_def_id, clause_substs) in clauses {
+        // The clause is a function with the same arguments as the procedure
+        // that borrows each listed place.
+        let body = env
+            .body
+            .get_spec_body(clause_def_id, clause_substs, proc_def_id);
+        for block in body.basic_blocks.iter() {
+            for statement in &block.statements {
+                if let mir::StatementKind::Assign(box (_, mir::Rvalue::Ref(_, _, place))) =
+                    statement.kind
+                {
+                    if (1..=body.arg_count).contains(&place.local.index()) {
+                        places.push((place, statement.source_info.span));
+                    }
+                }
+            }
+        }
+    }
+    places
+}
+
+/// Returns the places behind `&mut` arguments that are unchanged because of
+/// the `modifies` clauses (if there are any) and the `reads` clauses.
+pub(super) fn framed_places<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    args: &[(mir::Local, Ty<'tcx>)],
+    modified: Option<&[mir::Place<'tcx>]>,
+    read: &[mir::Place<'tcx>],
+) -> Vec<mir::Place<'tcx>> {
+    let mut framed = vec![];
+    if let Some(modified) = modified {
+        for &(local, ty) in args {
+            if let ty::TyKind::Ref(_, target_ty, Mutability::Mut) = ty.kind() {
+                let target = tcx.mk_place_deref(local.into());
+                collect_unmodified_places(tcx, target, *target_ty, modified, &mut framed);
+            }
+        }
+    }
+    for place in read {
+        if !is_behind_mut_arg(*place, args)
+            || modified.map_or(false, |modified| overlaps_any(*place, modified))
+            || framed.iter().any(|framed| is_prefix(*framed, *place))
+        {
+            continue;
+        }
+        framed.push(*place);
+    }
+    framed
+}
+
+/// Collects the largest places inside `place` that do not overlap with any of
+/// the `modified` places. Places of types other than structs and tuples are not
+/// split into their fields.
+fn collect_unmodified_places<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    place: mir::Place<'tcx>,
+    ty: Ty<'tcx>,
+    modified: &[mir::Place<'tcx>],
+    framed: &mut Vec<mir::Place<'tcx>>,
+) {
+    if modified.iter().any(|modified| is_prefix(*modified, place)) {
+        return;
+    }
+    if !modified.iter().any(|modified| is_prefix(place, *modified)) {
+        framed.push(place);
+        return;
+    }
+    match ty.kind() {
+        ty::TyKind::Adt(adt_def, substs) if adt_def.is_struct() => {
+            for (index, field_def) in adt_def.non_enum_variant().fields.iter().enumerate() {
+                let field_ty = field_def.ty(tcx, substs);
+                let field_place = tcx.mk_place_field(place, mir::Field::new(index), field_ty);
+                collect_unmodified_places(tcx, field_place, field_ty, modified, framed);
+            }
+        }
+        ty::TyKind::Tuple(field_tys) => {
+            for (index, field_ty) in field_tys.iter().enumerate() {
+                let field_place = tcx.mk_place_field(place, mir::Field::new(index), field_ty);
+                collect_unmodified_places(tcx, field_place, field_ty, modified, framed);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Checks that the places listed in the `modifies` and `reads` clauses of the
+/// procedure are behind `&mut` arguments, that no place is listed in both
+/// kinds of clauses, and that the body only writes to places behind `&mut`
+/// arguments that are listed in a `modifies` clause.
+pub(crate) fn check_frame_conditions<'v, 'tcx: 'v>(
+    encoder: &Encoder<'v, 'tcx>,
+    proc_def_id: DefId,
+    substs: SubstsRef<'tcx>,
+    mir: &mir::Body<'tcx>,
+) -> SpannedEncodingResult<()> {
+    let env = encoder.env();
+    let contract = encoder
+        .get_mir_procedure_contract_for_def(proc_def_id, substs)
+        .with_span(mir.span)?;
+    let modifies_clauses = contract.modifies_clauses(env, substs);
+    let has_modifies_clause = !modifies_clauses.is_empty();
+    let modified = clause_places(env, modifies_clauses, contract.def_id);
+    let read = clause_places(env, contract.reads_clauses(env, substs), contract.def_id);
+    let args = mir
+        .args_iter()
+        .map(|local| (local, mir.local_decls[local].ty))
+        .collect::<Vec<_>>();
+    for (place, span) in &modified {
+        if !is_behind_mut_arg(*place, &args) {
+            return Err(SpannedEncodingError::incorrect(
+                "only places behind `&mut` arguments can be listed in a `modifies` clause",
+                *span,
+            ));
+        }
+    }
+    for (place, span) in &read {
+        if !is_behind_mut_arg(*place, &args) {
+            return Err(SpannedEncodingError::incorrect(
+                "only places behind `&mut` arguments can be listed in a `reads` clause",
+                *span,
+            ));
+        }
+        if modified
+            .iter()
+            .any(|(modified, _)| is_prefix(*modified, *place) || is_prefix(*place, *modified))
+        {
+            return Err(SpannedEncodingError::incorrect(
+                "this place overlaps with a place listed in a `modifies` clause",
+                *span,
+            ));
+        }
+    }
+    if !has_modifies_clause {
+        return Ok(());
+    }
+
+    let modified = modified
+        .into_iter()
+        .map(|(place, _)| place)
+        .collect::<Vec<_>>();
+    // Writes through an argument that gets reassigned in the body do not
+    // necessarily target the original pointee, so we leave them to the
+    // verifier.
+    let mut checked_args = args.clone();
+    for block in mir.basic_blocks.iter() {
+        for statement in &block.statements {
+            if let mir::StatementKind::Assign(box (target, _)) = &statement.kind {
+                if target.projection.is_empty() {
+                    checked_args.retain(|(local, _)| *local != target.local);
+                }
+            }
+        }
+    }
+    let check_write = |place: mir::Place<'tcx>, span: Span| {
+        if is_behind_mut_arg(place, &checked_args)
+            && !modified.iter().any(|modified| is_prefix(*modified, place))
+        {
+            Err(SpannedEncodingError::incorrect(
+                "the procedure modifies a place that is not listed in its `modifies` clause",
+                span,
+            ))
+        } else {
+            Ok(())
+        }
+    };
+    for block in mir.basic_blocks.iter() {
+        for statement in &block.statements {
+            let span = statement.source_info.span;
+            match &statement.kind {
+                mir::StatementKind::Assign(box (target, rvalue)) => {
+                    check_write(*target, span)?;
+                    if let mir::Rvalue::Ref(_, mir::BorrowKind::Mut { .. }, place)
+                    | mir::Rvalue::AddressOf(Mutability::Mut, place) = rvalue
+                    {
+                        // Reborrows of the entire pointee are passed on to
+                        // other procedures.
+                        if place.projection.len() > 1 {
+                            check_write(*place, span)?;
+                        }
+                    }
+                }
+                mir::StatementKind::SetDiscriminant { place, .. }
+                | mir::StatementKind::Deinit(place) => {
+                    check_write(**place, span)?;
+                }
+                _ => {}
+            }
+        }
+        let span = block.terminator().source_info.span;
+        match &block.terminator().kind {
+            mir::TerminatorKind::Call { destination, .. } => {
+                check_write(*destination, span)?;
+            }
+            mir::TerminatorKind::DropAndReplace { place, .. } => {
+                check_write(*place, span)?;
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Whether the place is behind one of the given arguments and the argument is
+/// a mutable reference.
+fn is_behind_mut_arg<'tcx>(place: mir::Place<'tcx>, args: &[(mir::Local, Ty<'tcx>)]) -> bool {
+    matches!(place.projection.first(), Some(mir::ProjectionElem::Deref))
+        && args.iter().any(|(local, ty)| {
+            *local == place.local && matches!(ty.kind(), ty::TyKind::Ref(_, _, Mutability::Mut))
+        })
+}
+
+fn overlaps_any<'tcx>(place: mir::Place<'tcx>, others: &[mir::Place<'tcx>]) -> bool {
+    others
+        .iter()
+        .any(|other| is_prefix(*other, place) || is_prefix(place, *other))
+}
+
+/// Whether `prefix` is a prefix of (or equal to) `place`. The types of the
+/// fields are not compared, because they may differ in their regions.
+fn is_prefix<'tcx>(prefix: mir::Place<'tcx>, place: mir::Place<'tcx>) -> bool {
+    prefix.local == place.local
+        && prefix.projection.len() <= place.projection.len()
+        && prefix
+            .projection
+            .iter()
+            .zip(place.projection.iter())
+            .all(|(a, b)| match (a, b) {
+                (mir::ProjectionElem::Field(a, _), mir::ProjectionElem::Field(b, _)) => a == b,
+                (a, b) => a == b,
+            })
+}
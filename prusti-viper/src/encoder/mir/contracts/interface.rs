@@ -1,6 +1,7 @@
 use super::{
     borrows::BorrowInfoCollectingVisitor,
     contracts::{ProcedureContract, ProcedureContractGeneric, ProcedureContractMirDef},
+    frames,
 };
 use crate::{
     encoder::{
@@ -152,7 +153,12 @@ fn get_procedure_contract<'p, 'v: 'p, 'tcx: 'v>(
         args_ty = (0usize..fn_sig.inputs().len())
             .map(|i| (mir::Local::from_usize(i + 1), fn_sig.inputs()[i]))
             .collect();
-        return_ty = fn_sig.output();
+        // The contract of an `async fn` is about the value produced by
+        // awaiting the returned future.
+        return_ty = env
+            .query
+            .get_async_fn_output_ty(proc_def_id, substs)
+            .unwrap_or_else(|| fn_sig.output());
     } else {
         let mir = env
             .body
@@ -175,7 +181,7 @@ fn get_procedure_contract<'p, 'v: 'p, 'tcx: 'v>(
 
     let mut fake_mir_args = Vec::new();
     let mut fake_mir_args_ty = Vec::new();
-    for (local, arg_ty) in args_ty {
+    for &(local, arg_ty) in &args_ty {
         fake_mir_args.push(local);
         fake_mir_args_ty.push(arg_ty);
     }
@@ -210,14 +216,35 @@ fn get_procedure_contract<'p, 'v: 'p, 'tcx: 'v>(
         .into_iter()
         .filter(|(place, _)| is_not_blocked(*place))
         .collect();
-    let contract = ProcedureContractGeneric {
+    let mut contract = ProcedureContractGeneric {
         def_id: proc_def_id,
         args: fake_mir_args,
         returned_refs,
         returned_value: mir::RETURN_PLACE,
         borrow_infos,
+        framed_places: vec![],
         specification,
     };
+    let modifies_clauses = contract.modifies_clauses(env, substs);
+    let modified = if modifies_clauses.is_empty() {
+        None
+    } else {
+        Some(frames::clause_places(env, modifies_clauses, proc_def_id))
+    };
+    let read = frames::clause_places(env, contract.reads_clauses(env, substs), proc_def_id);
+    contract.framed_places = frames::framed_places(
+        env.tcx(),
+        &args_ty,
+        modified
+            .map(|places| {
+                places
+                    .into_iter()
+                    .map(|(place, _)| place)
+                    .collect::<Vec<_>>()
+            })
+            .as_deref(),
+        &read.into_iter().map(|(place, _)| place).collect::<Vec<_>>(),
+    );
 
     trace!("[get_procedure_contract] exit result={}", contract);
     Ok(contract)
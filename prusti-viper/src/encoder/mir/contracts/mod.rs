@@ -3,7 +3,9 @@
 mod borrows;
 #[allow(clippy::module_inception)]
 mod contracts;
+mod frames;
 mod interface;
 
 pub(crate) use contracts::{ProcedureContract, ProcedureContractMirDef};
+pub(crate) use frames::check_frame_conditions;
 pub(crate) use interface::{ContractsEncoderInterface, ContractsEncoderState};
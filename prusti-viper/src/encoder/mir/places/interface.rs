@@ -225,18 +225,30 @@ impl<'v, 'tcx: 'v> PlacesEncoderInterface<'tcx> for super::super::super::Encoder
                     ..
                 } => {
                     debug!("constantindex: {:?}[len - {}]", expr, offset);
-                    unimplemented!("TODO: Unify encoding of slices and arrays.");
+                    let len = self
+                        .encode_len_call(expr.clone())
+                        .with_span(declaration_span)?;
+                    let encoded_index =
+                        vir_high::Expression::subtract(len, (offset as usize).into());
+                    self.encode_index_call(expr, encoded_index)
+                        .with_span(declaration_span)?
+                }
+                mir::ProjectionElem::Subslice { from, to, from_end } => {
+                    debug!(
+                        "subslice: {:?}[{}..{}] (from_end: {})",
+                        expr, from, to, from_end
+                    );
+                    let encoded_to = if from_end {
+                        let len = self
+                            .encode_len_call(expr.clone())
+                            .with_span(declaration_span)?;
+                        vir_high::Expression::subtract(len, (to as usize).into())
+                    } else {
+                        (to as usize).into()
+                    };
+                    self.encode_subslice_bounds_call(expr, (from as usize).into(), encoded_to, ty)
+                        .with_span(declaration_span)?
                 }
-                // ConstantIndex {
-                //     offset: u64,
-                //     min_length: u64,
-                //     from_end: bool,
-                // },
-                // Subslice {
-                //     from: u64,
-                //     to: u64,
-                //     from_end: bool,
-                // },
                 mir::ProjectionElem::Downcast(Some(symbol), _variant) => {
                     let variant_index = symbol.as_str().to_string().into();
                     vir_high::Expression::variant_no_pos(expr, variant_index, ty)
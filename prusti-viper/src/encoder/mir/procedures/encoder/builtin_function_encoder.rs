@@ -224,6 +224,9 @@ impl<'p, 'v, 'tcx> BuiltinFuncAppEncoder<'p, 'v, 'tcx> for super::ProcedureEncod
             "prusti_contracts::Map::<K, V>::lookup" => {
                 make_builtin_call(self, block_builder, vir_high::BuiltinFunc::LookupMap)?
             }
+            "prusti_contracts::Map::<K, V>::domain" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::MapDomain)?
+            }
             "prusti_contracts::Seq::<T>::empty" => {
                 make_builtin_call(self, block_builder, vir_high::BuiltinFunc::EmptySeq)?
             }
@@ -236,12 +239,98 @@ impl<'p, 'v, 'tcx> BuiltinFuncAppEncoder<'p, 'v, 'tcx> for super::ProcedureEncod
             "prusti_contracts::Seq::<T>::lookup" => {
                 make_builtin_call(self, block_builder, vir_high::BuiltinFunc::LookupSeq)?
             }
+            "prusti_contracts::Seq::<T>::take" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::TakeSeq)?
+            }
+            "prusti_contracts::Seq::<T>::skip" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::SkipSeq)?
+            }
+            "prusti_contracts::Seq::<T>::update" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::UpdateSeq)?
+            }
+            "prusti_contracts::Seq::<T>::contains" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::SeqContains)?
+            }
+            "prusti_contracts::Seq::<T>::from_slice" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::SeqFromSlice)?
+            }
+            "prusti_contracts::Seq::<T>::to_set" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::SeqToSet)?
+            }
+            "prusti_contracts::Seq::<T>::to_multiset" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::SeqToMultiSet)?
+            }
+            "prusti_contracts::Seq::<prusti_contracts::Int>::range" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::RangeSeq)?
+            }
+            "prusti_contracts::Set::<T>::empty" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::EmptySet)?
+            }
+            "prusti_contracts::Set::<T>::single" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::SingleSet)?
+            }
+            "prusti_contracts::Set::<T>::union" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::SetUnion)?
+            }
+            "prusti_contracts::Set::<T>::intersection" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::SetIntersection)?
+            }
+            "prusti_contracts::Set::<T>::difference" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::SetMinus)?
+            }
+            "prusti_contracts::Set::<T>::is_subset" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::SetSubset)?
+            }
+            "prusti_contracts::Set::<T>::contains" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::SetContains)?
+            }
+            "prusti_contracts::Set::<T>::len" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::SetLen)?
+            }
+            "prusti_contracts::Multiset::<T>::empty" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::EmptyMultiSet)?
+            }
+            "prusti_contracts::Multiset::<T>::single" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::SingleMultiSet)?
+            }
+            "prusti_contracts::Multiset::<T>::union" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::MultiSetUnion)?
+            }
+            "prusti_contracts::Multiset::<T>::intersection" => make_builtin_call(
+                self,
+                block_builder,
+                vir_high::BuiltinFunc::MultiSetIntersection,
+            )?,
+            "prusti_contracts::Multiset::<T>::difference" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::MultiSetMinus)?
+            }
+            "prusti_contracts::Multiset::<T>::is_subset" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::MultiSetSubset)?
+            }
+            "prusti_contracts::Multiset::<T>::count" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::MultiSetCount)?
+            }
+            "prusti_contracts::Multiset::<T>::len" => {
+                make_builtin_call(self, block_builder, vir_high::BuiltinFunc::MultiSetLen)?
+            }
             "prusti_contracts::Ghost::<T>::new" => {
                 make_manual_assign(self, block_builder, &mut |_, args, _| args[0].clone())?
             }
             "prusti_contracts::snapshot_equality" => {
                 unreachable!();
             }
+            "std::iter::IntoIterator::into_iter" | "core::iter::IntoIterator::into_iter" => {
+                // Ranges and slice iterators are returned unchanged, which
+                // allows tracking the iterator of a `for` loop.
+                let self_type = call_substs.type_at(0);
+                if self.range_element_type(self_type).is_some()
+                    || self.slice_iterator_element_type(self_type).is_some()
+                {
+                    make_manual_assign(self, block_builder, &mut |_, args, _| args[0].clone())?
+                } else {
+                    return Ok(false);
+                }
+            }
             "std::ops::Index::index" | "core::ops::Index::index" => {
                 let lhs = self
                     .encode_statement_operand(location, &args[0])?
@@ -271,7 +360,9 @@ impl<'p, 'v, 'tcx> BuiltinFuncAppEncoder<'p, 'v, 'tcx> for super::ProcedureEncod
                     vir_high::Type::Reference(vir_high::ty::Reference {
                         target_type: box vir_high::Type::Int(vir_high::ty::Int::Unbounded)
                             | box vir_high::Type::Sequence(..)
-                            | box vir_high::Type::Map(..),
+                            | box vir_high::Type::Map(..)
+                            | box vir_high::Type::Set(..)
+                            | box vir_high::Type::MultiSet(..),
                         ..
                     })
                 ) {
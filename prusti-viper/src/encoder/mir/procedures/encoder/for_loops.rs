@@ -0,0 +1,901 @@
+//! Support for `for` loops over ranges and slices.
+//!
+//! A `for` loop is desugared by the compiler into a loop that repeatedly calls
+//! `Iterator::next` on an iterator. For ranges of integers and for slice
+//! iterators, we know what `next` does, and we track the progress of the loop
+//! in two ghost variables: the number of elements returned so far (the
+//! iteration index) and the sequence of these elements (the visited prefix).
+//! The user can refer to them in `body_invariant!` via
+//! `prusti_contracts::iteration_index()` and `prusti_contracts::visited()`.
+
+use super::ProcedureEncoder;
+use crate::encoder::{
+    encoder::encode_field_name,
+    errors::{ErrorCtxt, SpannedEncodingError, SpannedEncodingResult, WithSpan},
+    high::pure_functions::HighPureFunctionEncoderInterface,
+    mir::{places::PlacesEncoderInterface, types::MirTypeEncoderInterface},
+};
+use prusti_rustc_interface::{
+    middle::{mir, ty},
+    span::Span,
+};
+use std::collections::BTreeMap;
+use vir_crate::{
+    common::expression::{BinaryOperationHelpers, UnaryOperationHelpers},
+    high::{
+        self as vir_high,
+        ast::expression::visitors::{default_fold_expression, ExpressionFolder},
+        operations::{lifetimes::WithLifetimes, ty::Typed},
+    },
+};
+
+/// A `for` loop whose iterator is understood by the encoder.
+#[derive(Clone)]
+pub(super) struct ForLoop {
+    /// The local that stores the iterator.
+    pub(super) iterator: mir::Local,
+    /// The block whose terminator calls `Iterator::next`.
+    pub(super) next_call: mir::BasicBlock,
+    /// The only block through which the loop is entered.
+    pub(super) entry_block: mir::BasicBlock,
+    pub(super) element_type: vir_high::Type,
+    pub(super) iteration_index: vir_high::VariableDecl,
+    pub(super) visited: vir_high::VariableDecl,
+    pub(super) iterated: IteratedValues,
+}
+
+#[derive(Clone)]
+pub(super) enum IteratedValues {
+    /// A range of integers. `start` and `end` store the bounds of the range
+    /// when the loop was entered.
+    Range {
+        iterator_type: vir_high::Type,
+        start_field: vir_high::FieldDecl,
+        end_field: vir_high::FieldDecl,
+        start: vir_high::VariableDecl,
+        end: vir_high::VariableDecl,
+    },
+    /// The elements of the iterated slice.
+    Slice { elements: vir_high::VariableDecl },
+}
+
+impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
+    /// Finds the `for` loops over ranges and slices.
+    pub(super) fn collect_for_loops(&mut self) -> SpannedEncodingResult<()> {
+        let slice_iterator_origins = self.collect_slice_iterator_origins();
+        let mut loop_heads: Vec<_> = self
+            .procedure
+            .loop_info()
+            .loop_heads
+            .iter()
+            .copied()
+            .collect();
+        loop_heads.sort();
+        for loop_head in loop_heads {
+            if let Some(for_loop) = self.try_collect_for_loop(loop_head, &slice_iterator_origins)? {
+                self.for_loops.insert(loop_head, for_loop);
+            }
+        }
+        Ok(())
+    }
+
+    fn try_collect_for_loop(
+        &mut self,
+        loop_head: mir::BasicBlock,
+        slice_iterator_origins: &BTreeMap<mir::Local, mir::Location>,
+    ) -> SpannedEncodingResult<Option<ForLoop>> {
+        let Some((next_call, iterator)) = self.find_next_call(loop_head) else {
+            return Ok(None);
+        };
+        let Some(entry_block) = self.find_entry_block(loop_head) else {
+            return Ok(None);
+        };
+        if !self.is_only_advanced_by(loop_head, iterator, next_call) {
+            return Ok(None);
+        }
+        let iterator_mir_type = self.mir.local_decls[iterator].ty;
+        let (element_type, iterated) = if let Some(element_mir_type) =
+            self.range_element_type(iterator_mir_type)
+        {
+            let element_type = self.encoder.encode_type_high(element_mir_type)?;
+            let iterator_type = self.encoder.encode_type_high(iterator_mir_type)?;
+            let range_decl = self
+                .encoder
+                .encode_type_def_high(&iterator_type)?
+                .unwrap_struct();
+            let find_field = |name: &str| {
+                let name = encode_field_name(name);
+                range_decl
+                    .fields
+                    .iter()
+                    .find(|field| field.name == name)
+                    .cloned()
+                    .unwrap()
+            };
+            let start_field = find_field("start");
+            let end_field = find_field("end");
+            let start = self.fresh_ghost_variable("range_start", element_type.clone());
+            let end = self.fresh_ghost_variable("range_end", element_type.clone());
+            let iterated = IteratedValues::Range {
+                iterator_type,
+                start_field,
+                end_field,
+                start,
+                end,
+            };
+            (element_type, iterated)
+        } else if let Some(element_mir_type) = self.slice_iterator_element_type(iterator_mir_type) {
+            let Some(&origin) = slice_iterator_origins.get(&iterator) else {
+                return Ok(None);
+            };
+            let element_type = self.encoder.encode_type_high(element_mir_type)?;
+            let elements = if let Some(elements) = self.slice_iterator_sources.get(&origin) {
+                elements.clone()
+            } else {
+                let sequence_type =
+                    vir_high::Type::sequence(element_type.clone(), element_type.get_lifetimes());
+                let elements = self.fresh_ghost_variable("slice_elements", sequence_type);
+                self.slice_iterator_sources.insert(origin, elements.clone());
+                elements
+            };
+            (element_type, IteratedValues::Slice { elements })
+        } else {
+            return Ok(None);
+        };
+        let iteration_index = self.fresh_ghost_variable(
+            "iteration_index",
+            vir_high::Type::Int(vir_high::ty::Int::Usize),
+        );
+        let visited = self.fresh_ghost_variable(
+            "visited",
+            vir_high::Type::sequence(element_type.clone(), element_type.get_lifetimes()),
+        );
+        Ok(Some(ForLoop {
+            iterator,
+            next_call,
+            entry_block,
+            element_type,
+            iteration_index,
+            visited,
+            iterated,
+        }))
+    }
+
+    /// Follows the jumps from the loop head to the call of `Iterator::next`
+    /// and returns the block of the call together with the local that stores
+    /// the advanced iterator.
+    fn find_next_call(&self, loop_head: mir::BasicBlock) -> Option<(mir::BasicBlock, mir::Local)> {
+        let mut block = loop_head;
+        let mut visited_blocks = Vec::new();
+        let (args, target) = loop {
+            if visited_blocks.contains(&block) {
+                return None;
+            }
+            visited_blocks.push(block);
+            match &self.mir[block].terminator().kind {
+                mir::TerminatorKind::Goto { target }
+                | mir::TerminatorKind::FalseUnwind {
+                    real_target: target,
+                    ..
+                } => block = *target,
+                mir::TerminatorKind::Call {
+                    func, args, target, ..
+                } => {
+                    let (name, _) = self.called_function(func)?;
+                    if !matches!(
+                        name.as_str(),
+                        "std::iter::Iterator::next" | "core::iter::Iterator::next"
+                    ) {
+                        return None;
+                    }
+                    break (args, target);
+                }
+                _ => return None,
+            }
+        };
+        target.as_ref()?;
+        let [mir::Operand::Move(argument)] = args.as_slice() else {
+            return None;
+        };
+        // The iterator is passed as `&mut iterator`, possibly reborrowed.
+        let mut reference = argument.as_local()?;
+        for statement in self.mir[block].statements.iter().rev() {
+            if let mir::StatementKind::Assign(box (
+                target,
+                mir::Rvalue::Ref(_, mir::BorrowKind::Mut { .. }, borrowed),
+            )) = &statement.kind
+            {
+                if target.as_local() == Some(reference) {
+                    if let Some(local) = borrowed.as_local() {
+                        return Some((block, local));
+                    } else if matches!(borrowed.projection[..], [mir::ProjectionElem::Deref]) {
+                        reference = borrowed.local;
+                    } else {
+                        return None;
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the only predecessor of the loop head that is not a back edge
+    /// if it jumps unconditionally into the loop.
+    fn find_entry_block(&self, loop_head: mir::BasicBlock) -> Option<mir::BasicBlock> {
+        let predecessors = self.mir.basic_blocks.predecessors();
+        let dominators = self.mir.basic_blocks.dominators();
+        let mut entries = predecessors[loop_head]
+            .iter()
+            .filter(|predecessor| !dominators.dominates(loop_head, **predecessor));
+        let entry_block = *entries.next()?;
+        if entries.next().is_some() {
+            return None;
+        }
+        if let mir::TerminatorKind::Goto { .. } = self.mir[entry_block].terminator().kind {
+            Some(entry_block)
+        } else {
+            None
+        }
+    }
+
+    /// Checks that inside the loop the iterator is modified only by the call
+    /// to `Iterator::next` in `next_call`. Otherwise, the ghost state would
+    /// not track the progress of the iterator.
+    fn is_only_advanced_by(
+        &self,
+        loop_head: mir::BasicBlock,
+        iterator: mir::Local,
+        next_call: mir::BasicBlock,
+    ) -> bool {
+        let loop_body = self.procedure.loop_info().get_loop_body(loop_head);
+        for &block in loop_body {
+            let data = &self.mir[block];
+            for statement in &data.statements {
+                if let mir::StatementKind::Assign(box (target, rvalue)) = &statement.kind {
+                    if target.local == iterator {
+                        return false;
+                    }
+                    if let mir::Rvalue::Ref(_, mir::BorrowKind::Mut { .. }, borrowed)
+                    | mir::Rvalue::AddressOf(_, borrowed) = rvalue
+                    {
+                        if borrowed.local == iterator && block != next_call {
+                            return false;
+                        }
+                    }
+                }
+            }
+            if let mir::TerminatorKind::Call { destination, .. } = &data.terminator().kind {
+                if destination.local == iterator {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Finds the locals that store a slice iterator and the location of the
+    /// call that created the iterator from a slice.
+    fn collect_slice_iterator_origins(&self) -> BTreeMap<mir::Local, mir::Location> {
+        let mut origins = BTreeMap::new();
+        for (block, data) in self.mir.basic_blocks.iter_enumerated() {
+            if let mir::TerminatorKind::Call {
+                func, destination, ..
+            } = &data.terminator().kind
+            {
+                if let (Some(local), true) =
+                    (destination.as_local(), self.is_slice_iterator_source(func))
+                {
+                    let location = mir::Location {
+                        block,
+                        statement_index: data.statements.len(),
+                    };
+                    origins.insert(local, location);
+                }
+            }
+        }
+        // Propagate the origins through moves and calls of `into_iter`.
+        let mut ambiguous = Vec::new();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            let mut propagate = |target: mir::Local, source: mir::Local| {
+                if ambiguous.contains(&target) {
+                    return;
+                }
+                if let Some(&origin) = origins.get(&source) {
+                    match origins.get(&target) {
+                        None => {
+                            origins.insert(target, origin);
+                            changed = true;
+                        }
+                        Some(&existing) if existing != origin => {
+                            origins.remove(&target);
+                            ambiguous.push(target);
+                            changed = true;
+                        }
+                        Some(_) => {}
+                    }
+                }
+            };
+            for data in self.mir.basic_blocks.iter() {
+                for statement in &data.statements {
+                    if let mir::StatementKind::Assign(box (
+                        target,
+                        mir::Rvalue::Use(mir::Operand::Move(source) | mir::Operand::Copy(source)),
+                    )) = &statement.kind
+                    {
+                        if let (Some(target), Some(source)) = (target.as_local(), source.as_local())
+                        {
+                            propagate(target, source);
+                        }
+                    }
+                }
+                if let mir::TerminatorKind::Call {
+                    func,
+                    args,
+                    destination,
+                    ..
+                } = &data.terminator().kind
+                {
+                    if let (Some((name, _)), [mir::Operand::Move(source)], Some(target)) = (
+                        self.called_function(func),
+                        args.as_slice(),
+                        destination.as_local(),
+                    ) {
+                        if let (
+                            "std::iter::IntoIterator::into_iter"
+                            | "core::iter::IntoIterator::into_iter",
+                            Some(source),
+                        ) = (name.as_str(), source.as_local())
+                        {
+                            propagate(target, source);
+                        }
+                    }
+                }
+            }
+        }
+        origins
+    }
+
+    /// Whether the function creates a slice iterator from a slice.
+    fn is_slice_iterator_source(&self, func: &mir::Operand<'tcx>) -> bool {
+        let Some((name, substs)) = self.called_function(func) else {
+            return false;
+        };
+        match name.as_str() {
+            "std::slice::<impl [T]>::iter" | "core::slice::<impl [T]>::iter" => true,
+            "std::iter::IntoIterator::into_iter" | "core::iter::IntoIterator::into_iter" => {
+                if let ty::TyKind::Ref(_, target, mir::Mutability::Not) = substs.type_at(0).kind() {
+                    matches!(target.kind(), ty::TyKind::Slice(_))
+                } else {
+                    false
+                }
+            }
+            _ => false,
+        }
+    }
+
+    fn called_function(
+        &self,
+        func: &mir::Operand<'tcx>,
+    ) -> Option<(String, ty::subst::SubstsRef<'tcx>)> {
+        if let ty::TyKind::FnDef(def_id, substs) =
+            func.ty(self.mir, self.encoder.env().tcx()).kind()
+        {
+            let name = self.encoder.env().name.get_absolute_item_name(*def_id);
+            Some((name, substs))
+        } else {
+            None
+        }
+    }
+
+    /// If the type is a range of integers, returns the type of the integers.
+    pub(super) fn range_element_type(&self, ty: ty::Ty<'tcx>) -> Option<ty::Ty<'tcx>> {
+        if let ty::TyKind::Adt(adt_def, substs) = ty.kind() {
+            let name = self
+                .encoder
+                .env()
+                .name
+                .get_absolute_item_name(adt_def.did());
+            if matches!(name.as_str(), "std::ops::Range" | "core::ops::Range")
+                && substs.type_at(0).is_integral()
+            {
+                return Some(substs.type_at(0));
+            }
+        }
+        None
+    }
+
+    /// If the type is a slice iterator, returns the type of the slice elements.
+    pub(super) fn slice_iterator_element_type(&self, ty: ty::Ty<'tcx>) -> Option<ty::Ty<'tcx>> {
+        if let ty::TyKind::Adt(adt_def, substs) = ty.kind() {
+            let name = self
+                .encoder
+                .env()
+                .name
+                .get_absolute_item_name(adt_def.did());
+            if matches!(name.as_str(), "std::slice::Iter" | "core::slice::Iter") {
+                // The first parameter of `Iter<'a, T>` is the lifetime.
+                return Some(substs.type_at(1));
+            }
+        }
+        None
+    }
+
+    /// The ghost statement to be executed before the call at `location` if
+    /// it creates the iterator of a `for` loop from a slice.
+    pub(super) fn encode_slice_iterator_source(
+        &mut self,
+        location: mir::Location,
+        args: &[mir::Operand<'tcx>],
+        span: Span,
+    ) -> SpannedEncodingResult<Option<vir_high::Statement>> {
+        let Some(elements) = self.slice_iterator_sources.get(&location).cloned() else {
+            return Ok(None);
+        };
+        let slice = self
+            .encoder
+            .encode_operand_high(self.mir, &args[0], span)
+            .with_span(span)?;
+        let sequence_type = elements.ty.clone();
+        let vir_high::Type::Sequence(sequence) = &sequence_type else {
+            unreachable!();
+        };
+        let value = vir_high::Expression::builtin_func_app_no_pos(
+            vir_high::BuiltinFunc::SeqFromSlice,
+            vec![(*sequence.element_type).clone()],
+            vec![slice],
+            sequence_type.clone(),
+        );
+        let statement = self.set_statement_error(
+            location,
+            ErrorCtxt::LoopInvariant,
+            vir_high::Statement::ghost_assign_no_pos(elements.into(), value),
+        )?;
+        Ok(Some(statement))
+    }
+
+    /// Initialises the ghost state of the `for` loop entered from `block`.
+    pub(super) fn encode_for_loop_initialisation(
+        &mut self,
+        block: mir::BasicBlock,
+    ) -> SpannedEncodingResult<Vec<vir_high::Statement>> {
+        let Some(for_loop) = self
+            .for_loops
+            .values()
+            .find(|for_loop| for_loop.entry_block == block)
+            .cloned()
+        else {
+            return Ok(Vec::new());
+        };
+        let mut assignments = vec![
+            (
+                for_loop.iteration_index.clone(),
+                vir_high::Expression::constant_no_pos(
+                    vir_high::expression::ConstantValue::Int(0),
+                    for_loop.iteration_index.ty.clone(),
+                ),
+            ),
+            (
+                for_loop.visited.clone(),
+                vir_high::Expression::builtin_func_app_no_pos(
+                    vir_high::BuiltinFunc::EmptySeq,
+                    vec![for_loop.element_type.clone()],
+                    Vec::new(),
+                    for_loop.visited.ty.clone(),
+                ),
+            ),
+        ];
+        if let IteratedValues::Range {
+            start_field,
+            end_field,
+            start,
+            end,
+            ..
+        } = &for_loop.iterated
+        {
+            let iterator: vir_high::Expression = self.encode_local(for_loop.iterator)?.into();
+            assignments.push((
+                start.clone(),
+                iterator.clone().field_no_pos(start_field.clone()),
+            ));
+            assignments.push((end.clone(), iterator.field_no_pos(end_field.clone())));
+        }
+        let location = mir::Location {
+            block,
+            statement_index: self.mir[block].statements.len(),
+        };
+        let mut statements = Vec::new();
+        for (variable, value) in assignments {
+            statements.push(self.set_statement_error(
+                location,
+                ErrorCtxt::LoopInvariant,
+                vir_high::Statement::ghost_assign_no_pos(variable.into(), value),
+            )?);
+        }
+        Ok(statements)
+    }
+
+    /// The facts about `Iterator::next` called in `block` on the iterator of
+    /// a `for` loop. The only argument is the mutable reference to the
+    /// iterator and `result` is the returned option.
+    pub(super) fn encode_for_loop_next_postconditions(
+        &mut self,
+        block: mir::BasicBlock,
+        arguments: &[vir_high::Expression],
+        result: &vir_high::Expression,
+        old_label: &str,
+        span: Span,
+    ) -> SpannedEncodingResult<Vec<vir_high::Expression>> {
+        let Some(for_loop) = self
+            .for_loops
+            .values()
+            .find(|for_loop| for_loop.next_call == block)
+            .cloned()
+        else {
+            return Ok(Vec::new());
+        };
+        let (is_some, is_none, payload) = self.encode_option_parts(result, span)?;
+        let postconditions = match &for_loop.iterated {
+            IteratedValues::Range {
+                iterator_type,
+                start_field,
+                end_field,
+                ..
+            } => {
+                let argument = &arguments[0];
+                let old_argument = vir_high::Expression::labelled_old_no_pos(
+                    old_label.to_string(),
+                    argument.clone(),
+                );
+                let iterator =
+                    vir_high::Expression::deref_no_pos(old_argument, iterator_type.clone());
+                let old_iterator = vir_high::Expression::labelled_old_no_pos(
+                    old_label.to_string(),
+                    iterator.clone(),
+                );
+                let start = iterator.clone().field_no_pos(start_field.clone());
+                let end = iterator.field_no_pos(end_field.clone());
+                let old_start = old_iterator.clone().field_no_pos(start_field.clone());
+                let old_end = old_iterator.field_no_pos(end_field.clone());
+                let one = vir_high::Expression::constant_no_pos(
+                    vir_high::expression::ConstantValue::Int(1),
+                    for_loop.element_type.clone(),
+                );
+                let has_next = vir_high::Expression::less_than(old_start.clone(), old_end.clone());
+                vec![
+                    vir_high::Expression::implies(
+                        has_next.clone(),
+                        vir_high::Expression::and(
+                            vir_high::Expression::and(
+                                is_some,
+                                vir_high::Expression::equals(payload, old_start.clone()),
+                            ),
+                            vir_high::Expression::and(
+                                vir_high::Expression::equals(
+                                    start.clone(),
+                                    vir_high::Expression::add(old_start.clone(), one),
+                                ),
+                                vir_high::Expression::equals(end.clone(), old_end.clone()),
+                            ),
+                        ),
+                    ),
+                    vir_high::Expression::implies(
+                        vir_high::Expression::not(has_next),
+                        vir_high::Expression::and(
+                            is_none,
+                            vir_high::Expression::and(
+                                vir_high::Expression::equals(start, old_start),
+                                vir_high::Expression::equals(end, old_end),
+                            ),
+                        ),
+                    ),
+                ]
+            }
+            IteratedValues::Slice { elements } => {
+                let has_next = vir_high::Expression::less_than(
+                    new_int(for_loop.iteration_index.clone().into()),
+                    sequence_length(elements.clone().into(), &for_loop.element_type),
+                );
+                let element = vir_high::Expression::builtin_func_app_no_pos(
+                    vir_high::BuiltinFunc::LookupSeq,
+                    vec![for_loop.element_type.clone()],
+                    vec![
+                        elements.clone().into(),
+                        for_loop.iteration_index.clone().into(),
+                    ],
+                    for_loop.element_type.clone(),
+                );
+                let referenced_element =
+                    vir_high::Expression::deref_no_pos(payload, for_loop.element_type.clone());
+                vec![
+                    vir_high::Expression::implies(
+                        has_next.clone(),
+                        vir_high::Expression::and(
+                            is_some,
+                            vir_high::Expression::equals(referenced_element, element),
+                        ),
+                    ),
+                    vir_high::Expression::implies(vir_high::Expression::not(has_next), is_none),
+                ]
+            }
+        };
+        Ok(postconditions)
+    }
+
+    /// Updates the ghost state of a `for` loop after `Iterator::next` called
+    /// in `block` returned `result`.
+    pub(super) fn encode_for_loop_next_ghost_updates(
+        &mut self,
+        block: mir::BasicBlock,
+        result: &vir_high::Expression,
+        span: Span,
+    ) -> SpannedEncodingResult<Vec<vir_high::Statement>> {
+        let Some(for_loop) = self
+            .for_loops
+            .values()
+            .find(|for_loop| for_loop.next_call == block)
+            .cloned()
+        else {
+            return Ok(Vec::new());
+        };
+        let (is_some, _, payload) = self.encode_option_parts(result, span)?;
+        let element = match &for_loop.iterated {
+            IteratedValues::Range { .. } => payload,
+            IteratedValues::Slice { elements } => vir_high::Expression::builtin_func_app_no_pos(
+                vir_high::BuiltinFunc::LookupSeq,
+                vec![for_loop.element_type.clone()],
+                vec![
+                    elements.clone().into(),
+                    for_loop.iteration_index.clone().into(),
+                ],
+                for_loop.element_type.clone(),
+            ),
+        };
+        let visited: vir_high::Expression = for_loop.visited.clone().into();
+        let iteration_index: vir_high::Expression = for_loop.iteration_index.clone().into();
+        let new_visited = vir_high::Expression::builtin_func_app_no_pos(
+            vir_high::BuiltinFunc::ConcatSeq,
+            vec![for_loop.element_type.clone()],
+            vec![
+                visited.clone(),
+                vir_high::Expression::builtin_func_app_no_pos(
+                    vir_high::BuiltinFunc::SingleSeq,
+                    vec![for_loop.element_type.clone()],
+                    vec![element],
+                    for_loop.visited.ty.clone(),
+                ),
+            ],
+            for_loop.visited.ty.clone(),
+        );
+        let new_iteration_index = vir_high::Expression::add(
+            iteration_index.clone(),
+            vir_high::Expression::constant_no_pos(
+                vir_high::expression::ConstantValue::Int(1),
+                for_loop.iteration_index.ty,
+            ),
+        );
+        // The visited prefix has to be updated first because the new element
+        // of a slice is looked up with the old iteration index.
+        let assignments = vec![
+            (
+                visited.clone(),
+                vir_high::Expression::conditional_no_pos(is_some.clone(), new_visited, visited),
+            ),
+            (
+                iteration_index.clone(),
+                vir_high::Expression::conditional_no_pos(
+                    is_some,
+                    new_iteration_index,
+                    iteration_index,
+                ),
+            ),
+        ];
+        let location = mir::Location {
+            block,
+            statement_index: self.mir[block].statements.len(),
+        };
+        let mut statements = Vec::new();
+        for (target, value) in assignments {
+            statements.push(self.set_statement_error(
+                location,
+                ErrorCtxt::LoopInvariant,
+                vir_high::Statement::ghost_assign_no_pos(target, value),
+            )?);
+        }
+        Ok(statements)
+    }
+
+    /// The invariants that relate the ghost state of a `for` loop to its
+    /// iterator.
+    pub(super) fn encode_for_loop_invariants(
+        &mut self,
+        loop_head: mir::BasicBlock,
+    ) -> SpannedEncodingResult<Vec<vir_high::Expression>> {
+        let for_loop = self.for_loops[&loop_head].clone();
+        let iteration_index: vir_high::Expression = for_loop.iteration_index.clone().into();
+        let visited_length =
+            sequence_length(for_loop.visited.clone().into(), &for_loop.element_type);
+        let invariants = match &for_loop.iterated {
+            IteratedValues::Range {
+                start_field,
+                end_field,
+                start,
+                end,
+                ..
+            } => {
+                let iterator: vir_high::Expression = self.encode_local(for_loop.iterator)?.into();
+                let current_start = iterator.clone().field_no_pos(start_field.clone());
+                let current_end = iterator.field_no_pos(end_field.clone());
+                vec![
+                    vir_high::Expression::equals(current_end, end.clone().into()),
+                    vir_high::Expression::equals(
+                        new_int(current_start.clone()),
+                        vir_high::Expression::add(
+                            new_int(start.clone().into()),
+                            new_int(iteration_index.clone()),
+                        ),
+                    ),
+                    vir_high::Expression::implies(
+                        vir_high::Expression::less_equals(start.clone().into(), end.clone().into()),
+                        vir_high::Expression::less_equals(current_start, end.clone().into()),
+                    ),
+                    vir_high::Expression::equals(visited_length, new_int(iteration_index)),
+                ]
+            }
+            IteratedValues::Slice { elements } => vec![
+                vir_high::Expression::less_equals(
+                    new_int(iteration_index.clone()),
+                    sequence_length(elements.clone().into(), &for_loop.element_type),
+                ),
+                vir_high::Expression::equals(
+                    for_loop.visited.clone().into(),
+                    vir_high::Expression::builtin_func_app_no_pos(
+                        vir_high::BuiltinFunc::TakeSeq,
+                        vec![for_loop.element_type.clone()],
+                        vec![elements.clone().into(), iteration_index],
+                        for_loop.visited.ty.clone(),
+                    ),
+                ),
+            ],
+        };
+        Ok(invariants)
+    }
+
+    /// Replaces `iteration_index()` and `visited()` in a specification of
+    /// the loop with the given head by the ghost variables of the loop.
+    pub(super) fn replace_for_loop_placeholders(
+        &self,
+        loop_head: mir::BasicBlock,
+        specification: vir_high::Expression,
+        span: Span,
+    ) -> SpannedEncodingResult<vir_high::Expression> {
+        struct PlaceholderReplacer<'a> {
+            for_loop: Option<&'a ForLoop>,
+            unsupported: bool,
+            mismatched_type: Option<vir_high::Type>,
+        }
+        impl<'a> ExpressionFolder for PlaceholderReplacer<'a> {
+            fn fold_expression(
+                &mut self,
+                expression: vir_high::Expression,
+            ) -> vir_high::Expression {
+                if !expression.is_iteration_index() && !expression.is_visited() {
+                    return default_fold_expression(self, expression);
+                }
+                let Some(for_loop) = self.for_loop else {
+                    self.unsupported = true;
+                    return expression;
+                };
+                let is_iteration_index = expression.is_iteration_index();
+                let vir_high::Expression::Local(local) = expression else {
+                    unreachable!();
+                };
+                let variable = if is_iteration_index {
+                    for_loop.iteration_index.clone()
+                } else {
+                    if local.variable.ty.erase_lifetimes() != for_loop.visited.ty.erase_lifetimes()
+                    {
+                        self.mismatched_type = Some(local.variable.ty.clone());
+                    }
+                    for_loop.visited.clone()
+                };
+                vir_high::Expression::local(variable, local.position)
+            }
+        }
+        let mut replacer = PlaceholderReplacer {
+            for_loop: self.for_loops.get(&loop_head),
+            unsupported: false,
+            mismatched_type: None,
+        };
+        let specification = replacer.fold_expression(specification);
+        if replacer.unsupported {
+            return Err(SpannedEncodingError::unsupported(
+                "`iteration_index()` and `visited()` can only be used in `body_invariant!` of \
+                `for` loops over ranges and slices",
+                span,
+            ));
+        }
+        if let Some(ty) = replacer.mismatched_type {
+            return Err(SpannedEncodingError::incorrect(
+                format!(
+                    "`visited()` of this loop has type `{}`, not `{ty}`",
+                    self.for_loops[&loop_head].visited.ty
+                ),
+                span,
+            ));
+        }
+        Ok(specification)
+    }
+
+    /// Returns whether the option is `Some`, whether it is `None`, and its
+    /// payload.
+    fn encode_option_parts(
+        &mut self,
+        option: &vir_high::Expression,
+        span: Span,
+    ) -> SpannedEncodingResult<(
+        vir_high::Expression,
+        vir_high::Expression,
+        vir_high::Expression,
+    )> {
+        let option_type = option.get_type().clone();
+        let option_decl = self
+            .encoder
+            .encode_type_def_high(&option_type)?
+            .unwrap_enum();
+        let discriminant = |variant_name: &str| {
+            let index = option_decl
+                .variants
+                .iter()
+                .position(|variant| variant.name == variant_name)
+                .unwrap();
+            vir_high::Expression::constant_no_pos(
+                vir_high::expression::ConstantValue::Int(
+                    option_decl.discriminant_values[index] as i64,
+                ),
+                option_decl.discriminant_type.clone(),
+            )
+        };
+        let some_discriminant = discriminant("Some");
+        let none_discriminant = discriminant("None");
+        let option_discriminant = self
+            .encoder
+            .encode_discriminant_call(option.clone(), option_decl.discriminant_type.clone())
+            .with_span(span)?;
+        let some_variant: vir_high::ty::VariantIndex = "Some".to_string().into();
+        let payload_field = option_decl.variant("Some").unwrap().fields[0].clone();
+        let payload = vir_high::Expression::variant_no_pos(
+            option.clone(),
+            some_variant.clone(),
+            option_type.variant(some_variant),
+        )
+        .field_no_pos(payload_field);
+        Ok((
+            vir_high::Expression::equals(option_discriminant.clone(), some_discriminant),
+            vir_high::Expression::equals(option_discriminant, none_discriminant),
+            payload,
+        ))
+    }
+}
+
+fn new_int(value: vir_high::Expression) -> vir_high::Expression {
+    vir_high::Expression::builtin_func_app_no_pos(
+        vir_high::BuiltinFunc::NewInt,
+        Vec::new(),
+        vec![value],
+        vir_high::Type::Int(vir_high::ty::Int::Unbounded),
+    )
+}
+
+fn sequence_length(
+    sequence: vir_high::Expression,
+    element_type: &vir_high::Type,
+) -> vir_high::Expression {
+    vir_high::Expression::builtin_func_app_no_pos(
+        vir_high::BuiltinFunc::SeqLen,
+        vec![element_type.clone()],
+        vec![sequence],
+        vir_high::Type::Int(vir_high::ty::Int::Unbounded),
+    )
+}
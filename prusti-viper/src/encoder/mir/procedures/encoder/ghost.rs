@@ -3,12 +3,15 @@ use crate::encoder::{
     errors::{SpannedEncodingError, SpannedEncodingResult},
     mir::specifications::SpecificationsInterface,
 };
-use prusti_rustc_interface::middle::{
-    mir::{
-        self,
-        visit::{PlaceContext, Visitor},
+use prusti_rustc_interface::{
+    hir::def_id::DefId,
+    middle::{
+        mir::{
+            self,
+            visit::{PlaceContext, Visitor},
+        },
+        ty,
     },
-    ty,
 };
 use rustc_hash::FxHashSet;
 
@@ -16,10 +19,6 @@ impl<'p, 'v: 'p, 'tcx: 'v> super::ProcedureEncoder<'p, 'v, 'tcx> {
     pub(super) fn encode_ghost_blocks(&mut self) -> SpannedEncodingResult<()> {
         let ghost_blocks = self.specification_blocks.ghost_blocks();
 
-        if ghost_blocks.is_empty() {
-            return Ok(());
-        }
-
         // Check that ghost blocks don't transfer control flow outside
         for &bb in ghost_blocks {
             let data = &self.mir.basic_blocks[bb];
@@ -28,6 +27,10 @@ impl<'p, 'v: 'p, 'tcx: 'v> super::ProcedureEncoder<'p, 'v, 'tcx> {
             }
         }
 
+        if self.encoder.is_lemma(self.def_id) {
+            self.check_lemma_parameters()?;
+        }
+
         let violations = GhostChecker::find_violations(self);
         for violation in violations {
             Err(violation)?;
@@ -35,6 +38,26 @@ impl<'p, 'v: 'p, 'tcx: 'v> super::ProcedureEncoder<'p, 'v, 'tcx> {
 
         Ok(())
     }
+
+    /// A lemma is erased from the compiled program, so it must not be able to
+    /// change the memory that its arguments point to.
+    fn check_lemma_parameters(&self) -> SpannedEncodingResult<()> {
+        for local in self.mir.args_iter() {
+            let local_decl = &self.mir.local_decls[local];
+            let is_mutable = match local_decl.ty.kind() {
+                ty::TyKind::Ref(_, _, mutability) => mutability.is_mut(),
+                ty::TyKind::RawPtr(_) => true,
+                _ => false,
+            };
+            if is_mutable {
+                return Err(SpannedEncodingError::incorrect(
+                    "lemmas cannot take mutable references or raw pointers",
+                    local_decl.source_info.span,
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 struct GhostChecker<'a, 'p: 'a, 'v: 'p, 'tcx: 'v> {
@@ -47,6 +70,9 @@ impl<'a, 'p, 'v, 'tcx> GhostChecker<'a, 'p, 'v, 'tcx> {
     fn is_ghost_place(&self, location: mir::Location) -> bool {
         self.p.specification_blocks.is_ghost_block(location.block)
     }
+    fn is_lemma(&self, def_id: DefId) -> bool {
+        self.p.encoder.is_lemma(def_id)
+    }
     fn is_ghost_local(&self, local: &mir::Local) -> bool {
         let ty = &self.p.mir.local_decls[*local].ty;
         let ty_str = format!("{ty:?}");
@@ -97,28 +123,48 @@ impl<'a, 'p, 'v, 'tcx> Visitor<'tcx> for GhostChecker<'a, 'p, 'v, 'tcx> {
         }
     }
     fn visit_terminator(&mut self, term: &mir::Terminator<'tcx>, location: mir::Location) {
-        if self.is_ghost_place(location) {
-            match &term.kind {
-                mir::TerminatorKind::Call {
-                    func: mir::Operand::Constant(box mir::Constant { literal, .. }),
-                    ..
-                } => {
-                    if let ty::TyKind::FnDef(def_id, _call_substs) = literal.ty().kind() {
-                        if !self.p.encoder.is_pure(*def_id, None) {
-                            self.violations.push(SpannedEncodingError::incorrect(
-                                "Only pure function calls are allowed in ghost blocks.",
-                                term.source_info.span,
-                            ));
-                        }
-                    } else {
-                        unimplemented!();
+        let is_ghost = self.is_ghost_place(location);
+        match &term.kind {
+            mir::TerminatorKind::Call {
+                func: mir::Operand::Constant(box mir::Constant { literal, .. }),
+                target,
+                ..
+            } => {
+                if let ty::TyKind::FnDef(def_id, _call_substs) = literal.ty().kind() {
+                    let is_lemma = self.is_lemma(*def_id);
+                    let is_pure = self.p.encoder.is_pure(*def_id, None);
+                    if is_ghost && !is_lemma && !is_pure {
+                        self.violations.push(SpannedEncodingError::incorrect(
+                            "Only pure function calls are allowed in ghost blocks.",
+                            term.source_info.span,
+                        ));
+                    } else if self.is_lemma(self.p.def_id)
+                        && !is_lemma
+                        && !is_pure
+                        && target.is_some()
+                    {
+                        // Calls that do not return, such as panics, are
+                        // checked to be unreachable instead.
+                        self.violations.push(SpannedEncodingError::incorrect(
+                            "lemmas can call only pure functions and other lemmas",
+                            term.source_info.span,
+                        ));
+                    } else if !is_ghost && is_lemma && !self.is_lemma(self.p.def_id) {
+                        // Lemmas are erased from compiled code, so only ghost
+                        // code and the proofs of other lemmas can use them.
+                        self.violations.push(SpannedEncodingError::incorrect(
+                            "lemmas can be called only in ghost blocks",
+                            term.source_info.span,
+                        ));
                     }
-                }
-                mir::TerminatorKind::Call { .. } => {
+                } else if is_ghost {
                     unimplemented!();
                 }
-                _ => (),
             }
+            mir::TerminatorKind::Call { .. } if is_ghost => {
+                unimplemented!();
+            }
+            _ => (),
         }
         self.super_terminator(term, location);
     }
@@ -8,7 +8,7 @@ use crate::encoder::{
 };
 use prusti_interface::{environment::LoopAnalysisError, specs::typed::LoopSpecification};
 use prusti_rustc_interface::middle::mir;
-use vir_crate::high::{self as vir_high};
+use vir_crate::high::{self as vir_high, operations::ty::Typed};
 
 impl<'p, 'v: 'p, 'tcx: 'v> super::ProcedureEncoder<'p, 'v, 'tcx> {
     /// Encode loop invariants and loop variants
@@ -26,6 +26,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> super::ProcedureEncoder<'p, 'v, 'tcx> {
         let mut encoded_invariant_specs = Vec::new();
         let mut encoded_variant_specs = Vec::new();
         for block in specification_blocks {
+            // The components of a lexicographic variant are all in the block
+            // of the same `body_variant!`.
+            let mut block_variant_specs = Vec::new();
             for statement in &self.mir[block].statements {
                 if let mir::StatementKind::Assign(box (
                     _,
@@ -41,7 +44,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> super::ProcedureEncoder<'p, 'v, 'tcx> {
                             (inv, &mut encoded_invariant_specs, ErrorCtxt::LoopInvariant)
                         }
                         LoopSpecification::Variant(var) => {
-                            (var, &mut encoded_variant_specs, ErrorCtxt::LoopVariant)
+                            (var, &mut block_variant_specs, ErrorCtxt::LoopVariant)
                         }
                     };
                     let span = self.encoder.get_definition_span(spec.to_def_id());
@@ -56,9 +59,14 @@ impl<'p, 'v: 'p, 'tcx: 'v> super::ProcedureEncoder<'p, 'v, 'tcx> {
                         err_ctxt,
                         self.def_id,
                     );
+                    let encoded_specification =
+                        self.replace_for_loop_placeholders(loop_head, encoded_specification, span)?;
                     encoding_vec.push(encoded_specification);
                 }
             }
+            if encoded_variant_specs.is_empty() {
+                encoded_variant_specs = block_variant_specs;
+            }
         }
         let encoded_back_edges = {
             let predecessors = self.mir.basic_blocks.predecessors();
@@ -87,17 +95,30 @@ impl<'p, 'v: 'p, 'tcx: 'v> super::ProcedureEncoder<'p, 'v, 'tcx> {
                 }
             })?;
 
+        // A reference whose lifetime is dead is never used again and the
+        // fold-unfold algorithm tracks it only as a memory block.
+        let live_lifetimes = self
+            .lifetimes
+            .get_origin_live_on_entry_at_mid(invariant_location);
+        let is_dead_reference = |place: &vir_high::Expression| {
+            if let vir_high::Type::Reference(reference) = place.get_type() {
+                !live_lifetimes.contains(&reference.lifetime.name)
+            } else {
+                false
+            }
+        };
+
         let mut maybe_modified_places = Vec::new();
         for place in written_places.into_iter().chain(mutably_borrowed_places) {
-            if initialized_places.contains_prefix_of(place) {
-                maybe_modified_places.push(vir_high::Predicate::owned_non_aliased_no_pos(
-                    self.encoder.encode_place_high(self.mir, place, None)?,
-                ));
+            let encoded_place = self.encoder.encode_place_high(self.mir, place, None)?;
+            if initialized_places.contains_prefix_of(place) && !is_dead_reference(&encoded_place) {
+                maybe_modified_places
+                    .push(vir_high::Predicate::owned_non_aliased_no_pos(encoded_place));
             } else if allocated_locals.contains_prefix_of(place) {
                 let mir_type = place.ty(self.mir, self.encoder.env().tcx()).ty;
                 let size = self.encoder.encode_type_size_expression(mir_type)?;
                 maybe_modified_places.push(vir_high::Predicate::memory_block_stack_no_pos(
-                    self.encoder.encode_place_high(self.mir, place, None)?,
+                    encoded_place,
                     size,
                 ));
             }
@@ -136,14 +157,33 @@ impl<'p, 'v: 'p, 'tcx: 'v> super::ProcedureEncoder<'p, 'v, 'tcx> {
             encoded_invariant_specs.push(equality_expr);
         }
 
+        // Relate the ghost state of a `for` loop to its iterator.
+        let mut maybe_modified_ghost_variables = Vec::new();
+        if let Some(for_loop) = self.for_loops.get(&loop_head) {
+            maybe_modified_ghost_variables.push(for_loop.iteration_index.clone());
+            maybe_modified_ghost_variables.push(for_loop.visited.clone());
+            let span = self.mir[for_loop.next_call].terminator().source_info.span;
+            for invariant in self.encode_for_loop_invariants(loop_head)? {
+                encoded_invariant_specs.push(self.encoder.set_expression_error_ctxt(
+                    invariant,
+                    span,
+                    ErrorCtxt::LoopInvariant,
+                    self.def_id,
+                ));
+            }
+        }
+
         // Construct the variant info.
-        let loop_variant = encoded_variant_specs.into_iter().next().map(|spec| {
-            let var = self.fresh_ghost_variable(
-                "loop_variant",
-                vir_high::Type::Int(vir_high::ty::Int::Unbounded),
-            );
-            vir_high::ast::statement::LoopVariant { var, expr: spec }
-        });
+        let loop_variant = encoded_variant_specs
+            .into_iter()
+            .map(|spec| {
+                let var = self.fresh_ghost_variable(
+                    "loop_variant",
+                    vir_high::Type::Int(vir_high::ty::Int::Unbounded),
+                );
+                vir_high::ast::statement::LoopVariant { var, expr: spec }
+            })
+            .collect();
 
         // Construct the invariant info.
         let loop_invariant = vir_high::Statement::loop_invariant_no_pos(
@@ -152,6 +192,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> super::ProcedureEncoder<'p, 'v, 'tcx> {
             maybe_modified_places,
             encoded_invariant_specs,
             loop_variant,
+            maybe_modified_ghost_variables,
         );
         let statement = self.set_statement_error(
             invariant_location,
@@ -37,7 +37,7 @@ use prusti_rustc_interface::{
     data_structures::graph::WithStartNode,
     hir::def_id::DefId,
     middle::{mir, ty, ty::subst::SubstsRef},
-    span::Span,
+    span::{MultiSpan, Span},
 };
 use rustc_hash::FxHashSet;
 use std::collections::{BTreeMap, BTreeSet};
@@ -58,13 +58,14 @@ use vir_crate::{
 
 mod builtin_function_encoder;
 mod elaborate_drops;
+mod for_loops;
 mod ghost;
 mod initialisation;
 mod lifetimes;
 mod loops;
 mod scc;
 pub mod specification_blocks;
-mod termination;
+pub(super) mod termination;
 
 pub(super) fn encode_procedure<'v, 'tcx: 'v>(
     encoder: &mut Encoder<'v, 'tcx>,
@@ -120,7 +121,9 @@ pub(super) fn encode_procedure<'v, 'tcx: 'v>(
         points_to_reborrow,
         reborrow_lifetimes_to_remove_for_block,
         current_basic_block,
-        termination_variable: None,
+        termination_variables: Vec::new(),
+        for_loops: Default::default(),
+        slice_iterator_sources: Default::default(),
     };
     procedure_encoder.encode()
 }
@@ -160,7 +163,12 @@ struct ProcedureEncoder<'p, 'v: 'p, 'tcx: 'v> {
     points_to_reborrow: BTreeSet<vir_high::Local>,
     reborrow_lifetimes_to_remove_for_block: BTreeMap<mir::BasicBlock, BTreeSet<String>>,
     current_basic_block: Option<mir::BasicBlock>,
-    termination_variable: Option<vir_high::VariableDecl>,
+    termination_variables: Vec<vir_high::VariableDecl>,
+    /// The `for` loops over ranges and slices, indexed by their loop heads.
+    for_loops: BTreeMap<mir::BasicBlock, for_loops::ForLoop>,
+    /// The calls that create the iterator of a `for` loop from a slice and
+    /// the ghost variables that store the elements of the slice.
+    slice_iterator_sources: BTreeMap<mir::Location, vir_high::VariableDecl>,
 }
 
 impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
@@ -373,6 +381,40 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         Ok(postconditions)
     }
 
+    /// The core proof does not encode the frame postconditions given by the
+    /// `modifies` and `reads` clauses, so we reject these clauses instead of
+    /// silently ignoring them. If `call_span` is given, the contract belongs to
+    /// a procedure called at that span.
+    fn check_no_frame_conditions(
+        &self,
+        procedure_contract: &ProcedureContractMirDef<'tcx>,
+        substs: SubstsRef<'tcx>,
+        call_span: Option<Span>,
+    ) -> SpannedEncodingResult<()> {
+        let env = self.encoder.env();
+        let clause_spans = procedure_contract
+            .modifies_clauses(env, substs)
+            .into_iter()
+            .chain(procedure_contract.reads_clauses(env, substs))
+            .map(|(clause, _)| env.query.get_def_span(clause))
+            .collect::<Vec<_>>();
+        if clause_spans.is_empty() {
+            return Ok(());
+        }
+        if let Some(call_span) = call_span {
+            Err(SpannedEncodingError::unsupported(
+                "calling a procedure with `modifies` or `reads` clauses is not supported \
+                with `unsafe_core_proof`",
+                call_span,
+            ))
+        } else {
+            Err(SpannedEncodingError::unsupported(
+                "`modifies` and `reads` clauses are not supported with `unsafe_core_proof`",
+                MultiSpan::from_spans(clause_spans),
+            ))
+        }
+    }
+
     fn encode_functional_specifications(
         &mut self,
     ) -> SpannedEncodingResult<(Vec<vir_high::Statement>, Vec<vir_high::Statement>)> {
@@ -383,6 +425,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             .encoder
             .get_mir_procedure_contract_for_def(self.def_id, substs)
             .with_span(mir_span)?;
+        self.check_no_frame_conditions(&procedure_contract, substs, None)?;
         let mut preconditions = vec![vir_high::Statement::comment(
             "Assume functional preconditions.".to_string(),
         )];
@@ -473,6 +516,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         }
         block_builder.build();
         procedure_builder.set_entry(entry_label);
+        self.collect_for_loops()?;
         self.encode_specification_blocks()?;
         self.reachable_blocks
             .insert(self.mir.basic_blocks.start_node());
@@ -567,10 +611,13 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             )?;
             let terminator = &terminator.kind;
             self.encode_terminator(&mut block_builder, location, terminator)?;
+            for statement in self.encode_for_loop_initialisation(bb)? {
+                block_builder.add_statement(statement);
+            }
         }
         if let Some(statement) = self.loop_invariant_encoding.remove(&bb) {
             if self.needs_termination(bb)
-                && statement.clone().unwrap_loop_invariant().variant.is_none()
+                && statement.clone().unwrap_loop_invariant().variant.is_empty()
             {
                 block_builder.add_statement(self.encoder.set_statement_error_ctxt(
                     vir_high::Statement::assert_no_pos(false.into()),
@@ -1442,6 +1489,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         _fn_span: Span,
     ) -> SpannedEncodingResult<()> {
         if let ty::TyKind::FnDef(called_def_id, call_substs) = ty.kind() {
+            if let Some(statement) = self.encode_slice_iterator_source(location, args, span)? {
+                block_builder.add_statement(statement);
+            }
             if !self.try_encode_builtin_call(
                 block_builder,
                 location,
@@ -1597,6 +1647,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             .encoder
             .get_mir_procedure_contract_for_call(self.def_id, called_def_id, call_substs)
             .with_span(span)?;
+        self.check_no_frame_conditions(&procedure_contract, call_substs, Some(span))?;
 
         if self.encoder.terminates(self.def_id, None) {
             self.encode_termination_measure_call_assertion(
@@ -1608,9 +1659,13 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             )?;
         }
 
-        for expression in
-            self.encode_precondition_expressions(&procedure_contract, call_substs, &arguments)?
-        {
+        let was_encoding_callee_contract = self.encoder.is_encoding_callee_contract.replace(true);
+        let precondition_expressions =
+            self.encode_precondition_expressions(&procedure_contract, call_substs, &arguments);
+        self.encoder
+            .is_encoding_callee_contract
+            .set(was_encoding_callee_contract);
+        for expression in precondition_expressions? {
             let assert_statement = self.encoder.set_statement_error_ctxt(
                 vir_high::Statement::assert_no_pos(expression),
                 span,
@@ -1634,13 +1689,19 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             let encoded_target_place = self
                 .encode_place(destination, None)?
                 .set_default_position(position);
+            let was_encoding_callee_contract =
+                self.encoder.is_encoding_callee_contract.replace(true);
             let postcondition_expressions = self.encode_postcondition_expressions(
                 &procedure_contract,
                 call_substs,
                 arguments.clone(),
                 &encoded_target_place,
                 &old_label,
-            )?;
+            );
+            self.encoder
+                .is_encoding_callee_contract
+                .set(was_encoding_callee_contract);
+            let postcondition_expressions = postcondition_expressions?;
             if let Some(target_place_local) = destination.as_local() {
                 let size = self.encoder.encode_type_size_expression(
                     self.encoder.get_local_type(self.mir, target_place_local)?,
@@ -1702,6 +1763,30 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                         post_call_block_builder.add_statement(assume_statement);
                     }
                 }
+                for expression in self.encode_for_loop_next_postconditions(
+                    location.block,
+                    &arguments,
+                    &encoded_target_place,
+                    &old_label,
+                    span,
+                )? {
+                    let assume_statement = self.encoder.set_statement_error_ctxt(
+                        vir_high::Statement::assume_no_pos(expression),
+                        span,
+                        ErrorCtxt::UnexpectedAssumeMethodPostcondition,
+                        self.def_id,
+                    )?;
+                    if self.check_mode != CheckMode::CoreProof {
+                        post_call_block_builder.add_statement(assume_statement);
+                    }
+                }
+                for statement in self.encode_for_loop_next_ghost_updates(
+                    location.block,
+                    &encoded_target_place,
+                    span,
+                )? {
+                    post_call_block_builder.add_statement(statement);
+                }
                 if self.encoder.is_pure(called_def_id, Some(call_substs))
                     && !self.encoder.env().callee_reaches_caller(
                         self.def_id,
@@ -49,8 +49,11 @@ impl SpecificationBlocks {
         // blocks.
         let dominators = body.basic_blocks.dominators();
         for specification_block in specification_blocks.clone() {
+            if !dominators.is_reachable(specification_block) {
+                continue;
+            }
             for bb in body.basic_blocks.indices() {
-                if dominators.dominates(specification_block, bb) {
+                if dominators.is_reachable(bb) && dominators.dominates(specification_block, bb) {
                     specification_blocks.insert(bb);
                 }
             }
@@ -17,12 +17,45 @@ use vir_crate::{
 };
 
 pub(super) enum TerminationMeasure {
-    /// A termination measure of type Int.
-    Int(vir_high::Expression),
+    /// A termination measure with one or more components of type Int, which
+    /// are compared lexicographically.
+    Int(Vec<vir_high::Expression>),
     /// The annotated item is trusted to always terminate.
     Trusted,
 }
 
+/// Returns the conditions that together ensure that the `new` value of a
+/// lexicographic measure is lower than its `old` value. The condition at
+/// index `i` of a measure with `n` components fails if:
+///
+/// * `i < n - 1`: component `i` increased while all components before it
+///   stayed the same;
+/// * `i = n - 1`: no component decreased.
+pub(in super::super) fn lexicographic_decrease_conditions(
+    new: &[vir_high::Expression],
+    old: &[vir_high::Expression],
+) -> Vec<vir_high::Expression> {
+    assert_eq!(new.len(), old.len());
+    let component_count = new.len();
+    let mut conditions = Vec::new();
+    let mut some_component_decreased: Option<vir_high::Expression> = None;
+    for (index, (new, old)) in new.iter().zip(old).enumerate() {
+        let decreased = vir_high::Expression::less_than(new.clone(), old.clone());
+        let condition = if index + 1 == component_count {
+            decreased.clone()
+        } else {
+            vir_high::Expression::less_equals(new.clone(), old.clone())
+        };
+        let with_previous = |expression| match &some_component_decreased {
+            Some(previous) => vir_high::Expression::or(previous.clone(), expression),
+            None => expression,
+        };
+        conditions.push(with_previous(condition));
+        some_component_decreased = Some(with_previous(decreased));
+    }
+    conditions
+}
+
 impl<'p, 'v: 'p, 'tcx: 'v> super::ProcedureEncoder<'p, 'v, 'tcx> {
     pub(super) fn needs_termination(&self, bb: BasicBlock) -> bool {
         let function_termination = self.encoder.terminates(self.def_id, None);
@@ -39,7 +72,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> super::ProcedureEncoder<'p, 'v, 'tcx> {
     ) -> SpannedEncodingResult<TerminationMeasure> {
         assert!(self.encoder.terminates(self.def_id, None));
 
-        let (expr, expr_substs) = procedure_contract
+        let (components, expr_substs) = procedure_contract
             .functional_termination_measure(self.encoder.env(), call_substs)
             .ok_or_else(|| {
                 SpannedEncodingError::incorrect(
@@ -48,21 +81,26 @@ impl<'p, 'v: 'p, 'tcx: 'v> super::ProcedureEncoder<'p, 'v, 'tcx> {
                 )
             })?;
 
-        let expression = self.encoder.encode_assertion_high(
-            expr.to_def_id(),
-            None,
-            arguments,
-            None,
-            self.def_id,
-            expr_substs,
-        )?;
-        if let vir_high::Expression::FuncApp(vir_high::FuncApp { function_name, .. }) = &expression
-        {
-            if function_name == "m_prusti_contracts$$prusti_terminates_trusted" {
-                return Ok(TerminationMeasure::Trusted);
+        let mut expressions = Vec::new();
+        for component in components {
+            let expression = self.encoder.encode_assertion_high(
+                component.to_def_id(),
+                None,
+                arguments,
+                None,
+                self.def_id,
+                expr_substs,
+            )?;
+            if let vir_high::Expression::FuncApp(vir_high::FuncApp { function_name, .. }) =
+                &expression
+            {
+                if function_name == "m_prusti_contracts$$prusti_terminates_trusted" {
+                    return Ok(TerminationMeasure::Trusted);
+                }
             }
+            expressions.push(expression);
         }
-        Ok(TerminationMeasure::Int(expression))
+        Ok(TerminationMeasure::Int(expressions))
     }
 
     pub(super) fn encode_termination_initialization(
@@ -87,24 +125,26 @@ impl<'p, 'v: 'p, 'tcx: 'v> super::ProcedureEncoder<'p, 'v, 'tcx> {
                 substs,
                 &arguments,
             )?;
-            if let TerminationMeasure::Int(termination_expr) = termination_expr {
-                let term_var = self.fresh_ghost_variable(
-                    "termination_var",
-                    vir_high::Type::Int(vir_high::ty::Int::Unbounded),
-                );
-                self.termination_variable = Some(term_var.clone());
-                let assign_stmt =
-                    vir_high::Statement::ghost_assign_no_pos(term_var.into(), termination_expr);
-                let assign_stmt = self.encoder.set_statement_error_ctxt(
-                    assign_stmt,
-                    mir_span,
-                    ErrorCtxt::UnexpectedAssignMethodTerminationMeasure,
-                    self.def_id,
-                )?;
-                Ok(vec![assign_stmt])
-            } else {
-                Ok(vec![])
+            let mut statements = Vec::new();
+            if let TerminationMeasure::Int(termination_exprs) = termination_expr {
+                for termination_expr in termination_exprs {
+                    let term_var = self.fresh_ghost_variable(
+                        "termination_var",
+                        vir_high::Type::Int(vir_high::ty::Int::Unbounded),
+                    );
+                    self.termination_variables.push(term_var.clone());
+                    let assign_stmt =
+                        vir_high::Statement::ghost_assign_no_pos(term_var.into(), termination_expr);
+                    let assign_stmt = self.encoder.set_statement_error_ctxt(
+                        assign_stmt,
+                        mir_span,
+                        ErrorCtxt::UnexpectedAssignMethodTerminationMeasure,
+                        self.def_id,
+                    )?;
+                    statements.push(assign_stmt);
+                }
             }
+            Ok(statements)
         } else {
             Ok(vec![])
         }
@@ -137,36 +177,59 @@ impl<'p, 'v: 'p, 'tcx: 'v> super::ProcedureEncoder<'p, 'v, 'tcx> {
             return Ok(());
         }
 
-        let Some(term_var) = self.termination_variable.clone() else {
+        if self.termination_variables.is_empty() {
             return Ok(());
-        };
+        }
+        let term_vars: Vec<vir_high::Expression> = self
+            .termination_variables
+            .iter()
+            .cloned()
+            .map(Into::into)
+            .collect();
         let term_ty = vir_high::Type::Int(vir_high::ty::Int::Unbounded);
 
         // called termination measure is lower
         let call_expr =
             self.encode_termination_expression(procedure_contract, span, call_substs, arguments)?;
-        if let TerminationMeasure::Int(call_expr) = call_expr {
-            let cond = vir_high::Expression::greater_than(term_var.clone().into(), call_expr);
+        if let TerminationMeasure::Int(call_exprs) = call_expr {
+            if call_exprs.len() != term_vars.len() {
+                return Err(SpannedEncodingError::incorrect(
+                    "the termination measures of mutually recursive functions must have the \
+                    same number of components",
+                    span,
+                ));
+            }
+            let conditions = lexicographic_decrease_conditions(&call_exprs, &term_vars);
+            let component_count = conditions.len();
+            for (component, cond) in conditions.into_iter().enumerate() {
+                let error_ctxt = if component + 1 == component_count {
+                    ErrorCtxt::CallTerminationMeasureLower
+                } else {
+                    ErrorCtxt::CallTerminationMeasureComponentIncreased(component)
+                };
+                let assert_statement = self.encoder.set_statement_error_ctxt(
+                    vir_high::Statement::assert_no_pos(cond),
+                    span,
+                    error_ctxt,
+                    self.def_id,
+                )?;
+                block_builder.add_statement(assert_statement);
+            }
+        }
+
+        // called termination measure should be non-negative
+        for term_var in term_vars {
+            let zero = vir_high::Expression::constant_no_pos(0.into(), term_ty.clone());
+            let cond = vir_high::Expression::greater_equals(term_var, zero);
             let assert_statement = self.encoder.set_statement_error_ctxt(
                 vir_high::Statement::assert_no_pos(cond),
                 span,
-                ErrorCtxt::CallTerminationMeasureLower,
+                ErrorCtxt::CallTerminationMeasureNonNegative,
                 self.def_id,
             )?;
             block_builder.add_statement(assert_statement);
         }
 
-        // called termination measure should be non-negative
-        let zero = vir_high::Expression::constant_no_pos(0.into(), term_ty);
-        let cond = vir_high::Expression::greater_equals(term_var.into(), zero);
-        let assert_statement = self.encoder.set_statement_error_ctxt(
-            vir_high::Statement::assert_no_pos(cond),
-            span,
-            ErrorCtxt::CallTerminationMeasureNonNegative,
-            self.def_id,
-        )?;
-        block_builder.add_statement(assert_statement);
-
         Ok(())
     }
 }
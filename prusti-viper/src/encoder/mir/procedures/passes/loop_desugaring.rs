@@ -1,6 +1,8 @@
 use crate::encoder::{
     errors::{ErrorCtxt, SpannedEncodingResult},
-    mir::errors::ErrorInterface,
+    mir::{
+        errors::ErrorInterface, procedures::encoder::termination::lexicographic_decrease_conditions,
+    },
     Encoder,
 };
 use std::collections::{BTreeMap, BTreeSet};
@@ -52,7 +54,7 @@ pub(in super::super) fn desugar_loops<'v, 'tcx: 'v>(
             .unwrap()
             .unwrap_loop_invariant();
 
-        if let Some(variant) = &loop_invariant.variant {
+        for variant in &loop_invariant.variant {
             let stmt = encoder.set_surrounding_error_context_for_statement(
                 vir_high::Statement::ghost_assign_no_pos(
                     vir_high::Expression::local_no_pos(variant.var.clone()),
@@ -78,7 +80,9 @@ pub(in super::super) fn desugar_loops<'v, 'tcx: 'v>(
             invariant_block.statements.push(statement);
         }
 
-        if let Some(variant) = &loop_invariant.variant {
+        // Only the first component of a lexicographic variant has to be
+        // positive; the others just must not become negative.
+        if let Some(variant) = loop_invariant.variant.first() {
             let stmt = encoder.set_surrounding_error_context_for_statement(
                 vir_high::Statement::assert_no_pos(vir_high::Expression::greater_than(
                     vir_high::Expression::local_no_pos(variant.var.clone()),
@@ -111,7 +115,7 @@ pub(in super::super) fn desugar_loops<'v, 'tcx: 'v>(
             invariant_block.statements.push(statement);
         }
 
-        if let Some(variant) = &loop_invariant.variant {
+        for variant in &loop_invariant.variant {
             let stmt = encoder.set_surrounding_error_context_for_statement(
                 vir_high::Statement::ghost_havoc_no_pos(variant.var.clone()),
                 loop_invariant.position,
@@ -119,6 +123,14 @@ pub(in super::super) fn desugar_loops<'v, 'tcx: 'v>(
             )?;
             invariant_block.statements.push(stmt);
         }
+        for variable in loop_invariant.maybe_modified_ghost_variables {
+            let stmt = encoder.set_surrounding_error_context_for_statement(
+                vir_high::Statement::ghost_havoc_no_pos(variable),
+                loop_invariant.position,
+                ErrorCtxt::UnexpectedAssumeLoopInvariantOnEntry,
+            )?;
+            invariant_block.statements.push(stmt);
+        }
 
         for assertion in loop_invariant.functional_specifications {
             let statement = encoder.set_surrounding_error_context_for_statement(
@@ -129,7 +141,7 @@ pub(in super::super) fn desugar_loops<'v, 'tcx: 'v>(
             invariant_block.statements.push(statement);
         }
 
-        if let Some(variant) = &loop_invariant.variant {
+        for variant in &loop_invariant.variant {
             let stmt = encoder.set_surrounding_error_context_for_statement(
                 vir_high::Statement::assume_no_pos(vir_high::Expression::equals(
                     vir_high::Expression::local_no_pos(variant.var.clone()),
@@ -204,18 +216,33 @@ fn duplicate_blocks<'v, 'tcx: 'v>(
                 )?;
                 block.statements.push(statement);
             }
-            if let Some(variant) = &loop_invariant.variant {
+            let (new_values, old_values): (Vec<_>, Vec<_>) = loop_invariant
+                .variant
+                .iter()
+                .map(|variant| {
+                    (
+                        variant.expr.clone(),
+                        vir_high::Expression::local_no_pos(variant.var.clone()),
+                    )
+                })
+                .unzip();
+            let decrease_conditions = lexicographic_decrease_conditions(&new_values, &old_values);
+            let component_count = decrease_conditions.len();
+            for (component, condition) in decrease_conditions.into_iter().enumerate() {
+                let error_ctxt = if component + 1 == component_count {
+                    ErrorCtxt::LoopVariantNonDecreased
+                } else {
+                    ErrorCtxt::LoopVariantComponentIncreased(component)
+                };
                 block
                     .statements
                     .push(encoder.set_surrounding_error_context_for_statement(
-                        vir_high::Statement::assert_no_pos(vir_high::Expression::less_than(
-                            variant.expr.clone(),
-                            vir_high::Expression::local_no_pos(variant.var.clone()),
-                        )),
+                        vir_high::Statement::assert_no_pos(condition),
                         loop_invariant.position,
-                        ErrorCtxt::LoopVariantNonDecreased,
+                        error_ctxt,
                     )?);
-
+            }
+            for variant in &loop_invariant.variant {
                 block
                     .statements
                     .push(encoder.set_surrounding_error_context_for_statement(
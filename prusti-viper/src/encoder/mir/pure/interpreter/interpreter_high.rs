@@ -29,7 +29,7 @@ use crate::encoder::{
 };
 use log::{debug, trace};
 use prusti_common::vir_high_local;
-use prusti_interface::environment::mir_utils::SliceOrArrayRef;
+use prusti_interface::{environment::mir_utils::SliceOrArrayRef, specs::typed::LoopSpecification};
 use prusti_rustc_interface::{
     hir::def_id::DefId,
     middle::{mir, ty, ty::subst::SubstsRef},
@@ -469,6 +469,15 @@ impl<'p, 'v: 'p, 'tcx: 'v> ExpressionBackwardInterpreter<'p, 'v, 'tcx> {
                         span,
                         substs,
                     )?
+                } else if self.encoder.is_lemma(def_id) {
+                    self.encode_call_lemma(
+                        *target_block,
+                        states,
+                        def_id,
+                        encoded_args,
+                        span,
+                        substs,
+                    )?
                 } else {
                     return Err(SpannedEncodingError::incorrect(
                         format!(
@@ -556,7 +565,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ExpressionBackwardInterpreter<'p, 'v, 'tcx> {
 
             let key_type = type_arguments[0].clone();
             let val_type = type_arguments[1].clone();
-            let map_type = Type::map(key_type, val_type.clone(), lifetimes);
+            let map_type = Type::map(key_type.clone(), val_type.clone(), lifetimes.clone());
 
             return builtin(match proc_name {
                 "empty" => (EmptyMap, map_type),
@@ -565,13 +574,14 @@ impl<'p, 'v: 'p, 'tcx: 'v> ExpressionBackwardInterpreter<'p, 'v, 'tcx> {
                 "lookup" => (LookupMap, val_type),
                 "delete" => unimplemented!(),
                 "contains" => (MapContains, Type::bool()),
+                "domain" => (MapDomain, Type::set(key_type, lifetimes)),
                 _ => unreachable!("no further Map functions"),
             });
         } else if let Some(proc_name) = proc_name.strip_prefix("prusti_contracts::Seq::<T>::") {
             assert_eq!(type_arguments.len(), 1);
 
             let elem_type = type_arguments[0].clone();
-            let seq_type = Type::sequence(elem_type.clone(), lifetimes);
+            let seq_type = Type::sequence(elem_type.clone(), lifetimes.clone());
 
             return builtin(match proc_name {
                 "empty" => (EmptySeq, seq_type),
@@ -579,8 +589,53 @@ impl<'p, 'v: 'p, 'tcx: 'v> ExpressionBackwardInterpreter<'p, 'v, 'tcx> {
                 "len" => (SeqLen, Type::Int(vir_high::ty::Int::Unbounded)),
                 "lookup" => (LookupSeq, elem_type),
                 "concat" => (ConcatSeq, seq_type),
+                "take" => (TakeSeq, seq_type),
+                "skip" => (SkipSeq, seq_type),
+                "update" => (UpdateSeq, seq_type),
+                "contains" => (SeqContains, Type::bool()),
+                "from_slice" => (SeqFromSlice, seq_type),
+                "to_set" => (SeqToSet, Type::set(elem_type, lifetimes)),
+                "to_multiset" => (SeqToMultiSet, Type::multi_set(elem_type, lifetimes)),
                 _ => unreachable!("no further Seq functions"),
             });
+        } else if proc_name == "prusti_contracts::Seq::<prusti_contracts::Int>::range" {
+            let seq_type = Type::sequence(Type::Int(Int::Unbounded), lifetimes);
+            return builtin((RangeSeq, seq_type));
+        } else if let Some(proc_name) = proc_name.strip_prefix("prusti_contracts::Set::<T>::") {
+            assert_eq!(type_arguments.len(), 1);
+
+            let elem_type = type_arguments[0].clone();
+            let set_type = Type::set(elem_type, lifetimes);
+
+            return builtin(match proc_name {
+                "empty" => (EmptySet, set_type),
+                "single" => (SingleSet, set_type),
+                "union" => (SetUnion, set_type),
+                "intersection" => (SetIntersection, set_type),
+                "difference" => (SetMinus, set_type),
+                "is_subset" => (SetSubset, Type::bool()),
+                "contains" => (SetContains, Type::bool()),
+                "len" => (SetLen, Type::Int(Int::Unbounded)),
+                _ => unreachable!("no further Set functions"),
+            });
+        } else if let Some(proc_name) = proc_name.strip_prefix("prusti_contracts::Multiset::<T>::")
+        {
+            assert_eq!(type_arguments.len(), 1);
+
+            let elem_type = type_arguments[0].clone();
+            let multi_set_type = Type::multi_set(elem_type, lifetimes);
+
+            return builtin(match proc_name {
+                "empty" => (EmptyMultiSet, multi_set_type),
+                "single" => (SingleMultiSet, multi_set_type),
+                "union" => (MultiSetUnion, multi_set_type),
+                "intersection" => (MultiSetIntersection, multi_set_type),
+                "difference" => (MultiSetMinus, multi_set_type),
+                "is_subset" => (MultiSetSubset, Type::bool()),
+                "count" => (MultiSetCount, Type::Int(Int::Unbounded)),
+                "len" => (MultiSetLen, Type::Int(Int::Unbounded)),
+                _ => unreachable!("no further Multiset functions"),
+            });
         } else if let Some(proc_name) = proc_name.strip_prefix("prusti_contracts::Int::") {
             assert!(type_arguments.is_empty());
             return match proc_name {
@@ -659,6 +714,21 @@ impl<'p, 'v: 'p, 'tcx: 'v> ExpressionBackwardInterpreter<'p, 'v, 'tcx> {
                 );
                 subst_with(encoded_rhs)
             }
+            "prusti_contracts::old_seq" => {
+                let seq_type = Type::sequence(type_arguments[0].clone(), lifetimes);
+                let position = encoded_args[0].position();
+                let encoded_rhs = vir_high::Expression::labelled_old(
+                    PRECONDITION_LABEL.to_string(),
+                    vir_high::Expression::builtin_func_app_no_pos(
+                        SeqFromSlice,
+                        type_arguments.clone(),
+                        encoded_args.into(),
+                        seq_type,
+                    ),
+                    position,
+                );
+                subst_with(encoded_rhs)
+            }
             "prusti_contracts::snapshot_equality" => {
                 let position = encoded_args[0].position();
                 let encoded_rhs = vir_high::Expression::builtin_func_app(
@@ -670,6 +740,21 @@ impl<'p, 'v: 'p, 'tcx: 'v> ExpressionBackwardInterpreter<'p, 'v, 'tcx> {
                 );
                 subst_with(encoded_rhs)
             }
+            "prusti_contracts::iteration_index" | "prusti_contracts::visited"
+                if !self.is_in_loop_invariant() =>
+            {
+                Err(SpannedEncodingError::unsupported(
+                    "`iteration_index()` and `visited()` can only be used in `body_invariant!` of \
+                    `for` loops over ranges and slices",
+                    span,
+                ))
+            }
+            "prusti_contracts::iteration_index" => {
+                subst_with(vir_high::Expression::iteration_index())
+            }
+            "prusti_contracts::visited" => subst_with(vir_high::Expression::visited(
+                encoded_lhs.get_type().clone(),
+            )),
             "prusti_contracts::before_expiry" => {
                 // self.encode_call_before_expiry()?
                 unimplemented!();
@@ -692,6 +777,14 @@ impl<'p, 'v: 'p, 'tcx: 'v> ExpressionBackwardInterpreter<'p, 'v, 'tcx> {
                 );
                 subst_with(encoded_rhs)
             }
+            "std::ops::RangeInclusive::<Idx>::new" | "core::ops::RangeInclusive::<Idx>::new" => {
+                assert_eq!(encoded_args.len(), 2);
+                // The fields are `start`, `end`, and `exhausted`.
+                let mut arguments = encoded_args.to_vec();
+                arguments.push(false.into());
+                let ty = encoded_lhs.get_type().clone();
+                subst_with(vir_high::Expression::constructor_no_pos(ty, arguments))
+            }
             "core::slice::<impl [T]>::len" => {
                 assert_eq!(encoded_args.len(), 1);
                 self.encode_call_len(
@@ -703,6 +796,17 @@ impl<'p, 'v: 'p, 'tcx: 'v> ExpressionBackwardInterpreter<'p, 'v, 'tcx> {
                 )
                 .map(Some)
             }
+            "core::str::<impl str>::as_bytes"
+            | "core::str::<impl str>::starts_with"
+            | "core::str::<impl str>::is_char_boundary" => {
+                let function_name = proc_name.rsplit("::").next().unwrap();
+                subst_with(vir_high::Expression::function_call(
+                    format!("str_{function_name}"),
+                    vec![],
+                    encoded_args.into(),
+                    encoded_lhs.get_type().clone(),
+                ))
+            }
             "std::ops::IndexMut::index_mut"
             | "core::ops::IndexMut::index_mut"
             | "std::ops::Index::index"
@@ -821,6 +925,56 @@ impl<'p, 'v: 'p, 'tcx: 'v> ExpressionBackwardInterpreter<'p, 'v, 'tcx> {
         Ok(state)
     }
 
+    /// A lemma call `L(args)` followed by `E` is encoded as `L(args) && E`,
+    /// where the function `L` is always true and has the lemma's contract.
+    fn encode_call_lemma(
+        &self,
+        target_block: mir::BasicBlock,
+        states: FxHashMap<mir::BasicBlock, &ExprBackwardInterpreterState>,
+        def_id: DefId,
+        args: Vec<vir_high::Expression>,
+        span: Span,
+        substs: SubstsRef<'tcx>,
+    ) -> SpannedEncodingResult<ExprBackwardInterpreterState> {
+        let (function_name, return_type) = self
+            .encoder
+            .encode_pure_function_use_high(def_id, self.caller_def_id, substs)
+            .with_span(span)?;
+        trace!("Encoding lemma call '{}'", function_name);
+        let pos = self.encoder.error_manager().register_error(
+            span,
+            ErrorCtxt::PureFunctionCall,
+            self.caller_def_id,
+        );
+        let type_arguments = self
+            .encoder
+            .encode_generic_arguments_high(def_id, substs)
+            .with_span(span)?;
+        let encoded_call =
+            vir_high::Expression::function_call(function_name, type_arguments, args, return_type)
+                .set_default_position(pos.into());
+        let expr = states[&target_block]
+            .expr()
+            .map(|expr| vir_high::Expression::and(encoded_call, expr.clone()));
+        Ok(ExprBackwardInterpreterState::new(expr))
+    }
+
+    /// Whether the encoded body is a loop invariant or a closure (for
+    /// example, of a quantifier) nested in one.
+    fn is_in_loop_invariant(&self) -> bool {
+        let tcx = self.encoder.env().tcx();
+        let mut def_id = self.mir.source.def_id();
+        loop {
+            if let Some(LoopSpecification::Invariant(_)) = self.encoder.get_loop_specs(def_id) {
+                return true;
+            }
+            if !tcx.is_closure(def_id) {
+                return false;
+            }
+            def_id = tcx.parent(def_id);
+        }
+    }
+
     fn unreachable_expr(
         &self,
         position: vir_high::Position,
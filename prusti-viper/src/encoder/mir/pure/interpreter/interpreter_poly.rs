@@ -2,7 +2,8 @@ use crate::{
     encoder::{
         builtin_encoder::BuiltinFunctionKind,
         errors::{
-            EncodingResult, ErrorCtxt, SpannedEncodingError, SpannedEncodingResult, WithSpan,
+            EncodingError, EncodingResult, ErrorCtxt, SpannedEncodingError, SpannedEncodingResult,
+            WithSpan,
         },
         high::{
             builtin_functions::HighBuiltinFunctionEncoderInterface,
@@ -103,6 +104,42 @@ impl<'p, 'v: 'p, 'tcx: 'v> PureFunctionBackwardInterpreter<'p, 'v, 'tcx> {
         Ok(())
     }
 
+    /// Encodes an arithmetic operation on the mathematical integers of
+    /// `prusti_contracts::Int`, or returns `None` if the call is not such an
+    /// operation.
+    fn encode_int_operation(
+        &self,
+        proc_name: &str,
+        args: &[mir::Operand<'tcx>],
+        encoded_args: &[vir::Expr],
+    ) -> Option<vir::Expr> {
+        let arg_ty = self.mir_encoder.get_operand_ty(args.first()?);
+        let ty::TyKind::Adt(adt_def, _) = arg_ty.kind() else {
+            return None;
+        };
+        let type_name = self
+            .encoder
+            .env()
+            .name
+            .get_absolute_item_name(adt_def.did());
+        if type_name != "prusti_contracts::Int" {
+            return None;
+        }
+        let op_name = proc_name
+            .strip_prefix("std::ops::")
+            .or_else(|| proc_name.strip_prefix("core::ops::"))?;
+        let arg = |index: usize| encoded_args[index].clone();
+        Some(match op_name {
+            "Add::add" => vir::Expr::add(arg(0), arg(1)),
+            "Sub::sub" => vir::Expr::sub(arg(0), arg(1)),
+            "Mul::mul" => vir::Expr::mul(arg(0), arg(1)),
+            "Div::div" => vir::Expr::div(arg(0), arg(1)),
+            "Rem::rem" => vir::Expr::modulo(arg(0), arg(1)),
+            "Neg::neg" => vir::Expr::minus(arg(0)),
+            _ => return None,
+        })
+    }
+
     pub(crate) fn mir_encoder(&self) -> &MirEncoder<'p, 'v, 'tcx> {
         &self.mir_encoder
     }
@@ -191,6 +228,38 @@ impl<'p, 'v: 'p, 'tcx: 'v> PureFunctionBackwardInterpreter<'p, 'v, 'tcx> {
                     idx_val_int,
                 )?
             }
+            PlaceEncoding::Subslice {
+                box base,
+                from,
+                to,
+                from_end,
+                rust_base_ty,
+                rust_subslice_ty,
+                ..
+            } => {
+                if !rust_subslice_ty.is_slice() {
+                    return Err(EncodingError::unsupported(
+                        "rest patterns of arrays are not supported in pure functions",
+                    ));
+                }
+                let postprocessed_base = self.postprocess_place_encoding(base)?;
+                let to = if from_end {
+                    let len = self
+                        .encoder
+                        .encode_snapshot_slice_len(rust_base_ty, postprocessed_base.clone())?;
+                    vir::Expr::sub(len, vir::Expr::from(to as usize))
+                } else {
+                    vir::Expr::from(to as usize)
+                };
+
+                self.encoder.encode_snapshot_slicing(
+                    rust_base_ty,
+                    postprocessed_base,
+                    rust_subslice_ty,
+                    vir::Expr::from(from as usize),
+                    to,
+                )?
+            }
         })
     }
 }
@@ -427,6 +496,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                             .collect::<Result<_, _>>()
                             .with_span(span)?;
 
+                        let int_operation =
+                            self.encode_int_operation(full_func_proc_name, args, &encoded_args);
+
                         match full_func_proc_name {
                             "prusti_contracts::old" => {
                                 assert_eq!(args.len(), 1);
@@ -484,7 +556,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                                 state
                             }
 
-                            "core::slice::<impl [T]>::len" => {
+                            "core::slice::<impl [T]>::len" | "core::str::<impl str>::len" => {
                                 assert_eq!(args.len(), 1);
                                 let slice_ty = self.mir_encoder.get_operand_ty(&args[0]);
                                 let len = self
@@ -497,6 +569,113 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                                 state
                             }
 
+                            "core::str::<impl str>::as_bytes" => {
+                                assert_eq!(args.len(), 1);
+                                let str_ty = self.mir_encoder.get_operand_ty(&args[0]);
+                                let bytes = self
+                                    .encoder
+                                    .encode_snapshot_str_as_bytes(
+                                        str_ty,
+                                        encoded_args[0].clone(),
+                                        ty,
+                                    )
+                                    .with_span(span)?;
+
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(&encoded_lhs, bytes);
+                                state
+                            }
+
+                            "core::str::<impl str>::starts_with"
+                                if self.mir_encoder.get_operand_ty(&args[1]).is_str_ref() =>
+                            {
+                                assert_eq!(args.len(), 2);
+                                let str_ty = self.mir_encoder.get_operand_ty(&args[0]);
+                                let starts_with = self
+                                    .encoder
+                                    .encode_snapshot_str_starts_with(
+                                        str_ty,
+                                        encoded_args[0].clone(),
+                                        encoded_args[1].clone(),
+                                    )
+                                    .with_span(span)?;
+
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(&encoded_lhs, starts_with);
+                                state
+                            }
+
+                            "core::str::<impl str>::is_char_boundary" => {
+                                assert_eq!(args.len(), 2);
+                                let str_ty = self.mir_encoder.get_operand_ty(&args[0]);
+                                let is_char_boundary = self
+                                    .encoder
+                                    .encode_snapshot_str_is_char_boundary(
+                                        str_ty,
+                                        encoded_args[0].clone(),
+                                        encoded_args[1].clone(),
+                                    )
+                                    .with_span(span)?;
+
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(&encoded_lhs, is_char_boundary);
+                                state
+                            }
+
+                            // The fields of `RangeInclusive` are private, so `a..=b` is
+                            // desugared to a call instead of an aggregate.
+                            "std::ops::RangeInclusive::<Idx>::new"
+                            | "core::ops::RangeInclusive::<Idx>::new" => {
+                                assert_eq!(args.len(), 2);
+                                let mut state = states[&target_block].clone();
+                                let bool_ty = tcx.mk_ty_from_kind(ty::TyKind::Bool);
+                                let mut field_exprs = vec![];
+                                for (field_name, operand) in ["start", "end"].into_iter().zip(args)
+                                {
+                                    let field_ty = self.mir_encoder.get_operand_ty(operand);
+                                    let encoded_field = self
+                                        .encoder
+                                        .encode_struct_field(field_name, field_ty)
+                                        .with_span(span)?;
+                                    let field_place = encoded_lhs.clone().field(encoded_field);
+                                    let (encoded_rhs, is_value) =
+                                        self.encode_operand(operand).with_span(span)?;
+                                    if is_value {
+                                        state.substitute_value(
+                                            &self
+                                                .encoder
+                                                .encode_value_expr(field_place, field_ty)
+                                                .with_span(span)?,
+                                            encoded_rhs.clone(),
+                                        );
+                                    } else {
+                                        state.substitute_value(&field_place, encoded_rhs.clone());
+                                    }
+                                    field_exprs.push(encoded_rhs);
+                                }
+                                let exhausted_field = self
+                                    .encoder
+                                    .encode_struct_field("exhausted", bool_ty)
+                                    .with_span(span)?;
+                                state.substitute_value(
+                                    &self
+                                        .encoder
+                                        .encode_value_expr(
+                                            encoded_lhs.clone().field(exhausted_field),
+                                            bool_ty,
+                                        )
+                                        .with_span(span)?,
+                                    false.into(),
+                                );
+                                field_exprs.push(false.into());
+                                let snapshot = self
+                                    .encoder
+                                    .encode_snapshot(ty, Some(0), field_exprs)
+                                    .with_span(span)?;
+                                state.substitute_value(&encoded_lhs, snapshot);
+                                state
+                            }
+
                             "std::ops::Index::index" | "core::ops::Index::index" => {
                                 assert_eq!(args.len(), 2);
                                 trace!("slice::index(args={:?}, encoded_args={:?}, ty={:?}, encoded_lhs={:?})", args, encoded_args, ty, encoded_lhs);
@@ -522,42 +701,121 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                                 let usize_ty =
                                     tcx.mk_ty_from_kind(ty::TyKind::Uint(ty::UintTy::Usize));
                                 let start = match &*idx_ident {
-                                    "std::ops::Range" | "core::ops::Range" |
-                                    "std::ops::RangeFrom" | "core::ops::RangeFrom" =>
-                                        self.encoder.encode_struct_field_value(encoded_idx.clone(), "start", usize_ty).with_span(span)?,
+                                    "std::ops::Range"
+                                    | "core::ops::Range"
+                                    | "std::ops::RangeFrom"
+                                    | "core::ops::RangeFrom" => self
+                                        .encoder
+                                        .encode_struct_field_value(
+                                            encoded_idx.clone(),
+                                            "start",
+                                            usize_ty,
+                                        )
+                                        .with_span(span)?,
                                     // See procedure_encoder.rs
-                                    "std::ops::RangeInclusive" | "core::ops::RangeInclusive" => return Err(
-                                        SpannedEncodingError::unsupported("slicing with RangeInclusive (e.g. [x..=y]) currently not supported".to_string(), span)
-                                    ),
-                                    "std::ops::RangeTo" | "core::ops::RangeTo" |
-                                    "std::ops::RangeFull" | "core::ops::RangeFull" |
-                                    "std::ops::RangeToInclusive" | "core::ops::RangeToInclusive" => vir::Expr::from(0u32),
-                                    _ => unreachable!("{}", idx_ident)
+                                    "std::ops::RangeInclusive" | "core::ops::RangeInclusive" => {
+                                        let bool_ty = tcx.mk_ty_from_kind(ty::TyKind::Bool);
+                                        let start_expr = self
+                                            .encoder
+                                            .encode_struct_field_value(
+                                                encoded_idx.clone(),
+                                                "start",
+                                                usize_ty,
+                                            )
+                                            .with_span(span)?;
+                                        let end_expr = self
+                                            .encoder
+                                            .encode_struct_field_value(
+                                                encoded_idx.clone(),
+                                                "end",
+                                                usize_ty,
+                                            )
+                                            .with_span(span)?;
+                                        let exhausted_expr = self
+                                            .encoder
+                                            .encode_struct_field_value(
+                                                encoded_idx.clone(),
+                                                "exhausted",
+                                                bool_ty,
+                                            )
+                                            .with_span(span)?;
+                                        vir::Expr::ite(
+                                            exhausted_expr,
+                                            vir::Expr::add(end_expr, vir::Expr::from(1u32)),
+                                            start_expr,
+                                        )
+                                    }
+                                    "std::ops::RangeTo"
+                                    | "core::ops::RangeTo"
+                                    | "std::ops::RangeFull"
+                                    | "core::ops::RangeFull"
+                                    | "std::ops::RangeToInclusive"
+                                    | "core::ops::RangeToInclusive" => vir::Expr::from(0u32),
+                                    _ => unreachable!("{}", idx_ident),
                                 };
                                 let end = match &*idx_ident {
-                                    "std::ops::Range" | "core::ops::Range" |
-                                    "std::ops::RangeTo" | "core::ops::RangeTo" =>
-                                        self.encoder.encode_struct_field_value(encoded_idx.clone(), "end", usize_ty).with_span(span)?,
-                                    "std::ops::RangeInclusive" | "core::ops::RangeInclusive" => return Err(
-                                        SpannedEncodingError::unsupported("slicing with RangeInclusive (e.g. [x..=y]) currently not supported".to_string(), span)
-                                    ),
-                                    "std::ops::RangeToInclusive" | "core::ops::RangeToInclusive" => {
-                                        let end_expr = self.encoder.encode_struct_field_value(encoded_idx.clone(), "end", usize_ty).with_span(span)?;
+                                    "std::ops::Range" | "core::ops::Range"
+                                    | "std::ops::RangeTo" | "core::ops::RangeTo" => self
+                                        .encoder
+                                        .encode_struct_field_value(
+                                            encoded_idx.clone(),
+                                            "end",
+                                            usize_ty,
+                                        )
+                                        .with_span(span)?,
+                                    "std::ops::RangeInclusive"
+                                    | "core::ops::RangeInclusive"
+                                    | "std::ops::RangeToInclusive"
+                                    | "core::ops::RangeToInclusive" => {
+                                        let end_expr = self
+                                            .encoder
+                                            .encode_struct_field_value(
+                                                encoded_idx.clone(),
+                                                "end",
+                                                usize_ty,
+                                            )
+                                            .with_span(span)?;
                                         vir::Expr::add(end_expr, vir::Expr::from(1u32))
                                     }
-                                    "std::ops::RangeFrom" | "core::ops::RangeFrom" |
-                                    "std::ops::RangeFull" | "core::ops::RangeFull" => {
+                                    "std::ops::RangeFrom"
+                                    | "core::ops::RangeFrom"
+                                    | "std::ops::RangeFull"
+                                    | "core::ops::RangeFull" => {
                                         if base_ty.peel_refs().is_array() {
-                                            let array_len = self.encoder.encode_sequence_types(base_ty.peel_refs()).with_span(span)?.sequence_len.unwrap();
+                                            let array_len = self
+                                                .encoder
+                                                .encode_sequence_types(base_ty.peel_refs())
+                                                .with_span(span)?
+                                                .sequence_len
+                                                .unwrap();
                                             vir::Expr::from(array_len)
                                         } else if base_ty.is_slice() {
-                                            let base = self.mir_encoder.encode_operand_place(&args[0]).with_span(span)?.unwrap();
-                                            let base_expr = self.encoder.encode_value_expr(base, base_ty).with_span(span)?;
-                                            let slice_types_base = self.encoder.encode_sequence_types(base_ty.peel_refs()).with_span(span)?;
+                                            let base = self
+                                                .mir_encoder
+                                                .encode_operand_place(&args[0])
+                                                .with_span(span)?
+                                                .unwrap();
+                                            let base_expr = self
+                                                .encoder
+                                                .encode_value_expr(base, base_ty)
+                                                .with_span(span)?;
+                                            let slice_types_base = self
+                                                .encoder
+                                                .encode_sequence_types(base_ty.peel_refs())
+                                                .with_span(span)?;
                                             slice_types_base.len(self.encoder, base_expr)
-                                        } else { todo!("Get last idx for {}", base_ty) }
+                                        } else if base_ty.is_str_or_ref() {
+                                            self.encoder
+                                                .encode_snapshot_slice_len(
+                                                    base_ty,
+                                                    encoded_args[0].clone(),
+                                                )
+                                                .with_span(span)?
+                                        } else {
+                                            todo!("Get last idx for {}", base_ty)
+                                        }
                                     }
-                                    _ => unreachable!("{}", idx_ident)
+                                    _ => unreachable!("{}", idx_ident),
                                 };
 
                                 let slice_expr = self
@@ -596,6 +854,19 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                                 state
                             }
 
+                            "prusti_contracts::Int::new" | "prusti_contracts::Int::new_usize" => {
+                                assert_eq!(args.len(), 1);
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(&encoded_lhs, encoded_args[0].clone());
+                                state
+                            }
+
+                            _ if int_operation.is_some() => {
+                                let mut state = states[&target_block].clone();
+                                state.substitute_value(&encoded_lhs, int_operation.unwrap());
+                                state
+                            }
+
                             // simple function call
                             _ => {
                                 let (called_def_id, call_substs) = self
@@ -615,6 +886,11 @@ impl<'p, 'v: 'p, 'tcx: 'v> BackwardMirInterpreter<'tcx>
                                             call_substs,
                                         )
                                         .with_span(span)?
+                                } else if self.encoder.is_lemma(called_def_id) {
+                                    return Err(SpannedEncodingError::unsupported(
+                                        "lemmas are supported only with the unsafe_core_proof flag",
+                                        span,
+                                    ));
                                 } else {
                                     return Err(SpannedEncodingError::incorrect(
                                         format!(
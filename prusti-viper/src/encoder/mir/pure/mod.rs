@@ -3,6 +3,9 @@ mod pure_functions;
 mod specifications;
 
 pub(crate) use self::{
-    pure_functions::{PureEncodingContext, PureFunctionEncoderInterface, PureFunctionEncoderState},
+    pure_functions::{
+        encode_call_termination_conditions, PureEncodingContext, PureFunctionEncoderInterface,
+        PureFunctionEncoderState,
+    },
     specifications::SpecificationEncoderInterface,
 };
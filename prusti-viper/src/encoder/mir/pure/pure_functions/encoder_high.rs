@@ -23,7 +23,10 @@ use prusti_rustc_interface::{
     span::Span,
 };
 use vir_crate::{
-    common::{expression::ExpressionIterator, position::Positioned},
+    common::{
+        expression::{BinaryOperationHelpers, ExpressionIterator},
+        position::Positioned,
+    },
     high::{self as vir_high},
 };
 
@@ -198,7 +201,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> PureEncoder<'p, 'v, 'tcx> {
 
     #[tracing::instrument(level = "debug", skip(self), fields(proc_def_id = ?self.proc_def_id))]
     fn encode_function_decl(&self) -> SpannedEncodingResult<vir_high::FunctionDecl> {
+        // The body of a lemma is its proof, which is verified as a procedure.
         let is_bodyless = self.encoder.is_trusted(self.proc_def_id, Some(self.substs))
+            || self.encoder.is_lemma(self.proc_def_id)
             || !self.encoder.env().query.has_body(self.proc_def_id);
         let body = if is_bodyless {
             None
@@ -234,7 +239,13 @@ impl<'p, 'v: 'p, 'tcx: 'v> PureEncoder<'p, 'v, 'tcx> {
     }
 
     fn encode_function_name(&self) -> String {
-        self.encoder.encode_item_name(self.proc_def_id)
+        let name = self.encoder.encode_item_name(self.proc_def_id);
+        if self.encoder.is_lemma(self.proc_def_id) {
+            // Avoid a clash with the method that checks the proof.
+            format!("lemma${name}")
+        } else {
+            name
+        }
     }
 
     #[tracing::instrument(level = "debug", skip_all, fields(proc_def_id = ?self.proc_def_id))]
@@ -305,6 +316,11 @@ impl<'p, 'v: 'p, 'tcx: 'v> PureEncoder<'p, 'v, 'tcx> {
     }
 
     fn encode_return_type(&self) -> SpannedEncodingResult<vir_high::Type> {
+        if self.encoder.is_lemma(self.proc_def_id) {
+            // A lemma used in a specification is encoded as a function that
+            // always returns `true` and whose postcondition is the lemma's.
+            return Ok(vir_high::Type::Bool);
+        }
         let ty = self.sig.output();
 
         let span = self.get_return_span();
@@ -375,7 +391,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> PureEncoder<'p, 'v, 'tcx> {
             );
             conjuncts.push(encoded_assertion);
         }
-        let post = conjuncts.into_iter().conjoin();
+        let mut post = conjuncts.into_iter().conjoin();
 
         // TODO: use a better span
         let postcondition_pos = self.encoder.error_manager().register_error(
@@ -387,9 +403,12 @@ impl<'p, 'v: 'p, 'tcx: 'v> PureEncoder<'p, 'v, 'tcx> {
         // Fix return variable
         let pure_fn_return_variable = vir_high_local! { __result: {self.encode_return_type()?} };
 
-        let post = post
-            .replace_place(&encoded_return.into(), &pure_fn_return_variable.into())
-            .set_default_position(postcondition_pos.into());
+        if self.encoder.is_lemma(self.proc_def_id) {
+            post = vir_high::Expression::and(post, pure_fn_return_variable.into());
+        } else {
+            post = post.replace_place(&encoded_return.into(), &pure_fn_return_variable.into());
+        }
+        let post = post.set_default_position(postcondition_pos.into());
 
         Ok(post)
     }
@@ -9,7 +9,9 @@
 mod interface;
 mod encoder_high;
 mod encoder_poly;
+mod termination;
 
 pub(crate) use interface::{
     PureEncodingContext, PureFunctionEncoderInterface, PureFunctionEncoderState,
 };
+pub(crate) use termination::encode_call_termination_conditions;
@@ -0,0 +1,419 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! Termination checks of recursive calls in pure functions.
+//!
+//! The definition of a pure function is used as an axiom, which is unsound if
+//! the function does not terminate. Therefore, each recursive call of a pure
+//! function either has to decrease the termination measure of the function or,
+//! if the function has no measure, has to be structurally recursive. The
+//! decrease is checked by the method that verifies the body of the function.
+
+use crate::encoder::{
+    errors::{ErrorCtxt, SpannedEncodingError, SpannedEncodingResult, WithSpan},
+    mir::{
+        contracts::ContractsEncoderInterface, pure::SpecificationEncoderInterface,
+        specifications::SpecificationsInterface,
+    },
+    mir_encoder::{MirEncoder, PlaceEncoder},
+    Encoder,
+};
+use prusti_common::config;
+use prusti_interface::specs::typed::ProcedureSpecificationKind;
+use prusti_rustc_interface::{
+    hir::def_id::DefId,
+    middle::{
+        mir,
+        ty::{self, subst::SubstsRef},
+    },
+    span::Span,
+};
+use vir_crate::polymorphic as vir;
+
+enum TerminationMeasure {
+    /// A termination measure with one or more components of type Int, which
+    /// are compared lexicographically.
+    Int(Vec<vir::Expr>),
+    /// The annotated function is trusted to always terminate.
+    Trusted,
+}
+
+/// Returns the conditions under which the call from the pure function
+/// `def_id` to `called_def_id` is known to terminate, together with the error
+/// to report if a condition does not hold. Returns an error if the call is
+/// recursive but the termination cannot be checked.
+pub(crate) fn encode_call_termination_conditions<'v, 'tcx: 'v>(
+    encoder: &Encoder<'v, 'tcx>,
+    mir: &mir::Body<'tcx>,
+    def_id: DefId,
+    called_def_id: DefId,
+    call_substs: SubstsRef<'tcx>,
+    encoded_args: &[vir::Expr],
+    span: Span,
+) -> SpannedEncodingResult<Vec<(vir::Expr, ErrorCtxt)>> {
+    if !config::check_pure_function_termination()
+        || !def_id.is_local()
+        || !encoder.is_pure(def_id, None)
+        || !callee_reaches_caller(encoder, def_id, called_def_id, call_substs)
+    {
+        return Ok(vec![]);
+    }
+
+    let called_measure = encode_termination_measure(
+        encoder,
+        called_def_id,
+        call_substs,
+        encoded_args,
+        def_id,
+        span,
+    )?;
+    let mir_encoder = MirEncoder::new(encoder, mir, def_id);
+    let mut arguments = Vec::new();
+    for local in mir.args_iter() {
+        let local_ty = mir.local_decls[local].ty;
+        let argument = vir::Expr::local(mir_encoder.encode_local(local)?);
+        arguments.push(
+            encoder
+                .encode_value_expr(argument, local_ty)
+                .with_span(span)?,
+        );
+    }
+    let substs = encoder.env().query.identity_substs(def_id);
+    let measure = encode_termination_measure(encoder, def_id, substs, &arguments, def_id, span)?;
+
+    match (called_measure, measure) {
+        (Some(TerminationMeasure::Trusted), _) | (_, Some(TerminationMeasure::Trusted)) => {
+            Ok(vec![])
+        }
+        (Some(TerminationMeasure::Int(new)), Some(TerminationMeasure::Int(old))) => {
+            if new.len() != old.len() {
+                return Err(SpannedEncodingError::incorrect(
+                    "the termination measures of mutually recursive functions must have the \
+                    same number of components",
+                    span,
+                ));
+            }
+            let conditions = lexicographic_decrease_conditions(&new, &old);
+            let component_count = conditions.len();
+            let mut checks: Vec<_> = conditions
+                .into_iter()
+                .enumerate()
+                .map(|(component, condition)| {
+                    let error_ctxt = if component + 1 == component_count {
+                        ErrorCtxt::CallTerminationMeasureLower
+                    } else {
+                        ErrorCtxt::CallTerminationMeasureComponentIncreased(component)
+                    };
+                    (condition, error_ctxt)
+                })
+                .collect();
+            for component in old {
+                checks.push((
+                    vir::Expr::ge_cmp(component, 0.into()),
+                    ErrorCtxt::CallTerminationMeasureNonNegative,
+                ));
+            }
+            Ok(checks)
+        }
+        _ if called_def_id == def_id && is_structurally_recursive(mir, def_id) => Ok(vec![]),
+        _ => Err(SpannedEncodingError::incorrect(
+            "this recursive call of a pure function might not terminate; add a termination \
+            measure to the function with `#[terminates(..)]`",
+            span,
+        )),
+    }
+}
+
+/// Returns whether the call from `caller_def_id` to `called_def_id` might lead
+/// back to `caller_def_id`. Pure functions only call pure functions, so it
+/// suffices to follow the calls in the bodies of local pure functions.
+fn callee_reaches_caller<'v, 'tcx: 'v>(
+    encoder: &Encoder<'v, 'tcx>,
+    caller_def_id: DefId,
+    called_def_id: DefId,
+    call_substs: SubstsRef<'tcx>,
+) -> bool {
+    let tcx = encoder.env().tcx();
+    let mut visited = Vec::new();
+    let mut pending = vec![(called_def_id, call_substs, tcx.param_env(caller_def_id))];
+    while let Some((called_def_id, call_substs, param_env)) = pending.pop() {
+        let Some(callees) = resolve_callees(encoder, called_def_id, call_substs, param_env) else {
+            return true;
+        };
+        for (def_id, substs, param_env) in callees {
+            if def_id == caller_def_id {
+                return true;
+            }
+            if !def_id.is_local()
+                || visited.contains(&(def_id, substs))
+                || encoder.get_proc_kind(def_id, Some(substs)) != ProcedureSpecificationKind::Pure
+                || encoder.is_trusted(def_id, Some(substs))
+                || !encoder.env().query.has_body(def_id)
+            {
+                continue;
+            }
+            visited.push((def_id, substs));
+            let body = encoder
+                .env()
+                .body
+                .get_pure_fn_body(def_id, substs, caller_def_id);
+            for block in body.basic_blocks.iter() {
+                if let mir::TerminatorKind::Call { func, .. } = &block.terminator().kind {
+                    pending.extend(
+                        func.const_fn_def()
+                            .map(|(def_id, substs)| (def_id, substs, param_env)),
+                    );
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Returns the functions that a call might execute, each with the parameter
+/// environment of its substitutions. A call of a trait method that cannot be
+/// resolved (e.g. because the receiver is a type parameter) might execute the
+/// default implementation of the method or any of its implementations in this
+/// crate; implementations in other crates cannot call local functions other
+/// than through such calls. Returns `None` if the call cannot be resolved at
+/// all.
+#[allow(clippy::type_complexity)]
+fn resolve_callees<'v, 'tcx: 'v>(
+    encoder: &Encoder<'v, 'tcx>,
+    called_def_id: DefId,
+    call_substs: SubstsRef<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+) -> Option<Vec<(DefId, SubstsRef<'tcx>, ty::ParamEnv<'tcx>)>> {
+    let tcx = encoder.env().tcx();
+    let call_substs = tcx.erase_regions(call_substs);
+    match tcx.resolve_instance(param_env.and((called_def_id, call_substs))) {
+        Ok(Some(instance)) => Some(vec![(instance.def_id(), instance.substs, param_env)]),
+        Ok(None) => {
+            let trait_def_id = tcx.trait_of_item(called_def_id)?;
+            let mut callees = vec![(called_def_id, call_substs, param_env)];
+            let impls = tcx.all_local_trait_impls(()).get(&trait_def_id);
+            for impl_def_id in impls.into_iter().flatten() {
+                let implementors = tcx.impl_item_implementor_ids(impl_def_id.to_def_id());
+                if let Some(&impl_item_def_id) = implementors.get(&called_def_id) {
+                    callees.push((
+                        impl_item_def_id,
+                        encoder.env().query.identity_substs(impl_item_def_id),
+                        tcx.param_env(impl_item_def_id),
+                    ));
+                }
+            }
+            Some(callees)
+        }
+        Err(_) => None,
+    }
+}
+
+fn encode_termination_measure<'v, 'tcx: 'v>(
+    encoder: &Encoder<'v, 'tcx>,
+    proc_def_id: DefId,
+    substs: SubstsRef<'tcx>,
+    arguments: &[vir::Expr],
+    parent_def_id: DefId,
+    span: Span,
+) -> SpannedEncodingResult<Option<TerminationMeasure>> {
+    let contract = encoder
+        .get_procedure_contract_for_def(proc_def_id, substs)
+        .with_span(span)?;
+    let Some((components, measure_substs)) =
+        contract.functional_termination_measure(encoder.env(), substs) else {
+        return Ok(None);
+    };
+    let mut expressions = Vec::new();
+    for component in components {
+        let component = component.to_def_id();
+        if is_trusted_termination_measure(encoder, component, measure_substs, parent_def_id) {
+            return Ok(Some(TerminationMeasure::Trusted));
+        }
+        expressions.push(encoder.encode_assertion(
+            &component,
+            None,
+            arguments,
+            None,
+            true,
+            parent_def_id,
+            measure_substs,
+        )?);
+    }
+    Ok(Some(TerminationMeasure::Int(expressions)))
+}
+
+/// `#[terminates(trusted)]` is desugared to a measure that calls
+/// `prusti_terminates_trusted`. Note that `#[terminates]` without a measure is
+/// desugared to the constant measure `Int::new(1)`, which is not trusted.
+fn is_trusted_termination_measure<'v, 'tcx: 'v>(
+    encoder: &Encoder<'v, 'tcx>,
+    measure: DefId,
+    substs: SubstsRef<'tcx>,
+    parent_def_id: DefId,
+) -> bool {
+    let body = encoder
+        .env()
+        .body
+        .get_expression_body(measure, substs, parent_def_id);
+    body.basic_blocks.iter().any(|block| {
+        let mir::TerminatorKind::Call { func, .. } = &block.terminator().kind else {
+            return false;
+        };
+        func.const_fn_def().map_or(false, |(called_def_id, _)| {
+            encoder.env().name.get_absolute_item_name(called_def_id)
+                == "prusti_contracts::prusti_terminates_trusted"
+        })
+    })
+}
+
+/// Returns the conditions that together ensure that the `new` value of a
+/// lexicographic measure is lower than its `old` value; see the termination
+/// checks of procedures.
+fn lexicographic_decrease_conditions(new: &[vir::Expr], old: &[vir::Expr]) -> Vec<vir::Expr> {
+    let component_count = new.len();
+    let mut conditions = Vec::new();
+    let mut some_component_decreased: Option<vir::Expr> = None;
+    for (index, (new, old)) in new.iter().zip(old).enumerate() {
+        let decreased = vir::Expr::lt_cmp(new.clone(), old.clone());
+        let condition = if index + 1 == component_count {
+            decreased.clone()
+        } else {
+            vir::Expr::le_cmp(new.clone(), old.clone())
+        };
+        let with_previous = |expression| match &some_component_decreased {
+            Some(previous) => vir::Expr::or(previous.clone(), expression),
+            None => expression,
+        };
+        conditions.push(with_previous(condition));
+        some_component_decreased = Some(with_previous(decreased));
+    }
+    conditions
+}
+
+/// How an argument of a recursive call relates to the parameter at the same
+/// position of the caller.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ArgumentChange {
+    /// The argument is a field (at any depth) of the parameter.
+    Decreased,
+    /// The argument is the parameter itself.
+    Unchanged,
+    Unknown,
+}
+
+/// The recursion of `def_id` is structural if there is a lexicographic order
+/// of some of its parameters that decreases in every recursive call of its
+/// body: each call passes a field (at any depth) of the parameter of some
+/// position and passes the parameters of all the positions earlier in the
+/// order unchanged. Since values are finite, such a recursion always
+/// terminates.
+fn is_structurally_recursive<'tcx>(mir: &mir::Body<'tcx>, def_id: DefId) -> bool {
+    let mut calls: Vec<Vec<ArgumentChange>> = mir
+        .basic_blocks
+        .iter()
+        .filter_map(|block| {
+            let mir::TerminatorKind::Call { func, args, .. } = &block.terminator().kind else {
+                return None;
+            };
+            let (called_def_id, _) = func.const_fn_def()?;
+            (called_def_id == def_id).then(|| {
+                args.iter()
+                    .enumerate()
+                    .map(|(index, arg)| argument_change(mir, index, arg))
+                    .collect()
+            })
+        })
+        .collect();
+    // A parameter that decreases or is unchanged in all the remaining calls
+    // can always be the next one in the order, so picking any of them never
+    // rules out an order that would be found otherwise.
+    while !calls.is_empty() {
+        let parameter_count = calls[0].len();
+        let Some(next) = (0..parameter_count).find(|&index| {
+            calls
+                .iter()
+                .all(|call| call[index] != ArgumentChange::Unknown)
+                && calls
+                    .iter()
+                    .any(|call| call[index] == ArgumentChange::Decreased)
+        }) else {
+            return false;
+        };
+        calls.retain(|call| call[next] == ArgumentChange::Unchanged);
+    }
+    true
+}
+
+fn argument_change<'tcx>(
+    mir: &mir::Body<'tcx>,
+    index: usize,
+    arg: &mir::Operand<'tcx>,
+) -> ArgumentChange {
+    let Some((local, projection)) = arg.place().and_then(|place| place_origin(mir, place)) else {
+        return ArgumentChange::Unknown;
+    };
+    if local.index() != index + 1 {
+        ArgumentChange::Unknown
+    } else if projection
+        .iter()
+        .any(|elem| matches!(elem, mir::ProjectionElem::Field(..)))
+    {
+        ArgumentChange::Decreased
+    } else if projection
+        .iter()
+        .all(|elem| matches!(elem, mir::ProjectionElem::Deref))
+    {
+        // Reborrowing a reference parameter passes the same value.
+        ArgumentChange::Unchanged
+    } else {
+        ArgumentChange::Unknown
+    }
+}
+
+/// Follows the moves, copies and borrows of `place` back to an argument of the
+/// function. Returns the argument and the projection of the argument that is
+/// equal to `place` (borrows do not appear in the projection).
+fn place_origin<'tcx>(
+    mir: &mir::Body<'tcx>,
+    place: mir::Place<'tcx>,
+) -> Option<(mir::Local, Vec<mir::PlaceElem<'tcx>>)> {
+    if mir.args_iter().any(|arg| arg == place.local) {
+        return Some((place.local, place.projection.to_vec()));
+    }
+    let mut assignments = mir.basic_blocks.iter().flat_map(|block| {
+        block
+            .statements
+            .iter()
+            .filter_map(|statement| match &statement.kind {
+                mir::StatementKind::Assign(box (lhs, rvalue))
+                    if lhs.as_local() == Some(place.local) =>
+                {
+                    Some(rvalue)
+                }
+                _ => None,
+            })
+    });
+    let rvalue = assignments.next()?;
+    if assignments.next().is_some() {
+        return None;
+    }
+    let mut projection = place.projection.iter();
+    let (local, mut origin_projection) = match rvalue {
+        mir::Rvalue::Use(mir::Operand::Copy(origin) | mir::Operand::Move(origin))
+        | mir::Rvalue::CopyForDeref(origin) => place_origin(mir, *origin)?,
+        mir::Rvalue::Ref(_, _, origin) => {
+            // Dereferencing the reference gives back the borrowed place.
+            if !place.projection.is_empty() && projection.next() != Some(mir::ProjectionElem::Deref)
+            {
+                return None;
+            }
+            place_origin(mir, *origin)?
+        }
+        _ => return None,
+    };
+    origin_projection.extend(projection);
+    Some((local, origin_projection))
+}
@@ -8,7 +8,10 @@ use crate::encoder::{
     errors::SpannedEncodingResult,
     mir::{
         places::PlacesEncoderInterface,
-        pure::{specifications::utils::extract_closure_from_ty, PureFunctionEncoderInterface},
+        pure::{
+            specifications::utils::{extract_closure_from_ty, report_trigger_warnings},
+            PureFunctionEncoderInterface,
+        },
         types::MirTypeEncoderInterface,
     },
     mir_encoder::{MirEncoder, PlaceEncoder},
@@ -116,7 +119,7 @@ pub(super) fn inline_spec_item_high<'tcx>(
 
 pub(super) fn encode_quantifier_high<'tcx>(
     encoder: &Encoder<'_, 'tcx>,
-    _span: Span, // TODO: use span somehow? or remove arg
+    span: Span,
     encoded_args: Vec<vir_high::Expression>,
     is_exists: bool,
     parent_def_id: DefId,
@@ -201,6 +204,22 @@ pub(super) fn encode_quantifier_high<'tcx>(
         body_substs,
     )?;
 
+    let body_mentions_qvars = encoded_qvars
+        .iter()
+        .any(|qvar| encoded_body.find(&qvar.clone().into()));
+    if encoded_trigger_sets.is_empty() && body_mentions_qvars {
+        encoded_trigger_sets = infer_trigger_sets(&encoded_qvars, &encoded_body);
+    }
+    report_trigger_warnings(
+        encoder,
+        body_def_id,
+        span,
+        encoded_trigger_sets.is_empty() && body_mentions_qvars,
+        encoded_trigger_sets
+            .iter()
+            .any(|set| may_cause_matching_loop(&encoded_qvars, set, &encoded_body)),
+    );
+
     // TODO: implement cache-friendly qvar renaming
 
     let final_body = if bounds.is_empty() {
@@ -224,3 +243,174 @@ pub(super) fn encode_quantifier_high<'tcx>(
         ))
     }
 }
+
+/// Whether the expression can be used as a trigger term: a function
+/// application whose arguments are built only from variables, constants,
+/// field accesses, dereferences, and further function applications. This is
+/// the same check as for the polymorphic encoding.
+fn is_valid_trigger_term(term: &vir_high::Expression) -> bool {
+    fn is_valid_argument(argument: &vir_high::Expression) -> bool {
+        match argument {
+            vir_high::Expression::Local(..) | vir_high::Expression::Constant(..) => true,
+            vir_high::Expression::Field(vir_high::Field { base, .. })
+            | vir_high::Expression::Deref(vir_high::Deref { base, .. }) => is_valid_argument(base),
+            _ => is_valid_trigger_term(argument),
+        }
+    }
+    match term {
+        vir_high::Expression::FuncApp(vir_high::FuncApp { arguments, .. }) => {
+            arguments.iter().all(is_valid_argument)
+        }
+        // Snapshot equality is encoded as an equality, which cannot be used
+        // in triggers.
+        vir_high::Expression::BuiltinFuncApp(vir_high::BuiltinFuncApp {
+            function,
+            arguments,
+            ..
+        }) => {
+            *function != vir_high::BuiltinFunc::SnapshotEquality
+                && arguments.iter().all(is_valid_argument)
+        }
+        _ => false,
+    }
+}
+
+/// Infer trigger sets from the function applications in the encoded body of
+/// a quantifier, like `encoder_poly::infer_trigger_sets`.
+fn infer_trigger_sets(
+    bound_vars: &[vir_high::VariableDecl],
+    body: &vir_high::Expression,
+) -> Vec<vir_high::Trigger> {
+    use vir_high::visitors::ExpressionWalker;
+    struct TermCollector<'a> {
+        bound_vars: &'a [vir_high::Expression],
+        terms: Vec<vir_high::Expression>,
+    }
+    impl<'a> ExpressionWalker for TermCollector<'a> {
+        fn walk_expression(&mut self, expression: &vir_high::Expression) {
+            match expression {
+                vir_high::Expression::Quantifier(..) | vir_high::Expression::LetExpr(..) => {}
+                vir_high::Expression::FuncApp(..) | vir_high::Expression::BuiltinFuncApp(..) => {
+                    if is_valid_trigger_term(expression)
+                        && self.bound_vars.iter().any(|var| expression.find(var))
+                        && !self.terms.contains(expression)
+                    {
+                        self.terms.push(expression.clone());
+                    }
+                    vir_high::visitors::default_walk_expression(self, expression);
+                }
+                _ => vir_high::visitors::default_walk_expression(self, expression),
+            }
+        }
+    }
+    let bound_vars = bound_vars
+        .iter()
+        .map(|var| var.clone().into())
+        .collect::<Vec<vir_high::Expression>>();
+    let mut collector = TermCollector {
+        bound_vars: &bound_vars,
+        terms: Vec::new(),
+    };
+    collector.walk_expression(body);
+    let terms = collector.terms;
+
+    // Prefer the smallest terms that mention all bound variables on their own.
+    let covers_all = |term: &vir_high::Expression| bound_vars.iter().all(|var| term.find(var));
+    let single_term_sets = terms
+        .iter()
+        .filter(|term| covers_all(term))
+        .filter(|term| {
+            !terms
+                .iter()
+                .any(|other| other != *term && covers_all(other) && term.find(other))
+        })
+        .map(|term| vir_high::Trigger::new(vec![term.clone()]))
+        .collect::<Vec<_>>();
+    if !single_term_sets.is_empty() {
+        return single_term_sets;
+    }
+
+    // Otherwise, try to cover all bound variables with a multi-term set.
+    let mut covered = Vec::new();
+    let mut multi_term_set = Vec::new();
+    for term in terms {
+        let mentioned = bound_vars
+            .iter()
+            .filter(|var| term.find(var))
+            .collect::<Vec<_>>();
+        if mentioned.iter().any(|var| !covered.contains(var)) {
+            for var in mentioned {
+                if !covered.contains(&var) {
+                    covered.push(var);
+                }
+            }
+            multi_term_set.push(term);
+        }
+    }
+    if covered.len() == bound_vars.len() {
+        vec![vir_high::Trigger::new(multi_term_set)]
+    } else {
+        Vec::new()
+    }
+}
+
+/// The check for matching loops of `encoder_poly::may_cause_matching_loop`.
+fn may_cause_matching_loop(
+    bound_vars: &[vir_high::VariableDecl],
+    trigger_set: &vir_high::Trigger,
+    body: &vir_high::Expression,
+) -> bool {
+    use vir_high::visitors::ExpressionWalker;
+    fn function_and_arguments(
+        expression: &vir_high::Expression,
+    ) -> Option<(String, &[vir_high::Expression])> {
+        match expression {
+            vir_high::Expression::FuncApp(func_app) => {
+                Some((func_app.function_name.clone(), &func_app.arguments))
+            }
+            vir_high::Expression::BuiltinFuncApp(func_app) => {
+                Some((func_app.function.to_string(), &func_app.arguments))
+            }
+            _ => None,
+        }
+    }
+    struct LoopFinder<'a> {
+        bound_vars: &'a [vir_high::Expression],
+        triggers: &'a [vir_high::Expression],
+        trigger_functions: Vec<String>,
+        found: bool,
+    }
+    impl<'a> ExpressionWalker for LoopFinder<'a> {
+        fn walk_expression(&mut self, expression: &vir_high::Expression) {
+            if let Some((name, arguments)) = function_and_arguments(expression) {
+                if self.trigger_functions.contains(&name)
+                    && !self.triggers.contains(expression)
+                    && arguments.iter().any(|argument| {
+                        !self.bound_vars.contains(argument)
+                            && self.bound_vars.iter().any(|var| argument.find(var))
+                    })
+                {
+                    self.found = true;
+                }
+            }
+            vir_high::visitors::default_walk_expression(self, expression);
+        }
+    }
+    let bound_vars = bound_vars
+        .iter()
+        .map(|var| var.clone().into())
+        .collect::<Vec<vir_high::Expression>>();
+    let mut finder = LoopFinder {
+        bound_vars: &bound_vars,
+        triggers: &trigger_set.terms,
+        trigger_functions: trigger_set
+            .terms
+            .iter()
+            .filter_map(function_and_arguments)
+            .map(|(name, _)| name)
+            .collect(),
+        found: false,
+    };
+    finder.walk_expression(body);
+    finder.found
+}
@@ -9,7 +9,10 @@ use crate::{
         errors::{EncodingError, EncodingResult, SpannedEncodingResult, WithSpan},
         high::types::HighTypeEncoderInterface,
         mir::{
-            pure::{specifications::utils::extract_closure_from_ty, PureFunctionEncoderInterface},
+            pure::{
+                specifications::utils::{extract_closure_from_ty, report_trigger_warnings},
+                PureFunctionEncoderInterface,
+            },
             types::MirTypeEncoderInterface,
         },
         mir_encoder::{MirEncoder, PlaceEncoder},
@@ -135,7 +138,7 @@ pub(super) fn inline_spec_item<'tcx>(
 
 pub(super) fn encode_quantifier<'tcx>(
     encoder: &Encoder<'_, 'tcx>,
-    _span: Span,
+    span: Span,
     encoded_args: Vec<vir_crate::polymorphic::Expr>,
     is_exists: bool,
     parent_def_id: DefId,
@@ -241,6 +244,22 @@ pub(super) fn encode_quantifier<'tcx>(
         body_substs,
     )?;
 
+    let body_mentions_qvars = encoded_qvars
+        .iter()
+        .any(|qvar| encoded_body.find(&qvar.clone().into()));
+    if encoded_trigger_sets.is_empty() && body_mentions_qvars {
+        encoded_trigger_sets = infer_trigger_sets(&encoded_qvars, &encoded_body);
+    }
+    report_trigger_warnings(
+        encoder,
+        body_def_id,
+        span,
+        encoded_trigger_sets.is_empty() && body_mentions_qvars,
+        encoded_trigger_sets
+            .iter()
+            .any(|set| may_cause_matching_loop(&encoded_qvars, set, &encoded_body)),
+    );
+
     // replace qvars with a nicer name based on quantifier depth to ensure that
     // quantifiers remain stable for caching
     let quantifier_depth = find_quantifier_depth(&encoded_body);
@@ -369,3 +388,182 @@ fn check_trigger_set(
     }
     Ok(())
 }
+
+/// Whether the expression can be used as a trigger term: a function
+/// application whose arguments are built only from variables, constants,
+/// field accesses, and further function applications.
+fn is_valid_trigger_term(term: &vir_crate::polymorphic::Expr) -> bool {
+    fn is_valid_argument(argument: &vir_crate::polymorphic::Expr) -> bool {
+        match argument {
+            vir_crate::polymorphic::Expr::Local(..) | vir_crate::polymorphic::Expr::Const(..) => {
+                true
+            }
+            vir_crate::polymorphic::Expr::Field(vir_crate::polymorphic::FieldExpr {
+                base, ..
+            })
+            | vir_crate::polymorphic::Expr::SnapApp(vir_crate::polymorphic::SnapApp {
+                base, ..
+            }) => is_valid_argument(base),
+            _ => is_valid_trigger_term(argument),
+        }
+    }
+    match term {
+        vir_crate::polymorphic::Expr::FuncApp(vir_crate::polymorphic::FuncApp {
+            arguments,
+            ..
+        })
+        | vir_crate::polymorphic::Expr::DomainFuncApp(vir_crate::polymorphic::DomainFuncApp {
+            arguments,
+            ..
+        }) => arguments.iter().all(is_valid_argument),
+        _ => false,
+    }
+}
+
+/// Infer trigger sets from the function applications in the encoded body of
+/// a quantifier. Terms inside nested quantifiers and let expressions are not
+/// considered because they may depend on variables bound there.
+fn infer_trigger_sets(
+    bound_vars: &[vir_crate::polymorphic::LocalVar],
+    body: &vir_crate::polymorphic::Expr,
+) -> Vec<vir_crate::polymorphic::Trigger> {
+    use vir_crate::polymorphic::ExprWalker;
+    struct TermCollector<'a> {
+        bound_vars: &'a [vir_crate::polymorphic::Expr],
+        terms: Vec<vir_crate::polymorphic::Expr>,
+    }
+    impl<'a> ExprWalker for TermCollector<'a> {
+        fn walk(&mut self, expr: &vir_crate::polymorphic::Expr) {
+            match expr {
+                vir_crate::polymorphic::Expr::ForAll(..)
+                | vir_crate::polymorphic::Expr::Exists(..)
+                | vir_crate::polymorphic::Expr::LetExpr(..) => {}
+                vir_crate::polymorphic::Expr::FuncApp(..)
+                | vir_crate::polymorphic::Expr::DomainFuncApp(..) => {
+                    if is_valid_trigger_term(expr)
+                        && self.bound_vars.iter().any(|var| expr.find(var))
+                        && !self.terms.contains(expr)
+                    {
+                        self.terms.push(expr.clone());
+                    }
+                    vir_crate::polymorphic::default_walk_expr(self, expr);
+                }
+                _ => vir_crate::polymorphic::default_walk_expr(self, expr),
+            }
+        }
+    }
+    let bound_vars = bound_vars
+        .iter()
+        .map(|var| var.clone().into())
+        .collect::<Vec<_>>();
+    let mut collector = TermCollector {
+        bound_vars: &bound_vars,
+        terms: Vec::new(),
+    };
+    collector.walk(body);
+    let terms = collector.terms;
+
+    // Prefer the smallest terms that mention all bound variables on their own.
+    let covers_all =
+        |term: &vir_crate::polymorphic::Expr| bound_vars.iter().all(|var| term.find(var));
+    let single_term_sets = terms
+        .iter()
+        .filter(|term| covers_all(term))
+        .filter(|term| {
+            !terms
+                .iter()
+                .any(|other| other != *term && covers_all(other) && term.find(other))
+        })
+        .map(|term| vir_crate::polymorphic::Trigger::new(vec![term.clone()]))
+        .collect::<Vec<_>>();
+    if !single_term_sets.is_empty() {
+        return single_term_sets;
+    }
+
+    // Otherwise, try to cover all bound variables with a multi-term set.
+    let mut covered = FxHashSet::default();
+    let mut multi_term_set = Vec::new();
+    for term in terms {
+        let mentioned = bound_vars
+            .iter()
+            .filter(|var| term.find(var))
+            .collect::<Vec<_>>();
+        if mentioned.iter().any(|var| !covered.contains(*var)) {
+            covered.extend(mentioned);
+            multi_term_set.push(term);
+        }
+    }
+    if covered.len() == bound_vars.len() {
+        vec![vir_crate::polymorphic::Trigger::new(multi_term_set)]
+    } else {
+        Vec::new()
+    }
+}
+
+/// A simple check for matching loops: instantiating the quantifier with a
+/// term matching one of its triggers yields the body, which may contain an
+/// application of the same function whose arguments are built from bound
+/// variables (for example, `f(x + 1)` for the trigger `f(x)`). Such a term
+/// matches the trigger again, leading to an unbounded number of
+/// instantiations.
+fn may_cause_matching_loop(
+    bound_vars: &[vir_crate::polymorphic::LocalVar],
+    trigger_set: &vir_crate::polymorphic::Trigger,
+    body: &vir_crate::polymorphic::Expr,
+) -> bool {
+    use vir_crate::polymorphic::ExprWalker;
+    fn function_name(expr: &vir_crate::polymorphic::Expr) -> Option<&str> {
+        match expr {
+            vir_crate::polymorphic::Expr::FuncApp(func_app) => Some(&func_app.function_name),
+            vir_crate::polymorphic::Expr::DomainFuncApp(func_app) => {
+                Some(&func_app.domain_function.name)
+            }
+            _ => None,
+        }
+    }
+    fn arguments(expr: &vir_crate::polymorphic::Expr) -> &[vir_crate::polymorphic::Expr] {
+        match expr {
+            vir_crate::polymorphic::Expr::FuncApp(func_app) => &func_app.arguments,
+            vir_crate::polymorphic::Expr::DomainFuncApp(func_app) => &func_app.arguments,
+            _ => &[],
+        }
+    }
+    struct LoopFinder<'a> {
+        bound_vars: &'a [vir_crate::polymorphic::Expr],
+        triggers: &'a [vir_crate::polymorphic::Expr],
+        trigger_functions: Vec<&'a str>,
+        found: bool,
+    }
+    impl<'a> ExprWalker for LoopFinder<'a> {
+        fn walk(&mut self, expr: &vir_crate::polymorphic::Expr) {
+            if let Some(name) = function_name(expr) {
+                if self.trigger_functions.contains(&name)
+                    && !self.triggers.contains(expr)
+                    && arguments(expr).iter().any(|argument| {
+                        !self.bound_vars.contains(argument)
+                            && self.bound_vars.iter().any(|var| argument.find(var))
+                    })
+                {
+                    self.found = true;
+                }
+            }
+            vir_crate::polymorphic::default_walk_expr(self, expr);
+        }
+    }
+    let bound_vars = bound_vars
+        .iter()
+        .map(|var| var.clone().into())
+        .collect::<Vec<_>>();
+    let mut finder = LoopFinder {
+        bound_vars: &bound_vars,
+        triggers: trigger_set.elements(),
+        trigger_functions: trigger_set
+            .elements()
+            .iter()
+            .filter_map(function_name)
+            .collect(),
+        found: false,
+    };
+    finder.walk(body);
+    finder.found
+}
@@ -4,8 +4,10 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use prusti_interface::environment::EnvQuery;
+use crate::encoder::Encoder;
+use prusti_interface::{environment::EnvQuery, PrustiError};
 use prusti_rustc_interface::{
+    errors::MultiSpan,
     hir::def_id::DefId,
     middle::ty::{subst::SubstsRef, Ty, TyKind},
     span::Span,
@@ -36,3 +38,47 @@ pub(super) fn extract_closure_from_ty<'tcx>(
         _ => unreachable!("expected closure type"),
     }
 }
+
+/// Reports the problems found with the (given or inferred) triggers of the
+/// quantifier whose body is the closure `quantifier`. A quantifier is encoded
+/// every time its specification is used, e.g. at each call of a procedure, so
+/// the warnings are reported only when encoding the item that owns the
+/// quantifier, and only once.
+pub(super) fn report_trigger_warnings(
+    encoder: &Encoder<'_, '_>,
+    quantifier: DefId,
+    span: Span,
+    has_no_triggers: bool,
+    may_cause_matching_loop: bool,
+) {
+    if encoder.is_encoding_callee_contract.get()
+        || !encoder
+            .quantifiers_with_reported_triggers
+            .borrow_mut()
+            .insert(quantifier)
+    {
+        return;
+    }
+    if has_no_triggers {
+        PrustiError::warning(
+            "no valid trigger could be inferred for this quantifier",
+            MultiSpan::from_span(span),
+        )
+        .set_help(
+            "quantifiers without triggers may not be instantiated by the verifier; \
+            consider specifying triggers explicitly with `triggers=[...]`",
+        )
+        .emit(&encoder.env().diagnostic);
+    }
+    if may_cause_matching_loop {
+        PrustiError::warning(
+            "the triggers of this quantifier may cause a matching loop",
+            MultiSpan::from_span(span),
+        )
+        .set_help(
+            "instantiating the quantifier creates new terms that match its own triggers; \
+            consider choosing different triggers with `triggers=[...]`",
+        )
+        .emit(&encoder.env().diagnostic);
+    }
+}
@@ -1,8 +1,5 @@
 use super::interface::EncodedSequenceTypes;
-use crate::{
-    encoder::{errors::EncodingResult, high::types::HighTypeEncoderInterface, Encoder},
-    error_unsupported,
-};
+use crate::encoder::{errors::EncodingResult, high::types::HighTypeEncoderInterface, Encoder};
 use prusti_rustc_interface::middle::ty;
 
 pub(super) fn encode_sequence_types<'p, 'v: 'p, 'tcx: 'v>(
@@ -22,9 +19,8 @@ pub(super) fn encode_sequence_types<'p, 'v: 'p, 'tcx: 'v>(
             (*elem_ty, Some(len))
         }
         ty::TyKind::Slice(elem_ty) => (*elem_ty, None),
-        ty::TyKind::Str => {
-            error_unsupported!("Encoding of Str slice type");
-        }
+        // A `str` is a sequence of UTF-8 encoded bytes.
+        ty::TyKind::Str => (encoder.env().tcx().types.u8, None),
         _ => unreachable!(),
     };
 
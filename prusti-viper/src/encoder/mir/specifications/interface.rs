@@ -80,6 +80,9 @@ pub(crate) trait SpecificationsInterface<'tcx> {
 
     fn is_trusted(&self, def_id: DefId, substs: Option<SubstsRef<'tcx>>) -> bool;
 
+    /// Is the function marked with `#[lemma]`?
+    fn is_lemma(&self, def_id: DefId) -> bool;
+
     fn get_predicate_body(&self, def_id: DefId, substs: SubstsRef<'tcx>) -> Option<DefId>;
 
     fn terminates(&self, def_id: DefId, substs: Option<SubstsRef<'tcx>>) -> bool;
@@ -103,6 +106,9 @@ pub(crate) trait SpecificationsInterface<'tcx> {
     /// Get the end marker of the ghost block
     fn get_ghost_end(&self, def_id: DefId) -> Option<typed::GhostEnd>;
 
+    /// Get the specification of a function pointer type alias
+    fn get_fn_ptr_spec(&self, def_id: DefId) -> Option<typed::FnPointerSpecification>;
+
     /// Get the specifications attached to a function.
     fn get_procedure_specs(
         &self,
@@ -138,6 +144,8 @@ impl<'v, 'tcx: 'v> SpecificationsInterface<'tcx> for super::super::super::Encode
         let func_name = self.env().name.get_unique_item_name(def_id);
         if func_name.starts_with("prusti_contracts::prusti_contracts::Map")
             || func_name.starts_with("prusti_contracts::prusti_contracts::Seq")
+            || func_name.starts_with("prusti_contracts::prusti_contracts::Set")
+            || func_name.starts_with("prusti_contracts::prusti_contracts::Multiset")
             || func_name.starts_with("prusti_contracts::prusti_contracts::Ghost")
             || func_name.starts_with("prusti_contracts::prusti_contracts::Int")
         {
@@ -174,6 +182,11 @@ impl<'v, 'tcx: 'v> SpecificationsInterface<'tcx> for super::super::super::Encode
             .unwrap_or(false)
     }
 
+    #[tracing::instrument(level = "trace", skip(self), ret)]
+    fn is_lemma(&self, def_id: DefId) -> bool {
+        self.env().query.has_prusti_attribute(def_id, "lemma")
+    }
+
     #[tracing::instrument(level = "trace", skip(self), ret)]
     fn get_predicate_body(&self, def_id: DefId, substs: SubstsRef<'tcx>) -> Option<DefId> {
         let query = SpecQuery::FunctionDefEncoding(def_id, substs);
@@ -196,10 +209,9 @@ impl<'v, 'tcx: 'v> SpecificationsInterface<'tcx> for super::super::super::Encode
             .and_then(|spec| {
                 spec.terminates
                     .extract_with_selective_replacement()
-                    .copied()
+                    .map(Option::is_some)
             })
-            .unwrap_or(None)
-            .is_some()
+            .unwrap_or(false)
     }
 
     fn get_loop_specs(&self, def_id: DefId) -> Option<typed::LoopSpecification> {
@@ -250,6 +262,14 @@ impl<'v, 'tcx: 'v> SpecificationsInterface<'tcx> for super::super::super::Encode
             .cloned()
     }
 
+    fn get_fn_ptr_spec(&self, def_id: DefId) -> Option<typed::FnPointerSpecification> {
+        self.specifications_state
+            .specs
+            .borrow()
+            .get_fn_ptr_spec(&def_id)
+            .cloned()
+    }
+
     fn get_procedure_specs(
         &self,
         def_id: DefId,
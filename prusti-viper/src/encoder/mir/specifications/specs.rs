@@ -9,9 +9,9 @@ use log::debug;
 use prusti_interface::{
     environment::Environment,
     specs::typed::{
-        DefSpecificationMap, GhostBegin, GhostEnd, LoopSpecification, ProcedureSpecification,
-        ProcedureSpecificationKind, ProcedureSpecificationKindError, PrustiAssertion,
-        PrustiAssumption, Refinable, SpecificationItem, TypeSpecification,
+        DefSpecificationMap, FnPointerSpecification, GhostBegin, GhostEnd, LoopSpecification,
+        ProcedureSpecification, ProcedureSpecificationKind, ProcedureSpecificationKindError,
+        PrustiAssertion, PrustiAssumption, Refinable, SpecificationItem, TypeSpecification,
     },
     PrustiError,
 };
@@ -101,6 +101,11 @@ impl<'tcx> Specifications<'tcx> {
         self.user_typed_specs.get_ghost_end(def_id)
     }
 
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub(super) fn get_fn_ptr_spec(&self, def_id: &DefId) -> Option<&FnPointerSpecification> {
+        self.user_typed_specs.get_fn_ptr_spec(def_id)
+    }
+
     #[tracing::instrument(level = "trace", skip(self, env))]
     pub(super) fn get_and_refine_proc_spec<'a, 'env: 'a>(
         &'a mut self,
@@ -42,6 +42,11 @@ pub(super) fn extract_const_parameters_from_type<'tcx>(
         ty::TyKind::Ref(_, ty, _) => {
             extract_const_parameters_from_type(type_encoder, *ty, const_parameters)?
         }
+        ty::TyKind::Generator(_, substs, _) => {
+            for upvar_ty in substs.as_generator().upvar_tys() {
+                extract_const_parameters_from_type(type_encoder, upvar_ty, const_parameters)?;
+            }
+        }
         // ty::TyKind::Slice(ty) |
         ty::TyKind::Array(ty, _) => {
             let array_len = vir_high::VariableDecl::new(
@@ -74,7 +79,6 @@ pub(super) fn extract_const_parameters_from_type<'tcx>(
         ty::TyKind::Bound(_, _)
         | ty::TyKind::Placeholder(_)
         | ty::TyKind::Infer(_)
-        | ty::TyKind::Generator(..)
         | ty::TyKind::GeneratorWitness(_)
         | ty::TyKind::GeneratorWitnessMIR(..) => {
             return Err(SpannedEncodingError::unsupported(
@@ -48,6 +48,8 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
             type_name,
             "prusti_contracts::Seq"
                 | "prusti_contracts::Map"
+                | "prusti_contracts::Set"
+                | "prusti_contracts::Multiset"
                 | "prusti_contracts::Int"
                 | "prusti_contracts::Ghost"
         )
@@ -137,6 +139,24 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
                         val_type: enc_substs[1].clone(),
                         lifetimes,
                     })
+                } else if (type_name == "prusti_contracts::Set"
+                    || type_name == "prusti_contracts::Multiset")
+                    && !config::unsafe_core_proof()
+                {
+                    return Err(SpannedEncodingError::unsupported(
+                        "sets and multisets are supported only in the unsafe core proof",
+                        self.get_definition_span(),
+                    ));
+                } else if type_name == "prusti_contracts::Set" {
+                    vir::Type::Set(vir::ty::Set {
+                        element_type: enc_substs[0].clone(),
+                        lifetimes,
+                    })
+                } else if type_name == "prusti_contracts::Multiset" {
+                    vir::Type::MultiSet(vir::ty::MultiSet {
+                        element_type: enc_substs[0].clone(),
+                        lifetimes,
+                    })
                 } else if type_name == "prusti_contracts::Int" {
                     vir::Type::Int(vir::ty::Int::Unbounded)
                 } else if type_name == "prusti_contracts::Ghost" {
@@ -224,6 +244,10 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
                 // lifetimes,
             ),
 
+            ty::TyKind::Generator(def_id, _substs, _) => {
+                vir::Type::closure(encode_closure_name(self.encoder, *def_id))
+            }
+
             ty::TyKind::FnDef(def_id, _substs) => vir::Type::function_def(
                 encode_function_def_name(self.encoder, *def_id),
                 // FIXME: We are currently ignoring type arguments and lifetimes.
@@ -243,9 +267,23 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
                 )
             }
 
-            ty::TyKind::Dynamic(..) => vir::Type::unsupported("dynamic".to_string()),
+            ty::TyKind::Dynamic(predicates, ..) => {
+                if let Some(trait_def_id) = predicates.principal_def_id() {
+                    vir::Type::trusted(
+                        encode_dynamic_name(self.encoder, trait_def_id),
+                        Vec::new(),
+                        lifetimes,
+                    )
+                } else {
+                    vir::Type::unsupported("dynamic".to_string())
+                }
+            }
 
-            ty::TyKind::FnPtr(..) => vir::Type::unsupported("fnptr".to_string()),
+            // The lifetimes of a function pointer type are bound by its
+            // signature.
+            ty::TyKind::FnPtr(..) => {
+                vir::Type::trusted(encode_fn_pointer_name(), Vec::new(), Vec::new())
+            }
 
             ty::TyKind::Foreign(..) => vir::Type::unsupported("foreign".to_string()),
 
@@ -441,6 +479,12 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
                         enc_substs[0].clone(),
                         enc_substs[1].clone(),
                     ),
+                    "prusti_contracts::Set" => {
+                        vir::TypeDecl::set(lifetimes, const_parameters, enc_substs[0].clone())
+                    }
+                    "prusti_contracts::Multiset" => {
+                        vir::TypeDecl::multi_set(lifetimes, const_parameters, enc_substs[0].clone())
+                    }
                     "prusti_contracts::Int" => vir::TypeDecl::Int(vir::type_decl::Int {
                         lower_bound: None,
                         upper_bound: None,
@@ -469,6 +513,21 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
             ty::TyKind::Adt(adt_def, substs) => {
                 encode_adt_def(self.encoder, *adt_def, substs, None)?
             }
+            ty::TyKind::Dynamic(predicates, ..) if predicates.principal_def_id().is_some() => {
+                // Trait objects are abstract: their methods are verified
+                // against the specification of the trait.
+                let lifetimes = self.encoder.get_lifetimes_from_type_high(self.ty)?;
+                vir::TypeDecl::opaque(
+                    encode_dynamic_name(self.encoder, predicates.principal_def_id().unwrap()),
+                    lifetimes,
+                    Vec::new(),
+                )
+            }
+            ty::TyKind::FnPtr(..) => {
+                // Function pointers are abstract: calls through them are
+                // verified against the specification of their type.
+                vir::TypeDecl::opaque(encode_fn_pointer_name(), Vec::new(), Vec::new())
+            }
             ty::TyKind::Never => vir::TypeDecl::never(),
             ty::TyKind::Param(param_ty) => {
                 vir::TypeDecl::type_var(param_ty.name.as_str().to_string())
@@ -482,6 +541,17 @@ impl<'p, 'v, 'r: 'v, 'tcx: 'v> TypeEncoder<'p, 'v, 'tcx> {
                 let name = encode_closure_name(self.encoder, *def_id);
                 vir::TypeDecl::closure(name, arguments)
             }
+            ty::TyKind::Generator(def_id, internal_substs, _) => {
+                // Generators are encoded like closures that capture their
+                // upvars.
+                let arguments = internal_substs
+                    .as_generator()
+                    .upvar_tys()
+                    .filter_map(|ty| self.encoder.encode_type_high(ty).ok())
+                    .collect();
+                let name = encode_closure_name(self.encoder, *def_id);
+                vir::TypeDecl::closure(name, arguments)
+            }
             ty::TyKind::Array(elem_ty, _size) => {
                 let lifetimes = self.encoder.get_lifetimes_from_type_high(self.ty)?;
                 let const_parameters = self.encoder.get_const_parameters_from_type_high(self.ty)?;
@@ -696,6 +766,14 @@ fn encode_trusted_name<'v, 'tcx: 'v>(encoder: &Encoder<'v, 'tcx>, did: DefId) ->
     format!("trusted${}", encoder.encode_item_name(did))
 }
 
+fn encode_dynamic_name<'v, 'tcx: 'v>(encoder: &Encoder<'v, 'tcx>, trait_def_id: DefId) -> String {
+    format!("dyn${}", encoder.encode_item_name(trait_def_id))
+}
+
+fn encode_fn_pointer_name() -> String {
+    "fnptr$".to_string()
+}
+
 fn encode_variant<'v, 'tcx: 'v>(
     encoder: &Encoder<'v, 'tcx>,
     name: String,
@@ -386,6 +386,25 @@ impl<'v, 'tcx: 'v> MirTypeEncoderInterface<'tcx> for super::super::super::Encode
                         .unwrap_union();
                     vir_high::TypeDecl::Struct(encoded_union.into_variant(&variant.index).unwrap())
                 }
+                vir_high::Type::Sequence(vir_high::ty::Sequence {
+                    element_type,
+                    lifetimes,
+                }) => {
+                    // Sequences may be generated by our encoding without
+                    // having them in the original program.
+                    vir_high::TypeDecl::sequence(
+                        lifetimes.clone(),
+                        Vec::new(),
+                        (**element_type).clone(),
+                    )
+                }
+                vir_high::Type::Int(vir_high::ty::Int::Unbounded) => {
+                    // The same holds for the lengths of these sequences.
+                    vir_high::TypeDecl::Int(vir_high::type_decl::Int {
+                        lower_bound: None,
+                        upper_bound: None,
+                    })
+                }
                 _ => {
                     let original_ty = self.decode_type_high(ty);
                     let type_encoder = TypeEncoder::new(self, original_ty);
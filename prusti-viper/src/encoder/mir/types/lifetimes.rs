@@ -49,6 +49,11 @@ pub(super) fn extract_lifetimes_from_type<'tcx>(
         ty::TyKind::Array(ty, _) | ty::TyKind::Slice(ty) => {
             extract_lifetimes_from_type(type_encoder, *ty, lifetimes)?
         }
+        ty::TyKind::Generator(_, substs, _) => {
+            for upvar_ty in substs.as_generator().upvar_tys() {
+                extract_lifetimes_from_type(type_encoder, upvar_ty, lifetimes)?;
+            }
+        }
         ty::TyKind::Dynamic(_, region, _) => lifetimes.push(vir_high::ty::LifetimeConst {
             name: region.to_text(),
         }),
@@ -85,7 +90,6 @@ pub(super) fn extract_lifetimes_from_type<'tcx>(
         ty::TyKind::Bound(_, _)
         | ty::TyKind::Placeholder(_)
         | ty::TyKind::Infer(_)
-        | ty::TyKind::Generator(..)
         | ty::TyKind::GeneratorWitness(_)
         | ty::TyKind::GeneratorWitnessMIR(..) => {
             return Err(SpannedEncodingError::unsupported(
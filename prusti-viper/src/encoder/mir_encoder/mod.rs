@@ -157,9 +157,8 @@ pub trait PlaceEncoder<'v, 'tcx: 'v> {
                         (encoded_projection, field_ty, None)
                     }
 
-                    ty::TyKind::Closure(def_id, closure_subst) => {
-                        let closure_subst = closure_subst.as_closure();
-                        debug!("def_id={:?} closure_subst {:?}", def_id, closure_subst);
+                    ty::TyKind::Closure(def_id, _) | ty::TyKind::Generator(def_id, _, _) => {
+                        debug!("def_id={:?}", def_id);
 
                         let field_ty = *proj_field_ty;
                         let field_name = format!("closure_{}", field.index());
@@ -173,10 +172,6 @@ pub trait PlaceEncoder<'v, 'tcx: 'v> {
                         (encoded_projection, field_ty, None)
                     }
 
-                    ty::TyKind::Generator(..) => {
-                        error_unsupported!("generator fields are not supported");
-                    }
-
                     x => {
                         error_internal!("{} has no fields", utils::ty_to_string(x));
                     }
@@ -270,9 +265,27 @@ pub trait PlaceEncoder<'v, 'tcx: 'v> {
                 }
             }
 
-            mir::ProjectionElem::Subslice { .. } => error_unsupported!(
-                "slice patterns are not supported",
-            ),
+            mir::ProjectionElem::Subslice { from, to, from_end } => {
+                debug!("subslice: {:?}[{}..{}] (from_end: {})", encoded_base, from, to, from_end);
+                if !base_ty.is_array() && !base_ty.is_slice() {
+                    error_unsupported!("subslice of unsupported type '{:?}'", base_ty);
+                }
+                let tcx = self.encoder().env().tcx();
+                let subslice_ty = mir::tcx::PlaceTy::from_ty(base_ty).projection_ty(tcx, *elem).ty;
+                (
+                    PlaceEncoding::Subslice {
+                        base: box encoded_base,
+                        from: *from,
+                        to: *to,
+                        from_end: *from_end,
+                        encoded_ty: self.encoder().encode_type(subslice_ty)?,
+                        rust_base_ty: base_ty,
+                        rust_subslice_ty: subslice_ty,
+                    },
+                    subslice_ty,
+                    None,
+                )
+            }
         })
     }
 
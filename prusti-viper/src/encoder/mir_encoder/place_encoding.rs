@@ -44,6 +44,18 @@ pub enum PlaceEncoding<'tcx> {
         encoded_elem_ty: vir::Type,
         rust_slice_ty: ty::Ty<'tcx>,
     },
+    /// Subslice projection, e.g. `rest` in the slice pattern `[first, rest @ ..]`. The bounds
+    /// are kept as in MIR: if `from_end` is set, the subslice ends `to` elements before the
+    /// end of the base.
+    Subslice {
+        base: Box<PlaceEncoding<'tcx>>,
+        from: u64,
+        to: u64,
+        from_end: bool,
+        encoded_ty: vir::Type,
+        rust_base_ty: ty::Ty<'tcx>,
+        rust_subslice_ty: ty::Ty<'tcx>,
+    },
 }
 
 /// Return type of PlaceEncoding::into_array_base
@@ -102,6 +114,14 @@ impl<'tcx> PlaceEncoding<'tcx> {
                     base@ExprOrArrayBase::SliceBase(_) => base,
                 }
             }
+            PlaceEncoding::Subslice { base, rust_base_ty, .. } => {
+                match base.into_array_base() {
+                    ExprOrArrayBase::Expr(e) if rust_base_ty.is_array() => ExprOrArrayBase::ArrayBase(e),
+                    ExprOrArrayBase::Expr(e) => ExprOrArrayBase::SliceBase(e),
+                    base@ExprOrArrayBase::ArrayBase(_) => base,
+                    base@ExprOrArrayBase::SliceBase(_) => base,
+                }
+            }
         }
     }
 
@@ -116,6 +136,7 @@ impl<'tcx> PlaceEncoding<'tcx> {
             PlaceEncoding::ArrayAccess { ref encoded_elem_ty, .. } => encoded_elem_ty,
             PlaceEncoding::Variant { ref field, .. } => &field.typ,
             PlaceEncoding::SliceAccess { ref encoded_elem_ty, .. } => encoded_elem_ty,
+            PlaceEncoding::Subslice { ref encoded_ty, .. } => encoded_ty,
         }
     }
 
@@ -142,6 +163,8 @@ impl<'tcx> Display for PlaceEncoding<'tcx> {
             PlaceEncoding::ArrayAccess { base, index, .. } => write!(f, "{base}[{index}]"),
             PlaceEncoding::Variant { base, field } => write!(f, "{base}[{field}]"),
             PlaceEncoding::SliceAccess { base, index, .. } => write!(f, "{base}[{index}]"),
+            PlaceEncoding::Subslice { base, from, to, from_end: false, .. } => write!(f, "{base}[{from}..{to}]"),
+            PlaceEncoding::Subslice { base, from, to, from_end: true, .. } => write!(f, "{base}[{from}..-{to}]"),
         }
     }
 }
@@ -4,12 +4,13 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-pub use self::encoder::Encoder;
+pub use self::encoder::{Encoder, ProcedureEncodingSummary};
 
 mod builtin_encoder;
 #[allow(clippy::module_inception)]
 mod encoder;
-mod errors;
+pub(crate) mod errors;
+mod fn_pointer_contracts;
 mod foldunfold;
 mod initialisation;
 mod loop_encoder;
@@ -11,6 +11,7 @@ use crate::encoder::errors::{
     EncodingResult, SpannedEncodingResult
 };
 use crate::encoder::errors::error_manager::PanicCause;
+use crate::encoder::fn_pointer_contracts::FnPointerContracts;
 use crate::encoder::foldunfold;
 use crate::encoder::high::types::HighTypeEncoderInterface;
 use crate::encoder::initialisation::InitInfo;
@@ -71,10 +72,11 @@ use crate::utils::is_reference;
 use crate::encoder::mir::{
     sequences::MirSequencesEncoderInterface,
     contracts::{
+        check_frame_conditions,
         ContractsEncoderInterface,
         ProcedureContract,
     },
-    pure::PureFunctionEncoderInterface,
+    pure::{encode_call_termination_conditions, PureFunctionEncoderInterface},
     types::MirTypeEncoderInterface,
     pure::SpecificationEncoderInterface,
     specifications::SpecificationsInterface,
@@ -133,6 +135,12 @@ pub struct ProcedureEncoder<'p, 'v: 'p, 'tcx: 'v> {
     /// Type substitutions inside this procedure. Most likely identity for the
     /// given proc_def_id.
     substs: SubstsRef<'tcx>,
+    /// The contracts of the function pointers stored in the places of this
+    /// procedure.
+    fn_pointer_contracts: FnPointerContracts<'p, 'v, 'tcx>,
+    /// Checks that functions coerced to function pointers satisfy the
+    /// contracts of the function pointers.
+    fn_pointer_contract_checks: Vec<Vec<vir::Stmt>>,
 }
 
 impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
@@ -146,11 +154,18 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         let substs = encoder.env().query.identity_substs(proc_def_id);
         let tcx = encoder.env().tcx();
         let mir_encoder = MirEncoder::new(encoder, mir, proc_def_id);
-        let init_info = InitInfo::new(mir, tcx, proc_def_id, &mir_encoder)
+        let init_info = InitInfo::new(mir, tcx, procedure.get_body_def_id(), &mir_encoder)
             .with_default_span(procedure.get_span())?;
 
         let specification_blocks = SpecificationBlocks::build(encoder.env().query, mir, procedure, false);
 
+        let fn_pointer_contracts = FnPointerContracts::new(
+            encoder,
+            proc_def_id,
+            procedure.get_body_def_id(),
+            mir,
+        );
+
         let cfg_method = vir::CfgMethod::new(
             // method name
             encoder.encode_item_name(proc_def_id),
@@ -192,6 +207,8 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             old_ghost_vars: FxHashMap::default(),
             cached_loop_invariant_block: FxHashMap::default(),
             substs,
+            fn_pointer_contracts,
+            fn_pointer_contract_checks: Vec::new(),
         })
     }
 
@@ -364,10 +381,37 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         let mir_span = self.mir.span;
 
         // Retrieve the contract
-        let procedure_contract = self.encoder
-            .get_procedure_contract_for_def(self.proc_def_id, self.substs)
-            .with_span(mir_span)?;
+        let procedure_contract = if self.procedure.is_async_fn() {
+            // The arguments of an `async fn` are stored in locals of the
+            // generator that implements its body.
+            let arg_locals = self.procedure.get_async_fn_arg_locals().ok_or_else(|| {
+                SpannedEncodingError::unsupported(
+                    "the arguments of this `async fn` are not supported",
+                    mir_span,
+                )
+            })?;
+            // Only awaits on calls to `async fn`s are rewritten to calls, the
+            // remaining ones still suspend the generator.
+            if let Some(block) = self.mir.basic_blocks.iter().find(|block| {
+                matches!(block.terminator().kind, TerminatorKind::Yield { .. })
+            }) {
+                return Err(SpannedEncodingError::unsupported(
+                    "only futures returned by calls to `async fn`s can be awaited",
+                    block.terminator().source_info.span,
+                ));
+            }
+            let args = arg_locals.iter().map(|&local| local.into()).collect::<Vec<_>>();
+            self.encoder
+                .get_mir_procedure_contract_for_def(self.proc_def_id, self.substs)
+                .with_span(mir_span)?
+                .to_call_site_contract(&args, mir::RETURN_PLACE.into())
+        } else {
+            self.encoder
+                .get_procedure_contract_for_def(self.proc_def_id, self.substs)
+                .with_span(mir_span)?
+        };
         assert_one_magic_wand(procedure_contract.borrow_infos.len()).with_span(mir_span)?;
+        check_frame_conditions(self.encoder, self.proc_def_id, self.substs, self.mir)?;
         self.procedure_contract = Some(procedure_contract);
 
         // Declare the formal return
@@ -466,6 +510,25 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             Successor::Goto(opt_body_head.unwrap_or(return_cfg_block)),
         );
 
+        // The checks of the coercions to function pointers branch off
+        // non-deterministically at the start of the method.
+        if !self.fn_pointer_contract_checks.is_empty() {
+            let mut check_targets = vec![];
+            let checks = std::mem::take(&mut self.fn_pointer_contract_checks);
+            for (index, check_stmts) in checks.into_iter().enumerate() {
+                let check_block = self
+                    .cfg_method
+                    .add_block(&format!("fn_ptr_check_{index}"), check_stmts);
+                self.cfg_method.set_successor(check_block, Successor::Return);
+                let branch_var = self.cfg_method.add_fresh_local_var(vir::Type::Bool);
+                check_targets.push((vir::Expr::local(branch_var), check_block));
+            }
+            self.cfg_method.set_successor(
+                start_cfg_block,
+                Successor::GotoSwitch(check_targets, opt_body_head.unwrap_or(return_cfg_block)),
+            );
+        }
+
         // Prepare assertions to check specification refinement
         let (precondition_weakening, postcondition_strengthening)
             = self.encode_spec_refinement(PRECONDITION_LABEL)?;
@@ -1416,7 +1479,15 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             | mir::StatementKind::Coverage(..)
             | mir::StatementKind::Nop => vec![],
 
+            // Self-assignments do not change the state. Prusti introduces
+            // them for the arguments of `async fn`s.
+            mir::StatementKind::Assign(box (
+                lhs,
+                mir::Rvalue::Use(mir::Operand::Copy(rhs) | mir::Operand::Move(rhs)),
+            )) if lhs == rhs => vec![],
+
             mir::StatementKind::Assign(box (lhs, ref rhs)) => {
+                self.check_fn_pointer_assignment(lhs, rhs, location)?;
                 // Array access on the LHS should always be mutable (idx is always calculated
                 // before, and just a separate local variable here)
                 let (lhs_place_encoding, ty, _) = self.mir_encoder.encode_place(lhs).with_span(span)?;
@@ -1578,6 +1649,13 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                         cast_ty,
                         location,
                     )?
+                } else if cast_ty.builtin_deref(true).map_or(false, |target| target.ty.is_trait()) {
+                    self.encode_assign_trait_object(
+                        encoded_lhs,
+                        operand,
+                        cast_ty,
+                        location,
+                    )?
                 } else {
                     return Err(SpannedEncodingError::unsupported(
                         format!("unsizing a {rhs_ty} into a {cast_ty} is not supported"),
@@ -1585,6 +1663,11 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                     ));
                 }
             }
+            mir::Rvalue::Cast(mir::CastKind::Pointer(ty::adjustment::PointerCast::ReifyFnPointer), _, _) => {
+                // Function pointers are abstract; the coercion is checked
+                // against the contract of the target place separately.
+                self.encode_havoc_and_initialization(&encoded_lhs).with_span(span)?
+            }
             mir::Rvalue::Cast(mir::CastKind::Pointer(_), _, _) |
             mir::Rvalue::Cast(mir::CastKind::DynStar, _, _) => {
                 return Err(SpannedEncodingError::unsupported(
@@ -1708,6 +1791,12 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             Ok((expiring, restored, ref_field))
         };
         Ok(match loan_places.source {
+            // A borrowed subslice is a fresh sequence, see `encode_assign_subslice`
+            mir::Rvalue::Ref(_, mir::BorrowKind::Shared, rhs_place)
+                if matches!(rhs_place.projection.last(), Some(mir::ProjectionElem::Subslice { .. })) => {
+                let ref_field = self.encoder.encode_value_field(expiring_ty).with_span(span)?;
+                (expiring_base.field(ref_field), None, false, stmts)
+            }
             mir::Rvalue::Ref(_, mir_borrow_kind, rhs_place) => {
                 let is_mut = match mir_borrow_kind {
                     mir::BorrowKind::Shared => false,
@@ -1742,6 +1831,20 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 let (restored, r_stmts, ..) = self.encode_place(place, ArrayAccessKind::Shared, location)?;
                 stmts.extend(r_stmts);
 
+                if let ty::TyKind::Ref(_, target_ty, mir::Mutability::Mut) = ty.kind() {
+                    if target_ty.is_trait() {
+                        // The value might have been modified through the
+                        // trait object, so we havoc it.
+                        let operand_ty = self.mir_encoder.get_operand_ty(operand);
+                        let target = self.encoder.encode_value_expr(restored.clone(), operand_ty)
+                            .with_span(span)?;
+                        let target_perm = vir::Expr::pred_permission(target, vir::PermAmount::Write)
+                            .unwrap();
+                        stmts.push(vir_stmt!{ exhale [target_perm] });
+                        stmts.push(vir_stmt!{ inhale [target_perm] });
+                    }
+                }
+
                 (expiring_base, Some(restored), false, stmts)
             }
 
@@ -2445,6 +2548,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 if let ty::TyKind::FnDef(called_def_id, call_substs) = ty.kind() {
                     let called_def_id = *called_def_id;
                     debug!("Encode function call {:?} with substs {:?}", called_def_id, call_substs);
+                    self.check_fn_pointer_call(called_def_id, args, destination, span)?;
 
                     let full_func_proc_name: &str =
                         &self.encoder.env().name.get_absolute_item_name(called_def_id);
@@ -2519,6 +2623,20 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                             );
                         }
 
+                        // The fields of `RangeInclusive` are private, so `a..=b`
+                        // is desugared to a call instead of an aggregate.
+                        "std::ops::RangeInclusive::<Idx>::new"
+                        | "core::ops::RangeInclusive::<Idx>::new" => {
+                            stmts.extend(
+                                self.encode_range_inclusive_new_call(
+                                    destination,
+                                    args,
+                                    location,
+                                    span,
+                                )?
+                            );
+                        }
+
                         "std::cmp::PartialEq::eq" |
                         "core::cmp::PartialEq::eq"
                             if args.len() == 2 &&
@@ -2589,7 +2707,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                             }
                         }
 
-                        "core::slice::<impl [T]>::len" => {
+                        "core::slice::<impl [T]>::len" | "core::str::<impl str>::len" => {
                             stmts.extend(
                                 self.encode_slice_len_call(
                                     destination,
@@ -2600,6 +2718,34 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                             );
                         }
 
+                        "core::str::<impl str>::as_bytes" => {
+                            stmts.extend(
+                                self.encode_str_as_bytes_call(
+                                    destination,
+                                    args,
+                                    location,
+                                    span,
+                                )?
+                            );
+                        }
+
+                        // Only string patterns are supported by `starts_with`
+                        "core::str::<impl str>::starts_with" |
+                        "core::str::<impl str>::is_char_boundary"
+                            if full_func_proc_name == "core::str::<impl str>::is_char_boundary"
+                                || self.mir_encoder.get_operand_ty(&args[1]).is_str_ref() => {
+                            stmts.extend(
+                                self.encode_str_function_call(
+                                    full_func_proc_name,
+                                    location,
+                                    term.source_info.span,
+                                    args,
+                                    destination,
+                                    target,
+                                )?
+                            );
+                        }
+
                         "std::iter::Iterator::next" |
                         "core::iter::Iterator::next" => {
                             return Err(SpannedEncodingError::unsupported(
@@ -2695,9 +2841,38 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 }
             }
 
-            TerminatorKind::Call { .. } => {
-                // Other kind of calls?
-                unimplemented!();
+            TerminatorKind::Call {
+                ref func,
+                ref args,
+                destination,
+                target,
+                ..
+            } => {
+                // A call through a function pointer is encoded as a call to
+                // the contract of the function pointer type.
+                let Some(contract_def_id) = self.fn_pointer_contracts.of_operand(func) else {
+                    return Err(SpannedEncodingError::unsupported(
+                        "calling a function pointer is only supported if its type is a type alias with a specification",
+                        span,
+                    ));
+                };
+                self.check_fn_pointer_call(contract_def_id, args, destination, span)?;
+                stmts.extend(
+                    self.encode_impure_function_call(
+                        location,
+                        term.source_info.span,
+                        args,
+                        destination,
+                        target,
+                        contract_def_id,
+                        self.encoder.env().query.identity_substs(contract_def_id),
+                    )?
+                );
+                if let Some(target) = target {
+                    (stmts, MirSuccessor::Goto(target))
+                } else {
+                    (stmts, MirSuccessor::Kill)
+                }
             }
 
             TerminatorKind::Assert {
@@ -2756,9 +2931,17 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 (stmts, MirSuccessor::Goto(target))
             }
 
+            TerminatorKind::Yield { .. } => {
+                unreachable!("yield terminators are rejected before the encoding")
+            }
+
+            TerminatorKind::GeneratorDrop => {
+                // The generator is dropped while suspended, so the procedure
+                // does not return.
+                (stmts, MirSuccessor::Kill)
+            }
+
             TerminatorKind::Resume
-            | TerminatorKind::Yield { .. }
-            | TerminatorKind::GeneratorDrop
             | TerminatorKind::InlineAsm { .. } => unimplemented!("{:?}", term.kind),
         };
         Ok(result)
@@ -2816,6 +2999,121 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         Ok(stmts)
     }
 
+    /// Encodes `s.as_bytes()` like slicing the whole string: the resulting
+    /// byte slice has the length and the contents of the string.
+    #[tracing::instrument(level = "debug", skip(self))]
+    fn encode_str_as_bytes_call(
+        &mut self,
+        destination: mir::Place<'tcx>,
+        args: &[mir::Operand<'tcx>],
+        location: mir::Location,
+        span: Span,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        assert!(
+            args.len() == 1,
+            "unexpected args to str::as_bytes(): {args:?}"
+        );
+
+        let mut stmts = vec![];
+
+        // we need to put a label before, it seems..
+        let label = self.cfg_method.get_fresh_label_name();
+        stmts.push(vir::Stmt::label(label.clone()));
+
+        let loan = self.polonius_info().get_loan_at_location(location);
+        let (encoded_lhs, encode_stmts, lhs_ty, _) = self.encode_place(
+            destination,
+            ArrayAccessKind::Mutable(Some(loan.index().into()), location),
+            location,
+        )?;
+        stmts.extend(encode_stmts);
+        stmts.extend(self.encode_havoc(&encoded_lhs).with_span(span)?);
+        stmts.push(vir_stmt!{ inhale [vir::Expr::pred_permission(encoded_lhs.clone(), vir::PermAmount::Read).unwrap()] });
+
+        let lhs_bytes_expr = self
+            .encoder
+            .encode_value_expr(encoded_lhs.clone(), lhs_ty)
+            .with_span(span)?;
+        let base_str = self
+            .mir_encoder
+            .encode_operand_place(&args[0])
+            .with_span(span)?
+            .unwrap();
+        let base_str_ty = self.mir_encoder.get_operand_ty(&args[0]);
+        let base_str_expr = self
+            .encoder
+            .encode_value_expr(base_str, base_str_ty)
+            .with_span(span)?;
+        let str_len = self
+            .encoder
+            .encode_sequence_types(base_str_ty.peel_refs())
+            .with_span(span)?
+            .len(self.encoder, base_str_expr.clone());
+
+        self.slice_created_at.insert(location, encoded_lhs);
+
+        stmts.extend(
+            self.encode_subsequence_relation(
+                lhs_bytes_expr,
+                lhs_ty.peel_refs(),
+                base_str_expr,
+                base_str_ty.peel_refs(),
+                vir::Expr::from(0usize),
+                str_len,
+            )
+            .with_span(span)?,
+        );
+
+        self.encode_transfer_args_permissions(location, args, &mut stmts, &label, false)?;
+        // Store a label for permissions got back from the call
+        debug!(
+            "Pure function call location {:?} has label {}",
+            location, label
+        );
+        self.label_after_location.insert(location, label);
+
+        Ok(stmts)
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    fn encode_range_inclusive_new_call(
+        &mut self,
+        destination: mir::Place<'tcx>,
+        args: &[mir::Operand<'tcx>],
+        location: mir::Location,
+        span: Span,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        assert!(args.len() == 2, "unexpected args to RangeInclusive::new(): {args:?}");
+        let (encoded_lhs, mut stmts, _, _) = self.encode_place(
+            destination,
+            ArrayAccessKind::Mutable(None, location),
+            location,
+        )?;
+        stmts.extend(self.encode_havoc_and_initialization(&encoded_lhs).with_span(span)?);
+        for (field_name, operand) in ["start", "end"].into_iter().zip(args) {
+            let field_ty = self.mir_encoder.get_operand_ty(operand);
+            let encoded_field = self.encoder
+                .encode_struct_field(field_name, field_ty)
+                .with_span(span)?;
+            stmts.extend(self.encode_assign_operand(
+                &encoded_lhs.clone().field(encoded_field),
+                operand,
+                location,
+            )?);
+        }
+        let bool_ty = self.encoder.env().tcx().types.bool;
+        let exhausted_field = self.encoder
+            .encode_struct_field("exhausted", bool_ty)
+            .with_span(span)?;
+        stmts.extend(self.encode_copy_value_assign(
+            encoded_lhs.field(exhausted_field),
+            false.into(),
+            bool_ty,
+            location,
+        )?);
+        Ok(stmts)
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     fn encode_sequence_index_call(
         &mut self,
@@ -2842,7 +3140,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             location,
         )?;
         stmts.extend(encode_stmts);
-        if !lhs_ty.is_slice_or_ref() && !lhs_ty.is_array_or_ref() {
+        if !lhs_ty.is_slice_or_ref() && !lhs_ty.is_array_or_ref() && !lhs_ty.is_str_or_ref() {
             error_unsupported!("Non-slice LHS type '{:?}' not supported yet", lhs_ty);
         }
         let mutability = if let ty::TyKind::Ref(_, _, mutability) = lhs_ty.kind() { mutability } else { unreachable!() };
@@ -2860,8 +3158,8 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         let base_seq = self.mir_encoder.encode_operand_place(&args[0])?.unwrap();
         let base_seq_ty = self.mir_encoder.get_operand_ty(&args[0]);
 
-        if !base_seq_ty.is_slice_or_ref() && !base_seq_ty.is_array_or_ref() {
-            error_unsupported!("Slicing is only supported for arrays/slices currently, not '{:?}'", base_seq_ty);
+        if !base_seq_ty.is_slice_or_ref() && !base_seq_ty.is_array_or_ref() && !base_seq_ty.is_str_or_ref() {
+            error_unsupported!("Slicing is only supported for arrays/slices/strings currently, not '{:?}'", base_seq_ty);
         }
 
         // base_seq is expected to be ref$Array$.. or ref$Slice$.., but lookup_pure wants the
@@ -2870,15 +3168,6 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
 
         let enc_sequence_types = self.encoder.encode_sequence_types(base_seq_ty.peel_refs())?;
 
-        let j = vir_local!{ j: Int };
-        let elem_snap_ty = self.encoder.encode_snapshot_type(enc_sequence_types.elem_ty_rs)?;
-        let rhs_lookup_j = enc_sequence_types.encode_lookup_pure_call(
-            self.encoder,
-            base_seq_expr.clone(),
-            j.clone().into(),
-            elem_snap_ty,
-        );
-
         let encoded_idx = self.mir_encoder.encode_operand_place(&args[1])?.unwrap();
         trace!("idx: {:?}", encoded_idx);
         let idx_ty = self.mir_encoder.get_operand_ty(&args[1]);
@@ -2887,7 +3176,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
 
         self.slice_created_at.insert(location, encoded_lhs);
 
-        let original_len = enc_sequence_types.len(self.encoder, base_seq_expr);
+        let original_len = enc_sequence_types.len(self.encoder, base_seq_expr.clone());
 
         // TODO: there's fields like _5.f$start.val_int on `encoded_idx`, it just feels hacky to
         // manually re-do and hardcode them here when we probably just encoded the type
@@ -2909,9 +3198,26 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             // RangeInclusive is wierdly differnet to all of the other Range*s in that the struct fields are private
             // and it is created with a new() fn and start/end are accessed with getter fns
             // See https://github.com/rust-lang/rust/issues/67371 for why this is the case...
-            "std::ops::RangeInclusive" | "core::ops::RangeInclusive" => return Err(
-                EncodingError::unsupported("slicing with RangeInclusive (e.g. [x..=y]) currently not supported".to_string())
-            ),
+            // An exhausted range is empty and slices to `end + 1..end + 1`.
+            "std::ops::RangeInclusive" | "core::ops::RangeInclusive" => {
+                let bool_ty = self.encoder.env().tcx().types.bool;
+                let start_expr = self.encoder.encode_struct_field_value(encoded_idx.clone(), "start", usize_ty)?;
+                let end_expr = self.encoder.encode_struct_field_value(encoded_idx.clone(), "end", usize_ty)?;
+                let exhausted_expr = self.encoder.encode_struct_field_value(encoded_idx.clone(), "exhausted", bool_ty)?;
+                let start_expr = vir::Expr::ite(
+                    exhausted_expr,
+                    vir_expr!{ [end_expr] + [vir::Expr::from(1usize)] },
+                    start_expr,
+                );
+                if self.check_panics {
+                    // Check indexing in bounds
+                    stmts.push(vir::Stmt::Assert( vir::Assert {
+                        expr: vir_expr!{ [start_expr] >= [vir::Expr::from(0usize)] },
+                        position: self.register_error(error_span, ErrorCtxt::SliceRangeBoundsCheckAssert("the range start value may be smaller than 0 when slicing".to_string())),
+                    }));
+                }
+                start_expr
+            }
             "std::ops::RangeTo" | "core::ops::RangeTo" |
             "std::ops::RangeFull" | "core::ops::RangeFull" |
             "std::ops::RangeToInclusive" | "core::ops::RangeToInclusive" => vir::Expr::from(0usize),
@@ -2930,9 +3236,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 }
                 end_expr
             }
-            "std::ops::RangeInclusive" | "core::ops::RangeInclusive" => return Err(
-                EncodingError::unsupported("slicing with RangeInclusive (e.g. [x..=y]) currently not supported".to_string())
-            ),
+            "std::ops::RangeInclusive" | "core::ops::RangeInclusive" |
             "std::ops::RangeToInclusive" | "core::ops::RangeToInclusive" => {
                 let end_expr = self.encoder.encode_struct_field_value(encoded_idx, "end", usize_ty)?;
                 let end_expr = vir_expr!{ [end_expr] + [vir::Expr::from(1usize)] };
@@ -2952,11 +3256,6 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
 
         trace!("start: {}, end: {}", start, end);
 
-        let slice_types_lhs = self.encoder.encode_sequence_types(lhs_slice_ty)?;
-        let elem_snap_ty = self.encoder.encode_snapshot_type(slice_types_lhs.elem_ty_rs)?;
-
-        // length
-        let length = vir_expr!{ [end] - [start] };
         if self.check_panics {
             // start must be leq than end
             if idx_ident != "std::ops::RangeFull" && idx_ident != "core::ops::RangeFull" {
@@ -2965,9 +3264,77 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                     position: self.register_error(error_span, ErrorCtxt::SliceRangeBoundsCheckAssert("the range end may be smaller than the start when slicing".to_string())),
                 }));
             }
+            // strings can only be sliced at the boundaries of characters
+            if base_seq_ty.is_str_or_ref() {
+                for (bound, name) in [(&start, "start"), (&end, "end")] {
+                    let is_char_boundary = self.encoder.encode_snapshot_str_is_char_boundary(
+                        base_seq_ty,
+                        vir::Expr::snap_app(base_seq_expr.clone()),
+                        bound.clone(),
+                    )?;
+                    stmts.push(vir::Stmt::Assert( vir::Assert {
+                        expr: is_char_boundary,
+                        position: self.register_error(error_span, ErrorCtxt::SliceRangeBoundsCheckAssert(format!("the range {name} may not be at a character boundary when slicing a string"))),
+                    }));
+                }
+            }
         }
 
-        let slice_len_call = slice_types_lhs.len(self.encoder, lhs_slice_expr.clone());
+        stmts.extend(self.encode_subsequence_relation(
+            lhs_slice_expr,
+            lhs_slice_ty,
+            base_seq_expr,
+            base_seq_ty.peel_refs(),
+            start,
+            end,
+        )?);
+
+        self.encode_transfer_args_permissions(location, args,  &mut stmts, &label, false)?;
+        // Store a label for permissions got back from the call
+        debug!(
+            "Pure function call location {:?} has label {}",
+            location, label
+        );
+        self.label_after_location.insert(location, label);
+
+        // plan
+        //
+        // [x] inhale Array$lookup_pure == Slice$lookup_pure with quantifier from start to end
+        // [?] label, encode_transfer_permissions?
+        // [ ] what if the index is not a range? should support Index<usize> for arrays and slices maybe, mostly implemented anyway i guess
+
+        Ok(stmts)
+    }
+
+    /// Inhales that the sequence `lhs_seq_expr` has length `end - start` and contains the
+    /// elements of `base_seq_expr` in the range `start..end`.
+    fn encode_subsequence_relation(
+        &self,
+        lhs_seq_expr: vir::Expr,
+        lhs_seq_ty: ty::Ty<'tcx>,
+        base_seq_expr: vir::Expr,
+        base_seq_ty: ty::Ty<'tcx>,
+        start: vir::Expr,
+        end: vir::Expr,
+    ) -> EncodingResult<Vec<vir::Stmt>> {
+        let mut stmts = vec![];
+
+        let enc_sequence_types = self.encoder.encode_sequence_types(base_seq_ty)?;
+        let j = vir_local!{ j: Int };
+        let elem_snap_ty = self.encoder.encode_snapshot_type(enc_sequence_types.elem_ty_rs)?;
+        let rhs_lookup_j = enc_sequence_types.encode_lookup_pure_call(
+            self.encoder,
+            base_seq_expr,
+            j.clone().into(),
+            elem_snap_ty,
+        );
+
+        let slice_types_lhs = self.encoder.encode_sequence_types(lhs_seq_ty)?;
+        let elem_snap_ty = self.encoder.encode_snapshot_type(slice_types_lhs.elem_ty_rs)?;
+
+        // length
+        let length = vir_expr!{ [end] - [start] };
+        let slice_len_call = slice_types_lhs.len(self.encoder, lhs_seq_expr.clone());
         stmts.push(vir_stmt!{
             inhale [vir_expr!{ [slice_len_call] == [length] }]
         });
@@ -2980,7 +3347,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         let lhs_lookup_i = {
             slice_types_lhs.encode_lookup_pure_call(
                 self.encoder,
-                lhs_slice_expr,
+                lhs_seq_expr,
                 i_var.clone(),
                 elem_snap_ty,
             )
@@ -3015,20 +3382,6 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             ]
         });
 
-        self.encode_transfer_args_permissions(location, args,  &mut stmts, &label, false)?;
-        // Store a label for permissions got back from the call
-        debug!(
-            "Pure function call location {:?} has label {}",
-            location, label
-        );
-        self.label_after_location.insert(location, label);
-
-        // plan
-        //
-        // [x] inhale Array$lookup_pure == Slice$lookup_pure with quantifier from start to end
-        // [?] label, encode_transfer_permissions?
-        // [ ] what if the index is not a range? should support Index<usize> for arrays and slices maybe, mostly implemented anyway i guess
-
         Ok(stmts)
     }
 
@@ -3094,6 +3447,55 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         }
     }
 
+    /// Encodes a call of `str::starts_with` with a string pattern or of
+    /// `str::is_char_boundary` as an expression over the snapshots of the
+    /// arguments.
+    fn encode_str_function_call(
+        &mut self,
+        func_proc_name: &str,
+        location: mir::Location,
+        call_site_span: Span,
+        args: &[mir::Operand<'tcx>],
+        destination: mir::Place<'tcx>,
+        target: Option<BasicBlockIndex>,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        let str_ty = self.mir_encoder.get_operand_ty(&args[0]);
+        let string = self
+            .mir_encoder
+            .encode_operand_expr(&args[0])
+            .with_span(call_site_span)?;
+        let arg = self
+            .mir_encoder
+            .encode_operand_expr(&args[1])
+            .with_span(call_site_span)?;
+
+        let expr = match func_proc_name {
+            "core::str::<impl str>::starts_with" => self.encoder.encode_snapshot_str_starts_with(
+                str_ty,
+                vir::Expr::snap_app(string),
+                vir::Expr::snap_app(arg),
+            ),
+            "core::str::<impl str>::is_char_boundary" => self
+                .encoder
+                .encode_snapshot_str_is_char_boundary(str_ty, vir::Expr::snap_app(string), arg),
+            _ => unreachable!("{}", func_proc_name),
+        }
+        .with_span(call_site_span)?;
+
+        let (target_value, mut stmts) = self
+            .encode_pure_function_call_lhs_value(destination, target, location)
+            .with_span(call_site_span)?;
+        let inhaled_expr = vir::Expr::eq_cmp(target_value, expr);
+
+        let (call_stmts, label) =
+            self.encode_pure_function_call_site(location, destination, target, inhaled_expr)?;
+        stmts.extend(call_stmts);
+
+        self.encode_transfer_args_permissions(location, args, &mut stmts, &label, false)?;
+
+        Ok(stmts)
+    }
+
     /// Encode an edge of the MIR graph
     fn encode_edge_block(
         &mut self,
@@ -3150,6 +3552,24 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             .get_absolute_item_name(called_def_id);
         debug!("Encoding non-pure function call '{}' with args {:?} and substs {:?}", full_func_proc_name, mir_args, substs);
 
+        if self.encoder.is_lemma(called_def_id) {
+            return Err(SpannedEncodingError::unsupported(
+                "lemmas are supported only with the unsafe_core_proof flag",
+                call_site_span,
+            ));
+        }
+
+        // The contract of an `async fn` describes the awaited value, so only
+        // calls whose future is awaited immediately can be encoded.
+        if self.encoder.env().query.is_async_fn(called_def_id)
+            && destination.ty(self.mir, self.encoder.env().tcx()).ty.is_impl_trait()
+        {
+            return Err(SpannedEncodingError::unsupported(
+                "the future returned by an `async fn` must be awaited immediately",
+                call_site_span,
+            ));
+        }
+
         // Spans for fake exprs that cannot be encoded in viper
         let mut fake_expr_spans: FxHashMap<Local, Span> = FxHashMap::default();
 
@@ -3237,7 +3657,11 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         let mut type_invs: Vec<vir::Expr> = vec![];
         let mut constant_args = vec![];
 
-        let mut stmts = vec![];
+        let mut stmts = if self.encoder.env().query.is_closure(called_def_id) {
+            vec![]
+        } else {
+            self.encode_call_termination_checks(call_site_span, mir_args, called_def_id, substs)?
+        };
         let mut stmts_after: Vec<vir::Stmt> = vec![];
 
         for (mir_arg, arg, arg_ty, encoded_operand) in operands {
@@ -3395,7 +3819,12 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             pre_mandatory_type_spec,
             pre_invs_spec,
             pre_func_spec,
-        ) = self.encode_precondition_expr(&procedure_contract, substs, fake_expr_spans)?;
+        ) = {
+            let was_encoding_callee_contract = self.encoder.is_encoding_callee_contract.replace(true);
+            let result = self.encode_precondition_expr(&procedure_contract, substs, fake_expr_spans);
+            self.encoder.is_encoding_callee_contract.set(was_encoding_callee_contract);
+            result?
+        };
         let pos = self.register_error(call_site_span, ErrorCtxt::ExhaleMethodPrecondition);
         stmts.push(vir::Stmt::Assert( vir::Assert {
             expr: replace_fake_exprs(pre_func_spec),
@@ -3458,17 +3887,22 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             post_func_spec,
             magic_wands,
             read_transfer,
-        ) = self.encode_postcondition_expr(
-            Some(location),
-            &procedure_contract,
-            &pre_label,
-            &post_label,
-            Some((location, &fake_exprs)),
-            encoded_target.is_none(),
-            loan,
-            false,
-            substs,
-        )?;
+        ) = {
+            let was_encoding_callee_contract = self.encoder.is_encoding_callee_contract.replace(true);
+            let result = self.encode_postcondition_expr(
+                Some(location),
+                &procedure_contract,
+                &pre_label,
+                &post_label,
+                Some((location, &fake_exprs)),
+                encoded_target.is_none(),
+                loan,
+                false,
+                substs,
+            );
+            self.encoder.is_encoding_callee_contract.set(was_encoding_callee_contract);
+            result?
+        };
         // We inhale the magic wand just before applying it because we need
         // a magic wand that depends on the current value of ghost variables.
         let _magic_wands: Vec<_> = magic_wands
@@ -3543,7 +3977,8 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             arg_exprs.push(arg_expr);
         }
 
-        self.encode_specified_pure_function_call(
+        let mut stmts = self.encode_call_termination_checks(call_site_span, args, called_def_id, call_substs)?;
+        stmts.extend(self.encode_specified_pure_function_call(
             location,
             call_site_span,
             args,
@@ -3554,7 +3989,41 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             return_type,
             called_def_id,
             call_substs,
-        )
+        )?);
+        Ok(stmts)
+    }
+
+    /// If the procedure is a pure function, asserts that the termination
+    /// measure decreases at a call that might be recursive.
+    fn encode_call_termination_checks(
+        &self,
+        call_site_span: Span,
+        args: &[mir::Operand<'tcx>],
+        called_def_id: ProcedureDefId,
+        call_substs: SubstsRef<'tcx>,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        if !config::check_pure_function_termination() || !self.encoder.is_pure(self.proc_def_id, None) {
+            return Ok(vec![]);
+        }
+        let encoded_args = args.iter()
+            .map(|arg| self.mir_encoder.encode_operand_expr(arg))
+            .collect::<Result<Vec<_>, _>>()
+            .with_span(call_site_span)?;
+        let conditions = encode_call_termination_conditions(
+            self.encoder,
+            self.mir,
+            self.proc_def_id,
+            called_def_id,
+            call_substs,
+            &encoded_args,
+            call_site_span,
+        )?;
+        Ok(conditions.into_iter().map(|(condition, error_ctxt)| {
+            vir::Stmt::Assert(vir::Assert {
+                expr: condition,
+                position: self.register_error(call_site_span, error_ctxt),
+            })
+        }).collect())
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -4454,6 +4923,56 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
             )?;
             func_spec.push(assertion.set_default_pos(assertion_pos));
         }
+
+        // Encode the frame conditions given by the `modifies` and `reads` clauses
+        if !contract.framed_places.is_empty() {
+            let env = self.encoder.env();
+            let frame_spans = contract
+                .modifies_clauses(env, substs)
+                .into_iter()
+                .chain(contract.reads_clauses(env, substs))
+                .map(|(clause, _)| env.query.get_def_span(clause))
+                .collect::<Vec<_>>();
+            let frame_pos = self.mir_encoder.register_span(MultiSpan::from_spans(frame_spans.clone()));
+            for place in &contract.framed_places {
+                let (place_expr, place_ty, _) = self.encode_generic_place(
+                    contract.def_id, location, *place
+                ).with_span(frame_spans.clone())?;
+                // Places whose values cannot be compared are left
+                // unconstrained. Tell the user once, when encoding the
+                // procedure itself rather than each of its calls.
+                if !self.encoder.supports_snapshot_equality(place_ty).with_span(frame_spans.clone())? {
+                    if function_end {
+                        PrustiError::warning(
+                            format!(
+                                "the frame condition does not guarantee that this place of type \
+                                `{place_ty}` is unchanged, because its values cannot be compared"
+                            ),
+                            MultiSpan::from_spans(frame_spans.clone()),
+                        )
+                        .set_help(
+                            "callers of the procedure cannot assume anything about the value of \
+                            this place after the call",
+                        )
+                        .emit(&self.encoder.env().diagnostic);
+                    }
+                    continue;
+                }
+                let frame = vir::Expr::eq_cmp(
+                    vir::Expr::snap_app(place_expr.clone()).old(pre_label),
+                    vir::Expr::snap_app(place_expr),
+                );
+                let frame = self.wrap_arguments_into_old(
+                    frame,
+                    pre_label,
+                    contract,
+                    &encoded_args,
+                )?;
+                func_spec.push(frame.set_default_pos(frame_pos));
+            }
+            func_spec_spans.extend(frame_spans);
+        }
+
         let postcondition_span = MultiSpan::from_spans(func_spec_spans);
         let func_spec_pos = self.mir_encoder.register_span(postcondition_span.clone());
 
@@ -5776,9 +6295,19 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                             vir::AssignKind::Copy,
                             true
                         )?;
-                        // TODO Encoding of string literals is not yet supported,
-                        // so do not encode an assignment if the RHS is a string
-                        if !is_str(ty) {
+                        // String literals are not stored in memory, so only the
+                        // snapshot of the target is known to be the literal's bytes
+                        if ty.is_str_ref() {
+                            let const_val = self.encoder
+                                .encode_const_expr(ty, expr.literal)
+                                .with_span(span)?;
+                            stmts.push(vir::Stmt::Inhale(vir::Inhale {
+                                expr: vir::Expr::eq_cmp(
+                                    vir::Expr::snap_app(lhs.clone().field(field)),
+                                    const_val,
+                                ),
+                            }));
+                        } else if !is_str(ty) {
                             // Initialize the constant
                             let const_val = self.encoder
                                 .encode_const_expr(ty, expr.literal)
@@ -6097,6 +6626,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         encoded_lhs: vir::Expr,
         ty: ty::Ty<'tcx>,
     ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        if let Some(mir::ProjectionElem::Subslice { .. }) = place.projection.last() {
+            return self.encode_assign_subslice(mir_borrow_kind, place, location, encoded_lhs, ty);
+        }
         let span = self.mir_encoder.get_span_of_location(location);
         let loan = self.polonius_info().get_loan_at_location(location);
         let (vir_assign_kind, array_encode_kind) = match mir_borrow_kind {
@@ -6132,6 +6664,73 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         Ok(stmts)
     }
 
+    /// Borrow of the rest of a slice pattern, e.g. `rest` in `[first, rest @ ..]`. The borrow
+    /// is encoded as a fresh sequence with the contents of the matched range.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn encode_assign_subslice(
+        &mut self,
+        mir_borrow_kind: mir::BorrowKind,
+        place: mir::Place<'tcx>,
+        location: mir::Location,
+        encoded_lhs: vir::Expr,
+        ty: ty::Ty<'tcx>,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        let span = self.mir_encoder.get_span_of_location(location);
+        if mir_borrow_kind != mir::BorrowKind::Shared {
+            return Err(SpannedEncodingError::unsupported(
+                "mutably borrowing the rest of a slice pattern is not supported yet",
+                span,
+            ));
+        }
+        let (place_encoding, ..) = self.mir_encoder.encode_place(place).with_span(span)?;
+        let PlaceEncoding::Subslice { box base, from, to, from_end, rust_base_ty, rust_subslice_ty, .. } = place_encoding else {
+            unreachable!("not a subslice: {:?}", place_encoding)
+        };
+        let (base_seq_expr, mut stmts) = self.postprocess_place_encoding(base, ArrayAccessKind::Shared)
+            .with_span(span)?;
+        let to = if from_end {
+            let base_types = self.encoder.encode_sequence_types(rust_base_ty).with_span(span)?;
+            let base_len = base_types.len(self.encoder, base_seq_expr.clone());
+            vir_expr!{ [base_len] - [vir::Expr::from(to as usize)] }
+        } else {
+            vir::Expr::from(to as usize)
+        };
+
+        let subslice_types = self.encoder.encode_sequence_types(rust_subslice_ty).with_span(span)?;
+        stmts.extend(self.encode_havoc(&encoded_lhs).with_span(span)?);
+        let val_ref_field = self.encoder.encode_value_field(ty).with_span(span)?;
+        let subslice_expr = encoded_lhs.field(val_ref_field);
+        stmts.push(vir_stmt!{ inhale [vir::Expr::FieldAccessPredicate( vir::FieldAccessPredicate {
+            base: box subslice_expr.clone(),
+            permission: vir::PermAmount::Write,
+            position: vir::Position::default(),
+        })]});
+        stmts.push(vir_stmt!{ inhale [vir::Expr::PredicateAccessPredicate( vir::PredicateAccessPredicate {
+            predicate_type: subslice_types.sequence_pred_type,
+            argument: box subslice_expr.clone(),
+            permission: vir::PermAmount::Read,
+            position: vir::Position::default(),
+        })]});
+
+        stmts.extend(
+            self.encode_subsequence_relation(
+                subslice_expr,
+                rust_subslice_ty,
+                base_seq_expr,
+                rust_base_ty,
+                vir::Expr::from(from as usize),
+                to,
+            ).with_span(span)?
+        );
+
+        // Store a label for this state
+        let label = self.cfg_method.get_fresh_label_name();
+        debug!("Current loc {:?} has label {}", location, label);
+        self.label_after_location.insert(location, label.clone());
+        stmts.push(vir::Stmt::label(label));
+        Ok(stmts)
+    }
+
     /// Assignment where the RHS is a cast operation
     /// [encoded_lhs] = [operand] as [dst_ty]
     #[tracing::instrument(level = "trace", skip(self))]
@@ -6249,6 +6848,340 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
         Ok(stmts)
     }
 
+    /// Encodes the creation of a trait object from a reference or a box that
+    /// points to a value of a concrete type. The trait object is abstract;
+    /// only the results of the pure methods of the trait that take just
+    /// `&self` are connected to those of the concrete value.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn encode_assign_trait_object(
+        &mut self,
+        encoded_lhs: vir::Expr,
+        operand: &mir::Operand<'tcx>,
+        ty: ty::Ty<'tcx>,
+        location: mir::Location,
+    ) -> SpannedEncodingResult<Vec<vir::Stmt>> {
+        let span = self.mir_encoder.get_span_of_location(location);
+        let tcx = self.encoder.env().tcx();
+        let mut stmts = Vec::new();
+
+        let label = self.cfg_method.get_fresh_label_name();
+        stmts.push(vir::Stmt::label(label.clone()));
+
+        let object_ty = ty.builtin_deref(true).unwrap().ty;
+        let is_shared = matches!(ty.kind(), ty::TyKind::Ref(_, _, mir::Mutability::Not));
+
+        stmts.extend(self.encode_havoc(&encoded_lhs).with_span(span)?);
+        let object_field = self.encoder.encode_dereference_field(object_ty).with_span(span)?;
+        let object_expr = encoded_lhs.field(object_field);
+        stmts.push(vir_stmt!{ inhale [vir::Expr::FieldAccessPredicate( vir::FieldAccessPredicate {
+            base: box object_expr.clone(),
+            permission: vir::PermAmount::Write,
+            position: vir::Position::default(),
+        })]});
+        let object_perm = vir::Expr::pred_permission(
+            object_expr.clone(),
+            if is_shared { vir::PermAmount::Read } else { vir::PermAmount::Write },
+        ).unwrap();
+        stmts.push(vir_stmt!{ inhale [object_perm] });
+
+        let (rhs_place, rhs_ty) = match operand {
+            mir::Operand::Move(place) | mir::Operand::Copy(place) => {
+                let (rhs_place, rhs_ty, ..) = self.mir_encoder.encode_place(*place).with_span(span)?;
+                (rhs_place.try_into_expr().with_span(span)?, rhs_ty)
+            }
+            mir::Operand::Constant(_) => unreachable!("operand: {:?}", operand),
+        };
+        let concrete_ty = rhs_ty.builtin_deref(true).unwrap().ty;
+        let concrete_field = self.encoder.encode_dereference_field(concrete_ty).with_span(span)?;
+        let concrete_expr = rhs_place.field(concrete_field);
+
+        let ty::TyKind::Dynamic(predicates, ..) = object_ty.kind() else {
+            unreachable!("not a trait object: {:?}", object_ty)
+        };
+        let Some(principal) = predicates.principal() else {
+            return Ok(stmts);
+        };
+        let trait_ref = principal.skip_binder();
+        let object_substs = trait_ref.with_self_ty(tcx, object_ty).substs;
+        let concrete_substs = trait_ref.with_self_ty(tcx, concrete_ty).substs;
+        for item in tcx.associated_items(trait_ref.def_id).in_definition_order() {
+            if item.kind != ty::AssocKind::Fn
+                || !item.fn_has_self_parameter
+                || tcx.generics_of(item.def_id).count() != object_substs.len()
+                || tcx.fn_sig(item.def_id).skip_binder().inputs().skip_binder().len() != 1
+                || !self.encoder.is_pure(item.def_id, Some(object_substs))
+            {
+                continue;
+            }
+            let (concrete_def_id, concrete_substs) = self.encoder.env().query.resolve_method_call(
+                self.proc_def_id,
+                item.def_id,
+                concrete_substs,
+            );
+            if !self.encoder.is_pure(concrete_def_id, Some(concrete_substs)) {
+                continue;
+            }
+            let object_call = self.encode_pure_method_app(
+                item.def_id,
+                object_substs,
+                object_expr.clone(),
+                span,
+            )?;
+            let concrete_call = self.encode_pure_method_app(
+                concrete_def_id,
+                concrete_substs,
+                concrete_expr.clone(),
+                span,
+            )?;
+            stmts.push(vir::Stmt::Inhale( vir::Inhale {
+                expr: vir::Expr::eq_cmp(object_call, concrete_call),
+            }));
+        }
+
+        self.label_after_location.insert(location, label);
+
+        Ok(stmts)
+    }
+
+    /// Encodes an application of a pure method whose only argument is `self`.
+    fn encode_pure_method_app(
+        &self,
+        def_id: ProcedureDefId,
+        substs: SubstsRef<'tcx>,
+        self_expr: vir::Expr,
+        span: Span,
+    ) -> SpannedEncodingResult<vir::Expr> {
+        let (function_name, return_type) = self.encoder
+            .encode_pure_function_use(def_id, self.proc_def_id, substs)
+            .with_span(span)?;
+        let type_arguments = self.encoder.encode_generic_arguments(def_id, substs).with_span(span)?;
+        let tcx = self.encoder.env().tcx();
+        let self_ty = tcx.fn_sig(def_id).subst(tcx, substs).skip_binder().inputs()[0];
+        let formal_arg = vir::LocalVar::new(
+            "x0",
+            self.encoder.encode_snapshot_type(self_ty).with_span(span)?,
+        );
+        Ok(vir::Expr::func_app(
+            function_name,
+            type_arguments,
+            vec![self_expr],
+            vec![formal_arg],
+            return_type,
+            vir::Position::default(),
+        ))
+    }
+
+    /// Checks that the function pointers written by an assignment respect the
+    /// contracts of the places they are written to. A place whose type is a
+    /// function pointer type alias with a specification can only be assigned
+    /// a coerced function item, whose contract is checked, or a function
+    /// pointer with the same contract.
+    fn check_fn_pointer_assignment(
+        &mut self,
+        lhs: mir::Place<'tcx>,
+        rhs: &mir::Rvalue<'tcx>,
+        location: mir::Location,
+    ) -> SpannedEncodingResult<()> {
+        let span = self.mir_encoder.get_span_of_location(location);
+        match rhs {
+            mir::Rvalue::Ref(_, mir::BorrowKind::Mut { .. }, place)
+                if self.fn_pointer_contracts.of_place(*place).is_some() =>
+            {
+                return Err(SpannedEncodingError::unsupported(
+                    "mutably borrowing a function pointer with a specification is not supported",
+                    span,
+                ));
+            }
+            mir::Rvalue::Aggregate(box aggregate, operands) => {
+                for (index, operand) in operands.iter().enumerate() {
+                    let contract = self.fn_pointer_contracts.of_aggregate_operand(aggregate, index);
+                    self.check_fn_pointer_operand(operand, contract, span)?;
+                }
+                return Ok(());
+            }
+            _ => {}
+        }
+        let Some(contract) = self.fn_pointer_contracts.of_place(lhs) else {
+            return Ok(());
+        };
+        match rhs {
+            mir::Rvalue::Cast(
+                mir::CastKind::Pointer(ty::adjustment::PointerCast::ReifyFnPointer),
+                operand,
+                _,
+            )
+            | mir::Rvalue::Use(operand) => {
+                self.check_fn_pointer_operand(operand, Some(contract), span)
+            }
+            mir::Rvalue::CopyForDeref(place) => {
+                self.check_fn_pointer_operand(&mir::Operand::Copy(*place), Some(contract), span)
+            }
+            _ => Err(SpannedEncodingError::unsupported(
+                "only functions and function pointers with the same specification can be \
+                stored in a function pointer with a specification",
+                span,
+            )),
+        }
+    }
+
+    /// Checks that the function pointers passed to and returned by a call
+    /// respect the contracts of the signature of `called_def_id`.
+    fn check_fn_pointer_call(
+        &mut self,
+        called_def_id: ProcedureDefId,
+        args: &[mir::Operand<'tcx>],
+        destination: mir::Place<'tcx>,
+        span: Span,
+    ) -> SpannedEncodingResult<()> {
+        for (index, arg) in args.iter().enumerate() {
+            let contract = self.fn_pointer_contracts.of_argument(called_def_id, index);
+            self.check_fn_pointer_operand(arg, contract, span)?;
+        }
+        if let Some(contract) = self.fn_pointer_contracts.of_place(destination) {
+            if self.fn_pointer_contracts.of_result(called_def_id) != Some(contract) {
+                return Err(SpannedEncodingError::unsupported(
+                    "the result of the call does not have the specification of the \
+                    function pointer it is stored in",
+                    span,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that `operand` can be stored in a place that requires the
+    /// function pointer contract `contract`, if any.
+    fn check_fn_pointer_operand(
+        &mut self,
+        operand: &mir::Operand<'tcx>,
+        contract: Option<ProcedureDefId>,
+        span: Span,
+    ) -> SpannedEncodingResult<()> {
+        let Some(contract) = contract else {
+            return Ok(());
+        };
+        let operand_ty = self.mir_encoder.get_operand_ty(operand);
+        match operand_ty.kind() {
+            ty::TyKind::FnDef(def_id, substs) => {
+                self.encode_fn_pointer_contract_check(*def_id, substs, contract, span)
+            }
+            ty::TyKind::FnPtr(_) if self.fn_pointer_contracts.of_operand(operand) == Some(contract) => {
+                Ok(())
+            }
+            _ => Err(SpannedEncodingError::unsupported(
+                "only functions and function pointers with the same specification can be \
+                stored in a function pointer with a specification",
+                span,
+            )),
+        }
+    }
+
+    /// Encodes the check that the function `def_id` satisfies the contract of
+    /// a function pointer type. The check is added to the method as a
+    /// separate branch that starts from the precondition of the procedure.
+    fn encode_fn_pointer_contract_check(
+        &mut self,
+        def_id: ProcedureDefId,
+        substs: SubstsRef<'tcx>,
+        contract_def_id: ProcedureDefId,
+        span: Span,
+    ) -> SpannedEncodingResult<()> {
+        let tcx = self.encoder.env().tcx();
+        let contract_substs = self.encoder.env().query.identity_substs(contract_def_id);
+        let sig = tcx.fn_sig(contract_def_id).skip_binder().skip_binder();
+        if sig.inputs_and_output.iter().any(|ty| {
+            ty.walk().any(|arg| matches!(arg.unpack(), ty::subst::GenericArgKind::Lifetime(_)))
+        }) {
+            return Err(SpannedEncodingError::unsupported(
+                "function pointer types with specifications cannot have references in their signature",
+                span,
+            ));
+        }
+
+        let mut stmts = vec![vir::Stmt::comment(format!(
+            "Check that {} satisfies the contract of the function pointer type",
+            self.encoder.env().name.get_absolute_item_name(def_id),
+        ))];
+        let mut encoded_args = vec![];
+        for arg_ty in sig.inputs() {
+            let arg_var = self
+                .cfg_method
+                .add_fresh_local_var(self.encoder.encode_type(*arg_ty).with_span(span)?);
+            let arg = vir::Expr::local(arg_var);
+            stmts.extend(self.encode_havoc_and_initialization(&arg).with_span(span)?);
+            encoded_args.push(arg);
+        }
+        let return_var = self
+            .cfg_method
+            .add_fresh_local_var(self.encoder.encode_type(sig.output()).with_span(span)?);
+        let encoded_return = vir::Expr::local(return_var);
+
+        let contract = self
+            .encoder
+            .get_procedure_contract_for_def(contract_def_id, contract_substs)
+            .with_span(span)?;
+        let function_contract = self
+            .encoder
+            .get_procedure_contract_for_def(def_id, substs)
+            .with_span(span)?;
+        let encode_spec = |specs: Vec<(ProcedureDefId, SubstsRef<'tcx>)>,
+                           pre_label: Option<&str>,
+                           encoded_return: Option<&vir::Expr>| {
+            specs
+                .into_iter()
+                .map(|(spec_def_id, spec_substs)| {
+                    self.encoder.encode_assertion(
+                        &spec_def_id,
+                        pre_label,
+                        &encoded_args,
+                        encoded_return,
+                        false,
+                        self.proc_def_id,
+                        spec_substs,
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(|assertions| assertions.into_iter().conjoin())
+        };
+        let env = self.encoder.env();
+        let label = self.cfg_method.get_fresh_label_name();
+        let contract_pre = encode_spec(
+            contract.functional_precondition(env, contract_substs),
+            None,
+            None,
+        )?;
+        let function_pre = encode_spec(
+            function_contract.functional_precondition(env, substs),
+            None,
+            None,
+        )?;
+        let function_post = encode_spec(
+            function_contract.functional_postcondition(env, substs),
+            Some(&label),
+            Some(&encoded_return),
+        )?;
+        let contract_post = encode_spec(
+            contract.functional_postcondition(env, contract_substs),
+            Some(&label),
+            Some(&encoded_return),
+        )?;
+
+        stmts.push(vir::Stmt::Inhale(vir::Inhale { expr: contract_pre }));
+        stmts.push(vir::Stmt::label(label));
+        stmts.push(vir::Stmt::Assert(vir::Assert {
+            expr: function_pre,
+            position: self.register_error(span, ErrorCtxt::AssertFnPointerPreconditionWeakening),
+        }));
+        stmts.extend(self.encode_havoc_and_initialization(&encoded_return).with_span(span)?);
+        stmts.push(vir::Stmt::Inhale(vir::Inhale { expr: function_post }));
+        stmts.push(vir::Stmt::Assert(vir::Assert {
+            expr: contract_post,
+            position: self.register_error(span, ErrorCtxt::AssertFnPointerPostconditionStrengthening),
+        }));
+        self.fn_pointer_contract_checks.push(stmts);
+        Ok(())
+    }
+
     #[tracing::instrument(level = "trace", skip(self))]
     fn encode_assign_sequence_len(
         &mut self,
@@ -6527,6 +7460,7 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
 
             ty::TyKind::Adt(_, _)
             | ty::TyKind::Closure(_, _)
+            | ty::TyKind::FnPtr(_)
             | ty::TyKind::Tuple(_)
             | ty::TyKind::Param(_)
             | ty::TyKind::Array(_, _) => {
@@ -6691,11 +7625,19 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                 }
             }
 
-            mir::AggregateKind::Generator(..) => {
-                return Err(SpannedEncodingError::unsupported(
-                    "construction of generators is not supported",
-                    span
-                ));
+            mir::AggregateKind::Generator(_, substs, _) => {
+                for (field_index, field_ty) in substs.as_generator().upvar_tys().enumerate() {
+                    let operand = &operands[field_index];
+                    let field_name = format!("closure_{field_index}");
+                    let encoded_field = self.encoder
+                        .encode_raw_ref_field(field_name, field_ty)
+                        .with_span(span)?;
+                    stmts.extend(self.encode_assign_operand(
+                        &dst.clone().field(encoded_field),
+                        operand,
+                        location,
+                    )?);
+                }
             }
         }
 
@@ -6926,7 +7868,9 @@ impl<'p, 'v: 'p, 'tcx: 'v> ProcedureEncoder<'p, 'v, 'tcx> {
                     position: vir::Position::default(),
                 }),
                 stmts)
-            }
+            }            PlaceEncoding::Subslice { .. } => error_unsupported!(
+                "the rest of a slice pattern can only be bound by shared reference",
+            ),
         })
     }
 
@@ -41,7 +41,7 @@ pub(super) enum Snapshot {
         uncons: vir::DomainFunc,
         read: vir::DomainFunc,
     },
-    /// Slices
+    /// Slices and strings, which are sequences of bytes
     Slice {
         predicate_type: Type,
         _domain: String,
@@ -53,7 +53,7 @@ pub(super) enum Snapshot {
         /// result Seq[elem_ty]
         slice_helper: vir::FunctionIdentifier,
         cons: vir::DomainFunc,
-        _uncons: vir::DomainFunc,
+        uncons: vir::DomainFunc,
         read: vir::DomainFunc,
         len: vir::DomainFunc,
     },
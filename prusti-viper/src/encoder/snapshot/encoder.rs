@@ -552,6 +552,90 @@ impl SnapshotEncoder {
         }
     }
 
+    /// Encode `s.as_bytes()`, which converts the string snapshot `s` into a
+    /// byte slice snapshot with the same contents.
+    pub fn encode_str_as_bytes<'p, 'v: 'p, 'tcx: 'v>(
+        &mut self,
+        encoder: &'p Encoder<'v, 'tcx>,
+        str_ty: ty::Ty<'tcx>,
+        string: Expr,
+        bytes_ty: ty::Ty<'tcx>,
+    ) -> EncodingResult<Expr> {
+        let uncons =
+            if let Snapshot::Slice { uncons, .. } = self.encode_snapshot(encoder, str_ty)? {
+                uncons
+            } else {
+                error_internal!("called encode_str_as_bytes on non-str-type {:?}", str_ty);
+            };
+        let cons = if let Snapshot::Slice { cons, .. } = self.encode_snapshot(encoder, bytes_ty)? {
+            cons
+        } else {
+            error_internal!(
+                "called encode_str_as_bytes with non-slice-type {:?}",
+                bytes_ty
+            );
+        };
+
+        Ok(cons.apply(vec![uncons.apply(vec![string])]))
+    }
+
+    /// Encode `s.starts_with(pat)` for a string pattern `pat`.
+    pub fn encode_str_starts_with<'p, 'v: 'p, 'tcx: 'v>(
+        &mut self,
+        encoder: &'p Encoder<'v, 'tcx>,
+        str_ty: ty::Ty<'tcx>,
+        string: Expr,
+        pattern: Expr,
+    ) -> EncodingResult<Expr> {
+        let (slice_helper, uncons, len) = if let Snapshot::Slice {
+            slice_helper,
+            uncons,
+            len,
+            ..
+        } = self.encode_snapshot(encoder, str_ty)?
+        {
+            (slice_helper, uncons, len)
+        } else {
+            error_internal!("called encode_str_starts_with on non-str-type {:?}", str_ty);
+        };
+
+        let pattern_len = len.apply(vec![pattern.clone()]);
+        let string_len = len.apply(vec![string.clone()]);
+        let prefix =
+            self.apply_function(&slice_helper, vec![string, 0.into(), pattern_len.clone()]);
+        let pattern_bytes = uncons.apply(vec![pattern]);
+        Ok(vir_expr! { ([pattern_len] <= [string_len]) && ([prefix] == [pattern_bytes]) })
+    }
+
+    /// Encode `s.is_char_boundary(idx)`: the index is the start or the end of
+    /// the string, or a byte that is not a UTF-8 continuation byte.
+    pub fn encode_str_is_char_boundary<'p, 'v: 'p, 'tcx: 'v>(
+        &mut self,
+        encoder: &'p Encoder<'v, 'tcx>,
+        str_ty: ty::Ty<'tcx>,
+        string: Expr,
+        idx: Expr,
+    ) -> EncodingResult<Expr> {
+        let (read, len) =
+            if let Snapshot::Slice { read, len, .. } = self.encode_snapshot(encoder, str_ty)? {
+                (read, len)
+            } else {
+                error_internal!(
+                    "called encode_str_is_char_boundary on non-str-type {:?}",
+                    str_ty
+                );
+            };
+
+        let string_len = len.apply(vec![string.clone()]);
+        let byte = read.apply(vec![string, idx.clone()]);
+        let is_not_continuation =
+            vir_expr! { ([byte] < [Expr::from(0x80)]) || ([Expr::from(0xBF)] < [byte]) };
+        Ok(vir_expr! {
+            (([idx] == [Expr::from(0)]) || ([idx] == [string_len]))
+            || (([idx] < [string_len]) && [is_not_continuation])
+        })
+    }
+
     /// Encodes the snapshot of the given type and returns a VIR type
     /// representing that snapshot.
     pub fn encode_type<'p, 'v: 'p, 'tcx: 'v>(
@@ -1036,11 +1120,12 @@ impl SnapshotEncoder {
                 })
             }
 
-            ty::TyKind::Slice(elem_ty) => {
+            ty::TyKind::Slice(_) | ty::TyKind::Str => {
                 let slice_types = encoder.encode_sequence_types(ty)?;
+                let elem_ty = slice_types.elem_ty_rs;
                 let domain_name = format!("Snap${}", &slice_types.sequence_pred_type.name());
                 let slice_snap_ty = slice_types.sequence_pred_type.convert_to_snapshot();
-                let elem_snap_ty = self.encode_type(encoder, *elem_ty)?;
+                let elem_snap_ty = self.encode_type(encoder, elem_ty)?;
                 let seq_type = Type::Seq(vir::SeqType {
                     typ: box elem_snap_ty.clone(),
                 });
@@ -1084,6 +1169,17 @@ impl SnapshotEncoder {
                     domain_name: domain_name.clone(),
                 };
 
+                // Only the snapshots of strings in memory are known to be valid UTF-8,
+                // because the snapshot of a subslice does not need to be a string.
+                let valid_utf8 = ty.is_str().then(|| vir::DomainFunc {
+                    name: format!("valid_utf8${domain_name}$"),
+                    type_arguments: Vec::new(),
+                    formal_args: vec![vir_local! { self: {slice_snap_ty.clone()} }],
+                    return_type: Type::Bool,
+                    unique: false,
+                    domain_name: domain_name.clone(),
+                });
+
                 let slice_len = slice_types.len(encoder, arg_expr.clone());
                 let slice_collect_func = self.encode_seq_collect_func(
                     slice_types.sequence_pred_type.clone(),
@@ -1117,10 +1213,10 @@ impl SnapshotEncoder {
                     vir_expr! { forall i: Int :: { [read_call] } { [lookup_call] } :: ([indices] ==> ([read_call] == [lookup_call])) }
                 };
 
-                let snap_len = len.apply(vec![result_expr]);
+                let snap_len = len.apply(vec![result_expr.clone()]);
                 let snap_len_eq_call_len = vir_expr! { [slice_len] == [snap_len] };
 
-                let snap_func = vir::Function {
+                let mut snap_func = vir::Function {
                     name: SNAP_FUNC_NAME.to_string(),
                     type_arguments: vec![slice_snap_ty.clone()],
                     formal_args: vec![arg_self],
@@ -1144,6 +1240,13 @@ impl SnapshotEncoder {
                     ],
                     body: Some(snap_body),
                 };
+                if let Some(valid_utf8) = &valid_utf8 {
+                    snap_func.posts.push(Expr::InhaleExhale(vir::InhaleExhale {
+                        inhale_expr: box valid_utf8.apply(vec![result_expr]),
+                        exhale_expr: box true.into(),
+                        position: vir::Position::default(),
+                    }));
+                }
 
                 let slice_helper = self.encode_slice_helper(
                     slice_snap_ty.clone(),
@@ -1269,7 +1372,7 @@ impl SnapshotEncoder {
 
                 // TODO: ParamEnv::empty() should probably be tyctxt.param_env(def_id_of_method)
                 let ty_size_bytes = tcx
-                    .layout_of(ParamEnv::empty().and(*elem_ty))
+                    .layout_of(ParamEnv::empty().and(elem_ty))
                     .map(|layout| layout.layout.size().bytes())
                     .unwrap_or(0);
                 let len_usize = {
@@ -1306,6 +1409,17 @@ impl SnapshotEncoder {
                     type_vars: vec![],
                 };
 
+                if let Some(valid_utf8) = valid_utf8 {
+                    domain.axioms.extend(Self::encode_utf8_validity_axioms(
+                        &domain_name,
+                        slice_snap_ty.clone(),
+                        &valid_utf8,
+                        &read,
+                        &len,
+                    ));
+                    domain.functions.push(valid_utf8);
+                }
+
                 // encode type validity axiom for slice element
                 // TODO: encode type invariants rather than just integer bounds
                 match elem_ty.kind() {
@@ -1325,7 +1439,7 @@ impl SnapshotEncoder {
                                 vec![self_local, idx],
                                 vec![vir::Trigger::new(vec![read_call.clone()])],
                                 encoder
-                                    .encode_type_bounds(&read_call, *elem_ty)
+                                    .encode_type_bounds(&read_call, elem_ty)
                                     .into_iter()
                                     .conjoin(),
                             ),
@@ -1343,7 +1457,7 @@ impl SnapshotEncoder {
                     _slice_collect_func: self.insert_function(slice_collect_func),
                     slice_helper: self.insert_function(slice_helper),
                     cons,
-                    _uncons: uncons,
+                    uncons,
                     read,
                     len,
                 })
@@ -1589,6 +1703,94 @@ impl SnapshotEncoder {
         }
     }
 
+    /// Encodes necessary conditions for a string snapshot to be valid UTF-8:
+    /// each byte can occur in UTF-8, every leading byte is followed by as
+    /// many continuation bytes as its encoded character needs, and every
+    /// continuation byte follows a non-ASCII byte.
+    fn encode_utf8_validity_axioms(
+        domain_name: &str,
+        str_snap_ty: Type,
+        valid_utf8: &vir::DomainFunc,
+        read: &vir::DomainFunc,
+        len: &vir::DomainFunc,
+    ) -> Vec<vir::DomainAxiom> {
+        let is_continuation = |byte: &Expr| vir_expr! { ([Expr::from(0x80)] <= [byte]) && ([byte] <= [Expr::from(0xBF)]) };
+        // The number of bytes of the character that starts with `byte`.
+        let char_width = |byte: &Expr| {
+            Expr::ite(
+                vir_expr! { [byte] >= [Expr::from(0xF0)] },
+                4.into(),
+                Expr::ite(
+                    vir_expr! { [byte] >= [Expr::from(0xE0)] },
+                    3.into(),
+                    Expr::ite(
+                        vir_expr! { [byte] >= [Expr::from(0xC0)] },
+                        2.into(),
+                        1.into(),
+                    ),
+                ),
+            )
+        };
+
+        let self_local = vir::LocalVar::new("self", str_snap_ty);
+        let self_expr = Expr::local(self_local.clone());
+        let i = vir_local! { i: Int };
+        let i_expr: Expr = i.clone().into();
+        let j = vir_local! { j: Int };
+        let j_expr: Expr = j.clone().into();
+        let valid_call = valid_utf8.apply(vec![self_expr.clone()]);
+        let len_call = len.apply(vec![self_expr.clone()]);
+        let read_i = read.apply(vec![self_expr.clone(), i_expr.clone()]);
+        let read_j = read.apply(vec![self_expr, j_expr.clone()]);
+
+        let bytes = {
+            let allowed_byte = vir_expr! {
+                ([read_i] < [Expr::from(0xC0)])
+                || (([Expr::from(0xC2)] <= [read_i]) && ([read_i] <= [Expr::from(0xF4)]))
+            };
+            let indices = vir_expr! { ([Expr::from(0)] <= [i_expr]) && ([i_expr] < [len_call]) };
+            let body = vir_expr! {
+                [allowed_byte]
+                && (([is_continuation(&read_i)] ==> ([Expr::from(0)] < [i_expr]))
+                && (([i_expr] + [char_width(&read_i)]) <= [len_call]))
+            };
+            vir::DomainAxiom {
+                comment: None,
+                name: format!("{domain_name}$valid_utf8_bytes"),
+                expr: Expr::forall(
+                    vec![self_local.clone(), i.clone()],
+                    vec![vir::Trigger::new(vec![valid_call.clone(), read_i.clone()])],
+                    vir_expr! { ([valid_call] && [indices]) ==> [body] },
+                ),
+                domain_name: domain_name.to_string(),
+            }
+        };
+
+        let sequences = {
+            let indices = vir_expr! {
+                ([Expr::from(0)] <= [i_expr])
+                && (([i_expr] < [j_expr]) && ([j_expr] < [len_call]))
+            };
+            let body = vir_expr! {
+                (([j_expr] < ([i_expr] + [char_width(&read_i)])) ==> [is_continuation(&read_j)])
+                && ((([j_expr] == ([i_expr] + [Expr::from(1)])) && [is_continuation(&read_j)])
+                    ==> ([Expr::from(0x80)] <= [read_i]))
+            };
+            vir::DomainAxiom {
+                comment: None,
+                name: format!("{domain_name}$valid_utf8_sequences"),
+                expr: Expr::forall(
+                    vec![self_local, i, j],
+                    vec![vir::Trigger::new(vec![valid_call.clone(), read_i, read_j])],
+                    vir_expr! { ([valid_call] && [indices]) ==> [body] },
+                ),
+                domain_name: domain_name.to_string(),
+            }
+        };
+
+        vec![bytes, sequences]
+    }
+
     fn encode_abstract(&mut self, predicate_type: &Type) -> EncodingResult<Snapshot> {
         let domain_name = format!("Snap${}", predicate_type.name());
         let snapshot_type = predicate_type.convert_to_snapshot();
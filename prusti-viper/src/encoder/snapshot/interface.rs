@@ -71,6 +71,24 @@ pub(crate) trait SnapshotEncoderInterface<'tcx> {
         lo: vir_poly::Expr,
         hi: vir_poly::Expr,
     ) -> EncodingResult<vir_poly::Expr>;
+    fn encode_snapshot_str_as_bytes(
+        &self,
+        str_ty: ty::Ty<'tcx>,
+        string: vir_poly::Expr,
+        bytes_ty: ty::Ty<'tcx>,
+    ) -> EncodingResult<vir_poly::Expr>;
+    fn encode_snapshot_str_starts_with(
+        &self,
+        str_ty: ty::Ty<'tcx>,
+        string: vir_poly::Expr,
+        pattern: vir_poly::Expr,
+    ) -> EncodingResult<vir_poly::Expr>;
+    fn encode_snapshot_str_is_char_boundary(
+        &self,
+        str_ty: ty::Ty<'tcx>,
+        string: vir_poly::Expr,
+        idx: vir_poly::Expr,
+    ) -> EncodingResult<vir_poly::Expr>;
     fn supports_snapshot_equality(&self, ty: ty::Ty<'tcx>) -> EncodingResult<bool>;
     fn is_quantifiable(&self, ty: ty::Ty<'tcx>) -> EncodingResult<bool>;
 }
@@ -231,6 +249,42 @@ impl<'v, 'tcx: 'v> SnapshotEncoderInterface<'tcx> for super::super::Encoder<'v,
             .encode_slicing(self, base_ty, base, slice_ty, lo, hi)
     }
 
+    fn encode_snapshot_str_as_bytes(
+        &self,
+        str_ty: ty::Ty<'tcx>,
+        string: vir_poly::Expr,
+        bytes_ty: ty::Ty<'tcx>,
+    ) -> EncodingResult<vir_poly::Expr> {
+        self.snapshot_encoder_state
+            .encoder
+            .borrow_mut()
+            .encode_str_as_bytes(self, str_ty, string, bytes_ty)
+    }
+
+    fn encode_snapshot_str_starts_with(
+        &self,
+        str_ty: ty::Ty<'tcx>,
+        string: vir_poly::Expr,
+        pattern: vir_poly::Expr,
+    ) -> EncodingResult<vir_poly::Expr> {
+        self.snapshot_encoder_state
+            .encoder
+            .borrow_mut()
+            .encode_str_starts_with(self, str_ty, string, pattern)
+    }
+
+    fn encode_snapshot_str_is_char_boundary(
+        &self,
+        str_ty: ty::Ty<'tcx>,
+        string: vir_poly::Expr,
+        idx: vir_poly::Expr,
+    ) -> EncodingResult<vir_poly::Expr> {
+        self.snapshot_encoder_state
+            .encoder
+            .borrow_mut()
+            .encode_str_is_char_boundary(self, str_ty, string, idx)
+    }
+
     fn supports_snapshot_equality(&self, ty: ty::Ty<'tcx>) -> EncodingResult<bool> {
         self.snapshot_encoder_state
             .encoder
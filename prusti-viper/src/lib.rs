@@ -25,4 +25,5 @@
 
 pub mod encoder;
 mod utils;
+pub mod verification_report;
 pub mod verifier;
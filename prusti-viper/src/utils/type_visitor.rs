@@ -6,7 +6,7 @@
 
 use prusti_rustc_interface::hir::Mutability;
 use prusti_rustc_interface::middle::ty::{
-    AdtDef, FieldDef, List, ParamTy, Region, AliasKind, AliasTy, Ty, TyCtxt,
+    AdtDef, FieldDef, List, ParamTy, PolyFnSig, Region, AliasKind, AliasTy, Ty, TyCtxt,
     TypeFlags, TyKind, IntTy, UintTy, FloatTy, VariantDef, subst::SubstsRef, Const
 };
 use prusti_rustc_interface::hir::def_id::DefId;
@@ -69,6 +69,9 @@ pub trait TypeVisitor<'tcx>: Sized {
             TyKind::FnDef(def_id, substs) => {
                 self.visit_fndef(def_id, substs)
             }
+            TyKind::FnPtr(sig) => {
+                self.visit_fnptr(sig)
+            }
             TyKind::Array(ty, len) => {
                 self.visit_array(ty, len)
             }
@@ -207,6 +210,14 @@ pub trait TypeVisitor<'tcx>: Sized {
         walk_fndef(self, def_id, substs)
     }
 
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn visit_fnptr(
+        &mut self,
+        _sig: PolyFnSig<'tcx>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     #[tracing::instrument(level = "trace", skip(self))]
     fn visit_array(
         &mut self,
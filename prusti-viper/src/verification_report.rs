@@ -0,0 +1,437 @@
+// © 2023, ETH Zurich
+//
+// This Source Code Form is subject to the terms of the Mozilla Public
+// License, v. 2.0. If a copy of the MPL was not distributed with this
+// file, You can obtain one at http://mozilla.org/MPL/2.0/.
+
+//! A machine-readable report of the verification outcome of each item, which
+//! is written when the `REPORT_FILE` configuration flag is set. The report is
+//! emitted both in a Prusti-specific JSON format and in the SARIF format.
+
+use crate::encoder::{
+    errors::{EncodingErrorKind, ErrorCtxt},
+    ProcedureEncodingSummary,
+};
+use prusti_interface::{data::ProcedureDefId, environment::Environment, PrustiError};
+use prusti_rustc_interface::span::{source_map::SourceMap, Span};
+use rustc_hash::FxHashMap;
+use std::{fs::File, io, path::Path, time::Duration};
+
+/// The verification outcome of an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ItemStatus {
+    /// The item verified.
+    Verified,
+    /// The verification of the item reported errors.
+    Failed,
+    /// The item is marked as trusted, so it was not verified.
+    Trusted,
+    /// The item uses unsupported features, so it was not verified.
+    SkippedUnsupported,
+    /// The verification of the item did not finish in time.
+    TimedOut,
+}
+
+/// The kind of an error reported for an item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ErrorKind {
+    Verification,
+    Unsupported,
+    InvalidSpecification,
+    Internal,
+    Timeout,
+}
+
+impl ErrorKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            ErrorKind::Verification => "verification",
+            ErrorKind::Unsupported => "unsupported",
+            ErrorKind::InvalidSpecification => "invalid-specification",
+            ErrorKind::Internal => "internal",
+            ErrorKind::Timeout => "timeout",
+        }
+    }
+}
+
+/// A source code region, with 1-based lines and columns.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct SourceSpan {
+    pub file: String,
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl SourceSpan {
+    fn from_span(codemap: &SourceMap, span: Span) -> Option<Self> {
+        if span.is_dummy() {
+            return None;
+        }
+        let start = codemap.lookup_char_pos(span.lo());
+        let end = codemap.lookup_char_pos(span.hi());
+        Some(SourceSpan {
+            file: start.file.name.prefer_local().to_string(),
+            start_line: start.line,
+            start_column: start.col.0 + 1,
+            end_line: end.line,
+            end_column: end.col.0 + 1,
+        })
+    }
+}
+
+/// An error reported for an item.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ErrorReport {
+    pub kind: ErrorKind,
+    pub message: String,
+    /// The context in which a verification error was raised.
+    pub error_ctxt: Option<String>,
+    pub span: Option<SourceSpan>,
+}
+
+/// The verification outcome of a single item of `VerificationTask::procedures`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ItemReport {
+    pub name: String,
+    pub def_path: String,
+    pub span: Option<SourceSpan>,
+    pub status: ItemStatus,
+    /// The time spent encoding and verifying the item, in milliseconds.
+    pub duration_ms: u128,
+    pub errors: Vec<ErrorReport>,
+}
+
+/// The verification outcome of all items of a crate.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct VerificationReport {
+    pub items: Vec<ItemReport>,
+}
+
+impl VerificationReport {
+    /// Write the report in JSON to `path` and in SARIF to `path` with the
+    /// `sarif` extension.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let sarif_path = path.with_extension("sarif");
+        let json_path = if sarif_path == path {
+            path.with_extension("json")
+        } else {
+            path.to_path_buf()
+        };
+        serde_json::to_writer_pretty(File::create(json_path)?, self)?;
+        serde_json::to_writer_pretty(File::create(sarif_path)?, &self.to_sarif())?;
+        Ok(())
+    }
+
+    /// Convert the report to a SARIF 2.1.0 log. Every error is reported as a
+    /// result; the items themselves are listed in the properties of the run.
+    pub fn to_sarif(&self) -> serde_json::Value {
+        let rules: Vec<_> = [
+            ErrorKind::Verification,
+            ErrorKind::Unsupported,
+            ErrorKind::InvalidSpecification,
+            ErrorKind::Internal,
+            ErrorKind::Timeout,
+        ]
+        .into_iter()
+        .map(|kind| serde_json::json!({ "id": kind.as_str() }))
+        .collect();
+        let results: Vec<_> = self
+            .items
+            .iter()
+            .flat_map(|item| {
+                item.errors.iter().map(move |error| {
+                    let locations: Vec<_> = error
+                        .span
+                        .iter()
+                        .chain(item.span.iter())
+                        .take(1)
+                        .map(|span| {
+                            serde_json::json!({
+                                "physicalLocation": {
+                                    "artifactLocation": { "uri": span.file },
+                                    "region": {
+                                        "startLine": span.start_line,
+                                        "startColumn": span.start_column,
+                                        "endLine": span.end_line,
+                                        "endColumn": span.end_column,
+                                    },
+                                },
+                            })
+                        })
+                        .collect();
+                    serde_json::json!({
+                        "ruleId": error.kind.as_str(),
+                        "level": "error",
+                        "message": { "text": error.message },
+                        "locations": locations,
+                        "properties": {
+                            "item": item.def_path,
+                            "status": item.status,
+                            "errorCtxt": error.error_ctxt,
+                        },
+                    })
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "Prusti",
+                        "informationUri": "https://github.com/viperproject/prusti-dev",
+                        "rules": rules,
+                    },
+                },
+                "results": results,
+                "properties": { "items": self.items },
+            }],
+        })
+    }
+}
+
+/// Collects the outcome of the verification of each procedure while the
+/// procedures are encoded and verified.
+pub(crate) struct ReportCollector<'tcx> {
+    codemap: &'tcx SourceMap,
+    items: Vec<ItemReport>,
+    durations: Vec<Duration>,
+    item_indices: FxHashMap<ProcedureDefId, usize>,
+    /// Maps the names of the programs generated for a procedure to the index
+    /// of its item.
+    program_items: FxHashMap<String, usize>,
+}
+
+impl<'tcx> ReportCollector<'tcx> {
+    pub(crate) fn new(env: &Environment<'tcx>, procedures: &[ProcedureDefId]) -> Self {
+        let codemap = env.query.codemap();
+        let mut collector = ReportCollector {
+            codemap,
+            items: Vec::new(),
+            durations: Vec::new(),
+            item_indices: FxHashMap::default(),
+            program_items: FxHashMap::default(),
+        };
+        for &proc_id in procedures {
+            let index = collector.items.len();
+            collector.items.push(ItemReport {
+                name: env.name.get_absolute_item_name(proc_id),
+                def_path: env.name.get_item_def_path(proc_id),
+                span: SourceSpan::from_span(codemap, env.query.get_def_span(proc_id)),
+                status: ItemStatus::Verified,
+                duration_ms: 0,
+                errors: Vec::new(),
+            });
+            collector.durations.push(Duration::ZERO);
+            collector.item_indices.insert(proc_id, index);
+            // The legacy encoding names programs by the unique item name, the
+            // core proof encoding by the absolute item name.
+            collector
+                .program_items
+                .insert(env.name.get_unique_item_name(proc_id), index);
+            collector
+                .program_items
+                .insert(env.name.get_absolute_item_name(proc_id), index);
+        }
+        collector
+    }
+
+    fn add_error(&mut self, index: usize, kind: ErrorKind, error: ErrorReport) {
+        let item = &mut self.items[index];
+        item.status = match (item.status, kind) {
+            (ItemStatus::Failed, _) => ItemStatus::Failed,
+            (_, ErrorKind::Unsupported) => ItemStatus::SkippedUnsupported,
+            (_, ErrorKind::Timeout) => ItemStatus::TimedOut,
+            _ => ItemStatus::Failed,
+        };
+        item.errors.push(error);
+    }
+
+    fn error_report(
+        &self,
+        kind: ErrorKind,
+        prusti_error: &PrustiError,
+        error_ctxt: Option<&ErrorCtxt>,
+    ) -> ErrorReport {
+        ErrorReport {
+            kind,
+            message: prusti_error.message().to_string(),
+            error_ctxt: error_ctxt.map(|error_ctxt| format!("{error_ctxt:?}")),
+            span: prusti_error
+                .span()
+                .primary_span()
+                .and_then(|span| SourceSpan::from_span(self.codemap, span)),
+        }
+    }
+
+    /// Record the time spent encoding the procedures and the encoding errors
+    /// that were reported.
+    pub(crate) fn record_encoding(&mut self, summaries: Vec<ProcedureEncodingSummary>) {
+        for summary in summaries {
+            let Some(&index) = self.item_indices.get(&summary.def_id) else {
+                continue;
+            };
+            self.durations[index] += summary.duration;
+            if summary.is_trusted {
+                self.items[index].status = ItemStatus::Trusted;
+            }
+            for encoding_error in summary.encoding_errors {
+                let kind = match encoding_error.kind() {
+                    EncodingErrorKind::Unsupported(_) => ErrorKind::Unsupported,
+                    EncodingErrorKind::Incorrect(_) => ErrorKind::InvalidSpecification,
+                    EncodingErrorKind::Internal(_) => ErrorKind::Internal,
+                };
+                let prusti_error: PrustiError = encoding_error.into();
+                let error = self.error_report(kind, &prusti_error, None);
+                self.add_error(index, kind, error);
+            }
+        }
+    }
+
+    /// Record the time spent verifying a program.
+    pub(crate) fn record_verification(&mut self, program_name: &str, duration: Duration) {
+        if let Some(&index) = self.program_items.get(program_name) {
+            self.durations[index] += duration;
+        }
+    }
+
+    /// Record an error reported by the verifier. The error is attributed to
+    /// the procedure in which it was raised if known, otherwise to the
+    /// procedure of the program.
+    pub(crate) fn record_verification_error(
+        &mut self,
+        program_name: &str,
+        def_id: Option<ProcedureDefId>,
+        prusti_error: &PrustiError,
+        error_ctxt: Option<&ErrorCtxt>,
+    ) {
+        let index = def_id
+            .and_then(|def_id| self.item_indices.get(&def_id))
+            .or_else(|| self.program_items.get(program_name))
+            .copied();
+        if let Some(index) = index {
+            let kind = if error_ctxt.is_some() {
+                ErrorKind::Verification
+            } else {
+                ErrorKind::Internal
+            };
+            let error = self.error_report(kind, prusti_error, error_ctxt);
+            self.add_error(index, kind, error);
+        }
+    }
+
+    /// Record that the verification of a program exceeded its time budget.
+    pub(crate) fn record_timeout(&mut self, program_name: &str, prusti_error: &PrustiError) {
+        if let Some(&index) = self.program_items.get(program_name) {
+            let error = self.error_report(ErrorKind::Timeout, prusti_error, None);
+            self.add_error(index, ErrorKind::Timeout, error);
+        }
+    }
+
+    pub(crate) fn finish(mut self) -> VerificationReport {
+        for (item, duration) in self.items.iter_mut().zip(self.durations) {
+            item.duration_ms = duration.as_millis();
+        }
+        VerificationReport { items: self.items }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(line: usize) -> SourceSpan {
+        SourceSpan {
+            file: "src/main.rs".to_string(),
+            start_line: line,
+            start_column: 5,
+            end_line: line,
+            end_column: 20,
+        }
+    }
+
+    fn report() -> VerificationReport {
+        VerificationReport {
+            items: vec![
+                ItemReport {
+                    name: "main::foo".to_string(),
+                    def_path: "main::foo".to_string(),
+                    span: Some(span(3)),
+                    status: ItemStatus::Failed,
+                    duration_ms: 42,
+                    errors: vec![ErrorReport {
+                        kind: ErrorKind::Verification,
+                        message: "[Prusti: verification error] postcondition might not hold."
+                            .to_string(),
+                        error_ctxt: Some("ExhaleMethodPostcondition".to_string()),
+                        span: Some(span(2)),
+                    }],
+                },
+                ItemReport {
+                    name: "main::bar".to_string(),
+                    def_path: "main::bar".to_string(),
+                    span: None,
+                    status: ItemStatus::SkippedUnsupported,
+                    duration_ms: 0,
+                    errors: vec![],
+                },
+                ItemReport {
+                    name: "main::baz".to_string(),
+                    def_path: "main::baz".to_string(),
+                    span: Some(span(7)),
+                    status: ItemStatus::TimedOut,
+                    duration_ms: 5000,
+                    errors: vec![ErrorReport {
+                        kind: ErrorKind::Timeout,
+                        message: "[Prusti: verification timeout] the verification of main::baz \
+                            did not finish within 5 second(s)"
+                            .to_string(),
+                        error_ctxt: None,
+                        span: None,
+                    }],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn json_uses_kebab_case_statuses() {
+        let json = serde_json::to_value(report()).unwrap();
+        assert_eq!(json["items"][0]["status"], "failed");
+        assert_eq!(json["items"][1]["status"], "skipped-unsupported");
+        assert_eq!(json["items"][2]["status"], "timed-out");
+        assert_eq!(json["items"][0]["errors"][0]["kind"], "verification");
+        assert_eq!(json["items"][0]["duration_ms"], 42);
+    }
+
+    #[test]
+    fn sarif_contains_a_result_per_error() {
+        let sarif = report().to_sarif();
+        assert_eq!(sarif["version"], "2.1.0");
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["ruleId"], "verification");
+        assert_eq!(
+            results[0]["properties"]["errorCtxt"],
+            "ExhaleMethodPostcondition"
+        );
+        let region = &results[0]["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(region["startLine"], 2);
+        assert_eq!(region["startColumn"], 5);
+    }
+
+    #[test]
+    fn sarif_reports_timeouts_at_the_item() {
+        let sarif = report().to_sarif();
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results[1]["ruleId"], "timeout");
+        assert_eq!(results[1]["properties"]["status"], "timed-out");
+        let region = &results[1]["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(region["startLine"], 7);
+    }
+}
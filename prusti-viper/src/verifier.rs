@@ -4,20 +4,23 @@
 // License, v. 2.0. If a copy of the MPL was not distributed with this
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
-use crate::encoder::{
-    counterexamples::{counterexample_translation, counterexample_translation_refactored},
-    Encoder,
+use crate::{
+    encoder::{
+        counterexamples::{counterexample_translation, counterexample_translation_refactored},
+        Encoder,
+    },
+    verification_report::ReportCollector,
 };
 use ::log::{debug, error, info};
 use once_cell::sync::Lazy;
 use prusti_common::{
     config,
     report::log,
-    vir::{optimizations::optimize_program, program::Program},
+    vir::{optimizations::optimize_program, program::Program, ToSilver},
     Stopwatch,
 };
 use prusti_interface::{
-    data::{VerificationResult, VerificationTask},
+    data::{ProcedureDefId, VerificationResult, VerificationTask},
     environment::Environment,
     specs::typed,
     PrustiError,
@@ -27,6 +30,8 @@ use prusti_server::{
     process_verification_request, spawn_server_thread, tokio::runtime::Builder, PrustiClient,
     VerificationRequest, ViperBackendConfig,
 };
+use rustc_hash::FxHashMap;
+use std::path::Path;
 use viper::{self, PersistentCache, Viper};
 use vir_crate::common::check_mode::CheckMode;
 
@@ -38,6 +43,11 @@ where
 {
     env: &'v Environment<'tcx>,
     encoder: Encoder<'v, 'tcx>,
+    /// Collects the outcome of each procedure if `report_file` is set.
+    report: Option<ReportCollector<'tcx>>,
+    /// Maps the names of the programs generated for a procedure to the
+    /// procedure.
+    program_procedures: FxHashMap<String, ProcedureDefId>,
 }
 
 impl<'v, 'tcx> Verifier<'v, 'tcx> {
@@ -45,18 +55,74 @@ impl<'v, 'tcx> Verifier<'v, 'tcx> {
         Verifier {
             env,
             encoder: Encoder::new(env, def_spec),
+            report: None,
+            program_procedures: FxHashMap::default(),
         }
     }
 
     #[tracing::instrument(name = "prusti_viper::verify", level = "info", skip(self))]
     pub fn verify(&mut self, task: &VerificationTask<'tcx>) -> VerificationResult {
-        let mut stopwatch = Stopwatch::start("prusti-viper", "encoding to Viper");
-
         // Dump the configuration
         if config::dump_debug_info() {
             log::report("config", "prusti", config::dump());
         }
 
+        self.queue_encoding(task);
+        for &proc_id in &task.procedures {
+            // The legacy encoding names programs by the unique item name, the
+            // core proof encoding by the absolute item name.
+            self.program_procedures
+                .insert(self.env.name.get_unique_item_name(proc_id), proc_id);
+            self.program_procedures
+                .insert(self.env.name.get_absolute_item_name(proc_id), proc_id);
+        }
+        if config::report_file().is_some() {
+            self.report = Some(ReportCollector::new(self.env, &task.procedures));
+        }
+
+        let mut result = if config::pipelined_verification() {
+            self.encode_and_verify_pipelined()
+        } else {
+            self.encode_and_verify()
+        };
+
+        if self.encoder.count_encoding_errors() != 0 {
+            result = VerificationResult::Failure;
+        }
+
+        if let (Some(report), Some(report_file)) = (self.report.take(), config::report_file()) {
+            if let Err(error) = report.finish().write(Path::new(&report_file)) {
+                PrustiError::warning(
+                    format!("failed to write the verification report to {report_file}: {error}"),
+                    DUMMY_SP.into(),
+                )
+                .emit(&self.env.diagnostic);
+            }
+        }
+
+        result
+    }
+
+    /// Encode the items of `task` and dump the resulting programs in the
+    /// textual Silver syntax, without verifying them. This does not need a JVM
+    /// and is used when both `no_verify` and `dump_viper_program` are enabled.
+    pub fn dump_programs(&mut self, task: &VerificationTask<'tcx>) {
+        self.queue_encoding(task);
+        let mut stopwatch = Stopwatch::start("prusti-viper", "encoding to Viper");
+        self.encoder.process_encoding_queue();
+        let programs = self.take_encoded_programs();
+        stopwatch.start_next("dumping Viper programs");
+        let rust_program_name = rust_program_name(self.env);
+        for mut program in programs {
+            program.set_name(format!("{rust_program_name}_{}", program.get_name()));
+            let namespace = "viper_program";
+            let filename = format!("{}.vpr", program.get_name_with_check_mode());
+            info!("Dumping Viper program to '{}/{}'", namespace, filename);
+            log::report(namespace, filename, program.to_silver());
+        }
+    }
+
+    fn queue_encoding(&mut self, task: &VerificationTask<'tcx>) {
         for &proc_id in &task.procedures {
             let proc_name = self.env.name.get_absolute_item_name(proc_id);
             let proc_def_path = self.env.name.get_item_def_path(proc_id);
@@ -72,18 +138,112 @@ impl<'v, 'tcx> Verifier<'v, 'tcx> {
             // FIXME: Use the loop above.
             self.encoder.queue_type_encoding(type_id);
         }
+    }
+
+    /// Encode all procedures of the crate and only then verify the resulting
+    /// programs.
+    fn encode_and_verify(&mut self) -> VerificationResult {
+        let mut stopwatch = Stopwatch::start("prusti-viper", "encoding to Viper");
         self.encoder.process_encoding_queue();
+        self.record_encoding();
 
-        let encoding_errors_count = self.encoder.count_encoding_errors();
+        if config::simplify_encoding() {
+            stopwatch.start_next("optimizing Viper program");
+        }
+        let programs = self.take_encoded_programs();
 
+        stopwatch.start_next("verifying Viper program");
+        let program_flags = self.program_flags();
+        let report = &mut self.report;
+        let verification_results =
+            with_program_verifier(self.env, &program_flags, |verify_program| {
+                programs
+                    .into_iter()
+                    .map(|program| verify_and_record(report, verify_program, program))
+                    .collect()
+            });
+        stopwatch.finish();
+
+        self.report_verification_results(verification_results)
+    }
+
+    /// Verify the programs of each procedure as soon as its encoding is
+    /// finished, reporting the verification errors while the remaining
+    /// procedures are still being encoded.
+    fn encode_and_verify_pipelined(&mut self) -> VerificationResult {
+        let env = self.env;
+        let program_flags = self.program_flags();
+        with_program_verifier(env, &program_flags, |verify_program| {
+            let mut result = VerificationResult::Success;
+            self.encoder.initialize_encoding();
+            loop {
+                let mut stopwatch = Stopwatch::start("prusti-viper", "encoding to Viper");
+                if !self.encoder.process_next_encoding_task() {
+                    stopwatch.finish();
+                    break;
+                }
+                self.record_encoding();
+                if config::simplify_encoding() {
+                    stopwatch.start_next("optimizing Viper program");
+                }
+                let programs = self.take_encoded_programs();
+                stopwatch.start_next("verifying Viper program");
+                let report = &mut self.report;
+                let verification_results = programs
+                    .into_iter()
+                    .map(|program| verify_and_record(report, verify_program, program))
+                    .collect();
+                stopwatch.finish();
+                if self.report_verification_results(verification_results)
+                    == VerificationResult::Failure
+                {
+                    result = VerificationResult::Failure;
+                }
+            }
+            result
+        })
+    }
+
+    /// The configuration flags overridden for the procedure of each program,
+    /// by program name.
+    fn program_flags(&self) -> FxHashMap<String, Vec<(String, String)>> {
+        self.program_procedures
+            .iter()
+            .map(|(program_name, &proc_id)| {
+                (program_name.clone(), self.encoder.item_flags(proc_id))
+            })
+            .filter(|(_, flags)| !flags.is_empty())
+            .collect()
+    }
+
+    /// Record the procedures encoded so far in the verification report.
+    fn record_encoding(&mut self) {
+        let summaries = self.encoder.take_procedure_encoding_summaries();
+        if let Some(report) = &mut self.report {
+            report.record_encoding(summaries);
+        }
+    }
+
+    /// Take the programs encoded so far out of the encoder, optimizing them if
+    /// requested.
+    fn take_encoded_programs(&mut self) -> Vec<Program> {
         let polymorphic_programs = self.encoder.get_viper_programs();
 
         let mut programs: Vec<Program> = if config::simplify_encoding() {
-            stopwatch.start_next("optimizing Viper program");
             let source_file_name = self.encoder.env().name.source_file_name();
+            let program_flags = self.program_flags();
             polymorphic_programs
                 .into_iter()
-                .map(|program| Program::Legacy(optimize_program(program, &source_file_name).into()))
+                .map(|program| {
+                    let flags = program_flags
+                        .get(&program.name)
+                        .map(Vec::as_slice)
+                        .unwrap_or_default();
+                    let program = config::with_item_flags(flags, || {
+                        optimize_program(program, &source_file_name)
+                    });
+                    Program::Legacy(program.into())
+                })
                 .collect()
         } else {
             polymorphic_programs
@@ -92,15 +252,20 @@ impl<'v, 'tcx> Verifier<'v, 'tcx> {
                 .collect()
         };
         programs.extend(self.encoder.get_core_proof_programs());
+        programs
+    }
 
-        stopwatch.start_next("verifying Viper program");
-        let verification_results = verify_programs(self.env, programs);
-        stopwatch.finish();
-
+    /// Convert the verification results to Prusti errors and emit them.
+    fn report_verification_results(
+        &mut self,
+        verification_results: Vec<(String, viper::VerificationResult)>,
+    ) -> VerificationResult {
         // Group verification results
         let mut verification_errors: Vec<_> = vec![];
         let mut consistency_errors: Vec<_> = vec![];
         let mut java_exceptions: Vec<_> = vec![];
+        let mut backend_failures: Vec<_> = vec![];
+        let mut timeouts: Vec<_> = vec![];
         for (method_name, result) in verification_results.into_iter() {
             match result {
                 viper::VerificationResult::Success => {}
@@ -117,6 +282,12 @@ impl<'v, 'tcx> Verifier<'v, 'tcx> {
                 viper::VerificationResult::JavaException(exception) => {
                     java_exceptions.push((method_name, exception));
                 }
+                viper::VerificationResult::BackendFailure(message) => {
+                    backend_failures.push((method_name, message));
+                }
+                viper::VerificationResult::TimedOut { timeout_secs } => {
+                    timeouts.push((method_name, timeout_secs));
+                }
             }
         }
 
@@ -125,18 +296,58 @@ impl<'v, 'tcx> Verifier<'v, 'tcx> {
         let mut result = VerificationResult::Success;
 
         for (method, error) in consistency_errors.into_iter() {
-            PrustiError::internal(
+            let prusti_error = PrustiError::internal(
                 format!("consistency error in {method}: {error}"),
                 DUMMY_SP.into(),
-            )
-            .emit(&self.env.diagnostic);
+            );
+            if let Some(report) = &mut self.report {
+                report.record_verification_error(&method, None, &prusti_error, None);
+            }
+            prusti_error.emit(&self.env.diagnostic);
             result = VerificationResult::Failure;
         }
 
         for (method, exception) in java_exceptions.into_iter() {
             error!("Java exception: {}", exception.get_stack_trace());
-            PrustiError::internal(format!("in {method}: {exception}"), DUMMY_SP.into())
-                .emit(&self.env.diagnostic);
+            let prusti_error =
+                PrustiError::internal(format!("in {method}: {exception}"), DUMMY_SP.into());
+            if let Some(report) = &mut self.report {
+                report.record_verification_error(&method, None, &prusti_error, None);
+            }
+            prusti_error.emit(&self.env.diagnostic);
+            result = VerificationResult::Failure;
+        }
+
+        for (method, message) in backend_failures.into_iter() {
+            let prusti_error =
+                PrustiError::internal(format!("in {method}: {message}"), DUMMY_SP.into());
+            if let Some(report) = &mut self.report {
+                report.record_verification_error(&method, None, &prusti_error, None);
+            }
+            prusti_error.emit(&self.env.diagnostic);
+            result = VerificationResult::Failure;
+        }
+
+        for (method, timeout_secs) in timeouts.into_iter() {
+            let proc_id = self.program_procedures.get(&method).copied();
+            let item_name = proc_id.map_or_else(
+                || method.clone(),
+                |proc_id| self.env.name.get_absolute_item_name(proc_id),
+            );
+            let span = proc_id.map_or(DUMMY_SP, |proc_id| self.env.query.get_def_span(proc_id));
+            let prusti_error = PrustiError::timeout(
+                format!(
+                    "the verification of {item_name} did not finish within {timeout_secs} second(s)"
+                ),
+                span.into(),
+            )
+            .set_help(
+                "increase the time budget with `#[timeout(..)]` or the `VERIFICATION_TIMEOUT` flag",
+            );
+            if let Some(report) = &mut self.report {
+                report.record_timeout(&method, &prusti_error);
+            }
+            prusti_error.emit(&self.env.diagnostic);
             result = VerificationResult::Failure;
         }
 
@@ -187,6 +398,17 @@ impl<'v, 'tcx> Verifier<'v, 'tcx> {
                 }
             }
 
+            if let Some(report) = &mut self.report {
+                if !prusti_error.is_disabled() {
+                    report.record_verification_error(
+                        &method,
+                        error_manager.get_def_id(&verification_error),
+                        &prusti_error,
+                        error_manager.get_error_ctxt(&verification_error),
+                    );
+                }
+            }
+
             prusti_errors.push(prusti_error);
         }
         prusti_errors.sort();
@@ -201,28 +423,50 @@ impl<'v, 'tcx> Verifier<'v, 'tcx> {
             result = VerificationResult::Failure;
         }
 
-        if encoding_errors_count != 0 {
-            result = VerificationResult::Failure;
-        }
-
         result
     }
 }
 
-/// Verify a list of programs.
-/// Returns a list of (program_name, verification_result) tuples.
-fn verify_programs(
-    env: &Environment,
-    programs: Vec<Program>,
-) -> Vec<(String, viper::VerificationResult)> {
-    let source_path = env.name.source_path();
-    let rust_program_name = source_path
+/// Verify a single program, recording the time spent in the verification
+/// report.
+fn verify_and_record(
+    report: &mut Option<ReportCollector>,
+    verify_program: &mut dyn FnMut(Program) -> (String, viper::VerificationResult),
+    program: Program,
+) -> (String, viper::VerificationResult) {
+    let stopwatch = Stopwatch::start_debug("prusti-viper", "verification of a program");
+    let (program_name, result) = verify_program(program);
+    let duration = stopwatch.finish();
+    if let Some(report) = report {
+        report.record_verification(&program_name, duration);
+    }
+    (program_name, result)
+}
+
+/// The file name of the crate root, which is prepended to the names of the
+/// programs.
+fn rust_program_name(env: &Environment) -> String {
+    env.name
+        .source_path()
         .file_name()
         .unwrap()
         .to_str()
         .unwrap()
-        .to_owned();
-    let verification_requests = programs.into_iter().map(|mut program| {
+        .to_owned()
+}
+
+/// Call `f` with a function that verifies a single program using the
+/// configured backend (a local Viper instance or a Prusti server) and returns
+/// the (program_name, verification_result) tuple. The backend is started
+/// lazily, when the first program is verified. `program_flags` are the
+/// configuration flags overridden for some programs.
+fn with_program_verifier<R>(
+    env: &Environment,
+    program_flags: &FxHashMap<String, Vec<(String, String)>>,
+    f: impl FnOnce(&mut dyn FnMut(Program) -> (String, viper::VerificationResult)) -> R,
+) -> R {
+    let rust_program_name = rust_program_name(env);
+    let build_verification_request = |mut program: Program| {
         let program_name = program.get_name().to_string();
         let check_mode = program.get_check_mode();
         // Prepend the Rust file name to the program.
@@ -234,12 +478,28 @@ fn verify_programs(
         }
         .parse()
         .unwrap();
+        let flags = program_flags
+            .get(&program_name)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
         let request = VerificationRequest {
             program,
-            backend_config: ViperBackendConfig::new(backend),
+            backend_config: config::with_item_flags(flags, || ViperBackendConfig::new(backend)),
         };
+        if config::dump_verification_requests() {
+            let namespace = "verification_request";
+            let filename = format!("{}.json", request.program.get_name_with_check_mode());
+            info!(
+                "Dumping verification request to '{}/{}'",
+                namespace, filename
+            );
+            log::report_with_writer(namespace, filename, |writer| {
+                serde_json::to_writer(writer, &request)
+                    .expect("could not serialize the verification request")
+            });
+        }
         (program_name, request)
-    });
+    };
     if let Some(server_address) = config::server_address() {
         let server_address = if server_address == "MOCK" {
             spawn_server_thread().to_string()
@@ -251,22 +511,21 @@ fn verify_programs(
             panic!("Could not parse server address ({server_address}) due to {error:?}")
         });
         // Here we construct a Tokio runtime to block until completion of the futures returned by
-        // `client.verify`. However, to report verification errors as early as possible,
-        // `verify_programs` should return an asynchronous stream of verification results.
+        // `client.verify`. To report verification errors as early as possible, enable
+        // `pipelined_verification`, which sends each program as soon as it is encoded.
         let runtime = Builder::new_current_thread()
             .thread_name("prusti-viper")
             .enable_all()
             .build()
             .expect("failed to construct Tokio runtime");
-        verification_requests
-            .map(|(program_name, request)| {
-                let remote_result = runtime.block_on(client.verify(request));
-                let result = remote_result.unwrap_or_else(|error| {
-                    panic!("Verification request of program {program_name} failed: {error:?}")
-                });
-                (program_name, result)
-            })
-            .collect()
+        f(&mut |program| {
+            let (program_name, request) = build_verification_request(program);
+            let remote_result = runtime.block_on(client.verify(request));
+            let result = remote_result.unwrap_or_else(|error| {
+                panic!("Verification request of program {program_name} failed: {error:?}")
+            });
+            (program_name, result)
+        })
     } else {
         let mut stopwatch = Stopwatch::start("prusti-viper", "JVM startup");
         stopwatch.start_next("attach current thread to the JVM");
@@ -275,11 +534,10 @@ fn verify_programs(
         let viper_thread = Lazy::new(|| viper.attach_current_thread());
         stopwatch.finish();
         let mut cache = PersistentCache::load_cache(config::cache_path());
-        verification_requests
-            .map(|(program_name, request)| {
-                let result = process_verification_request(&viper_thread, request, &mut cache);
-                (program_name, result)
-            })
-            .collect()
+        f(&mut |program| {
+            let (program_name, request) = build_verification_request(program);
+            let result = process_verification_request(&viper_thread, request, &mut cache);
+            (program_name, result)
+        })
     }
 }
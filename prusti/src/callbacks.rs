@@ -1,4 +1,4 @@
-use crate::verifier::verify;
+use crate::verifier::{dump_viper_programs, verify};
 use prusti_common::config;
 use prusti_interface::{
     environment::{mir_storage, Environment},
@@ -54,7 +54,7 @@ fn mir_borrowck<'tcx>(tcx: TyCtxt<'tcx>, def_id: LocalDefId) -> mir_borrowck<'tc
 impl prusti_rustc_interface::driver::Callbacks for PrustiCompilerCalls {
     fn config(&mut self, config: &mut Config) {
         // *Don't take MIR bodies with borrowck info if we won't need them*
-        if !config::no_verify() {
+        if !config::no_verify() || config::dump_viper_program() {
             assert!(config.override_queries.is_none());
             config.override_queries = Some(
                 |_session: &Session, providers: &mut Providers, _external: &mut ExternProviders| {
@@ -139,6 +139,8 @@ impl prusti_rustc_interface::driver::Callbacks for PrustiCompilerCalls {
             CrossCrateSpecs::import_export_cross_crate(&mut env, &mut def_spec);
             if !config::no_verify() {
                 verify(env, def_spec);
+            } else if config::dump_viper_program() {
+                dump_viper_programs(env, def_spec);
             }
         });
 
@@ -166,6 +166,8 @@ fn main() {
     // are not verified. However, we still run Prusti on them to export potential specs.
     if is_no_verify_dep_crate || are_lints_disabled {
         config::set_no_verify(true);
+        // Together with `no_verify`, this would encode the dependency.
+        config::set_dump_viper_program(false);
     }
 
     lazy_static::initialize(&ICE_HOOK);
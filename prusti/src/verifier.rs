@@ -83,3 +83,27 @@ pub fn verify(env: Environment<'_>, def_spec: typed::DefSpecificationMap) {
         };
     }
 }
+
+/// Encode the annotated items and dump the Viper programs without verifying
+/// them. Used when both `no_verify` and `dump_viper_program` are enabled.
+#[tracing::instrument(name = "prusti::dump_viper_programs", level = "debug", skip(env))]
+pub fn dump_viper_programs(env: Environment<'_>, def_spec: typed::DefSpecificationMap) {
+    if env.diagnostic.has_errors() {
+        warn!("The compiler reported an error, so the program will not be encoded.");
+        return;
+    }
+    let (annotated_procedures, types) = env.get_annotated_procedures_and_types();
+    let task = VerificationTask {
+        procedures: annotated_procedures,
+        types,
+    };
+    if task.procedures.is_empty() && task.types.is_empty() {
+        return;
+    }
+    user::message(format!(
+        "Dumping the Viper programs of {} items...",
+        task.procedures.len()
+    ));
+    let mut verifier = Verifier::new(&env, def_spec);
+    verifier.dump_programs(&task);
+}
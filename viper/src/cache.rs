@@ -17,7 +17,7 @@ use std::{
 
 pub trait Cache {
     fn get(&self, request: u64) -> Option<VerificationResult>;
-    fn insert(self, request: u64, result: VerificationResult) -> Option<VerificationResult>;
+    fn insert(&mut self, request: u64, result: VerificationResult) -> Option<VerificationResult>;
 }
 // We can change the representation here (e.g. adding fields):
 #[derive(Debug, Clone)]
@@ -131,7 +131,7 @@ impl Cache for &mut PersistentCache {
     fn get(&self, request: u64) -> Option<VerificationResult> {
         self.data.get(&request).cloned()
     }
-    fn insert(self, request: u64, result: VerificationResult) -> Option<VerificationResult> {
+    fn insert(&mut self, request: u64, result: VerificationResult) -> Option<VerificationResult> {
         self.updated = true;
         self.data.insert(request, result)
     }
@@ -142,8 +142,8 @@ impl Cache for &Arc<Mutex<PersistentCache>> {
         let mut cache = self.lock().unwrap();
         cache.deref_mut().get(request)
     }
-    fn insert(self, request: u64, result: VerificationResult) -> Option<VerificationResult> {
+    fn insert(&mut self, request: u64, result: VerificationResult) -> Option<VerificationResult> {
         let mut cache = self.lock().unwrap();
-        cache.insert(request, result)
+        cache.deref_mut().insert(request, result)
     }
 }
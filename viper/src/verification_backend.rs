@@ -10,6 +10,9 @@ use std::fmt;
 pub enum VerificationBackend {
     Silicon,
     Carbon,
+    /// Encodes `vir::low` programs directly into SMT-LIB and runs an SMT
+    /// solver as a subprocess, without going through Viper.
+    Smt,
 }
 
 #[derive(Clone, Debug)]
@@ -19,7 +22,7 @@ impl fmt::Display for UknownBackendError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "Invalid verification backend: '{}'. Allowed values are 'Silicon' and 'Carbon'",
+            "Invalid verification backend: '{}'. Allowed values are 'Silicon', 'Carbon' and 'Smt'",
             self.0
         )
     }
@@ -31,6 +34,7 @@ impl std::str::FromStr for VerificationBackend {
         match backend.to_lowercase().as_str() {
             "silicon" => Ok(VerificationBackend::Silicon),
             "carbon" => Ok(VerificationBackend::Carbon),
+            "smt" => Ok(VerificationBackend::Smt),
             _ => Err(UknownBackendError(backend.to_string())),
         }
     }
@@ -41,6 +45,7 @@ impl fmt::Display for VerificationBackend {
         match self {
             VerificationBackend::Silicon => write!(f, "Silicon"),
             VerificationBackend::Carbon => write!(f, "Carbon"),
+            VerificationBackend::Smt => write!(f, "Smt"),
         }
     }
 }
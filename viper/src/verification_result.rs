@@ -17,6 +17,12 @@ pub enum VerificationResult {
     ConsistencyErrors(Vec<String>),
     /// The verification raised a Java exception.
     JavaException(JavaException),
+    /// The verification was stopped because it exceeded its time budget of
+    /// `timeout_secs` seconds.
+    TimedOut { timeout_secs: u64 },
+    /// The verifier could not be run, e.g. because its solver failed to start
+    /// or an I/O error occurred while communicating with it.
+    BackendFailure(String),
 }
 
 impl VerificationResult {
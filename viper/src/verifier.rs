@@ -13,7 +13,11 @@ use crate::{
     verification_backend::VerificationBackend,
     verification_result::{VerificationError, VerificationResult},
 };
-use jni::{errors::Result, objects::JObject, JNIEnv};
+use jni::{
+    errors::Result,
+    objects::{GlobalRef, JObject},
+    JNIEnv, JavaVM,
+};
 use log::{debug, error, info};
 use std::path::PathBuf;
 use viper_sys::wrappers::{scala, viper::*};
@@ -82,6 +86,9 @@ impl<'a> Verifier<'a> {
             VerificationBackend::Carbon => {
                 carbon::CarbonVerifier::with(env).new(reporter, debug_info)
             }
+            VerificationBackend::Smt => {
+                unreachable!("the SMT backend does not run inside the JVM")
+            }
         }
     }
 
@@ -144,10 +151,15 @@ impl<'a> Verifier<'a> {
                 );
             }
 
+            // The verification throws if it is stopped via a `VerifierStopHandle`.
             run_timed!("Viper verification", debug,
-                let viper_result = self.jni.unwrap_result(
-                    self.call_verify(program)
-                );
+                let viper_result = match self.jni.unwrap_or_exception(self.call_verify(program)) {
+                    Ok(result) => result,
+                    Err(java_exception) => {
+                        self.smt_manager.stop_and_check();
+                        return VerificationResult::JavaException(java_exception);
+                    }
+                };
             );
             debug!(
                 "Viper verification result: {}",
@@ -345,6 +357,17 @@ impl<'a> Verifier<'a> {
         })
     }
 
+    /// Returns a handle that can stop this verifier from another thread, for
+    /// example to abort a verification that is no longer needed.
+    pub fn stop_handle(&self) -> VerifierStopHandle {
+        VerifierStopHandle {
+            jvm: self.jni.unwrap_result(self.env.get_java_vm()),
+            verifier_instance: self
+                .jni
+                .unwrap_result(self.env.new_global_ref(self.verifier_instance)),
+        }
+    }
+
     #[tracing::instrument(level = "debug", skip_all)]
     fn call_verify(&self, program: Program) -> Result<JObject<'a>> {
         self.verifier_wrapper
@@ -363,3 +386,27 @@ impl<'a> Drop for Verifier<'a> {
             .unwrap_result(self.env.delete_local_ref(self.verifier_instance));
     }
 }
+
+/// Stops a running `Verifier` from any thread. A verification that is stopped
+/// returns a `VerificationResult::JavaException`.
+pub struct VerifierStopHandle {
+    jvm: JavaVM,
+    verifier_instance: GlobalRef,
+}
+
+impl VerifierStopHandle {
+    pub fn stop(&self) {
+        let env = self
+            .jvm
+            .attach_current_thread()
+            .expect("failed to attach the current thread to the JVM");
+        let jni = JniUtils::new(&env);
+        // Stopping a verifier that already finished is harmless, so any
+        // exception raised by the verifier is only logged.
+        if let Err(java_exception) = jni.unwrap_or_exception(
+            silver::verifier::Verifier::with(&env).call_stop(self.verifier_instance.as_obj()),
+        ) {
+            error!("Failed to stop the verifier: {java_exception}");
+        }
+    }
+}
@@ -246,6 +246,31 @@ pub enum BuiltinFunc {
     LookupSeq,
     ConcatSeq,
     SeqLen,
+    TakeSeq,
+    SkipSeq,
+    UpdateSeq,
+    SeqContains,
+    RangeSeq,
+    SeqFromSlice,
+    SeqToSet,
+    SeqToMultiSet,
+    MapDomain,
+    EmptySet,
+    SingleSet,
+    SetUnion,
+    SetIntersection,
+    SetMinus,
+    SetSubset,
+    SetContains,
+    SetLen,
+    EmptyMultiSet,
+    SingleMultiSet,
+    MultiSetUnion,
+    MultiSetIntersection,
+    MultiSetMinus,
+    MultiSetSubset,
+    MultiSetCount,
+    MultiSetLen,
     NewInt,
     Index,
     Len,
@@ -129,7 +129,12 @@ pub struct LoopInvariant {
     /// memory.
     pub maybe_modified_places: Vec<Predicate>,
     pub functional_specifications: Vec<Expression>,
-    pub variant: Option<LoopVariant>,
+    /// The components of the loop variant, which are compared
+    /// lexicographically. Empty if the loop has no variant.
+    pub variant: Vec<LoopVariant>,
+    /// Ghost variables that are potentially modified inside the loop body,
+    /// such as the iteration index of a `for` loop.
+    pub maybe_modified_ghost_variables: Vec<VariableDecl>,
     pub position: Position,
 }
 
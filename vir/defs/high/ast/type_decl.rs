@@ -19,6 +19,8 @@ pub enum TypeDecl {
     Struct(Struct),
     Sequence(Sequence),
     Map(Map),
+    Set(Set),
+    MultiSet(MultiSet),
     Enum(Enum),
     Union(Union),
     Array(Array),
@@ -31,6 +33,7 @@ pub enum TypeDecl {
     Closure(Closure),
     // Projection(Projection),
     Unsupported(Unsupported),
+    Opaque(Opaque),
     Trusted(Trusted),
 }
 
@@ -129,6 +132,20 @@ pub struct Map {
     pub val_type: Type,
 }
 
+#[display(fmt = "Set({})", element_type)]
+pub struct Set {
+    pub lifetimes: Vec<LifetimeConst>,
+    pub const_parameters: Vec<VariableDecl>,
+    pub element_type: Type,
+}
+
+#[display(fmt = "MultiSet({})", element_type)]
+pub struct MultiSet {
+    pub lifetimes: Vec<LifetimeConst>,
+    pub const_parameters: Vec<VariableDecl>,
+    pub element_type: Type,
+}
+
 #[display(fmt = "&{} {}", uniqueness, target_type)]
 pub struct Reference {
     pub lifetimes: Vec<LifetimeConst>,
@@ -156,6 +173,15 @@ pub struct Unsupported {
     pub ty: Type,
 }
 
+/// A type whose values can be observed only through the specifications of
+/// its methods, such as a trait object or a function pointer.
+#[display(fmt = "{}", name)]
+pub struct Opaque {
+    pub name: String,
+    pub lifetimes: Vec<LifetimeConst>,
+    pub const_parameters: Vec<VariableDecl>,
+}
+
 #[display(fmt = "{}", name)]
 pub struct Trusted {
     pub name: String,
@@ -25,6 +25,32 @@ impl Expression {
             false
         }
     }
+    /// A placeholder for the iteration index of the innermost enclosing
+    /// `for` loop.
+    pub fn iteration_index() -> Self {
+        let variable = VariableDecl::new("iteration_index$", Type::Int(ty::Int::Usize));
+        Expression::local_no_pos(variable)
+    }
+    pub fn is_iteration_index(&self) -> bool {
+        if let Expression::Local(Local { variable, .. }) = self {
+            variable.name == "iteration_index$"
+        } else {
+            false
+        }
+    }
+    /// A placeholder for the sequence of elements visited by the innermost
+    /// enclosing `for` loop.
+    pub fn visited(ty: Type) -> Self {
+        let variable = VariableDecl::new("visited$", ty);
+        Expression::local_no_pos(variable)
+    }
+    pub fn is_visited(&self) -> bool {
+        if let Expression::Local(Local { variable, .. }) = self {
+            variable.name == "visited$"
+        } else {
+            false
+        }
+    }
 }
 
 const DISCRIMINANT_INDEX: usize = 100000;
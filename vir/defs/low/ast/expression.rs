@@ -178,6 +178,10 @@ pub enum ContainerOpKind {
     SeqIndex,
     SeqConcat,
     SeqLen,
+    SeqTake,
+    SeqDrop,
+    SeqUpdate,
+    SeqContains,
     MapEmpty,
     MapUpdate,
     MapContains,
@@ -28,6 +28,8 @@ impl TypeDecl {
             Self::Struct(decl) => &decl.lifetimes,
             Self::Sequence(decl) => &decl.lifetimes,
             Self::Map(decl) => &decl.lifetimes,
+            Self::Set(decl) => &decl.lifetimes,
+            Self::MultiSet(decl) => &decl.lifetimes,
             Self::Enum(decl) => &decl.lifetimes,
             // Self::Union(decl) => &decl.lifetimes,
             Self::Array(decl) => &decl.lifetimes,
@@ -53,6 +55,8 @@ impl TypeDecl {
             Self::Struct(decl) => &decl.const_parameters,
             Self::Sequence(decl) => &decl.const_parameters,
             Self::Map(decl) => &decl.const_parameters,
+            Self::Set(decl) => &decl.const_parameters,
+            Self::MultiSet(decl) => &decl.const_parameters,
             Self::Enum(decl) => &decl.const_parameters,
             // Self::Union(decl) => &decl.const_parameters,
             Self::Array(decl) => &decl.const_parameters,
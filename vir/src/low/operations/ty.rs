@@ -181,6 +181,9 @@ impl Typed for ContainerOp {
             ContainerOpKind::SeqEmpty
             | ContainerOpKind::SeqConstructor
             | ContainerOpKind::SeqConcat
+            | ContainerOpKind::SeqTake
+            | ContainerOpKind::SeqDrop
+            | ContainerOpKind::SeqUpdate
             | ContainerOpKind::MapEmpty
             | ContainerOpKind::MapUpdate
             | ContainerOpKind::SetEmpty
@@ -193,15 +196,18 @@ impl Typed for ContainerOp {
             | ContainerOpKind::MultiSetUnion
             | ContainerOpKind::MultiSetIntersection
             | ContainerOpKind::MultiSetMinus => &self.container_type,
+            // Like in Viper, the membership test of a multiset returns the
+            // number of occurrences of the element.
             ContainerOpKind::SeqLen
             | ContainerOpKind::MapLen
             | ContainerOpKind::SetCardinality
-            | ContainerOpKind::MultiSetCardinality => &Type::Int,
-            ContainerOpKind::MapContains
+            | ContainerOpKind::MultiSetCardinality
+            | ContainerOpKind::MultiSetContains => &Type::Int,
+            ContainerOpKind::SeqContains
+            | ContainerOpKind::MapContains
             | ContainerOpKind::SetSubset
             | ContainerOpKind::SetContains
-            | ContainerOpKind::MultiSetSubset
-            | ContainerOpKind::MultiSetContains => &Type::Bool,
+            | ContainerOpKind::MultiSetSubset => &Type::Bool,
             ContainerOpKind::SeqIndex => match &self.container_type {
                 Type::Seq(ty::Seq { element_type, .. }) => element_type,
                 _ => unreachable!("Expected Seq type, got {:?}", self.container_type),